@@ -1306,10 +1306,10 @@ pub enum ExprKind {
     While(P<Expr>, P<Block>, Option<Label>),
     /// A `for` loop, with an optional label.
     ///
-    /// `'label: for pat in expr { block }`
+    /// `'label: for pat in expr { block }` or `'label: for await pat in expr { block }`
     ///
     /// This is desugared to a combination of `loop` and `match` expressions.
-    ForLoop(P<Pat>, P<Expr>, P<Block>, Option<Label>),
+    ForLoop(P<Pat>, P<Expr>, P<Block>, Option<Label>, ForLoopKind),
     /// Conditionless loop (can be exited with `break`, `continue`, or `return`).
     ///
     /// `'label: loop { block }`
@@ -1434,6 +1434,15 @@ pub enum CaptureBy {
     Ref,
 }
 
+/// Whether a `for` loop iterates over an iterator or awaits the elements of a stream.
+#[derive(Clone, Copy, PartialEq, Eq, Encodable, Decodable, Debug, HashStable_Generic)]
+pub enum ForLoopKind {
+    /// `for pat in expr { block }`
+    For,
+    /// `for await pat in expr { block }`
+    ForAwait,
+}
+
 /// The movability of a generator / closure literal:
 /// whether a generator contains self-references, causing it to be `!Unpin`.
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Encodable, Decodable, Debug, Copy)]
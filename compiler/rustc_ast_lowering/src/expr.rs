@@ -15,6 +15,15 @@
 use rustc_span::symbol::{sym, Ident, Symbol};
 use rustc_span::{hygiene::ForLoopLoc, DUMMY_SP};
 
+/// What the `.await` loop built by `make_lowered_await` polls.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum FutureKind {
+    /// `Future::poll` on the awaited value.
+    Future,
+    /// `Stream::poll_next` on the stream behind the awaited `&mut` reference.
+    Stream,
+}
+
 impl<'hir> LoweringContext<'_, 'hir> {
     fn lower_exprs(&mut self, exprs: &[AstP<Expr>]) -> &'hir [hir::Expr<'hir>] {
         self.arena.alloc_from_iter(exprs.iter().map(|x| self.lower_expr_mut(x)))
@@ -274,8 +283,8 @@ pub(super) fn lower_expr_mut(&mut self, e: &Expr) -> hir::Expr<'hir> {
 
                 // Desugar `ExprForLoop`
                 // from: `[opt_ident]: for <pat> in <head> <body>`
-                ExprKind::ForLoop(ref pat, ref head, ref body, opt_label) => {
-                    return self.lower_expr_for(e, pat, head, body, opt_label);
+                ExprKind::ForLoop(ref pat, ref head, ref body, opt_label, kind) => {
+                    return self.lower_expr_for(e, pat, head, body, opt_label, kind);
                 }
                 ExprKind::MacCall(_) => panic!("{:?} shouldn't exist here", e.span),
             };
@@ -719,6 +728,34 @@ pub(super) fn make_async_expr(
     /// }
     /// ```
     fn lower_expr_await(&mut self, await_span: Span, expr: &Expr) -> hir::ExprKind<'hir> {
+        let expr = self.lower_expr(expr);
+        self.make_lowered_await(await_span, expr, FutureKind::Future)
+    }
+
+    /// Builds the `.await` loop around the already lowered `expr`, see `lower_expr_await`.
+    ///
+    /// For `FutureKind::Stream`, `expr` is a `&mut` reference to a stream, and the loop
+    /// awaits its next element instead:
+    /// ```rust
+    /// match <expr> {
+    ///     mut pinned => loop {
+    ///         match unsafe { ::std::stream::Stream::poll_next(
+    ///             <::std::pin::Pin>::new_unchecked(&mut *pinned),
+    ///             ::std::future::get_context(task_context),
+    ///         ) } {
+    ///             ::std::task::Poll::Ready(result) => break result,
+    ///             ::std::task::Poll::Pending => {}
+    ///         }
+    ///         task_context = yield ();
+    ///     }
+    /// }
+    /// ```
+    fn make_lowered_await(
+        &mut self,
+        await_span: Span,
+        expr: &'hir hir::Expr<'hir>,
+        kind: FutureKind,
+    ) -> hir::ExprKind<'hir> {
         match self.generator_kind {
             Some(hir::GeneratorKind::Async(_)) => {}
             Some(hir::GeneratorKind::Gen) | None => {
@@ -741,7 +778,6 @@ fn lower_expr_await(&mut self, await_span: Span, expr: &Expr) -> hir::ExprKind<'
             await_span,
             self.allow_gen_future.clone(),
         );
-        let expr = self.lower_expr(expr);
 
         let pinned_ident = Ident::with_dummy_span(sym::pinned);
         let (pinned_pat, pinned_pat_hid) =
@@ -757,6 +793,15 @@ fn lower_expr_await(&mut self, await_span: Span, expr: &Expr) -> hir::ExprKind<'
         // }
         let poll_expr = {
             let pinned = self.expr_ident(span, pinned_ident, pinned_pat_hid);
+            let pinned = match kind {
+                FutureKind::Future => pinned,
+                // `*pinned`, the stream behind the reference.
+                FutureKind::Stream => self.arena.alloc(self.expr(
+                    span,
+                    hir::ExprKind::Unary(hir::UnOp::Deref, pinned),
+                    ThinVec::new(),
+                )),
+            };
             let ref_mut_pinned = self.expr_mut_addr_of(span, pinned);
             let task_context = if let Some(task_context_hid) = self.task_context {
                 self.expr_ident_mut(span, task_context_ident, task_context_hid)
@@ -774,9 +819,13 @@ fn lower_expr_await(&mut self, await_span: Span, expr: &Expr) -> hir::ExprKind<'
                 hir::LangItem::GetContext,
                 arena_vec![self; task_context],
             );
+            let poll = match kind {
+                FutureKind::Future => hir::LangItem::FuturePoll,
+                FutureKind::Stream => hir::LangItem::StreamPollNext,
+            };
             let call = self.expr_call_lang_item_fn(
                 span,
-                hir::LangItem::FuturePoll,
+                poll,
                 arena_vec![self; new_unchecked, get_context],
             );
             self.arena.alloc(self.expr_unsafe(call))
@@ -1392,7 +1441,7 @@ fn lower_expr_yield(&mut self, span: Span, opt_expr: Option<&Expr>) -> hir::Expr
         hir::ExprKind::Yield(expr, hir::YieldSource::Yield)
     }
 
-    /// Desugar `ExprForLoop` from: `[opt_ident]: for <pat> in <head> <body>` into:
+    /// Desugar `ExprForLoop` from: `[opt_ident]: for [await] <pat> in <head> <body>` into:
     /// ```rust
     /// {
     ///     let result = match ::std::iter::IntoIterator::into_iter(<head>) {
@@ -1411,6 +1460,11 @@ fn lower_expr_yield(&mut self, span: Span, opt_expr: Option<&Expr>) -> hir::Expr
     ///     result
     /// }
     /// ```
+    ///
+    /// A `for await` loop calls `::std::stream::IntoStream::into_stream(<head>)` instead, and
+    /// gets the next element by awaiting the stream's `poll_next` on `&mut iter`, see
+    /// `make_lowered_await`. `iter` is never moved while the loop runs, which keeps the stream
+    /// pinned.
     fn lower_expr_for(
         &mut self,
         e: &Expr,
@@ -1418,6 +1472,7 @@ fn lower_expr_for(
         head: &Expr,
         body: &Block,
         opt_label: Option<Label>,
+        loop_kind: ForLoopKind,
     ) -> hir::Expr<'hir> {
         let orig_head_span = head.span;
         // expand <head>
@@ -1466,14 +1521,24 @@ fn lower_expr_for(
             self.pat_ident_binding_mode(desugared_span, iter, hir::BindingAnnotation::Mutable);
 
         // `match ::std::iter::Iterator::next(&mut iter) { ... }`
+        // or, for `for await`, `match (&mut iter).await { ... }`
         let match_expr = {
             let iter = self.expr_ident(desugared_span, iter, iter_pat_nid);
             let ref_mut_iter = self.expr_mut_addr_of(desugared_span, iter);
-            let next_expr = self.expr_call_lang_item_fn(
-                desugared_span,
-                hir::LangItem::IteratorNext,
-                arena_vec![self; ref_mut_iter],
-            );
+            let next_expr = match loop_kind {
+                ForLoopKind::For => self.expr_call_lang_item_fn(
+                    desugared_span,
+                    hir::LangItem::IteratorNext,
+                    arena_vec![self; ref_mut_iter],
+                ),
+                ForLoopKind::ForAwait => {
+                    let ref_mut_iter = self.arena.alloc(ref_mut_iter);
+                    let await_span = e.span.with_hi(orig_head_span.hi());
+                    let kind =
+                        self.make_lowered_await(await_span, ref_mut_iter, FutureKind::Stream);
+                    self.arena.alloc(self.expr(desugared_span, kind, ThinVec::new()))
+                }
+            };
             let arms = arena_vec![self; pat_arm, break_arm];
 
             self.expr_match(desugared_span, next_expr, arms, hir::MatchSource::ForLoopDesugar)
@@ -1532,11 +1597,11 @@ fn lower_expr_for(
 
         // `match ::std::iter::IntoIterator::into_iter(<head>) { ... }`
         let into_iter_expr = {
-            self.expr_call_lang_item_fn(
-                into_iter_span,
-                hir::LangItem::IntoIterIntoIter,
-                arena_vec![self; head],
-            )
+            let into_iter = match loop_kind {
+                ForLoopKind::For => hir::LangItem::IntoIterIntoIter,
+                ForLoopKind::ForAwait => hir::LangItem::IntoStreamIntoStream,
+            };
+            self.expr_call_lang_item_fn(into_iter_span, into_iter, arena_vec![self; head])
         };
 
         let match_expr = self.arena.alloc(self.expr_match(
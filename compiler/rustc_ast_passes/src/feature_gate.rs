@@ -706,6 +706,7 @@ macro_rules! gate_all {
         "to use an async block, remove the `||`: `async {`"
     );
     gate_all!(more_qualified_paths, "usage of qualified paths in this context is experimental");
+    gate_all!(async_for_loop, "`for await` loops are experimental");
     gate_all!(generators, "yield syntax is experimental");
     gate_all!(raw_ref_op, "raw address of syntax is experimental");
     gate_all!(const_trait_bound_opt_out, "`?const` on trait bounds is experimental");
@@ -663,6 +663,9 @@ pub fn set(&self, features: &mut Features, span: Span) {
     /// Allows qualified paths in struct expressions, struct patterns and tuple struct patterns.
     (active, more_qualified_paths, "1.54.0", Some(80080), None),
 
+    /// Allows `for await` loops over streams in async functions and blocks.
+    (active, async_for_loop, "1.54.0", None, None),
+
     // -------------------------------------------------------------------------
     // feature-group-end: actual feature gates
     // -------------------------------------------------------------------------
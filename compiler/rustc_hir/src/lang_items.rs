@@ -323,6 +323,9 @@ pub fn extract<'a, F>(check_name: F, attrs: &'a [ast::Attribute]) -> Option<(Sym
 
     FuturePoll,              sym::poll,                future_poll_fn,             Target::Method(MethodKind::Trait { body: false });
 
+    IntoStreamIntoStream,    sym::into_stream,         into_stream_fn,             Target::Method(MethodKind::Trait { body: false });
+    StreamPollNext,          sym::poll_next,           stream_poll_next_fn,        Target::Method(MethodKind::Trait { body: false });
+
     FromFrom,                sym::from,                from_fn,                    Target::Method(MethodKind::Trait { body: false });
 
     OptionSome,              sym::Some,                option_some_variant,        Target::Variant;
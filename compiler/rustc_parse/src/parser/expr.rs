@@ -12,7 +12,8 @@
 use rustc_ast::util::parser::{prec_let_scrutinee_needs_par, AssocOp, Fixity};
 use rustc_ast::{self as ast, AttrStyle, AttrVec, CaptureBy, ExprField, Lit, UnOp, DUMMY_NODE_ID};
 use rustc_ast::{AnonConst, BinOp, BinOpKind, FnDecl, FnRetTy, MacCall, Param, Ty, TyKind};
-use rustc_ast::{Arm, Async, BlockCheckMode, Expr, ExprKind, Label, Movability, RangeLimits};
+use rustc_ast::{Arm, Async, BlockCheckMode, Expr, ExprKind, ForLoopKind};
+use rustc_ast::{Label, Movability, RangeLimits};
 use rustc_ast_pretty::pprust;
 use rustc_errors::{Applicability, DiagnosticBuilder, PResult};
 use rustc_span::edition::LATEST_STABLE_EDITION;
@@ -1861,13 +1862,20 @@ fn error_on_if_block_attrs(
             .emit();
     }
 
-    /// Parses `for <src_pat> in <src_expr> <src_loop_block>` (`for` token already eaten).
+    /// Parses `for [await] <src_pat> in <src_expr> <src_loop_block>` (`for` token already eaten).
     fn parse_for_expr(
         &mut self,
         opt_label: Option<Label>,
         lo: Span,
         mut attrs: AttrVec,
     ) -> PResult<'a, P<Expr>> {
+        let kind = if self.token.uninterpolated_span().rust_2018() && self.eat_keyword(kw::Await) {
+            self.sess.gated_spans.gate(sym::async_for_loop, self.prev_token.span);
+            ForLoopKind::ForAwait
+        } else {
+            ForLoopKind::For
+        };
+
         // Record whether we are about to parse `for (`.
         // This is used below for recovery in case of `for ( $stuff ) $block`
         // in which case we will suggest `for $stuff $block`.
@@ -1888,7 +1896,7 @@ fn parse_for_expr(
         let (iattrs, loop_block) = self.parse_inner_attrs_and_block()?;
         attrs.extend(iattrs);
 
-        let kind = ExprKind::ForLoop(pat, expr, loop_block, opt_label);
+        let kind = ExprKind::ForLoop(pat, expr, loop_block, opt_label, kind);
         Ok(self.mk_expr(lo.to(self.prev_token.span), kind, attrs))
     }
 
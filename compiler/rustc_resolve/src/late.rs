@@ -2337,7 +2337,7 @@ fn resolve_expr(&mut self, expr: &'ast Expr, parent: Option<&'ast Expr>) {
                 });
             }
 
-            ExprKind::ForLoop(ref pat, ref iter_expr, ref block, label) => {
+            ExprKind::ForLoop(ref pat, ref iter_expr, ref block, label, _) => {
                 self.visit_expr(iter_expr);
                 self.with_rib(ValueNS, NormalRibKind, |this| {
                     this.resolve_pattern_top(pat, PatternSource::For);
@@ -297,6 +297,7 @@
         assume_init,
         async_await,
         async_closure,
+        async_for_loop,
         atomics,
         att_syntax,
         attr,
@@ -658,6 +659,7 @@
         instruction_set,
         intel,
         into_iter,
+        into_stream,
         into_trait,
         intra_doc_pointers,
         intrinsics,
@@ -881,6 +883,7 @@
         pointer_trait,
         pointer_trait_fmt,
         poll,
+        poll_next,
         position,
         post_dash_lto: "post-lto",
         powerpc_target_feature,
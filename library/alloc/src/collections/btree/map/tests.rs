@@ -3,6 +3,7 @@
 use super::super::testing::rng::DeterministicRng;
 use super::Entry::{Occupied, Vacant};
 use super::*;
+use crate::alloc::Global;
 use crate::boxed::Box;
 use crate::fmt::Debug;
 use crate::rc::Rc;
@@ -119,8 +120,8 @@ fn compact(&mut self)
         K: Ord,
     {
         let iter = mem::take(self).into_iter();
-        let root = BTreeMap::ensure_is_owned(&mut self.root);
-        root.bulk_push(iter, &mut self.length);
+        let root = BTreeMap::ensure_is_owned(&mut self.root, Global);
+        root.bulk_push(iter, &mut self.length, Global);
     }
 }
 
@@ -247,6 +248,26 @@ fn test_basic_large() {
     map.check();
 }
 
+#[test]
+fn test_try_reserve_and_insert() {
+    let mut map = BTreeMap::new();
+    // Insert in both directions to split nodes at either end, up to the root.
+    for i in 0..MIN_INSERTS_HEIGHT_2 {
+        assert_eq!(map.try_reserve_and_insert(2 * i, i), Ok(None));
+        map.check();
+    }
+    for i in (0..MIN_INSERTS_HEIGHT_2).rev() {
+        assert_eq!(map.try_reserve_and_insert(2 * i + 1, i), Ok(None));
+        map.check();
+    }
+    assert_eq!(map.height(), Some(2));
+    for i in 0..MIN_INSERTS_HEIGHT_2 {
+        assert_eq!(map.try_reserve_and_insert(2 * i, 10 * i), Ok(Some(i)));
+    }
+    assert_eq!(map.len(), 2 * MIN_INSERTS_HEIGHT_2);
+    map.check();
+}
+
 #[test]
 fn test_basic_small() {
     let mut map = BTreeMap::new();
@@ -1449,6 +1470,27 @@ fn test_clear_drop_panic_leak() {
     assert_eq!(c.dropped(), 1);
 }
 
+#[test]
+fn test_new_in() {
+    let mut map = BTreeMap::new_in(Global);
+    let size = MIN_INSERTS_HEIGHT_2;
+    for i in 0..size {
+        assert_eq!(map.insert(i, 10 * i), None);
+    }
+    map.check();
+
+    let mut right = map.split_off(&(size / 2));
+    map.check();
+    right.check();
+    assert_eq!(map.len() + right.len(), size);
+
+    map.append(&mut right);
+    map.check();
+    assert!(right.is_empty());
+    assert_eq!(map, map.clone());
+    assert!(map.into_iter().eq((0..size).map(|i| (i, 10 * i))));
+}
+
 #[test]
 fn test_clone() {
     let mut map = BTreeMap::new();
@@ -2173,3 +2215,157 @@ fn test_insert_remove_intertwined_ord_chaos() {
     }
     map.check_invariants();
 }
+
+#[test]
+fn test_cursor() {
+    let map = BTreeMap::from_iter((1..=MIN_INSERTS_HEIGHT_2).map(|i| (i, i * 10)));
+
+    let mut cur = map.lower_bound(Unbounded);
+    assert_eq!(cur.key_value(), Some((&1, &10)));
+    assert_eq!(cur.peek_prev(), None);
+    assert_eq!(cur.peek_next(), Some((&2, &20)));
+    for i in 2..=MIN_INSERTS_HEIGHT_2 {
+        cur.move_next();
+        assert_eq!(cur.key(), Some(&i));
+        assert_eq!(cur.value(), Some(&(i * 10)));
+    }
+    cur.move_next();
+    assert_eq!(cur.key_value(), None);
+    assert_eq!(cur.peek_next(), Some((&1, &10)));
+    assert_eq!(cur.peek_prev(), Some((&MIN_INSERTS_HEIGHT_2, &(MIN_INSERTS_HEIGHT_2 * 10))));
+    cur.move_next();
+    assert_eq!(cur.key(), Some(&1));
+    cur.move_prev();
+    assert_eq!(cur.key(), None);
+    cur.move_prev();
+    assert_eq!(cur.key(), Some(&MIN_INSERTS_HEIGHT_2));
+
+    let mut cur = map.upper_bound(Excluded(&1));
+    assert_eq!(cur.key(), None);
+    cur.move_next();
+    assert_eq!(cur.key(), Some(&1));
+    cur.move_prev();
+    assert_eq!(cur.key(), None);
+    assert_eq!(cur.peek_prev(), Some((&MIN_INSERTS_HEIGHT_2, &(MIN_INSERTS_HEIGHT_2 * 10))));
+}
+
+#[test]
+fn test_cursor_bounds() {
+    let map = BTreeMap::from_iter([(2, 'a'), (4, 'b'), (6, 'c')].iter().cloned());
+
+    assert_eq!(map.lower_bound(Unbounded).key(), Some(&2));
+    assert_eq!(map.lower_bound(Included(&4)).key(), Some(&4));
+    assert_eq!(map.lower_bound(Excluded(&4)).key(), Some(&6));
+    assert_eq!(map.lower_bound(Included(&5)).key(), Some(&6));
+    assert_eq!(map.lower_bound(Excluded(&6)).key(), None);
+
+    assert_eq!(map.upper_bound(Unbounded).key(), Some(&6));
+    assert_eq!(map.upper_bound(Included(&4)).key(), Some(&4));
+    assert_eq!(map.upper_bound(Excluded(&4)).key(), Some(&2));
+    assert_eq!(map.upper_bound(Included(&3)).key(), Some(&2));
+    assert_eq!(map.upper_bound(Excluded(&2)).key(), None);
+
+    let empty = BTreeMap::<i32, char>::new();
+    assert_eq!(empty.lower_bound(Unbounded).key(), None);
+    assert_eq!(empty.upper_bound(Included(&1)).key(), None);
+}
+
+#[test]
+fn test_cursor_mut() {
+    let mut map = BTreeMap::from_iter([(1, 'a'), (3, 'c'), (5, 'e')].iter().cloned());
+    let mut cur = map.lower_bound_mut(Excluded(&1));
+    assert_eq!(cur.key_value(), Some((&3, &'c')));
+
+    cur.insert_before(2, 'b');
+    assert_eq!(cur.key_value(), Some((&3, &'c')));
+    assert_eq!(cur.peek_prev(), Some((&2, &mut 'b')));
+
+    cur.insert_after(4, 'd');
+    assert_eq!(cur.key_value(), Some((&3, &'c')));
+    assert_eq!(cur.peek_next(), Some((&4, &mut 'd')));
+
+    *cur.value_mut().unwrap() = 'C';
+    assert_eq!(cur.as_cursor().key_value(), Some((&3, &'C')));
+
+    assert_eq!(cur.remove_current(), Some((3, 'C')));
+    assert_eq!(cur.key_value(), Some((&4, &'d')));
+    assert_eq!(cur.remove_current_and_move_back(), Some((4, 'd')));
+    assert_eq!(cur.key_value(), Some((&2, &'b')));
+    drop(cur);
+    map.check();
+    assert_eq!(map.into_iter().collect::<Vec<_>>(), [(1, 'a'), (2, 'b'), (5, 'e')]);
+}
+
+#[test]
+fn test_cursor_mut_empty() {
+    let mut map = BTreeMap::new();
+    let mut cur = map.lower_bound_mut(Unbounded);
+    assert_eq!(cur.key(), None);
+    assert_eq!(cur.peek_next(), None);
+    assert_eq!(cur.remove_current(), None);
+
+    cur.insert_after(1, 'a');
+    assert_eq!(cur.key(), None);
+    cur.insert_before(2, 'b');
+    assert_eq!(cur.key(), None);
+    cur.move_next();
+    assert_eq!(cur.key(), Some(&1));
+    assert_eq!(cur.remove_current(), Some((1, 'a')));
+    assert_eq!(cur.remove_current(), Some((2, 'b')));
+    assert_eq!(cur.key(), None);
+    drop(cur);
+    map.check();
+    assert!(map.is_empty());
+}
+
+#[test]
+fn test_cursor_mut_insert_grow_shrink() {
+    let size = MIN_INSERTS_HEIGHT_2;
+    let mut map = BTreeMap::new();
+    let mut cur = map.lower_bound_mut(Unbounded);
+    for i in 0..size {
+        cur.insert_before(i, i * 2);
+    }
+    drop(cur);
+    map.check();
+    assert_eq!(map.len(), size);
+    assert_eq!(map.height(), Some(2));
+
+    let mut cur = map.upper_bound_mut(Unbounded);
+    for i in (0..size).rev() {
+        *cur.value_mut().unwrap() += 1;
+        cur.move_prev();
+        let next = cur.peek_next().unwrap();
+        assert_eq!(next, (&i, &mut (i * 2 + 1)));
+        *next.1 -= 1;
+    }
+    assert_eq!(cur.key(), None);
+    drop(cur);
+    map.check();
+    assert!(map.iter().all(|(&k, &v)| v == k * 2));
+
+    let mut cur = map.lower_bound_mut(Unbounded);
+    for i in 0..size {
+        assert_eq!(cur.remove_current(), Some((i, i * 2)));
+    }
+    assert_eq!(cur.key(), None);
+    drop(cur);
+    map.check();
+    assert!(map.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "key must be ordered above the current element")]
+fn test_cursor_mut_insert_after_before_current() {
+    let mut map = BTreeMap::from_iter([(1, 'a'), (3, 'c')].iter().cloned());
+    let mut cur = map.lower_bound_mut(Included(&3));
+    cur.insert_after(2, 'b');
+}
+
+#[test]
+#[should_panic(expected = "key must be ordered above the previous element")]
+fn test_cursor_mut_insert_before_previous() {
+    let mut map = BTreeMap::from_iter([(1, 'a'), (3, 'c')].iter().cloned());
+    let mut cur = map.lower_bound_mut(Included(&3));
+    cur.insert_before(1, 'b');
+}
@@ -31,13 +31,15 @@
 //   since leaf edges are empty and need no data representation. In an internal node,
 //   an edge both identifies a position and contains a pointer to a child node.
 
+use core::cell::Cell;
 use core::marker::PhantomData;
 use core::mem::{self, MaybeUninit};
 use core::ptr::{self, NonNull};
 use core::slice::SliceIndex;
 
-use crate::alloc::{Allocator, Global, Layout};
+use crate::alloc::{AllocError, Allocator, Layout};
 use crate::boxed::Box;
+use crate::collections::TryReserveError;
 
 const B: usize = 6;
 pub const CAPACITY: usize = 2 * B - 1;
@@ -78,9 +80,9 @@ unsafe fn init(this: *mut Self) {
     }
 
     /// Creates a new boxed `LeafNode`.
-    fn new() -> Box<Self> {
+    fn new<A: Allocator + Clone>(alloc: A) -> Box<Self, A> {
         unsafe {
-            let mut leaf = Box::new_uninit();
+            let mut leaf = Box::new_uninit_in(alloc);
             LeafNode::init(leaf.as_mut_ptr());
             leaf.assume_init()
         }
@@ -110,9 +112,9 @@ impl<K, V> InternalNode<K, V> {
     /// An invariant of internal nodes is that they have at least one
     /// initialized and valid edge. This function does not set up
     /// such an edge.
-    unsafe fn new() -> Box<Self> {
+    unsafe fn new<A: Allocator + Clone>(alloc: A) -> Box<Self, A> {
         unsafe {
-            let mut node = Box::<Self>::new_uninit();
+            let mut node = Box::<Self, _>::new_uninit_in(alloc);
             // We only need to initialize the data; the edges are MaybeUninit.
             LeafNode::init(ptr::addr_of_mut!((*node.as_mut_ptr()).data));
             node.assume_init()
@@ -142,8 +144,10 @@ unsafe fn new() -> Box<Self> {
 ///    - When this is `ValMut<'a>`, the `NodeRef` acts roughly like `&'a Node`
 ///      with respect to keys and tree structure, but also allows many
 ///      mutable references to values throughout the tree to coexist.
-///    - When this is `Mut<'a>`, the `NodeRef` acts roughly like `&'a mut Node`,
-///      although insert methods allow a mutable pointer to a value to coexist.
+///    - When this is `Mut<'a>`, the `NodeRef` acts roughly like `&'a mut Node`.
+///    - When this is `DormantMut`, the `NodeRef` acts roughly like a raw pointer
+///      that was taken from a `Mut<'a>` and may be awakened into one again, once
+///      all other borrows of the tree have ended.
 ///    - When this is `Owned`, the `NodeRef` acts roughly like `Box<Node>`,
 ///      but does not have a destructor, and must be cleaned up manually.
 ///    - When this is `Dying`, the `NodeRef` still acts roughly like `Box<Node>`,
@@ -177,8 +181,6 @@ unsafe fn new() -> Box<Self> {
 ///   lifetime, instead of the lifetime carried by `BorrowType`. That way,
 ///   the borrow checker guarantees that the `NodeRef` remains borrowed as long
 ///   as the returned reference is used.
-///   The methods supporting insert bend this rule by returning a raw pointer,
-///   i.e., a reference without any lifetime.
 pub struct NodeRef<BorrowType, K, V, Type> {
     /// The number of levels that the node and the level of leaves are apart, a
     /// constant of the node that cannot be entirely described by `Type`, and that
@@ -213,25 +215,28 @@ unsafe impl<K: Send, V: Send, Type> Send for NodeRef<marker::Owned, K, V, Type>
 unsafe impl<K: Send, V: Send, Type> Send for NodeRef<marker::Dying, K, V, Type> {}
 
 impl<K, V> NodeRef<marker::Owned, K, V, marker::Leaf> {
-    fn new_leaf() -> Self {
-        Self::from_new_leaf(LeafNode::new())
+    fn new_leaf<A: Allocator + Clone>(alloc: A) -> Self {
+        Self::from_new_leaf(LeafNode::new(alloc))
     }
 
-    fn from_new_leaf(leaf: Box<LeafNode<K, V>>) -> Self {
+    fn from_new_leaf<A: Allocator + Clone>(leaf: Box<LeafNode<K, V>, A>) -> Self {
         NodeRef { height: 0, node: NonNull::from(Box::leak(leaf)), _marker: PhantomData }
     }
 }
 
 impl<K, V> NodeRef<marker::Owned, K, V, marker::Internal> {
-    fn new_internal(child: Root<K, V>) -> Self {
-        let mut new_node = unsafe { InternalNode::new() };
+    fn new_internal<A: Allocator + Clone>(child: Root<K, V>, alloc: A) -> Self {
+        let mut new_node = unsafe { InternalNode::new(alloc) };
         new_node.edges[0].write(child.node);
         unsafe { NodeRef::from_new_internal(new_node, child.height + 1) }
     }
 
     /// # Safety
     /// `height` must not be zero.
-    unsafe fn from_new_internal(internal: Box<InternalNode<K, V>>, height: usize) -> Self {
+    unsafe fn from_new_internal<A: Allocator + Clone>(
+        internal: Box<InternalNode<K, V>, A>,
+        height: usize,
+    ) -> Self {
         debug_assert!(height > 0);
         let node = NonNull::from(Box::leak(internal)).cast();
         let mut this = NodeRef { height, node, _marker: PhantomData };
@@ -387,14 +392,15 @@ impl<K, V> NodeRef<marker::Dying, K, V, marker::LeafOrInternal> {
     /// Similar to `ascend`, gets a reference to a node's parent node, but also
     /// deallocates the current node in the process. This is unsafe because the
     /// current node will still be accessible despite being deallocated.
-    pub unsafe fn deallocate_and_ascend(
+    pub unsafe fn deallocate_and_ascend<A: Allocator + Clone>(
         self,
+        alloc: A,
     ) -> Option<Handle<NodeRef<marker::Dying, K, V, marker::Internal>, marker::Edge>> {
         let height = self.height;
         let node = self.node;
         let ret = self.ascend().ok();
         unsafe {
-            Global.deallocate(
+            alloc.deallocate(
                 node.cast(),
                 if height > 0 {
                     Layout::new::<InternalNode<K, V>>()
@@ -555,15 +561,18 @@ fn clear_parent_link(&mut self) {
 
 impl<K, V> NodeRef<marker::Owned, K, V, marker::LeafOrInternal> {
     /// Returns a new owned tree, with its own root node that is initially empty.
-    pub fn new() -> Self {
-        NodeRef::new_leaf().forget_type()
+    pub fn new<A: Allocator + Clone>(alloc: A) -> Self {
+        NodeRef::new_leaf(alloc).forget_type()
     }
 
     /// Adds a new internal node with a single edge pointing to the previous root node,
     /// make that new node the root node, and return it. This increases the height by 1
     /// and is the opposite of `pop_internal_level`.
-    pub fn push_internal_level(&mut self) -> NodeRef<marker::Mut<'_>, K, V, marker::Internal> {
-        super::mem::take_mut(self, |old_root| NodeRef::new_internal(old_root).forget_type());
+    pub fn push_internal_level<A: Allocator + Clone>(
+        &mut self,
+        alloc: A,
+    ) -> NodeRef<marker::Mut<'_>, K, V, marker::Internal> {
+        super::mem::take_mut(self, |old_root| NodeRef::new_internal(old_root, alloc).forget_type());
 
         // `self.borrow_mut()`, except that we just forgot we're internal now:
         NodeRef { height: self.height, node: self.node, _marker: PhantomData }
@@ -578,7 +587,7 @@ pub fn push_internal_level(&mut self) -> NodeRef<marker::Mut<'_>, K, V, marker::
     /// it will not invalidate other handles or references to the root node.
     ///
     /// Panics if there is no internal level, i.e., if the root node is a leaf.
-    pub fn pop_internal_level(&mut self) {
+    pub fn pop_internal_level<A: Allocator + Clone>(&mut self, alloc: A) {
         assert!(self.height > 0);
 
         let top = self.node;
@@ -593,11 +602,31 @@ pub fn pop_internal_level(&mut self) {
         self.clear_parent_link();
 
         unsafe {
-            Global.deallocate(top.cast(), Layout::new::<InternalNode<K, V>>());
+            alloc.deallocate(top.cast(), Layout::new::<InternalNode<K, V>>());
         }
     }
 }
 
+impl<'a, K, V, Type> NodeRef<marker::Mut<'a>, K, V, Type> {
+    /// Returns a dormant copy of this node with its lifetime erased, which can
+    /// be awakened later.
+    pub fn dormant(&self) -> NodeRef<marker::DormantMut, K, V, Type> {
+        NodeRef { height: self.height, node: self.node, _marker: PhantomData }
+    }
+}
+
+impl<K, V, Type> NodeRef<marker::DormantMut, K, V, Type> {
+    /// Reverts to the unique borrow initially captured.
+    ///
+    /// # Safety
+    ///
+    /// The node must still exist, and all mutable references into the tree that
+    /// were taken out after the dormant copy was made must no longer be used.
+    pub unsafe fn awaken<'a>(self) -> NodeRef<marker::Mut<'a>, K, V, Type> {
+        NodeRef { height: self.height, node: self.node, _marker: PhantomData }
+    }
+}
+
 impl<K, V, Type> NodeRef<marker::Owned, K, V, Type> {
     /// Mutably borrows the owned root node. Unlike `reborrow_mut`, this is safe
     /// because the return value cannot be used to destroy the root, and there
@@ -789,6 +818,25 @@ pub unsafe fn reborrow_mut(
     }
 }
 
+impl<'a, K, V, NodeType, HandleType> Handle<NodeRef<marker::Mut<'a>, K, V, NodeType>, HandleType> {
+    /// Returns a dormant copy of this handle, which can be awakened later.
+    pub fn dormant(&self) -> Handle<NodeRef<marker::DormantMut, K, V, NodeType>, HandleType> {
+        Handle { node: self.node.dormant(), idx: self.idx, _marker: PhantomData }
+    }
+}
+
+impl<K, V, NodeType, HandleType> Handle<NodeRef<marker::DormantMut, K, V, NodeType>, HandleType> {
+    /// Reverts to the unique borrow initially captured.
+    ///
+    /// # Safety
+    ///
+    /// The node must still exist, and all mutable references into the tree that
+    /// were taken out after the dormant copy was made must no longer be used.
+    pub unsafe fn awaken<'a>(self) -> Handle<NodeRef<marker::Mut<'a>, K, V, NodeType>, HandleType> {
+        Handle { node: unsafe { self.node.awaken() }, idx: self.idx, _marker: PhantomData }
+    }
+}
+
 impl<BorrowType, K, V, NodeType> Handle<NodeRef<BorrowType, K, V, NodeType>, marker::Edge> {
     /// Creates a new handle to an edge in `node`.
     /// Unsafe because the caller must ensure that `idx <= node.len()`.
@@ -841,8 +889,12 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, mark
     /// this edge. This method assumes that there is enough space in the node for the new
     /// pair to fit.
     ///
-    /// The returned pointer points to the inserted value.
-    fn insert_fit(&mut self, key: K, val: V) -> *mut V {
+    /// Returns a handle to the inserted pair.
+    fn insert_fit(
+        mut self,
+        key: K,
+        val: V,
+    ) -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::KV> {
         debug_assert!(self.node.len() < CAPACITY);
         let new_len = self.node.len() + 1;
 
@@ -851,7 +903,7 @@ fn insert_fit(&mut self, key: K, val: V) -> *mut V {
             slice_insert(self.node.val_area_mut(..new_len), self.idx, val);
             *self.node.len_mut() = new_len as u16;
 
-            self.node.val_area_mut(self.idx).assume_init_mut()
+            Handle::new_kv(self.node, self.idx)
         }
     }
 }
@@ -860,17 +912,25 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, mark
     /// Inserts a new key-value pair between the key-value pairs to the right and left of
     /// this edge. This method splits the node if there isn't enough room.
     ///
-    /// The returned pointer points to the inserted value.
-    fn insert(mut self, key: K, val: V) -> (InsertResult<'a, K, V, marker::Leaf>, *mut V) {
+    /// Returns a dormant handle to the inserted pair, which can be awakened
+    /// once splitting is complete.
+    fn insert<A: Allocator + Clone>(
+        self,
+        key: K,
+        val: V,
+        alloc: A,
+    ) -> (
+        Option<SplitResult<'a, K, V, marker::Leaf>>,
+        Handle<NodeRef<marker::DormantMut, K, V, marker::Leaf>, marker::KV>,
+    ) {
         if self.node.len() < CAPACITY {
-            let val_ptr = self.insert_fit(key, val);
-            let kv = unsafe { Handle::new_kv(self.node, self.idx) };
-            (InsertResult::Fit(kv), val_ptr)
+            let handle = self.insert_fit(key, val);
+            (None, handle.dormant())
         } else {
             let (middle_kv_idx, insertion) = splitpoint(self.idx);
             let middle = unsafe { Handle::new_kv(self.node, middle_kv_idx) };
-            let mut result = middle.split();
-            let mut insertion_edge = match insertion {
+            let mut result = middle.split(alloc);
+            let insertion_edge = match insertion {
                 LeftOrRight::Left(insert_idx) => unsafe {
                     Handle::new_edge(result.left.reborrow_mut(), insert_idx)
                 },
@@ -878,8 +938,8 @@ fn insert(mut self, key: K, val: V) -> (InsertResult<'a, K, V, marker::Leaf>, *m
                     Handle::new_edge(result.right.borrow_mut(), insert_idx)
                 },
             };
-            let val_ptr = insertion_edge.insert_fit(key, val);
-            (InsertResult::Split(result), val_ptr)
+            let handle = insertion_edge.insert_fit(key, val).dormant();
+            (Some(result), handle)
         }
     }
 }
@@ -918,22 +978,22 @@ fn insert_fit(&mut self, key: K, val: V, edge: Root<K, V>) {
     /// Inserts a new key-value pair and an edge that will go to the right of that new pair
     /// between this edge and the key-value pair to the right of this edge. This method splits
     /// the node if there isn't enough room.
-    fn insert(
+    fn insert<A: Allocator + Clone>(
         mut self,
         key: K,
         val: V,
         edge: Root<K, V>,
-    ) -> InsertResult<'a, K, V, marker::Internal> {
+        alloc: A,
+    ) -> Option<SplitResult<'a, K, V, marker::Internal>> {
         assert!(edge.height == self.node.height - 1);
 
         if self.node.len() < CAPACITY {
             self.insert_fit(key, val, edge);
-            let kv = unsafe { Handle::new_kv(self.node, self.idx) };
-            InsertResult::Fit(kv)
+            None
         } else {
             let (middle_kv_idx, insertion) = splitpoint(self.idx);
             let middle = unsafe { Handle::new_kv(self.node, middle_kv_idx) };
-            let mut result = middle.split();
+            let mut result = middle.split(alloc);
             let mut insertion_edge = match insertion {
                 LeftOrRight::Left(insert_idx) => unsafe {
                     Handle::new_edge(result.left.reborrow_mut(), insert_idx)
@@ -943,7 +1003,7 @@ fn insert(
                 },
             };
             insertion_edge.insert_fit(key, val, edge);
-            InsertResult::Split(result)
+            Some(result)
         }
     }
 }
@@ -953,37 +1013,70 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, mark
     /// this edge. This method splits the node if there isn't enough room, and tries to
     /// insert the split off portion into the parent node recursively, until the root is reached.
     ///
-    /// If the returned result is a `Fit`, its handle's node can be this edge's node or an ancestor.
-    /// If the returned result is a `Split`, the `left` field will be the root node.
-    /// The returned pointer points to the inserted value.
-    pub fn insert_recursing(
+    /// If the root node itself gets split, `split_root` is called with the split off
+    /// portion, whose `left` field is the old root node.
+    /// The returned handle points to the inserted pair, which is always in a leaf node.
+    pub fn insert_recursing<A: Allocator + Clone>(
         self,
         key: K,
         value: V,
-    ) -> (InsertResult<'a, K, V, marker::LeafOrInternal>, *mut V) {
-        let (mut split, val_ptr) = match self.insert(key, value) {
-            (InsertResult::Fit(handle), ptr) => {
-                return (InsertResult::Fit(handle.forget_node_type()), ptr);
-            }
-            (InsertResult::Split(split), val_ptr) => (split.forget_node_type(), val_ptr),
+        alloc: A,
+        split_root: impl FnOnce(SplitResult<'a, K, V, marker::LeafOrInternal>),
+    ) -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::KV> {
+        let (mut split, handle) = match self.insert(key, value, alloc.clone()) {
+            // SAFETY: we have finished splitting and can now awaken the
+            // handle to the inserted pair.
+            (None, handle) => return unsafe { handle.awaken() },
+            (Some(split), handle) => (split.forget_node_type(), handle),
         };
 
         loop {
             split = match split.left.ascend() {
-                Ok(parent) => match parent.insert(split.kv.0, split.kv.1, split.right) {
-                    InsertResult::Fit(handle) => {
-                        return (InsertResult::Fit(handle.forget_node_type()), val_ptr);
+                Ok(parent) => {
+                    match parent.insert(split.kv.0, split.kv.1, split.right, alloc.clone()) {
+                        // SAFETY: we have finished splitting and can now awaken the
+                        // handle to the inserted pair.
+                        None => return unsafe { handle.awaken() },
+                        Some(split) => split.forget_node_type(),
                     }
-                    InsertResult::Split(split) => split.forget_node_type(),
-                },
+                }
                 Err(root) => {
-                    return (InsertResult::Split(SplitResult { left: root, ..split }), val_ptr);
+                    split_root(SplitResult { left: root, ..split });
+                    // SAFETY: we have finished splitting and can now awaken the
+                    // handle to the inserted pair.
+                    return unsafe { handle.awaken() };
                 }
             };
         }
     }
 }
 
+impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Immut<'a>, K, V, marker::Leaf>, marker::Edge> {
+    /// Returns which nodes `insert_recursing` allocates when inserting at this edge:
+    /// whether it splits the leaf node, and how many internal nodes it needs to split
+    /// the full ancestors and, if the root node gets split too, to push a new root.
+    pub fn insert_allocations(self) -> (bool, usize) {
+        let mut node = self.into_node().forget_type();
+        if node.len() < CAPACITY {
+            return (false, 0);
+        }
+        let mut internals = 0;
+        loop {
+            match node.ascend() {
+                Ok(parent) => {
+                    let parent = parent.into_node();
+                    if parent.len() < CAPACITY {
+                        return (true, internals);
+                    }
+                    internals += 1;
+                    node = parent.forget_type();
+                }
+                Err(_) => return (true, internals + 1),
+            }
+        }
+    }
+}
+
 impl<BorrowType: marker::BorrowType, K, V>
     Handle<NodeRef<BorrowType, K, V, marker::Internal>, marker::Edge>
 {
@@ -1028,6 +1121,14 @@ pub fn into_val_mut(self) -> &'a mut V {
         let leaf = self.node.into_leaf_mut();
         unsafe { leaf.vals.get_unchecked_mut(self.idx).assume_init_mut() }
     }
+
+    pub fn into_kv_mut(self) -> (&'a mut K, &'a mut V) {
+        debug_assert!(self.idx < self.node.len());
+        let leaf = self.node.into_leaf_mut();
+        let k = unsafe { leaf.keys.get_unchecked_mut(self.idx).assume_init_mut() };
+        let v = unsafe { leaf.vals.get_unchecked_mut(self.idx).assume_init_mut() };
+        (k, v)
+    }
 }
 
 impl<'a, K, V, NodeType> Handle<NodeRef<marker::ValMut<'a>, K, V, NodeType>, marker::KV> {
@@ -1115,8 +1216,8 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, mark
     /// - The key and value pointed to by this handle are extracted.
     /// - All the key-value pairs to the right of this handle are put into a newly
     ///   allocated node.
-    pub fn split(mut self) -> SplitResult<'a, K, V, marker::Leaf> {
-        let mut new_node = LeafNode::new();
+    pub fn split<A: Allocator + Clone>(mut self, alloc: A) -> SplitResult<'a, K, V, marker::Leaf> {
+        let mut new_node = LeafNode::new(alloc);
 
         let kv = self.split_leaf_data(&mut new_node);
 
@@ -1147,10 +1248,13 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>,
     /// - The key and value pointed to by this handle are extracted.
     /// - All the edges and key-value pairs to the right of this handle are put into
     ///   a newly allocated node.
-    pub fn split(mut self) -> SplitResult<'a, K, V, marker::Internal> {
+    pub fn split<A: Allocator + Clone>(
+        mut self,
+        alloc: A,
+    ) -> SplitResult<'a, K, V, marker::Internal> {
         let old_len = self.node.len();
         unsafe {
-            let mut new_node = InternalNode::new();
+            let mut new_node = InternalNode::new(alloc);
             let kv = self.split_leaf_data(&mut new_node.data);
             let new_len = usize::from(new_node.data.len);
             move_to_slice(
@@ -1255,9 +1359,11 @@ fn do_merge<
             NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>,
         ) -> R,
         R,
+        A: Allocator,
     >(
         self,
         result: F,
+        alloc: A,
     ) -> R {
         let Handle { node: mut parent_node, idx: parent_idx, _marker } = self.parent;
         let old_parent_len = parent_node.len();
@@ -1302,9 +1408,9 @@ fn do_merge<
 
                 left_node.correct_childrens_parent_links(old_left_len + 1..new_left_len + 1);
 
-                Global.deallocate(right_node.node.cast(), Layout::new::<InternalNode<K, V>>());
+                alloc.deallocate(right_node.node.cast(), Layout::new::<InternalNode<K, V>>());
             } else {
-                Global.deallocate(right_node.node.cast(), Layout::new::<LeafNode<K, V>>());
+                alloc.deallocate(right_node.node.cast(), Layout::new::<LeafNode<K, V>>());
             }
         }
         result(parent_node, left_node)
@@ -1314,16 +1420,22 @@ fn do_merge<
     /// the left child node and returns the shrunk parent node.
     ///
     /// Panics unless we `.can_merge()`.
-    pub fn merge_tracking_parent(self) -> NodeRef<marker::Mut<'a>, K, V, marker::Internal> {
-        self.do_merge(|parent, _child| parent)
+    pub fn merge_tracking_parent<A: Allocator + Clone>(
+        self,
+        alloc: A,
+    ) -> NodeRef<marker::Mut<'a>, K, V, marker::Internal> {
+        self.do_merge(|parent, _child| parent, alloc)
     }
 
     /// Merges the parent's key-value pair and both adjacent child nodes into
     /// the left child node and returns that child node.
     ///
     /// Panics unless we `.can_merge()`.
-    pub fn merge_tracking_child(self) -> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
-        self.do_merge(|_parent, child| child)
+    pub fn merge_tracking_child<A: Allocator + Clone>(
+        self,
+        alloc: A,
+    ) -> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
+        self.do_merge(|_parent, child| child, alloc)
     }
 
     /// Merges the parent's key-value pair and both adjacent child nodes into
@@ -1331,9 +1443,10 @@ pub fn merge_tracking_child(self) -> NodeRef<marker::Mut<'a>, K, V, marker::Leaf
     /// where the tracked child edge ended up,
     ///
     /// Panics unless we `.can_merge()`.
-    pub fn merge_tracking_child_edge(
+    pub fn merge_tracking_child_edge<A: Allocator + Clone>(
         self,
         track_edge_idx: LeftOrRight<usize>,
+        alloc: A,
     ) -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::Edge> {
         let old_left_len = self.left_child.len();
         let right_len = self.right_child.len();
@@ -1341,7 +1454,7 @@ pub fn merge_tracking_child_edge(
             LeftOrRight::Left(idx) => idx <= old_left_len,
             LeftOrRight::Right(idx) => idx <= right_len,
         });
-        let child = self.merge_tracking_child();
+        let child = self.merge_tracking_child(alloc);
         let new_idx = match track_edge_idx {
             LeftOrRight::Left(idx) => idx,
             LeftOrRight::Right(idx) => old_left_len + 1 + idx,
@@ -1525,14 +1638,6 @@ pub fn forget_node_type(
     }
 }
 
-impl<BorrowType, K, V> Handle<NodeRef<BorrowType, K, V, marker::Internal>, marker::KV> {
-    pub fn forget_node_type(
-        self,
-    ) -> Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::KV> {
-        unsafe { Handle::new_kv(self.node.forget_type(), self.idx) }
-    }
-}
-
 impl<BorrowType, K, V, Type> Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, Type> {
     /// Checks whether the underlying node is an `Internal` node or a `Leaf` node.
     pub fn force(
@@ -1635,11 +1740,6 @@ pub fn forget_node_type(self) -> SplitResult<'a, K, V, marker::LeafOrInternal> {
     }
 }
 
-pub enum InsertResult<'a, K, V, NodeType> {
-    Fit(Handle<NodeRef<marker::Mut<'a>, K, V, NodeType>, marker::KV>),
-    Split(SplitResult<'a, K, V, NodeType>),
-}
-
 pub mod marker {
     use core::marker::PhantomData;
 
@@ -1652,6 +1752,7 @@ pub enum Dying {}
     pub struct Immut<'a>(PhantomData<&'a ()>);
     pub struct Mut<'a>(PhantomData<&'a mut ()>);
     pub struct ValMut<'a>(PhantomData<&'a mut ()>);
+    pub enum DormantMut {}
 
     pub trait BorrowType {
         // Whether node references of this borrow type allow traversing
@@ -1668,6 +1769,7 @@ impl BorrowType for Dying {}
     impl<'a> BorrowType for Immut<'a> {}
     impl<'a> BorrowType for Mut<'a> {}
     impl<'a> BorrowType for ValMut<'a> {}
+    impl BorrowType for DormantMut {}
 
     pub enum KV {}
     pub enum Edge {}
@@ -1737,5 +1839,86 @@ fn move_to_slice<T>(src: &mut [MaybeUninit<T>], dst: &mut [MaybeUninit<T>]) {
     }
 }
 
+/// An allocator that hands out nodes allocated beforehand, so that an insertion
+/// can run out of memory before it starts modifying the tree, and not halfway.
+/// Other allocations, and all deallocations, are passed on to the wrapped allocator.
+pub struct NodeReserve<K, V, A: Allocator> {
+    alloc: A,
+    leaf: Cell<Option<NonNull<u8>>>,
+    /// A linked list of internal nodes, each starting with the pointer to the next.
+    internals: Cell<Option<NonNull<u8>>>,
+    _marker: PhantomData<fn() -> (K, V)>,
+}
+
+impl<K, V, A: Allocator> NodeReserve<K, V, A> {
+    const LEAF: Layout = Layout::new::<LeafNode<K, V>>();
+    const INTERNAL: Layout = Layout::new::<InternalNode<K, V>>();
+
+    /// Allocates a leaf node, if `leaf` is true, and `internals` internal nodes.
+    pub fn try_new(alloc: A, leaf: bool, internals: usize) -> Result<Self, TryReserveError> {
+        let reserve = NodeReserve {
+            alloc,
+            leaf: Cell::new(None),
+            internals: Cell::new(None),
+            _marker: PhantomData,
+        };
+        if leaf {
+            reserve.leaf.set(Some(reserve.try_allocate(Self::LEAF)?));
+        }
+        for _ in 0..internals {
+            let node = reserve.try_allocate(Self::INTERNAL)?;
+            // SAFETY: an internal node is large and aligned enough to hold a pointer.
+            unsafe { node.cast::<Option<NonNull<u8>>>().as_ptr().write(reserve.internals.get()) };
+            reserve.internals.set(Some(node));
+        }
+        Ok(reserve)
+    }
+
+    fn try_allocate(&self, layout: Layout) -> Result<NonNull<u8>, TryReserveError> {
+        match self.alloc.allocate(layout) {
+            Ok(ptr) => Ok(ptr.cast()),
+            Err(_) => Err(TryReserveError::AllocError { layout, non_exhaustive: () }),
+        }
+    }
+
+    /// Takes a reserved node with the given layout out of the reserve, if any is left.
+    fn take(&self, layout: Layout) -> Option<NonNull<u8>> {
+        if layout == Self::LEAF {
+            self.leaf.take()
+        } else if layout == Self::INTERNAL {
+            let node = self.internals.get()?;
+            // SAFETY: reserved internal nodes start with the pointer to the next one.
+            self.internals.set(unsafe { node.cast::<Option<NonNull<u8>>>().as_ptr().read() });
+            Some(node)
+        } else {
+            None
+        }
+    }
+}
+
+unsafe impl<K, V, A: Allocator> Allocator for NodeReserve<K, V, A> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        match self.take(layout) {
+            Some(node) => Ok(NonNull::slice_from_raw_parts(node, layout.size())),
+            None => self.alloc.allocate(layout),
+        }
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        unsafe { self.alloc.deallocate(ptr, layout) }
+    }
+}
+
+impl<K, V, A: Allocator> Drop for NodeReserve<K, V, A> {
+    fn drop(&mut self) {
+        for &layout in &[Self::LEAF, Self::INTERNAL] {
+            while let Some(node) = self.take(layout) {
+                // SAFETY: the node was allocated by `self.alloc` with this layout.
+                unsafe { self.alloc.deallocate(node, layout) };
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests;
@@ -4,6 +4,7 @@
 use crate::vec::Vec;
 use std::cmp::Ordering;
 use std::iter::FromIterator;
+use std::ops::Bound;
 use std::panic::{catch_unwind, AssertUnwindSafe};
 
 #[test]
@@ -738,3 +739,27 @@ fn test_split_off_large_random_sorted() {
     assert!(set.into_iter().eq(data.clone().into_iter().filter(|x| *x < key)));
     assert!(right.into_iter().eq(data.into_iter().filter(|x| *x >= key)));
 }
+
+#[test]
+fn test_cursor() {
+    let mut set = BTreeSet::from_iter([1, 3, 5].iter().cloned());
+
+    let mut cur = set.lower_bound(Bound::Excluded(&1));
+    assert_eq!(cur.key(), Some(&3));
+    assert_eq!(cur.peek_prev(), Some(&1));
+    assert_eq!(cur.peek_next(), Some(&5));
+    cur.move_next();
+    cur.move_next();
+    assert_eq!(cur.key(), None);
+    assert_eq!(set.upper_bound(Bound::Unbounded).key(), Some(&5));
+
+    let mut cur = set.upper_bound_mut(Bound::Included(&4));
+    assert_eq!(cur.key(), Some(&3));
+    cur.insert_after(4);
+    cur.insert_before(2);
+    assert_eq!(cur.as_cursor().peek_next(), Some(&4));
+    assert_eq!(cur.remove_current(), Some(3));
+    assert_eq!(cur.key(), Some(&4));
+    drop(cur);
+    assert_eq!(set.into_iter().collect::<Vec<_>>(), [1, 2, 4, 5]);
+}
@@ -46,6 +46,35 @@ unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
     assert_eq!(v.alloc.fuel.get(), 250);
 }
 
+#[test]
+fn try_with_capacity_in_reports_alloc_error() {
+    use crate::alloc::AllocError;
+
+    // An allocator that always fails.
+    struct FailingAlloc;
+    unsafe impl Allocator for FailingAlloc {
+        fn allocate(&self, _layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            Err(AllocError)
+        }
+        unsafe fn deallocate(&self, _ptr: NonNull<u8>, _layout: Layout) {
+            unreachable!("nothing was allocated")
+        }
+    }
+
+    match RawVec::<u32, _>::try_with_capacity_in(10, FailingAlloc) {
+        Err(TryReserveError::AllocError { layout, .. }) => {
+            assert_eq!(layout, Layout::array::<u32>(10).unwrap())
+        }
+        _ => panic!("allocation failure should be reported"),
+    }
+    match RawVec::<u32, _>::try_with_capacity_in(usize::MAX, FailingAlloc) {
+        Err(CapacityOverflow) => {}
+        _ => panic!("usize::MAX should trigger an overflow!"),
+    }
+    // Zero-sized types never hit the allocator.
+    assert!(RawVec::<(), _>::try_with_capacity_in(10, FailingAlloc).is_ok());
+}
+
 #[test]
 fn reserve_does_not_overallocate() {
     {
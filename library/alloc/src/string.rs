@@ -44,6 +44,7 @@
 
 #[cfg(not(no_global_oom_handling))]
 use core::char::{decode_utf16, REPLACEMENT_CHARACTER};
+use core::cmp;
 use core::fmt;
 use core::hash;
 #[cfg(not(no_global_oom_handling))]
@@ -61,7 +62,9 @@
 #[cfg(not(no_global_oom_handling))]
 use core::str::lossy;
 use core::str::pattern::Pattern;
+use core::stream::FromStream;
 
+use crate::alloc::{Allocator, Global};
 #[cfg(not(no_global_oom_handling))]
 use crate::borrow::{Cow, ToOwned};
 use crate::boxed::Box;
@@ -286,11 +289,10 @@
 /// [`&str`]: prim@str
 /// [`Deref`]: core::ops::Deref
 /// [`as_str()`]: String::as_str
-#[derive(PartialOrd, Eq, Ord)]
 #[cfg_attr(not(test), rustc_diagnostic_item = "string_type")]
 #[stable(feature = "rust1", since = "1.0.0")]
-pub struct String {
-    vec: Vec<u8>,
+pub struct String<#[unstable(feature = "allocator_api", issue = "32838")] A: Allocator = Global> {
+    vec: Vec<u8, A>,
 }
 
 /// A possible error value when converting a `String` from a UTF-8 byte vector.
@@ -426,6 +428,34 @@ pub fn with_capacity(capacity: usize) -> String {
         String { vec: Vec::with_capacity(capacity) }
     }
 
+    /// Creates a new empty `String` with a particular capacity, returning an
+    /// error instead of panicking or aborting if the allocation fails.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(try_with_capacity)]
+    ///
+    /// let mut s = String::try_with_capacity(10).expect("out of memory");
+    /// assert!(s.capacity() >= 10);
+    /// s.push_str("hello");
+    /// assert_eq!(s, "hello");
+    ///
+    /// assert!(String::try_with_capacity(usize::MAX).is_err());
+    /// ```
+    #[inline]
+    #[unstable(feature = "try_with_capacity", issue = "none")]
+    pub fn try_with_capacity(capacity: usize) -> Result<String, TryReserveError> {
+        Ok(String { vec: Vec::try_with_capacity(capacity)? })
+    }
+
     // HACK(japaric): with cfg(test) the inherent `[T]::to_vec` method, which is
     // required for this method definition, is not available. Since we don't
     // require this method for testing purposes, I'll just stub it
@@ -766,6 +796,103 @@ pub unsafe fn from_utf8_unchecked(bytes: Vec<u8>) -> String {
         String { vec: bytes }
     }
 
+    /// Converts this `String` into a [`Box`]`<`[`str`]`>`.
+    ///
+    /// This will drop any excess capacity.
+    ///
+    /// [`str`]: prim@str
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// let s = String::from("hello");
+    ///
+    /// let b = s.into_boxed_str();
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[stable(feature = "box_str", since = "1.4.0")]
+    #[inline]
+    pub fn into_boxed_str(self) -> Box<str> {
+        let slice = self.vec.into_boxed_slice();
+        unsafe { from_boxed_utf8_unchecked(slice) }
+    }
+}
+
+impl<A: Allocator> String<A> {
+    /// Creates a new empty `String` in the provided allocator.
+    ///
+    /// Like [`new`], this will not allocate any initial buffer.
+    ///
+    /// [`new`]: String::new
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::alloc::System;
+    ///
+    /// let s = String::new_in(System);
+    /// assert!(s.is_empty());
+    /// ```
+    #[inline]
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    pub const fn new_in(alloc: A) -> String<A> {
+        String { vec: Vec::new_in(alloc) }
+    }
+
+    /// Creates a new empty `String` with a particular capacity, in the provided
+    /// allocator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::alloc::System;
+    ///
+    /// let mut s = String::with_capacity_in(10, System);
+    /// assert!(s.capacity() >= 10);
+    /// s.push_str("hello");
+    /// assert_eq!(s, "hello");
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline]
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> String<A> {
+        String { vec: Vec::with_capacity_in(capacity, alloc) }
+    }
+
+    /// Creates a new empty `String` with a particular capacity, in the provided
+    /// allocator, returning an error instead of panicking or aborting if the
+    /// allocation fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api, try_with_capacity)]
+    ///
+    /// use std::alloc::System;
+    ///
+    /// let s = String::try_with_capacity_in(10, System).expect("out of memory");
+    /// assert!(s.capacity() >= 10);
+    /// ```
+    #[inline]
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    // #[unstable(feature = "try_with_capacity", issue = "none")]
+    pub fn try_with_capacity_in(capacity: usize, alloc: A) -> Result<String<A>, TryReserveError> {
+        Ok(String { vec: Vec::try_with_capacity_in(capacity, alloc)? })
+    }
+
+    /// Returns a reference to the underlying allocator.
+    #[inline]
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    pub fn allocator(&self) -> &A {
+        self.vec.allocator()
+    }
+
     /// Converts a `String` into a byte vector.
     ///
     /// This consumes the `String`, so we do not need to copy its contents.
@@ -782,7 +909,7 @@ pub unsafe fn from_utf8_unchecked(bytes: Vec<u8>) -> String {
     /// ```
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
-    pub fn into_bytes(self) -> Vec<u8> {
+    pub fn into_bytes(self) -> Vec<u8, A> {
         self.vec
     }
 
@@ -1367,13 +1494,13 @@ pub fn retain<F>(&mut self, mut f: F)
     where
         F: FnMut(char) -> bool,
     {
-        struct SetLenOnDrop<'a> {
-            s: &'a mut String,
+        struct SetLenOnDrop<'a, A: Allocator> {
+            s: &'a mut String<A>,
             idx: usize,
             del_bytes: usize,
         }
 
-        impl<'a> Drop for SetLenOnDrop<'a> {
+        impl<'a, A: Allocator> Drop for SetLenOnDrop<'a, A> {
             fn drop(&mut self) {
                 let new_len = self.idx - self.del_bytes;
                 debug_assert!(new_len <= self.s.len());
@@ -1514,7 +1641,7 @@ pub fn insert_str(&mut self, idx: usize, string: &str) {
     /// ```
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
-    pub unsafe fn as_mut_vec(&mut self) -> &mut Vec<u8> {
+    pub unsafe fn as_mut_vec(&mut self) -> &mut Vec<u8, A> {
         &mut self.vec
     }
 
@@ -1587,10 +1714,13 @@ pub fn is_empty(&self) -> bool {
     #[inline]
     #[stable(feature = "string_split_off", since = "1.16.0")]
     #[must_use = "use `.truncate()` if you don't need the other half"]
-    pub fn split_off(&mut self, at: usize) -> String {
+    pub fn split_off(&mut self, at: usize) -> String<A>
+    where
+        A: Clone,
+    {
         assert!(self.is_char_boundary(at));
         let other = self.vec.split_off(at);
-        unsafe { String::from_utf8_unchecked(other) }
+        String { vec: other }
     }
 
     /// Truncates this `String`, removing all contents.
@@ -1646,7 +1776,7 @@ pub fn clear(&mut self) {
     /// assert_eq!(s, "");
     /// ```
     #[stable(feature = "drain", since = "1.6.0")]
-    pub fn drain<R>(&mut self, range: R) -> Drain<'_>
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, A>
     where
         R: RangeBounds<usize>,
     {
@@ -1721,29 +1851,6 @@ pub fn replace_range<R>(&mut self, range: R, replace_with: &str)
         // an adversarial implementation could change between calls
         unsafe { self.as_mut_vec() }.splice((start, end), replace_with.bytes());
     }
-
-    /// Converts this `String` into a [`Box`]`<`[`str`]`>`.
-    ///
-    /// This will drop any excess capacity.
-    ///
-    /// [`str`]: prim@str
-    ///
-    /// # Examples
-    ///
-    /// Basic usage:
-    ///
-    /// ```
-    /// let s = String::from("hello");
-    ///
-    /// let b = s.into_boxed_str();
-    /// ```
-    #[cfg(not(no_global_oom_handling))]
-    #[stable(feature = "box_str", since = "1.4.0")]
-    #[inline]
-    pub fn into_boxed_str(self) -> Box<str> {
-        let slice = self.vec.into_boxed_slice();
-        unsafe { from_boxed_utf8_unchecked(slice) }
-    }
 }
 
 impl FromUtf8Error {
@@ -1834,7 +1941,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
-impl Clone for String {
+impl<A: Allocator + Clone> Clone for String<A> {
     fn clone(&self) -> Self {
         String { vec: self.vec.clone() }
     }
@@ -1923,9 +2030,56 @@ fn from_iter<I: IntoIterator<Item = Cow<'a, str>>>(iter: I) -> String {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
+#[unstable(feature = "async_stream", issue = "79024")]
+impl FromStream<char> for String {
+    #[inline]
+    fn start_collect(lower_bound: usize) -> String {
+        String::with_capacity(lower_bound)
+    }
+
+    #[inline]
+    fn collect_item(&mut self, c: char) {
+        self.push(c);
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+#[unstable(feature = "async_stream", issue = "79024")]
+impl<'a> FromStream<&'a str> for String {
+    #[inline]
+    fn start_collect(_lower_bound: usize) -> String {
+        String::new()
+    }
+
+    #[inline]
+    fn collect_item(&mut self, s: &'a str) {
+        self.push_str(s);
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+#[unstable(feature = "async_stream", issue = "79024")]
+impl FromStream<String> for String {
+    #[inline]
+    fn start_collect(_lower_bound: usize) -> String {
+        String::new()
+    }
+
+    #[inline]
+    fn collect_item(&mut self, s: String) {
+        // Reuse the allocation of the first string, like `FromIterator` does.
+        if self.is_empty() {
+            *self = s;
+        } else {
+            self.push_str(&s);
+        }
+    }
+}
+
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
-impl Extend<char> for String {
+impl<A: Allocator> Extend<char> for String<A> {
     fn extend<I: IntoIterator<Item = char>>(&mut self, iter: I) {
         let iterator = iter.into_iter();
         let (lower_bound, _) = iterator.size_hint();
@@ -1946,7 +2100,7 @@ fn extend_reserve(&mut self, additional: usize) {
 
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "extend_ref", since = "1.2.0")]
-impl<'a> Extend<&'a char> for String {
+impl<'a, A: Allocator> Extend<&'a char> for String<A> {
     fn extend<I: IntoIterator<Item = &'a char>>(&mut self, iter: I) {
         self.extend(iter.into_iter().cloned());
     }
@@ -1964,7 +2118,7 @@ fn extend_reserve(&mut self, additional: usize) {
 
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<'a> Extend<&'a str> for String {
+impl<'a, A: Allocator> Extend<&'a str> for String<A> {
     fn extend<I: IntoIterator<Item = &'a str>>(&mut self, iter: I) {
         iter.into_iter().for_each(move |s| self.push_str(s));
     }
@@ -1977,7 +2131,7 @@ fn extend_one(&mut self, s: &'a str) {
 
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "box_str2", since = "1.45.0")]
-impl Extend<Box<str>> for String {
+impl<A: Allocator> Extend<Box<str>> for String<A> {
     fn extend<I: IntoIterator<Item = Box<str>>>(&mut self, iter: I) {
         iter.into_iter().for_each(move |s| self.push_str(&s));
     }
@@ -1985,7 +2139,7 @@ fn extend<I: IntoIterator<Item = Box<str>>>(&mut self, iter: I) {
 
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "extend_string", since = "1.4.0")]
-impl Extend<String> for String {
+impl<A: Allocator> Extend<String> for String<A> {
     fn extend<I: IntoIterator<Item = String>>(&mut self, iter: I) {
         iter.into_iter().for_each(move |s| self.push_str(&s));
     }
@@ -1998,7 +2152,7 @@ fn extend_one(&mut self, s: String) {
 
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "herd_cows", since = "1.19.0")]
-impl<'a> Extend<Cow<'a, str>> for String {
+impl<'a, A: Allocator> Extend<Cow<'a, str>> for String<A> {
     fn extend<I: IntoIterator<Item = Cow<'a, str>>>(&mut self, iter: I) {
         iter.into_iter().for_each(move |s| self.push_str(&s));
     }
@@ -2021,7 +2175,7 @@ fn extend_one(&mut self, s: Cow<'a, str>) {
     reason = "API not fully fleshed out and ready to be stabilized",
     issue = "27721"
 )]
-impl<'a, 'b> Pattern<'a> for &'b String {
+impl<'a, 'b, A: Allocator> Pattern<'a> for &'b String<A> {
     type Searcher = <&'b str as Pattern<'a>>::Searcher;
 
     fn into_searcher(self, haystack: &'a str) -> <&'b str as Pattern<'a>>::Searcher {
@@ -2055,22 +2209,41 @@ fn strip_suffix_of(self, haystack: &'a str) -> Option<&'a str> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl PartialEq for String {
+impl<A: Allocator> PartialEq for String<A> {
     #[inline]
-    fn eq(&self, other: &String) -> bool {
+    fn eq(&self, other: &Self) -> bool {
         PartialEq::eq(&self[..], &other[..])
     }
     #[inline]
-    fn ne(&self, other: &String) -> bool {
+    fn ne(&self, other: &Self) -> bool {
         PartialEq::ne(&self[..], &other[..])
     }
 }
 
+#[stable(feature = "rust1", since = "1.0.0")]
+impl<A: Allocator> Eq for String<A> {}
+
+#[stable(feature = "rust1", since = "1.0.0")]
+impl<A: Allocator> PartialOrd for String<A> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        PartialOrd::partial_cmp(&self[..], &other[..])
+    }
+}
+
+#[stable(feature = "rust1", since = "1.0.0")]
+impl<A: Allocator> Ord for String<A> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        Ord::cmp(&self[..], &other[..])
+    }
+}
+
 macro_rules! impl_eq {
-    ($lhs:ty, $rhs: ty) => {
+    ([$($vars:tt)*] $lhs:ty, $rhs: ty) => {
         #[stable(feature = "rust1", since = "1.0.0")]
         #[allow(unused_lifetimes)]
-        impl<'a, 'b> PartialEq<$rhs> for $lhs {
+        impl<'a, 'b, $($vars)*> PartialEq<$rhs> for $lhs {
             #[inline]
             fn eq(&self, other: &$rhs) -> bool {
                 PartialEq::eq(&self[..], &other[..])
@@ -2083,7 +2256,7 @@ fn ne(&self, other: &$rhs) -> bool {
 
         #[stable(feature = "rust1", since = "1.0.0")]
         #[allow(unused_lifetimes)]
-        impl<'a, 'b> PartialEq<$lhs> for $rhs {
+        impl<'a, 'b, $($vars)*> PartialEq<$lhs> for $rhs {
             #[inline]
             fn eq(&self, other: &$lhs) -> bool {
                 PartialEq::eq(&self[..], &other[..])
@@ -2094,10 +2267,13 @@ fn ne(&self, other: &$lhs) -> bool {
             }
         }
     };
+    ($lhs:ty, $rhs: ty) => {
+        impl_eq! { [] $lhs, $rhs }
+    };
 }
 
-impl_eq! { String, str }
-impl_eq! { String, &'a str }
+impl_eq! { [A: Allocator] String<A>, str }
+impl_eq! { [A: Allocator] String<A>, &'a str }
 #[cfg(not(no_global_oom_handling))]
 impl_eq! { Cow<'a, str>, str }
 #[cfg(not(no_global_oom_handling))]
@@ -2115,7 +2291,7 @@ fn default() -> String {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl fmt::Display for String {
+impl<A: Allocator> fmt::Display for String<A> {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Display::fmt(&**self, f)
@@ -2123,7 +2299,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl fmt::Debug for String {
+impl<A: Allocator> fmt::Debug for String<A> {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Debug::fmt(&**self, f)
@@ -2131,7 +2307,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl hash::Hash for String {
+impl<A: Allocator> hash::Hash for String<A> {
     #[inline]
     fn hash<H: hash::Hasher>(&self, hasher: &mut H) {
         (**self).hash(hasher)
@@ -2177,11 +2353,11 @@ fn hash<H: hash::Hasher>(&self, hasher: &mut H) {
 /// ```
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
-impl Add<&str> for String {
-    type Output = String;
+impl<A: Allocator> Add<&str> for String<A> {
+    type Output = String<A>;
 
     #[inline]
-    fn add(mut self, other: &str) -> String {
+    fn add(mut self, other: &str) -> String<A> {
         self.push_str(other);
         self
     }
@@ -2192,7 +2368,7 @@ fn add(mut self, other: &str) -> String {
 /// This has the same behavior as the [`push_str`][String::push_str] method.
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "stringaddassign", since = "1.12.0")]
-impl AddAssign<&str> for String {
+impl<A: Allocator> AddAssign<&str> for String<A> {
     #[inline]
     fn add_assign(&mut self, other: &str) {
         self.push_str(other);
@@ -2200,7 +2376,7 @@ fn add_assign(&mut self, other: &str) {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl ops::Index<ops::Range<usize>> for String {
+impl<A: Allocator> ops::Index<ops::Range<usize>> for String<A> {
     type Output = str;
 
     #[inline]
@@ -2209,7 +2385,7 @@ fn index(&self, index: ops::Range<usize>) -> &str {
     }
 }
 #[stable(feature = "rust1", since = "1.0.0")]
-impl ops::Index<ops::RangeTo<usize>> for String {
+impl<A: Allocator> ops::Index<ops::RangeTo<usize>> for String<A> {
     type Output = str;
 
     #[inline]
@@ -2218,7 +2394,7 @@ fn index(&self, index: ops::RangeTo<usize>) -> &str {
     }
 }
 #[stable(feature = "rust1", since = "1.0.0")]
-impl ops::Index<ops::RangeFrom<usize>> for String {
+impl<A: Allocator> ops::Index<ops::RangeFrom<usize>> for String<A> {
     type Output = str;
 
     #[inline]
@@ -2227,7 +2403,7 @@ fn index(&self, index: ops::RangeFrom<usize>) -> &str {
     }
 }
 #[stable(feature = "rust1", since = "1.0.0")]
-impl ops::Index<ops::RangeFull> for String {
+impl<A: Allocator> ops::Index<ops::RangeFull> for String<A> {
     type Output = str;
 
     #[inline]
@@ -2236,7 +2412,7 @@ fn index(&self, _index: ops::RangeFull) -> &str {
     }
 }
 #[stable(feature = "inclusive_range", since = "1.26.0")]
-impl ops::Index<ops::RangeInclusive<usize>> for String {
+impl<A: Allocator> ops::Index<ops::RangeInclusive<usize>> for String<A> {
     type Output = str;
 
     #[inline]
@@ -2245,7 +2421,7 @@ fn index(&self, index: ops::RangeInclusive<usize>) -> &str {
     }
 }
 #[stable(feature = "inclusive_range", since = "1.26.0")]
-impl ops::Index<ops::RangeToInclusive<usize>> for String {
+impl<A: Allocator> ops::Index<ops::RangeToInclusive<usize>> for String<A> {
     type Output = str;
 
     #[inline]
@@ -2255,42 +2431,42 @@ fn index(&self, index: ops::RangeToInclusive<usize>) -> &str {
 }
 
 #[stable(feature = "derefmut_for_string", since = "1.3.0")]
-impl ops::IndexMut<ops::Range<usize>> for String {
+impl<A: Allocator> ops::IndexMut<ops::Range<usize>> for String<A> {
     #[inline]
     fn index_mut(&mut self, index: ops::Range<usize>) -> &mut str {
         &mut self[..][index]
     }
 }
 #[stable(feature = "derefmut_for_string", since = "1.3.0")]
-impl ops::IndexMut<ops::RangeTo<usize>> for String {
+impl<A: Allocator> ops::IndexMut<ops::RangeTo<usize>> for String<A> {
     #[inline]
     fn index_mut(&mut self, index: ops::RangeTo<usize>) -> &mut str {
         &mut self[..][index]
     }
 }
 #[stable(feature = "derefmut_for_string", since = "1.3.0")]
-impl ops::IndexMut<ops::RangeFrom<usize>> for String {
+impl<A: Allocator> ops::IndexMut<ops::RangeFrom<usize>> for String<A> {
     #[inline]
     fn index_mut(&mut self, index: ops::RangeFrom<usize>) -> &mut str {
         &mut self[..][index]
     }
 }
 #[stable(feature = "derefmut_for_string", since = "1.3.0")]
-impl ops::IndexMut<ops::RangeFull> for String {
+impl<A: Allocator> ops::IndexMut<ops::RangeFull> for String<A> {
     #[inline]
     fn index_mut(&mut self, _index: ops::RangeFull) -> &mut str {
         unsafe { str::from_utf8_unchecked_mut(&mut *self.vec) }
     }
 }
 #[stable(feature = "inclusive_range", since = "1.26.0")]
-impl ops::IndexMut<ops::RangeInclusive<usize>> for String {
+impl<A: Allocator> ops::IndexMut<ops::RangeInclusive<usize>> for String<A> {
     #[inline]
     fn index_mut(&mut self, index: ops::RangeInclusive<usize>) -> &mut str {
         IndexMut::index_mut(&mut **self, index)
     }
 }
 #[stable(feature = "inclusive_range", since = "1.26.0")]
-impl ops::IndexMut<ops::RangeToInclusive<usize>> for String {
+impl<A: Allocator> ops::IndexMut<ops::RangeToInclusive<usize>> for String<A> {
     #[inline]
     fn index_mut(&mut self, index: ops::RangeToInclusive<usize>) -> &mut str {
         IndexMut::index_mut(&mut **self, index)
@@ -2298,7 +2474,7 @@ fn index_mut(&mut self, index: ops::RangeToInclusive<usize>) -> &mut str {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl ops::Deref for String {
+impl<A: Allocator> ops::Deref for String<A> {
     type Target = str;
 
     #[inline]
@@ -2308,7 +2484,7 @@ fn deref(&self) -> &str {
 }
 
 #[stable(feature = "derefmut_for_string", since = "1.3.0")]
-impl ops::DerefMut for String {
+impl<A: Allocator> ops::DerefMut for String<A> {
     #[inline]
     fn deref_mut(&mut self) -> &mut str {
         unsafe { str::from_utf8_unchecked_mut(&mut *self.vec) }
@@ -2465,7 +2641,7 @@ fn to_string(&self) -> String {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl AsRef<str> for String {
+impl<A: Allocator> AsRef<str> for String<A> {
     #[inline]
     fn as_ref(&self) -> &str {
         self
@@ -2473,7 +2649,7 @@ fn as_ref(&self) -> &str {
 }
 
 #[stable(feature = "string_as_mut", since = "1.43.0")]
-impl AsMut<str> for String {
+impl<A: Allocator> AsMut<str> for String<A> {
     #[inline]
     fn as_mut(&mut self) -> &mut str {
         self
@@ -2481,7 +2657,7 @@ fn as_mut(&mut self) -> &mut str {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl AsRef<[u8]> for String {
+impl<A: Allocator> AsRef<[u8]> for String<A> {
     #[inline]
     fn as_ref(&self) -> &[u8] {
         self.as_bytes()
@@ -2683,7 +2859,7 @@ fn from_iter<I: IntoIterator<Item = String>>(it: I) -> Cow<'a, str> {
 }
 
 #[stable(feature = "from_string_for_vec_u8", since = "1.14.0")]
-impl From<String> for Vec<u8> {
+impl<A: Allocator> From<String<A>> for Vec<u8, A> {
     /// Converts the given [`String`] to a vector [`Vec`] that holds values of type [`u8`].
     ///
     /// # Examples
@@ -2698,14 +2874,14 @@ impl From<String> for Vec<u8> {
     ///     println!("{}", b);
     /// }
     /// ```
-    fn from(string: String) -> Vec<u8> {
+    fn from(string: String<A>) -> Vec<u8, A> {
         string.into_bytes()
     }
 }
 
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
-impl fmt::Write for String {
+impl<A: Allocator> fmt::Write for String<A> {
     #[inline]
     fn write_str(&mut self, s: &str) -> fmt::Result {
         self.push_str(s);
@@ -2726,9 +2902,12 @@ fn write_char(&mut self, c: char) -> fmt::Result {
 ///
 /// [`drain`]: String::drain
 #[stable(feature = "drain", since = "1.6.0")]
-pub struct Drain<'a> {
+pub struct Drain<
+    'a,
+    #[unstable(feature = "allocator_api", issue = "32838")] A: Allocator = Global,
+> {
     /// Will be used as &'a mut String in the destructor
-    string: *mut String,
+    string: *mut String<A>,
     /// Start of part to remove
     start: usize,
     /// End of part to remove
@@ -2738,19 +2917,19 @@ pub struct Drain<'a> {
 }
 
 #[stable(feature = "collection_debug", since = "1.17.0")]
-impl fmt::Debug for Drain<'_> {
+impl<A: Allocator> fmt::Debug for Drain<'_, A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_tuple("Drain").field(&self.as_str()).finish()
     }
 }
 
 #[stable(feature = "drain", since = "1.6.0")]
-unsafe impl Sync for Drain<'_> {}
+unsafe impl<A: Allocator + Sync> Sync for Drain<'_, A> {}
 #[stable(feature = "drain", since = "1.6.0")]
-unsafe impl Send for Drain<'_> {}
+unsafe impl<A: Allocator + Send> Send for Drain<'_, A> {}
 
 #[stable(feature = "drain", since = "1.6.0")]
-impl Drop for Drain<'_> {
+impl<A: Allocator> Drop for Drain<'_, A> {
     fn drop(&mut self) {
         unsafe {
             // Use Vec::drain. "Reaffirm" the bounds checks to avoid
@@ -2763,7 +2942,7 @@ fn drop(&mut self) {
     }
 }
 
-impl<'a> Drain<'a> {
+impl<'a, A: Allocator> Drain<'a, A> {
     /// Returns the remaining (sub)string of this iterator as a slice.
     ///
     /// # Examples
@@ -2798,7 +2977,7 @@ pub fn as_str(&self) -> &str {
 // }
 
 #[stable(feature = "drain", since = "1.6.0")]
-impl Iterator for Drain<'_> {
+impl<A: Allocator> Iterator for Drain<'_, A> {
     type Item = char;
 
     #[inline]
@@ -2817,7 +2996,7 @@ fn last(mut self) -> Option<char> {
 }
 
 #[stable(feature = "drain", since = "1.6.0")]
-impl DoubleEndedIterator for Drain<'_> {
+impl<A: Allocator> DoubleEndedIterator for Drain<'_, A> {
     #[inline]
     fn next_back(&mut self) -> Option<char> {
         self.iter.next_back()
@@ -2825,7 +3004,7 @@ fn next_back(&mut self) -> Option<char> {
 }
 
 #[stable(feature = "fused", since = "1.26.0")]
-impl FusedIterator for Drain<'_> {}
+impl<A: Allocator> FusedIterator for Drain<'_, A> {}
 
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "from_char_for_string", since = "1.46.0")]
@@ -68,6 +68,7 @@
 use core::ops::{self, Index, IndexMut, Range, RangeBounds};
 use core::ptr::{self, NonNull};
 use core::slice::{self, SliceIndex};
+use core::stream::FromStream;
 
 use crate::alloc::{Allocator, Global};
 use crate::borrow::{Cow, ToOwned};
@@ -465,6 +466,70 @@ pub fn with_capacity(capacity: usize) -> Self {
         Self::with_capacity_in(capacity, Global)
     }
 
+    /// Constructs a new, empty `Vec<T>` with the specified capacity, returning
+    /// an error instead of panicking or aborting if the allocation fails.
+    ///
+    /// The vector will be able to hold exactly `capacity` elements without
+    /// reallocating. If `capacity` is 0, the vector will not allocate.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(try_with_capacity)]
+    ///
+    /// let vec: Vec<u32> = Vec::try_with_capacity(10).expect("out of memory");
+    /// assert_eq!(vec.len(), 0);
+    /// assert_eq!(vec.capacity(), 10);
+    ///
+    /// assert!(Vec::<u32>::try_with_capacity(usize::MAX).is_err());
+    /// ```
+    #[inline]
+    #[unstable(feature = "try_with_capacity", issue = "none")]
+    pub fn try_with_capacity(capacity: usize) -> Result<Self, TryReserveError> {
+        Self::try_with_capacity_in(capacity, Global)
+    }
+
+    /// Creates a vector from the elements of `iter`, returning an error instead
+    /// of panicking or aborting if the allocation fails.
+    ///
+    /// This is the fallible counterpart of [`collect`]. On error, the elements
+    /// collected so far are dropped, and the rest of `iter` isn't consumed.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(try_reserve)]
+    ///
+    /// let vec = Vec::try_from_iter((1..4).map(|x| x * 2)).expect("out of memory");
+    /// assert_eq!(vec, [2, 4, 6]);
+    ///
+    /// assert!(Vec::<u64>::try_from_iter(0..u64::MAX).is_err());
+    /// ```
+    ///
+    /// [`collect`]: Iterator::collect
+    #[unstable(feature = "try_reserve", reason = "new API", issue = "48043")]
+    pub fn try_from_iter<I: IntoIterator<Item = T>>(iter: I) -> Result<Self, TryReserveError> {
+        let mut iter = iter.into_iter();
+        let mut vec = Vec::try_with_capacity(iter.size_hint().0)?;
+        while let Some(element) = iter.next() {
+            if vec.len() == vec.capacity() {
+                vec.try_reserve(iter.size_hint().0.saturating_add(1))?;
+            }
+            vec.push(element);
+        }
+        Ok(vec)
+    }
+
     /// Creates a `Vec<T>` directly from the raw components of another vector.
     ///
     /// # Safety
@@ -604,6 +669,35 @@ pub fn with_capacity_in(capacity: usize, alloc: A) -> Self {
         Vec { buf: RawVec::with_capacity_in(capacity, alloc), len: 0 }
     }
 
+    /// Constructs a new, empty `Vec<T, A>` with the specified capacity with the provided
+    /// allocator, returning an error instead of panicking or aborting if the
+    /// allocation fails.
+    ///
+    /// The vector will be able to hold exactly `capacity` elements without
+    /// reallocating. If `capacity` is 0, the vector will not allocate.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api, try_with_capacity)]
+    ///
+    /// use std::alloc::System;
+    ///
+    /// let vec: Vec<u32, _> = Vec::try_with_capacity_in(10, System).expect("out of memory");
+    /// assert_eq!(vec.capacity(), 10);
+    /// ```
+    #[inline]
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    // #[unstable(feature = "try_with_capacity", issue = "none")]
+    pub fn try_with_capacity_in(capacity: usize, alloc: A) -> Result<Self, TryReserveError> {
+        Ok(Vec { buf: RawVec::try_with_capacity_in(capacity, alloc)?, len: 0 })
+    }
+
     /// Creates a `Vec<T, A>` directly from the raw components of another vector.
     ///
     /// # Safety
@@ -2450,6 +2544,20 @@ fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Vec<T> {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
+#[unstable(feature = "async_stream", issue = "79024")]
+impl<T> FromStream<T> for Vec<T> {
+    #[inline]
+    fn start_collect(lower_bound: usize) -> Vec<T> {
+        Vec::with_capacity(lower_bound)
+    }
+
+    #[inline]
+    fn collect_item(&mut self, item: T) {
+        self.push(item);
+    }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T, A: Allocator> IntoIterator for Vec<T, A> {
     type Item = T;
@@ -487,3 +487,19 @@ fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
         }
     }
 }
+
+#[test]
+fn test_try_reserve() {
+    let mut heap = BinaryHeap::try_with_capacity(4).unwrap();
+    assert!(heap.capacity() >= 4);
+    heap.try_reserve(10).unwrap();
+    assert!(heap.capacity() >= 10);
+    heap.try_reserve_exact(20).unwrap();
+    assert!(heap.capacity() >= 20);
+    heap.push(1);
+
+    assert!(heap.try_reserve(usize::MAX).is_err());
+    assert!(heap.try_reserve_exact(usize::MAX).is_err());
+    assert!(BinaryHeap::<u32>::try_with_capacity(usize::MAX).is_err());
+    assert_eq!(heap.into_vec(), [1]);
+}
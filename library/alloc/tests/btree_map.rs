@@ -0,0 +1,50 @@
+use std::collections::BTreeMap;
+
+use crate::{AllocCounts, CountingAlloc};
+
+#[test]
+fn test_new_in() {
+    let counts = AllocCounts::default();
+    let mut map = BTreeMap::new_in(CountingAlloc(&counts));
+    assert_eq!(counts.allocs(), 0);
+
+    // Enough insertions to split the root.
+    for i in 0..100 {
+        assert_eq!(map.insert(i, 10 * i), None);
+    }
+    let mut right = map.split_off(&50);
+    map.append(&mut right);
+    assert_eq!(map, map.clone());
+    assert!(counts.allocs() > 0);
+
+    assert!(map.into_iter().eq((0..100).map(|i| (i, 10 * i))));
+    drop(right);
+    assert_eq!(counts.allocs(), counts.deallocs());
+}
+
+#[test]
+fn test_try_reserve_and_insert() {
+    let counts = AllocCounts::default();
+    let mut map = BTreeMap::new_in(CountingAlloc(&counts));
+
+    counts.fail_after(0);
+    assert!(map.try_reserve_and_insert(0, 0).is_err());
+    assert!(map.is_empty());
+
+    // Fill the root leaf, so that the next insertion has to split it and push a new root.
+    counts.fail_after(1);
+    for i in 0..11 {
+        assert_eq!(map.try_reserve_and_insert(i, i), Ok(None));
+    }
+    assert_eq!(map.try_reserve_and_insert(5, 50), Ok(Some(5)));
+    assert!(map.try_reserve_and_insert(11, 11).is_err());
+    let expected = (0..11).map(|i| (i, if i == 5 { 50 } else { i }));
+    assert!(map.iter().map(|(&k, &v)| (k, v)).eq(expected));
+
+    counts.fail_after(2);
+    assert_eq!(map.try_reserve_and_insert(11, 11), Ok(None));
+    assert_eq!(map.len(), 12);
+
+    drop(map);
+    assert_eq!(counts.allocs(), counts.deallocs());
+}
@@ -8,6 +8,7 @@
 #![feature(pattern)]
 #![feature(trusted_len)]
 #![feature(try_reserve)]
+#![feature(try_with_capacity)]
 #![feature(unboxed_closures)]
 #![feature(associated_type_bounds)]
 #![feature(binary_heap_into_iter_sorted)]
@@ -22,13 +23,17 @@
 #![feature(vec_spare_capacity)]
 #![feature(string_remove_matches)]
 
+use std::alloc::{AllocError, Allocator, Layout, System};
+use std::cell::Cell;
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
+use std::ptr::NonNull;
 
 mod arc;
 mod binary_heap;
 mod borrow;
 mod boxed;
+mod btree_map;
 mod btree_set_hash;
 mod cow_str;
 mod fmt;
@@ -47,6 +52,51 @@ fn hash<T: Hash>(t: &T) -> u64 {
     s.finish()
 }
 
+/// Counts the calls made through a `CountingAlloc`.
+#[derive(Default)]
+struct AllocCounts {
+    allocs: Cell<usize>,
+    deallocs: Cell<usize>,
+    /// The number of allocations from which on `CountingAlloc` fails, if any.
+    limit: Cell<Option<usize>>,
+}
+
+impl AllocCounts {
+    fn allocs(&self) -> usize {
+        self.allocs.get()
+    }
+
+    fn deallocs(&self) -> usize {
+        self.deallocs.get()
+    }
+
+    /// Makes every allocation fail once `allocs` more have succeeded.
+    fn fail_after(&self, allocs: usize) {
+        self.limit.set(Some(self.allocs() + allocs));
+    }
+}
+
+/// An allocator that forwards to `System` and records every allocation and
+/// deallocation, to check that a collection really uses the allocator it
+/// was given.
+#[derive(Clone, Copy)]
+struct CountingAlloc<'a>(&'a AllocCounts);
+
+unsafe impl Allocator for CountingAlloc<'_> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        if self.0.limit.get() == Some(self.0.allocs()) {
+            return Err(AllocError);
+        }
+        self.0.allocs.set(self.0.allocs.get() + 1);
+        System.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.0.deallocs.set(self.0.deallocs.get() + 1);
+        System.deallocate(ptr, layout)
+    }
+}
+
 // FIXME: Instantiated functions with i128 in the signature is not supported in Emscripten.
 // See https://github.com/kripken/emscripten-fastcomp/issues/169
 #[cfg(not(target_os = "emscripten"))]
@@ -5,6 +5,8 @@
 use std::mem;
 use std::rc::{Rc, Weak};
 
+use crate::{AllocCounts, CountingAlloc};
+
 #[test]
 fn uninhabited() {
     enum Void {}
@@ -206,3 +208,30 @@ fn hmm<'a>(val: &'a mut Weak<&'a str>) -> Weak<&'a str> {
     // `val` dropped here while still borrowed
     // borrow might be used here, when `val` is dropped and runs the `Drop` code for type `std::rc::Weak`
 }
+
+#[test]
+fn new_in() {
+    let counts = AllocCounts::default();
+    let x = Rc::new_in(RefCell::new(5), CountingAlloc(&counts));
+    assert_eq!(counts.allocs(), 1);
+    let y = Rc::clone(&x);
+    *y.borrow_mut() += 1;
+    assert_eq!(*x.borrow(), 6);
+
+    let w = Rc::downgrade(&x);
+    assert_eq!(Rc::strong_count(&x), 2);
+    assert_eq!(Rc::weak_count(&x), 1);
+    drop(y);
+    assert_eq!(Rc::try_unwrap(x).unwrap().into_inner(), 6);
+    assert!(w.upgrade().is_none());
+
+    // The weak pointer keeps the allocation alive until it is dropped.
+    assert_eq!(counts.deallocs(), 0);
+    drop(w);
+    assert_eq!(counts.deallocs(), 1);
+
+    let empty: Weak<i32, _> = Weak::new_in(CountingAlloc(&counts));
+    assert!(empty.upgrade().is_none());
+    drop(empty);
+    assert_eq!((counts.allocs(), counts.deallocs()), (1, 1));
+}
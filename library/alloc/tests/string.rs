@@ -7,6 +7,8 @@
 use std::panic;
 use std::str;
 
+use crate::{AllocCounts, CountingAlloc};
+
 pub trait IntoCow<'a, B: ?Sized>
 where
     B: ToOwned,
@@ -839,6 +841,22 @@ fn test_try_reserve_exact() {
     }
 }
 
+#[test]
+fn test_try_with_capacity() {
+    let string = String::try_with_capacity(1000).unwrap();
+    assert_eq!(0, string.len());
+    assert!(string.capacity() >= 1000 && string.capacity() <= isize::MAX as usize);
+
+    assert!(String::try_with_capacity(usize::MAX).is_err());
+
+    let counts = AllocCounts::default();
+    let string = String::try_with_capacity_in(10, CountingAlloc(&counts)).unwrap();
+    assert!(string.capacity() >= 10);
+    assert_eq!(counts.allocs(), 1);
+    drop(string);
+    assert_eq!(counts.deallocs(), 1);
+}
+
 #[test]
 fn test_from_char() {
     assert_eq!(String::from('a'), 'a'.to_string());
@@ -853,3 +871,44 @@ fn test_str_concat() {
     let s: String = format!("{}{}", a, b);
     assert_eq!(s.as_bytes()[9], 'd' as u8);
 }
+
+#[test]
+fn test_new_in() {
+    let counts = AllocCounts::default();
+    let mut s = String::new_in(CountingAlloc(&counts));
+    assert!(s.is_empty());
+    assert_eq!(counts.allocs(), 0);
+    s.push_str("hello");
+    s.push(' ');
+    s += "world";
+    assert_eq!(s, "hello world");
+    assert_eq!(s.clone(), s);
+
+    let world = s.split_off(6);
+    assert_eq!(s, "hello ");
+    assert_eq!(world, "world");
+
+    let bytes: Vec<u8, CountingAlloc<'_>> = world.into_bytes();
+    assert_eq!(bytes, b"world");
+
+    assert!(counts.allocs() > 0);
+    drop((s, bytes));
+    assert_eq!(counts.allocs(), counts.deallocs());
+}
+
+#[test]
+fn test_with_capacity_in() {
+    let counts = AllocCounts::default();
+    let mut s = String::with_capacity_in(16, CountingAlloc(&counts));
+    assert!(s.capacity() >= 16);
+    assert_eq!(counts.allocs(), 1);
+    s.extend(['a', 'b', 'c'].iter());
+    assert_eq!(s.drain(1..).collect::<String>(), "bc");
+    assert_eq!(s, "a");
+    assert_eq!(format!("{:?}", s), "\"a\"");
+
+    // Nothing was added past the initial capacity.
+    assert_eq!(counts.allocs(), 1);
+    drop(s);
+    assert_eq!(counts.deallocs(), 1);
+}
@@ -1,3 +1,4 @@
+use std::alloc::System;
 use std::borrow::Cow;
 use std::cell::Cell;
 use std::collections::TryReserveError::*;
@@ -1668,6 +1669,31 @@ fn test_try_reserve_exact() {
     }
 }
 
+#[test]
+fn test_try_with_capacity() {
+    let vec: Vec<u32> = Vec::try_with_capacity(5).unwrap();
+    assert_eq!(0, vec.len());
+    assert!(vec.capacity() >= 5 && vec.capacity() <= isize::MAX as usize / 4);
+
+    assert!(matches!(Vec::<u16>::try_with_capacity(usize::MAX), Err(CapacityOverflow)));
+    assert!(Vec::<u8>::try_with_capacity(isize::MAX as usize + 1).is_err());
+
+    let vec: Vec<(), _> = Vec::try_with_capacity_in(usize::MAX, System).unwrap();
+    assert_eq!(vec.capacity(), usize::MAX);
+}
+
+#[test]
+fn test_try_from_iter() {
+    let vec = Vec::try_from_iter(0..5).unwrap();
+    assert_eq!(vec, [0, 1, 2, 3, 4]);
+
+    // No size hint: the vector grows as the elements come.
+    let vec = Vec::try_from_iter((0..100).filter(|x| x % 3 == 0)).unwrap();
+    assert_eq!(vec.len(), 34);
+
+    assert!(matches!(Vec::<u64>::try_from_iter(0..u64::MAX), Err(CapacityOverflow)));
+}
+
 #[test]
 fn test_stable_pointers() {
     /// Pull an element from the iterator, then drop it.
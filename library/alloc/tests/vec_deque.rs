@@ -5,7 +5,7 @@
 use std::ops::Bound::*;
 use std::panic::{catch_unwind, AssertUnwindSafe};
 
-use crate::hash;
+use crate::{hash, AllocCounts, CountingAlloc};
 
 use Taggy::*;
 use Taggypar::*;
@@ -1357,6 +1357,24 @@ fn test_try_reserve_exact() {
     }
 }
 
+#[test]
+fn test_try_with_capacity() {
+    let deque: VecDeque<u32> = VecDeque::try_with_capacity(5).unwrap();
+    assert!(deque.is_empty());
+    assert!(deque.capacity() >= 5);
+
+    assert!(matches!(VecDeque::<u8>::try_with_capacity(usize::MAX), Err(CapacityOverflow)));
+    assert!(VecDeque::<u64>::try_with_capacity(usize::MAX / 16).is_err());
+
+    let counts = AllocCounts::default();
+    let deque: VecDeque<u32, _> =
+        VecDeque::try_with_capacity_in(5, CountingAlloc(&counts)).unwrap();
+    assert!(deque.capacity() >= 5);
+    assert_eq!(counts.allocs(), 1);
+    drop(deque);
+    assert_eq!(counts.deallocs(), 1);
+}
+
 #[test]
 fn test_rotate_nop() {
     let mut v: VecDeque<_> = (0..10).collect();
@@ -1753,3 +1771,44 @@ fn test_from_zero_sized_vec() {
     let queue = VecDeque::from(v);
     assert_eq!(queue.len(), 100);
 }
+
+#[test]
+fn test_new_in() {
+    let counts = AllocCounts::default();
+    let mut deq: VecDeque<i32, CountingAlloc<'_>> = VecDeque::new_in(CountingAlloc(&counts));
+    deq.push_back(2);
+    deq.push_front(1);
+    deq.extend(3..5);
+    assert_eq!(deq, [1, 2, 3, 4]);
+
+    let other = deq.split_off(2);
+    assert_eq!(deq, [1, 2]);
+    assert_eq!(other, [3, 4]);
+
+    let vec: Vec<i32, CountingAlloc<'_>> = Vec::from(deq);
+    assert_eq!(vec, [1, 2]);
+    let deq = VecDeque::from(vec);
+    assert_eq!(deq.into_iter().collect::<Vec<_>>(), [1, 2]);
+
+    assert!(counts.allocs() > 0);
+    drop(other);
+    assert_eq!(counts.allocs(), counts.deallocs());
+}
+
+#[test]
+fn test_with_capacity_in() {
+    let counts = AllocCounts::default();
+    let mut deq = VecDeque::with_capacity_in(10, CountingAlloc(&counts));
+    assert!(deq.capacity() >= 10);
+    assert_eq!(counts.allocs(), 1);
+    deq.extend(0..10);
+    let cloned = deq.clone();
+    assert_eq!(deq.drain(..5).collect::<Vec<_>>(), [0, 1, 2, 3, 4]);
+    assert_eq!(deq, [5, 6, 7, 8, 9]);
+    assert_eq!(cloned.len(), 10);
+
+    // The clone allocates its own buffer from the same allocator.
+    assert_eq!(counts.allocs(), 2);
+    drop((deq, cloned));
+    assert_eq!(counts.deallocs(), 2);
+}
@@ -68,6 +68,7 @@ pub struct PanicInfo<'a> {
     payload: &'a (dyn Any + Send),
     message: Option<&'a fmt::Arguments<'a>>,
     location: &'a Location<'a>,
+    can_unwind: bool,
 }
 
 impl<'a> PanicInfo<'a> {
@@ -81,9 +82,10 @@ impl<'a> PanicInfo<'a> {
     pub fn internal_constructor(
         message: Option<&'a fmt::Arguments<'a>>,
         location: &'a Location<'a>,
+        can_unwind: bool,
     ) -> Self {
         struct NoPayload;
-        PanicInfo { location, message, payload: &NoPayload }
+        PanicInfo { location, message, payload: &NoPayload, can_unwind }
     }
 
     #[unstable(
@@ -123,9 +125,33 @@ pub fn payload(&self) -> &(dyn Any + Send) {
         self.payload
     }
 
-    /// If the `panic!` macro from the `core` crate (not from `std`)
-    /// was used with a formatting string and some additional arguments,
-    /// returns that message ready to be used for example with [`fmt::write`]
+    /// Returns the message of the panic, ready to be used for example with
+    /// [`fmt::write`].
+    ///
+    /// The message is available when `panic!` was called with a formatting
+    /// string, with or without additional arguments, and when the payload is a
+    /// `&'static str`. It is not available for other payloads, such as those
+    /// passed to [`panic_any`], or the `String` payload of a `panic!` called
+    /// with a single `String` argument in the 2015 and 2018 editions, like
+    /// `panic!(message)`: use [`payload`] to get that one.
+    ///
+    /// [`panic_any`]: ../../std/panic/fn.panic_any.html
+    /// [`payload`]: PanicInfo::payload
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// #![feature(panic_info_message)]
+    /// use std::panic;
+    ///
+    /// panic::set_hook(Box::new(|panic_info| {
+    ///     if let Some(message) = panic_info.message() {
+    ///         eprintln!("panic occurred: {}", message);
+    ///     }
+    /// }));
+    ///
+    /// panic!("Normal panic with {} arguments", 2);
+    /// ```
     #[unstable(feature = "panic_info_message", issue = "66745")]
     pub fn message(&self) -> Option<&fmt::Arguments<'_>> {
         self.message
@@ -161,6 +187,37 @@ pub fn location(&self) -> Option<&Location<'_>> {
         // deal with that case in std::panicking::default_hook and std::panicking::begin_panic_fmt.
         Some(&self.location)
     }
+
+    /// Returns whether the panic can unwind the stack once the panic hook
+    /// returns.
+    ///
+    /// This is `false` when the thread panicked while it was already
+    /// panicking, for example in a `Drop` implementation run during
+    /// unwinding. In that case the process is aborted right after the hook
+    /// returns, which a hook can use to flush its output before the process
+    /// goes away.
+    ///
+    /// Note that a panic may still abort the process when this returns `true`,
+    /// if the program was built with the `abort` panic strategy.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// #![feature(panic_can_unwind)]
+    /// use std::panic;
+    ///
+    /// panic::set_hook(Box::new(|panic_info| {
+    ///     if !panic_info.can_unwind() {
+    ///         eprintln!("panicked while panicking, aborting");
+    ///     }
+    /// }));
+    ///
+    /// panic!("Normal panic");
+    /// ```
+    #[unstable(feature = "panic_can_unwind", issue = "none")]
+    pub fn can_unwind(&self) -> bool {
+        self.can_unwind
+    }
 }
 
 #[stable(feature = "panic_hook_display", since = "1.26.0")]
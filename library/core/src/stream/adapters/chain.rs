@@ -0,0 +1,68 @@
+use crate::pin::Pin;
+use crate::stream::Stream;
+use crate::task::{Context, Poll};
+
+/// A stream that links two streams together, in a chain.
+///
+/// This `struct` is created by the [`chain`] method on [`Stream`]. See its
+/// documentation for more.
+///
+/// [`chain`]: Stream::chain
+#[must_use = "streams do nothing unless polled"]
+#[unstable(feature = "async_stream", issue = "79024")]
+#[derive(Clone, Debug)]
+pub struct Chain<A, B> {
+    // `None` once the first stream is exhausted, so that it's never polled
+    // again.
+    a: Option<A>,
+    b: B,
+}
+
+impl<A, B> Chain<A, B> {
+    pub(in crate::stream) fn new(a: A, b: B) -> Chain<A, B> {
+        Chain { a: Some(a), b }
+    }
+}
+
+#[unstable(feature = "async_stream", issue = "79024")]
+impl<A, B> Stream for Chain<A, B>
+where
+    A: Stream,
+    B: Stream<Item = A::Item>,
+{
+    type Item = A::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<A::Item>> {
+        // SAFETY: both streams are pinned structurally: they are only ever
+        // dropped in place, and `Chain` doesn't implement `Drop`.
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut a = unsafe { Pin::new_unchecked(&mut this.a) };
+        if let Some(stream) = a.as_mut().as_pin_mut() {
+            match stream.poll_next(cx) {
+                Poll::Ready(None) => a.set(None),
+                item => return item,
+            }
+        }
+        unsafe { Pin::new_unchecked(&mut this.b) }.poll_next(cx)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (b_lower, b_upper) = self.b.size_hint();
+        match self.a {
+            Some(ref a) => {
+                let (a_lower, a_upper) = a.size_hint();
+
+                let lower = a_lower.saturating_add(b_lower);
+
+                let upper = match (a_upper, b_upper) {
+                    (Some(x), Some(y)) => x.checked_add(y),
+                    _ => None,
+                };
+
+                (lower, upper)
+            }
+            None => (b_lower, b_upper),
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use crate::pin::Pin;
+use crate::stream::Stream;
+use crate::task::{Context, Poll};
+
+/// A stream that yields the current count and the element during iteration.
+///
+/// This `struct` is created by the [`enumerate`] method on [`Stream`]. See its
+/// documentation for more.
+///
+/// [`enumerate`]: Stream::enumerate
+#[must_use = "streams do nothing unless polled"]
+#[unstable(feature = "async_stream", issue = "79024")]
+#[derive(Clone, Debug)]
+pub struct Enumerate<S> {
+    stream: S,
+    count: usize,
+}
+
+impl<S> Enumerate<S> {
+    pub(in crate::stream) fn new(stream: S) -> Enumerate<S> {
+        Enumerate { stream, count: 0 }
+    }
+}
+
+#[unstable(feature = "async_stream", issue = "79024")]
+impl<S: Stream> Stream for Enumerate<S> {
+    type Item = (usize, S::Item);
+
+    /// # Overflow Behavior
+    ///
+    /// The method does no guarding against overflows, so enumerating more than
+    /// `usize::MAX` elements either produces the wrong result or panics. If
+    /// debug assertions are enabled, a panic is guaranteed.
+    ///
+    /// # Panics
+    ///
+    /// Might panic if the index of the element overflows a `usize`.
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // SAFETY: `stream` is pinned structurally: it is never moved out of a
+        // pinned `Enumerate`, which doesn't implement `Drop`.
+        let this = unsafe { self.get_unchecked_mut() };
+        let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+        stream.poll_next(cx).map(|item| {
+            item.map(|a| {
+                let i = this.count;
+                this.count += 1;
+                (i, a)
+            })
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
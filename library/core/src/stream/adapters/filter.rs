@@ -0,0 +1,59 @@
+use crate::fmt;
+use crate::pin::Pin;
+use crate::stream::Stream;
+use crate::task::{ready, Context, Poll};
+
+/// A stream that filters the elements of `stream` with `predicate`.
+///
+/// This `struct` is created by the [`filter`] method on [`Stream`]. See its
+/// documentation for more.
+///
+/// [`filter`]: Stream::filter
+#[must_use = "streams do nothing unless polled"]
+#[unstable(feature = "async_stream", issue = "79024")]
+#[derive(Clone)]
+pub struct Filter<S, P> {
+    stream: S,
+    predicate: P,
+}
+
+impl<S, P> Filter<S, P> {
+    pub(in crate::stream) fn new(stream: S, predicate: P) -> Filter<S, P> {
+        Filter { stream, predicate }
+    }
+}
+
+#[unstable(feature = "async_stream", issue = "79024")]
+impl<S: fmt::Debug, P> fmt::Debug for Filter<S, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Filter").field("stream", &self.stream).finish()
+    }
+}
+
+#[unstable(feature = "async_stream", issue = "79024")]
+impl<S: Stream, P> Stream for Filter<S, P>
+where
+    P: FnMut(&S::Item) -> bool,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<S::Item>> {
+        // SAFETY: `stream` is pinned structurally: it is never moved out of a
+        // pinned `Filter`, which doesn't implement `Drop`. `predicate` is not
+        // pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+        loop {
+            match ready!(stream.as_mut().poll_next(cx)) {
+                Some(item) if !(this.predicate)(&item) => {}
+                item => return Poll::Ready(item),
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, upper) = self.stream.size_hint();
+        (0, upper) // can't know a lower bound, due to the predicate
+    }
+}
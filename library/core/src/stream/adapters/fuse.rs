@@ -0,0 +1,51 @@
+use crate::pin::Pin;
+use crate::stream::Stream;
+use crate::task::{Context, Poll};
+
+/// A stream that yields `None` forever after the underlying stream yields
+/// `None` once.
+///
+/// This `struct` is created by the [`fuse`] method on [`Stream`]. See its
+/// documentation for more.
+///
+/// [`fuse`]: Stream::fuse
+#[must_use = "streams do nothing unless polled"]
+#[unstable(feature = "async_stream", issue = "79024")]
+#[derive(Clone, Debug)]
+pub struct Fuse<S> {
+    // `None` once the stream is exhausted.
+    stream: Option<S>,
+}
+
+impl<S> Fuse<S> {
+    pub(in crate::stream) fn new(stream: S) -> Fuse<S> {
+        Fuse { stream: Some(stream) }
+    }
+}
+
+#[unstable(feature = "async_stream", issue = "79024")]
+impl<S: Stream> Stream for Fuse<S> {
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<S::Item>> {
+        // SAFETY: `stream` is pinned structurally: it is only ever dropped in
+        // place, and `Fuse` doesn't implement `Drop`.
+        let mut stream = unsafe { self.map_unchecked_mut(|this| &mut this.stream) };
+        let item = match stream.as_mut().as_pin_mut() {
+            Some(s) => s.poll_next(cx),
+            None => return Poll::Ready(None),
+        };
+        if let Poll::Ready(None) = item {
+            stream.set(None);
+        }
+        item
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.stream {
+            Some(ref stream) => stream.size_hint(),
+            None => (0, Some(0)),
+        }
+    }
+}
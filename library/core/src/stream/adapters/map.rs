@@ -0,0 +1,52 @@
+use crate::fmt;
+use crate::pin::Pin;
+use crate::stream::Stream;
+use crate::task::{Context, Poll};
+
+/// A stream that maps the values of `stream` with `f`.
+///
+/// This `struct` is created by the [`map`] method on [`Stream`]. See its
+/// documentation for more.
+///
+/// [`map`]: Stream::map
+#[must_use = "streams do nothing unless polled"]
+#[unstable(feature = "async_stream", issue = "79024")]
+#[derive(Clone)]
+pub struct Map<S, F> {
+    stream: S,
+    f: F,
+}
+
+impl<S, F> Map<S, F> {
+    pub(in crate::stream) fn new(stream: S, f: F) -> Map<S, F> {
+        Map { stream, f }
+    }
+}
+
+#[unstable(feature = "async_stream", issue = "79024")]
+impl<S: fmt::Debug, F> fmt::Debug for Map<S, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Map").field("stream", &self.stream).finish()
+    }
+}
+
+#[unstable(feature = "async_stream", issue = "79024")]
+impl<B, S: Stream, F> Stream for Map<S, F>
+where
+    F: FnMut(S::Item) -> B,
+{
+    type Item = B;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<B>> {
+        // SAFETY: `stream` is pinned structurally: it is never moved out of a
+        // pinned `Map`, which doesn't implement `Drop`. `f` is not pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+        let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+        stream.poll_next(cx).map(|item| item.map(&mut this.f))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
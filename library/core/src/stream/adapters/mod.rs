@@ -0,0 +1,22 @@
+mod chain;
+mod enumerate;
+mod filter;
+mod fuse;
+mod map;
+mod take;
+mod zip;
+
+#[unstable(feature = "async_stream", issue = "79024")]
+pub use self::chain::Chain;
+#[unstable(feature = "async_stream", issue = "79024")]
+pub use self::enumerate::Enumerate;
+#[unstable(feature = "async_stream", issue = "79024")]
+pub use self::filter::Filter;
+#[unstable(feature = "async_stream", issue = "79024")]
+pub use self::fuse::Fuse;
+#[unstable(feature = "async_stream", issue = "79024")]
+pub use self::map::Map;
+#[unstable(feature = "async_stream", issue = "79024")]
+pub use self::take::Take;
+#[unstable(feature = "async_stream", issue = "79024")]
+pub use self::zip::Zip;
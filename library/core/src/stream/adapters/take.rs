@@ -0,0 +1,61 @@
+use crate::pin::Pin;
+use crate::stream::Stream;
+use crate::task::{Context, Poll};
+
+/// A stream that only yields the first `n` elements of `stream`.
+///
+/// This `struct` is created by the [`take`] method on [`Stream`]. See its
+/// documentation for more.
+///
+/// [`take`]: Stream::take
+#[must_use = "streams do nothing unless polled"]
+#[unstable(feature = "async_stream", issue = "79024")]
+#[derive(Clone, Debug)]
+pub struct Take<S> {
+    stream: S,
+    n: usize,
+}
+
+impl<S> Take<S> {
+    pub(in crate::stream) fn new(stream: S, n: usize) -> Take<S> {
+        Take { stream, n }
+    }
+}
+
+#[unstable(feature = "async_stream", issue = "79024")]
+impl<S: Stream> Stream for Take<S> {
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<S::Item>> {
+        // SAFETY: `stream` is pinned structurally: it is never moved out of a
+        // pinned `Take`, which doesn't implement `Drop`.
+        let this = unsafe { self.get_unchecked_mut() };
+        if this.n == 0 {
+            return Poll::Ready(None);
+        }
+        let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+        let item = stream.poll_next(cx);
+        if let Poll::Ready(Some(_)) = item {
+            this.n -= 1;
+        }
+        item
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.n == 0 {
+            return (0, Some(0));
+        }
+
+        let (lower, upper) = self.stream.size_hint();
+
+        let lower = lower.min(self.n);
+
+        let upper = match upper {
+            Some(x) if x < self.n => Some(x),
+            _ => Some(self.n),
+        };
+
+        (lower, upper)
+    }
+}
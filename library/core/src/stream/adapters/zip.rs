@@ -0,0 +1,72 @@
+use crate::fmt;
+use crate::pin::Pin;
+use crate::stream::Stream;
+use crate::task::{ready, Context, Poll};
+
+/// A stream that yields the elements of two other streams in pairs.
+///
+/// This `struct` is created by the [`zip`] method on [`Stream`]. See its
+/// documentation for more.
+///
+/// [`zip`]: Stream::zip
+#[must_use = "streams do nothing unless polled"]
+#[unstable(feature = "async_stream", issue = "79024")]
+pub struct Zip<A: Stream, B> {
+    a: A,
+    b: B,
+    // An element of `a` waiting for its counterpart from `b`.
+    a_item: Option<A::Item>,
+}
+
+impl<A: Stream, B> Zip<A, B> {
+    pub(in crate::stream) fn new(a: A, b: B) -> Zip<A, B> {
+        Zip { a, b, a_item: None }
+    }
+}
+
+#[unstable(feature = "async_stream", issue = "79024")]
+impl<A: Stream + fmt::Debug, B: fmt::Debug> fmt::Debug for Zip<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Zip").field("a", &self.a).field("b", &self.b).finish()
+    }
+}
+
+#[unstable(feature = "async_stream", issue = "79024")]
+impl<A: Stream, B: Stream> Stream for Zip<A, B> {
+    type Item = (A::Item, B::Item);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // SAFETY: both streams are pinned structurally: they are never moved
+        // out of a pinned `Zip`, which doesn't implement `Drop`. `a_item` is
+        // not pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+        if this.a_item.is_none() {
+            match ready!(unsafe { Pin::new_unchecked(&mut this.a) }.poll_next(cx)) {
+                Some(x) => this.a_item = Some(x),
+                None => return Poll::Ready(None),
+            }
+        }
+        let y = ready!(unsafe { Pin::new_unchecked(&mut this.b) }.poll_next(cx));
+        Poll::Ready(y.and_then(|y| Some((this.a_item.take()?, y))))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let pending = self.a_item.is_some() as usize;
+        let (a_lower, a_upper) = self.a.size_hint();
+        let (a_lower, a_upper) =
+            (a_lower.saturating_add(pending), a_upper.and_then(|x| x.checked_add(pending)));
+        let (b_lower, b_upper) = self.b.size_hint();
+
+        let lower = crate::cmp::min(a_lower, b_lower);
+
+        let upper = match (a_upper, b_upper) {
+            (Some(x), Some(y)) => Some(crate::cmp::min(x, y)),
+            (Some(x), None) => Some(x),
+            (None, Some(y)) => Some(y),
+            (None, None) => None,
+        };
+
+        (lower, upper)
+    }
+}
@@ -0,0 +1,95 @@
+use crate::stream::Stream;
+
+/// Conversion into a [`Stream`].
+///
+/// By implementing `IntoStream` for a type, you define how it will be
+/// converted to a stream. This is common for types which describe a
+/// collection of some kind.
+///
+/// One benefit of implementing `IntoStream` is that your type will work
+/// with `for await` loops, which call [`into_stream`] on the expression
+/// they iterate over.
+///
+/// Every [`Stream`] implements `IntoStream` by returning itself.
+///
+/// [`into_stream`]: IntoStream::into_stream
+#[unstable(feature = "async_stream", issue = "79024")]
+pub trait IntoStream {
+    /// The type of the elements being streamed.
+    type Item;
+
+    /// Which kind of stream are we turning this into?
+    type IntoStream: Stream<Item = Self::Item>;
+
+    /// Creates a stream from a value.
+    #[cfg_attr(not(bootstrap), lang = "into_stream")]
+    fn into_stream(self) -> Self::IntoStream;
+}
+
+#[unstable(feature = "async_stream", issue = "79024")]
+impl<S: Stream> IntoStream for S {
+    type Item = S::Item;
+    type IntoStream = S;
+
+    #[inline]
+    fn into_stream(self) -> S {
+        self
+    }
+}
+
+/// Conversion from a [`Stream`].
+///
+/// By implementing `FromStream` for a type, you define how it will be
+/// created from a stream, which makes it a valid target for
+/// [`Stream::collect`].
+///
+/// Unlike [`FromIterator`], which builds its value in a single call, a
+/// `FromStream` value is built incrementally, since the elements of a stream
+/// arrive asynchronously: [`Stream::collect`] creates an empty value with
+/// [`start_collect`] and then hands it every element with [`collect_item`].
+///
+/// [`FromIterator`]: crate::iter::FromIterator
+/// [`start_collect`]: FromStream::start_collect
+/// [`collect_item`]: FromStream::collect_item
+///
+/// # Examples
+///
+/// ```
+/// #![feature(async_stream)]
+/// use std::stream::FromStream;
+///
+/// // A collection that only counts its elements.
+/// struct Count(usize);
+///
+/// impl<A> FromStream<A> for Count {
+///     fn start_collect(_lower_bound: usize) -> Count {
+///         Count(0)
+///     }
+///
+///     fn collect_item(&mut self, _item: A) {
+///         self.0 += 1;
+///     }
+/// }
+/// ```
+#[unstable(feature = "async_stream", issue = "79024")]
+pub trait FromStream<A>: Sized {
+    /// Creates an empty value to collect the elements of a stream into.
+    ///
+    /// `lower_bound` is the lower bound of the stream's [`size_hint`], and
+    /// may be used to preallocate space.
+    ///
+    /// [`size_hint`]: Stream::size_hint
+    fn start_collect(lower_bound: usize) -> Self;
+
+    /// Adds the next element of the stream to the collection.
+    fn collect_item(&mut self, item: A);
+}
+
+#[unstable(feature = "async_stream", issue = "79024")]
+impl FromStream<()> for () {
+    #[inline]
+    fn start_collect(_lower_bound: usize) {}
+
+    #[inline]
+    fn collect_item(&mut self, _item: ()) {}
+}
@@ -0,0 +1,49 @@
+use crate::pin::Pin;
+use crate::stream::Stream;
+use crate::task::{Context, Poll};
+
+/// A stream that was created from iterator.
+///
+/// This stream is created by the [`from_iter`] function.
+/// See its documentation for more.
+#[must_use = "streams do nothing unless polled"]
+#[unstable(feature = "async_stream", issue = "79024")]
+#[derive(Clone, Debug)]
+pub struct FromIter<I> {
+    iter: I,
+}
+
+#[unstable(feature = "async_stream", issue = "79024")]
+impl<I> Unpin for FromIter<I> {}
+
+/// Converts an iterator into a stream.
+///
+/// The returned stream never returns `Poll::Pending`: every poll yields the
+/// next element of the iterator right away.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(async_stream)]
+/// use std::stream::{self, Stream};
+///
+/// let s = stream::from_iter(1..=3);
+/// assert_eq!(s.size_hint(), (3, Some(3)));
+/// ```
+#[unstable(feature = "async_stream", issue = "79024")]
+pub fn from_iter<I: IntoIterator>(iter: I) -> FromIter<I::IntoIter> {
+    FromIter { iter: iter.into_iter() }
+}
+
+#[unstable(feature = "async_stream", issue = "79024")]
+impl<I: Iterator> Stream for FromIter<I> {
+    type Item = I::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Ready(self.iter.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
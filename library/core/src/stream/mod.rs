@@ -40,10 +40,10 @@
 //! ```
 //!
 //! Unlike `Iterator`, `Stream` makes a distinction between the [`poll_next`]
-//! method which is used when implementing a `Stream`, and a (to-be-implemented)
-//! `next` method which is used when consuming a stream. Consumers of `Stream`
-//! only need to consider `next`, which when called, returns a future which
-//! yields `Option<Stream::Item>`.
+//! method which is used when implementing a `Stream`, and a [`next`] method
+//! which is used when consuming a stream. Consumers of `Stream` only need to
+//! consider `next`, which when called, returns a future which yields
+//! `Option<Stream::Item>`.
 //!
 //! The future returned by `next` will yield `Some(Item)` as long as there are
 //! elements, and once they've all been exhausted, will yield `None` to indicate
@@ -59,6 +59,37 @@
 //!
 //! [`Poll`]: super::task::Poll
 //! [`poll_next`]: Stream::poll_next
+//! [`next`]: Stream::next
+//!
+//! Like iterators, streams come with adapters such as [`map`], [`filter`],
+//! [`take`] and [`zip`], and can be gathered into a collection with
+//! [`collect`]. The [`IntoStream`] and [`FromStream`] traits play the roles
+//! of `IntoIterator` and `FromIterator`, and [`from_iter`] turns any iterator
+//! into a stream.
+//!
+//! [`map`]: Stream::map
+//! [`filter`]: Stream::filter
+//! [`take`]: Stream::take
+//! [`zip`]: Stream::zip
+//! [`collect`]: Stream::collect
+//!
+//! # `for await` loops
+//!
+//! Inside `async` functions and blocks, the unstable `for await` loop consumes
+//! anything that implements [`IntoStream`], awaiting each element in turn:
+//!
+//! ```
+//! #![feature(async_stream, async_for_loop)]
+//! use std::stream;
+//!
+//! async fn sum() -> i32 {
+//!     let mut total = 0;
+//!     for await x in stream::from_iter(1..=3) {
+//!         total += x;
+//!     }
+//!     total
+//! }
+//! ```
 //!
 //! # Implementing Stream
 //!
@@ -122,6 +153,16 @@
 //! warning: unused result that must be used: streams do nothing unless polled
 //! ```
 
+mod adapters;
+mod collect;
+mod from_iter;
 mod stream;
 
-pub use stream::Stream;
+#[unstable(feature = "async_stream", issue = "79024")]
+pub use self::adapters::{Chain, Enumerate, Filter, Fuse, Map, Take, Zip};
+#[unstable(feature = "async_stream", issue = "79024")]
+pub use self::collect::{FromStream, IntoStream};
+#[unstable(feature = "async_stream", issue = "79024")]
+pub use self::from_iter::{from_iter, FromIter};
+#[unstable(feature = "async_stream", issue = "79024")]
+pub use self::stream::{Collect, Next, Stream};
@@ -0,0 +1,54 @@
+use crate::fmt;
+use crate::future::Future;
+use crate::pin::Pin;
+use crate::stream::{FromStream, Stream};
+use crate::task::{ready, Context, Poll};
+
+/// A future that collects all the elements of a stream.
+///
+/// This `struct` is created by the [`collect`] method on [`Stream`]. See its
+/// documentation for more.
+///
+/// [`collect`]: Stream::collect
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[unstable(feature = "async_stream", issue = "79024")]
+pub struct Collect<S, B> {
+    stream: S,
+    // `None` once the future has completed.
+    collection: Option<B>,
+}
+
+impl<S: Stream, B: FromStream<S::Item>> Collect<S, B> {
+    pub(super) fn new(stream: S) -> Collect<S, B> {
+        let (lower, _) = stream.size_hint();
+        Collect { stream, collection: Some(B::start_collect(lower)) }
+    }
+}
+
+#[unstable(feature = "async_stream", issue = "79024")]
+impl<S: fmt::Debug, B> fmt::Debug for Collect<S, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Collect").field("stream", &self.stream).finish_non_exhaustive()
+    }
+}
+
+#[unstable(feature = "async_stream", issue = "79024")]
+impl<S: Stream, B: FromStream<S::Item>> Future for Collect<S, B> {
+    type Output = B;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<B> {
+        // SAFETY: `stream` is pinned structurally: it is never moved out of a
+        // pinned `Collect`, which doesn't implement `Drop`. `collection` is
+        // not pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+        loop {
+            let item = ready!(stream.as_mut().poll_next(cx));
+            let collection = this.collection.as_mut().expect("`Collect` polled after completion");
+            match item {
+                Some(item) => collection.collect_item(item),
+                None => return Poll::Ready(this.collection.take().unwrap()),
+            }
+        }
+    }
+}
@@ -1,7 +1,17 @@
+mod collect;
+mod next;
+
 use crate::ops::DerefMut;
 use crate::pin::Pin;
+use crate::stream::adapters::{Chain, Enumerate, Filter, Fuse, Map, Take, Zip};
+use crate::stream::{FromStream, IntoStream};
 use crate::task::{Context, Poll};
 
+#[unstable(feature = "async_stream", issue = "79024")]
+pub use self::collect::Collect;
+#[unstable(feature = "async_stream", issue = "79024")]
+pub use self::next::Next;
+
 /// An interface for dealing with asynchronous iterators.
 ///
 /// This is the main stream trait. For more about the concept of streams
@@ -45,6 +55,7 @@ pub trait Stream {
     /// Rust's usual rules apply: calls must never cause undefined behavior
     /// (memory corruption, incorrect use of `unsafe` functions, or the like),
     /// regardless of the stream's state.
+    #[cfg_attr(not(bootstrap), lang = "poll_next")]
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>>;
 
     /// Returns the bounds on the remaining length of the stream.
@@ -77,6 +88,193 @@ pub trait Stream {
     fn size_hint(&self) -> (usize, Option<usize>) {
         (0, None)
     }
+
+    /// Returns a future that resolves to the next element of the stream, or
+    /// `None` once the stream is exhausted.
+    ///
+    /// This is the method consumers of a stream usually call, while
+    /// [`poll_next`] is the one implementors provide. The stream has to be
+    /// [`Unpin`]; pin it first, for example with [`Box::pin`], otherwise.
+    ///
+    /// [`poll_next`]: Stream::poll_next
+    /// [`Box::pin`]: ../../std/boxed/struct.Box.html#method.pin
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(async_stream)]
+    /// use std::stream::{self, Stream};
+    ///
+    /// async fn sum() -> i32 {
+    ///     let mut s = stream::from_iter(1..=3);
+    ///     let mut total = 0;
+    ///     while let Some(x) = s.next().await {
+    ///         total += x;
+    ///     }
+    ///     total
+    /// }
+    /// ```
+    #[inline]
+    #[unstable(feature = "async_stream", issue = "79024")]
+    fn next(&mut self) -> Next<'_, Self>
+    where
+        Self: Unpin,
+    {
+        Next::new(self)
+    }
+
+    /// Takes a closure and creates a stream which calls that closure on each
+    /// element.
+    ///
+    /// This is the asynchronous counterpart of [`Iterator::map`].
+    ///
+    /// [`Iterator::map`]: crate::iter::Iterator::map
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(async_stream)]
+    /// use std::stream::{self, Stream};
+    ///
+    /// let doubled = stream::from_iter(1..=3).map(|x| x * 2);
+    /// assert_eq!(doubled.size_hint(), (3, Some(3)));
+    /// ```
+    #[inline]
+    #[unstable(feature = "async_stream", issue = "79024")]
+    fn map<B, F>(self, f: F) -> Map<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(Self::Item) -> B,
+    {
+        Map::new(self, f)
+    }
+
+    /// Creates a stream which uses a closure to determine if an element
+    /// should be yielded.
+    ///
+    /// This is the asynchronous counterpart of [`Iterator::filter`].
+    ///
+    /// [`Iterator::filter`]: crate::iter::Iterator::filter
+    #[inline]
+    #[unstable(feature = "async_stream", issue = "79024")]
+    fn filter<P>(self, predicate: P) -> Filter<Self, P>
+    where
+        Self: Sized,
+        P: FnMut(&Self::Item) -> bool,
+    {
+        Filter::new(self, predicate)
+    }
+
+    /// Creates a stream that yields its first `n` elements.
+    ///
+    /// Once `n` elements have been yielded the underlying stream is not
+    /// polled anymore.
+    ///
+    /// This is the asynchronous counterpart of [`Iterator::take`].
+    ///
+    /// [`Iterator::take`]: crate::iter::Iterator::take
+    #[inline]
+    #[unstable(feature = "async_stream", issue = "79024")]
+    fn take(self, n: usize) -> Take<Self>
+    where
+        Self: Sized,
+    {
+        Take::new(self, n)
+    }
+
+    /// Creates a stream which gives the current count as well as the next
+    /// element.
+    ///
+    /// This is the asynchronous counterpart of [`Iterator::enumerate`].
+    ///
+    /// [`Iterator::enumerate`]: crate::iter::Iterator::enumerate
+    #[inline]
+    #[unstable(feature = "async_stream", issue = "79024")]
+    fn enumerate(self) -> Enumerate<Self>
+    where
+        Self: Sized,
+    {
+        Enumerate::new(self)
+    }
+
+    /// 'Zips up' two streams into a single stream of pairs.
+    ///
+    /// The new stream ends as soon as either stream ends. An element taken
+    /// from `self` is kept until `other` yields its counterpart, so neither
+    /// stream is polled further than needed.
+    ///
+    /// This is the asynchronous counterpart of [`Iterator::zip`].
+    ///
+    /// [`Iterator::zip`]: crate::iter::Iterator::zip
+    #[inline]
+    #[unstable(feature = "async_stream", issue = "79024")]
+    fn zip<U>(self, other: U) -> Zip<Self, U::IntoStream>
+    where
+        Self: Sized,
+        U: IntoStream,
+    {
+        Zip::new(self, other.into_stream())
+    }
+
+    /// Takes two streams and creates a new stream over both in sequence.
+    ///
+    /// This is the asynchronous counterpart of [`Iterator::chain`].
+    ///
+    /// [`Iterator::chain`]: crate::iter::Iterator::chain
+    #[inline]
+    #[unstable(feature = "async_stream", issue = "79024")]
+    fn chain<U>(self, other: U) -> Chain<Self, U::IntoStream>
+    where
+        Self: Sized,
+        U: IntoStream<Item = Self::Item>,
+    {
+        Chain::new(self, other.into_stream())
+    }
+
+    /// Creates a stream which ends after the first `None`.
+    ///
+    /// After a stream returns `Poll::Ready(None)`, future calls may or may
+    /// not yield `Poll::Ready(Some(T))` again. `fuse()` adapts a stream,
+    /// ensuring that after a `None` is given, it will always return `None`
+    /// forever, without polling the underlying stream again.
+    ///
+    /// This is the asynchronous counterpart of [`Iterator::fuse`].
+    ///
+    /// [`Iterator::fuse`]: crate::iter::Iterator::fuse
+    #[inline]
+    #[unstable(feature = "async_stream", issue = "79024")]
+    fn fuse(self) -> Fuse<Self>
+    where
+        Self: Sized,
+    {
+        Fuse::new(self)
+    }
+
+    /// Returns a future that transforms the stream into a collection.
+    ///
+    /// This is the asynchronous counterpart of [`Iterator::collect`]. The
+    /// target collection has to implement [`FromStream`].
+    ///
+    /// [`Iterator::collect`]: crate::iter::Iterator::collect
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(async_stream)]
+    /// use std::stream::{self, Stream};
+    ///
+    /// async fn evens() -> Vec<i32> {
+    ///     stream::from_iter(1..=6).filter(|x| x % 2 == 0).collect().await
+    /// }
+    /// ```
+    #[inline]
+    #[unstable(feature = "async_stream", issue = "79024")]
+    fn collect<B: FromStream<Self::Item>>(self) -> Collect<Self, B>
+    where
+        Self: Sized,
+    {
+        Collect::new(self)
+    }
 }
 
 #[unstable(feature = "async_stream", issue = "79024")]
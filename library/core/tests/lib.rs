@@ -3,6 +3,7 @@
 #![feature(array_methods)]
 #![feature(array_map)]
 #![feature(array_windows)]
+#![feature(async_stream)]
 #![feature(bool_to_option)]
 #![feature(bound_cloned)]
 #![feature(box_syntax)]
@@ -103,6 +104,7 @@
 mod slice;
 mod str;
 mod str_lossy;
+mod stream;
 mod task;
 mod time;
 mod tuple;
@@ -0,0 +1,122 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::ptr;
+use core::stream::{self, Stream};
+use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+fn noop_raw_waker() -> RawWaker {
+    fn clone(_: *const ()) -> RawWaker {
+        noop_raw_waker()
+    }
+    fn noop(_: *const ()) {}
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+    RawWaker::new(ptr::null(), &VTABLE)
+}
+
+fn block_on<F: Future>(mut fut: F) -> F::Output {
+    let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
+    let mut cx = Context::from_waker(&waker);
+    // SAFETY: `fut` is shadowed and never moved again.
+    let mut fut = unsafe { Pin::new_unchecked(&mut fut) };
+    loop {
+        if let Poll::Ready(output) = fut.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+/// A stream that returns `Poll::Pending` before each of its elements.
+struct Hesitant<I> {
+    iter: I,
+    ready: bool,
+}
+
+fn hesitant<I: IntoIterator>(iter: I) -> Hesitant<I::IntoIter> {
+    Hesitant { iter: iter.into_iter(), ready: false }
+}
+
+impl<I: Iterator + Unpin> Stream for Hesitant<I> {
+    type Item = I::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<I::Item>> {
+        if self.ready {
+            self.ready = false;
+            Poll::Ready(self.iter.next())
+        } else {
+            self.ready = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+#[test]
+fn from_iter_next() {
+    let mut s = stream::from_iter(vec![1, 2]);
+    assert_eq!(s.size_hint(), (2, Some(2)));
+    assert_eq!(block_on(s.next()), Some(1));
+    assert_eq!(block_on(s.next()), Some(2));
+    assert_eq!(block_on(s.next()), None);
+}
+
+#[test]
+fn collect() {
+    let v: Vec<i32> = block_on(hesitant(1..=4).collect());
+    assert_eq!(v, [1, 2, 3, 4]);
+    let s: String = block_on(stream::from_iter(vec!["ab", "c"]).collect());
+    assert_eq!(s, "abc");
+    let () = block_on(stream::from_iter(vec![(), ()]).collect());
+}
+
+#[test]
+fn map_filter_take() {
+    let s = hesitant(1..).map(|x| x * 3).filter(|x| x % 2 == 0).take(3);
+    let v: Vec<i32> = block_on(s.collect());
+    assert_eq!(v, [6, 12, 18]);
+}
+
+#[test]
+fn take_size_hint() {
+    assert_eq!(stream::from_iter(0..10).take(3).size_hint(), (3, Some(3)));
+    assert_eq!(stream::from_iter(0..2).take(3).size_hint(), (2, Some(2)));
+    assert_eq!(stream::from_iter(0..10).take(0).size_hint(), (0, Some(0)));
+    assert_eq!(stream::from_iter(0..10).filter(|_| true).size_hint(), (0, Some(10)));
+}
+
+#[test]
+fn zip() {
+    // The element taken from the first stream is kept while the second one
+    // is pending.
+    let s = stream::from_iter(vec!['a', 'b', 'c']).zip(hesitant(1..=2));
+    assert_eq!(s.size_hint(), (2, Some(2)));
+    let v: Vec<(char, i32)> = block_on(s.collect());
+    assert_eq!(v, [('a', 1), ('b', 2)]);
+}
+
+#[test]
+fn chain_enumerate() {
+    let s = hesitant(vec!['a', 'b']).chain(stream::from_iter(vec!['c'])).enumerate();
+    let v: Vec<(usize, char)> = block_on(s.collect());
+    assert_eq!(v, [(0, 'a'), (1, 'b'), (2, 'c')]);
+}
+
+#[test]
+fn fuse() {
+    /// A stream that resumes yielding after it returned `None`.
+    struct Flaky(u32);
+
+    impl Stream for Flaky {
+        type Item = u32;
+
+        fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<u32>> {
+            self.0 += 1;
+            Poll::Ready(if self.0 % 2 == 0 { None } else { Some(self.0) })
+        }
+    }
+
+    let mut s = Flaky(0).fuse();
+    assert_eq!(block_on(s.next()), Some(1));
+    assert_eq!(block_on(s.next()), None);
+    assert_eq!(block_on(s.next()), None);
+    assert_eq!(s.size_hint(), (0, Some(0)));
+}
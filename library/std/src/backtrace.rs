@@ -63,6 +63,21 @@
 //! variables is cached once the first backtrace is created, so altering
 //! `RUST_LIB_BACKTRACE` or `RUST_BACKTRACE` at runtime may not actually change
 //! how backtraces are captured.
+//!
+//! `RUST_BACKTRACE` also selects how the default panic hook prints the
+//! backtrace of a panicking thread: `full` prints every frame, `compact`
+//! additionally omits the frames of the standard library, and any other value
+//! but `0` omits the frames of the runtime before `main` and of the panic
+//! machinery.
+//!
+//! ## Inspecting frames
+//!
+//! Besides rendering a backtrace with `Display`, the frames of a captured
+//! backtrace can be inspected one by one through `Backtrace::frames`. Each
+//! `BacktraceFrame` exposes its instruction pointer and the symbols it
+//! resolved to, with their name, file, line and column. Symbols are resolved
+//! lazily, the first time a backtrace is rendered or its frames are
+//! requested, so capturing a backtrace that is never looked at stays cheap.
 
 #![unstable(feature = "backtrace", issue = "53487")]
 
@@ -99,9 +114,10 @@
 use crate::env;
 use crate::ffi::c_void;
 use crate::fmt;
+use crate::path::PathBuf;
 use crate::sync::atomic::{AtomicUsize, Ordering::SeqCst};
 use crate::sync::Once;
-use crate::sys_common::backtrace::{lock, output_filename};
+use crate::sys_common::backtrace::{filename_to_path, is_runtime_symbol, lock, output_filename};
 use crate::vec::Vec;
 
 /// A captured OS thread stack backtrace.
@@ -161,7 +177,9 @@ enum RawFrame {
     Fake,
 }
 
-struct BacktraceSymbol {
+/// A symbol a [`BacktraceFrame`] resolved to.
+#[unstable(feature = "backtrace_frames", issue = "79676")]
+pub struct BacktraceSymbol {
     name: Option<Vec<u8>>,
     filename: Option<BytesOrWide>,
     lineno: Option<u32>,
@@ -237,10 +255,7 @@ impl fmt::Debug for BytesOrWide {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         output_filename(
             fmt,
-            match self {
-                BytesOrWide::Bytes(w) => BytesOrWideString::Bytes(w),
-                BytesOrWide::Wide(w) => BytesOrWideString::Wide(w),
-            },
+            self.as_bows(),
             backtrace_rs::PrintFmt::Short,
             crate::env::current_dir().as_ref().ok(),
         )
@@ -365,13 +380,114 @@ pub fn status(&self) -> BacktraceStatus {
 }
 
 impl<'a> Backtrace {
-    /// Returns an iterator over the backtrace frames.
+    /// Returns the frames of the backtrace, innermost frame first.
+    ///
+    /// This resolves the symbols of every frame, unless that already happened
+    /// because the backtrace was rendered or its frames were requested before.
+    /// Returns an empty slice if the backtrace was not captured.
     #[unstable(feature = "backtrace_frames", issue = "79676")]
     pub fn frames(&'a self) -> &'a [BacktraceFrame] {
         if let Inner::Captured(c) = &self.inner { &c.force().frames } else { &[] }
     }
 }
 
+impl BacktraceFrame {
+    /// Returns the instruction pointer of the frame.
+    ///
+    /// For every frame but the innermost one this is usually the address of
+    /// the instruction following the call to the next frame.
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn ip(&self) -> *mut c_void {
+        self.frame.ip()
+    }
+
+    /// Returns the symbols the frame resolved to.
+    ///
+    /// A frame resolves to several symbols when functions were inlined into
+    /// it, innermost function first, and to none when no symbol information
+    /// is available for its instruction pointer.
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn symbols(&self) -> &[BacktraceSymbol] {
+        &self.symbols
+    }
+
+    /// Returns `true` if the frame is part of the Rust runtime: the standard
+    /// library crates and the entry points of the panic machinery.
+    ///
+    /// These are the frames the default panic hook omits when run with
+    /// `RUST_BACKTRACE=compact`. Frames without symbols are never considered
+    /// part of the runtime.
+    ///
+    /// # Examples
+    ///
+    /// A panic hook printing the function that panicked:
+    ///
+    /// ```
+    /// #![feature(backtrace, backtrace_frames)]
+    /// use std::backtrace::Backtrace;
+    /// use std::panic;
+    ///
+    /// panic::set_hook(Box::new(|_| {
+    ///     let backtrace = Backtrace::force_capture();
+    ///     // Skip capturing the backtrace, the hook itself and the panic machinery.
+    ///     let frame = backtrace
+    ///         .frames()
+    ///         .iter()
+    ///         .skip_while(|frame| frame.is_runtime())
+    ///         .skip_while(|frame| !frame.is_runtime())
+    ///         .find(|frame| !frame.is_runtime());
+    ///     if let Some(symbol) = frame.and_then(|frame| frame.symbols().first()) {
+    ///         eprintln!("panicked in {}", symbol.name().as_deref().unwrap_or("<unknown>"));
+    ///     }
+    /// }));
+    /// ```
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn is_runtime(&self) -> bool {
+        !self.symbols.is_empty() && self.symbols.iter().all(BacktraceSymbol::is_runtime)
+    }
+}
+
+impl BacktraceSymbol {
+    /// Returns the demangled name of the function, without the hash the
+    /// compiler appends to it.
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn name(&self) -> Option<String> {
+        self.name.as_ref().map(|b| format!("{:#}", backtrace_rs::SymbolName::new(b)))
+    }
+
+    /// Returns the path of the source file the function is defined in, as
+    /// recorded in the debug information.
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn filename(&self) -> Option<PathBuf> {
+        self.filename.as_ref().map(|f| filename_to_path(f.as_bows()).into_owned())
+    }
+
+    /// Returns the line number within [`filename`](BacktraceSymbol::filename).
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn lineno(&self) -> Option<u32> {
+        self.lineno
+    }
+
+    /// Returns the column number within [`filename`](BacktraceSymbol::filename).
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn colno(&self) -> Option<u32> {
+        self.colno
+    }
+
+    fn is_runtime(&self) -> bool {
+        self.name().map_or(false, |name| is_runtime_symbol(&name))
+    }
+}
+
+impl BytesOrWide {
+    fn as_bows(&self) -> BytesOrWideString<'_> {
+        match self {
+            BytesOrWide::Bytes(w) => BytesOrWideString::Bytes(w),
+            BytesOrWide::Wide(w) => BytesOrWideString::Wide(w),
+        }
+    }
+}
+
 impl fmt::Display for Backtrace {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         let capture = match &self.inner {
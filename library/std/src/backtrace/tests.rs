@@ -93,3 +93,50 @@ fn test_frames() {
 
     assert!(iter.all(|(f, e)| format!("{:#?}", f) == *e));
 }
+
+#[test]
+fn test_frame_accessors() {
+    let mut frames = generate_fake_frames();
+    frames.push(BacktraceFrame {
+        frame: RawFrame::Fake,
+        symbols: vec![BacktraceSymbol {
+            name: Some(b"<app::Config as core::str::FromStr>::from_str".to_vec()),
+            filename: Some(BytesOrWide::Bytes(b"src/config.rs".to_vec())),
+            lineno: Some(12),
+            colno: Some(9),
+        }],
+    });
+    frames.push(BacktraceFrame { frame: RawFrame::Fake, symbols: vec![] });
+    let backtrace = Backtrace {
+        inner: Inner::Captured(LazilyResolvedCapture::new(Capture {
+            actual_start: 1,
+            resolved: true,
+            frames,
+        })),
+    };
+
+    let frames = backtrace.frames();
+    assert_eq!(frames.len(), 5);
+    assert!(frames.iter().all(|frame| frame.ip() == 1 as *mut c_void));
+
+    let symbols = frames[2].symbols();
+    assert_eq!(symbols.len(), 2);
+    assert_eq!(symbols[0].name().as_deref(), Some("std::rt::lang_start_internal"));
+    assert_eq!(symbols[0].filename(), Some(PathBuf::from("rust/rt.rs")));
+    assert_eq!(symbols[0].lineno(), Some(300));
+    assert_eq!(symbols[0].colno(), Some(5));
+    assert_eq!(symbols[1].colno(), None);
+
+    let symbol = &frames[1].symbols()[0];
+    assert_eq!(symbol.name().as_deref(), Some("__rust_maybe_catch_panic"));
+    assert_eq!(symbol.filename(), None);
+    assert_eq!(symbol.lineno(), None);
+
+    let runtime: Vec<bool> = frames.iter().map(BacktraceFrame::is_runtime).collect();
+    assert_eq!(runtime, [true, true, true, false, false]);
+}
+
+#[test]
+fn test_disabled_frames() {
+    assert!(Backtrace::disabled().frames().is_empty());
+}
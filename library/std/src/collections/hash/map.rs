@@ -241,6 +241,37 @@ pub fn new() -> HashMap<K, V, RandomState> {
     pub fn with_capacity(capacity: usize) -> HashMap<K, V, RandomState> {
         HashMap::with_capacity_and_hasher(capacity, Default::default())
     }
+
+    /// Creates an empty `HashMap` with the specified capacity, returning an
+    /// error instead of panicking or aborting if the allocation fails.
+    ///
+    /// The hash map will be able to hold at least `capacity` elements without
+    /// reallocating. If `capacity` is 0, the hash map will not allocate.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(try_with_capacity)]
+    /// use std::collections::HashMap;
+    ///
+    /// let map: HashMap<&str, i32> = HashMap::try_with_capacity(10).expect("out of memory");
+    /// assert!(map.capacity() >= 10);
+    ///
+    /// assert!(HashMap::<u64, u64>::try_with_capacity(usize::MAX).is_err());
+    /// ```
+    #[inline]
+    #[unstable(feature = "try_with_capacity", issue = "none")]
+    pub fn try_with_capacity(capacity: usize) -> Result<HashMap<K, V, RandomState>, TryReserveError>
+    where
+        K: Eq + Hash,
+    {
+        HashMap::try_with_capacity_and_hasher(capacity, Default::default())
+    }
 }
 
 impl<K, V, S> HashMap<K, V, S> {
@@ -303,6 +334,49 @@ pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> HashMap<K,
         HashMap { base: base::HashMap::with_capacity_and_hasher(capacity, hash_builder) }
     }
 
+    /// Creates an empty `HashMap` with the specified capacity, using `hash_builder`
+    /// to hash the keys, returning an error instead of panicking or aborting if
+    /// the allocation fails.
+    ///
+    /// The hash map will be able to hold at least `capacity` elements without
+    /// reallocating. If `capacity` is 0, the hash map will not allocate.
+    ///
+    /// See [`with_capacity_and_hasher`] for the considerations that apply to
+    /// `hash_builder`.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(try_with_capacity)]
+    /// use std::collections::HashMap;
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let s = RandomState::new();
+    /// let mut map = HashMap::try_with_capacity_and_hasher(10, s).expect("out of memory");
+    /// map.insert(1, 2);
+    /// ```
+    ///
+    /// [`with_capacity_and_hasher`]: HashMap::with_capacity_and_hasher
+    #[inline]
+    #[unstable(feature = "try_with_capacity", issue = "none")]
+    pub fn try_with_capacity_and_hasher(
+        capacity: usize,
+        hash_builder: S,
+    ) -> Result<HashMap<K, V, S>, TryReserveError>
+    where
+        K: Eq + Hash,
+        S: BuildHasher,
+    {
+        let mut map = HashMap::with_hasher(hash_builder);
+        map.try_reserve(capacity)?;
+        Ok(map)
+    }
+
     /// Returns the number of elements the map can hold without reallocating.
     ///
     /// This number is a lower bound; the `HashMap<K, V>` might be able to hold
@@ -876,6 +950,100 @@ pub fn try_insert(&mut self, key: K, value: V) -> Result<&mut V, OccupiedError<'
         }
     }
 
+    /// Inserts a key-value pair into the map, returning an error instead of
+    /// panicking or aborting if the map needs to grow and the allocation fails.
+    ///
+    /// Otherwise this behaves like [`insert`]: if the map did not have this key
+    /// present, `Ok(None)` is returned, and if it did, the value is updated and
+    /// the old value is returned. The map is left unchanged on error.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(try_reserve)]
+    /// use std::collections::HashMap;
+    ///
+    /// let mut map = HashMap::new();
+    /// assert_eq!(map.try_reserve_and_insert(37, "a").expect("out of memory"), None);
+    /// assert_eq!(map.try_reserve_and_insert(37, "b").expect("out of memory"), Some("a"));
+    /// assert_eq!(map[&37], "b");
+    /// ```
+    ///
+    /// [`insert`]: HashMap::insert
+    #[unstable(feature = "try_reserve", reason = "new API", issue = "48043")]
+    pub fn try_reserve_and_insert(&mut self, k: K, v: V) -> Result<Option<V>, TryReserveError> {
+        let hash = {
+            let mut hasher = self.hasher().build_hasher();
+            k.hash(&mut hasher);
+            hasher.finish()
+        };
+        // A new key only needs to allocate if the table is full, and the table can't grow while
+        // the vacant entry is borrowed. In that case only, reserve and then probe for a free slot
+        // with the same hash, which doesn't compare any key.
+        let full = self.len() == self.capacity();
+        match self.raw_entry_mut().from_key_hashed_nocheck(hash, &k) {
+            RawEntryMut::Occupied(mut entry) => return Ok(Some(entry.insert(v))),
+            RawEntryMut::Vacant(entry) if !full => {
+                entry.insert_hashed_nocheck(hash, k, v);
+                return Ok(None);
+            }
+            RawEntryMut::Vacant(_) => {}
+        }
+        self.try_reserve(1)?;
+        match self.raw_entry_mut().from_hash(hash, |_| false) {
+            RawEntryMut::Vacant(entry) => entry.insert_hashed_nocheck(hash, k, v),
+            RawEntryMut::Occupied(_) => unreachable!(),
+        };
+        Ok(None)
+    }
+
+    /// Inserts the key-value pairs of `iter` into the map, returning an error
+    /// instead of panicking or aborting if the map needs to grow and the
+    /// allocation fails.
+    ///
+    /// This is the fallible counterpart of [`Extend::extend`], and of
+    /// [`collect`] when called on an empty map. On error, the pairs inserted
+    /// before the failure are kept in the map, and the rest of `iter` isn't
+    /// consumed.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(try_reserve)]
+    /// use std::collections::HashMap;
+    ///
+    /// let mut map = HashMap::new();
+    /// map.try_extend(vec![(1, "a"), (2, "b")]).expect("out of memory");
+    /// assert_eq!(map.len(), 2);
+    /// ```
+    ///
+    /// [`collect`]: Iterator::collect
+    #[unstable(feature = "try_reserve", reason = "new API", issue = "48043")]
+    pub fn try_extend<I: IntoIterator<Item = (K, V)>>(
+        &mut self,
+        iter: I,
+    ) -> Result<(), TryReserveError> {
+        let iter = iter.into_iter();
+        // As in `Extend`, only reserve half of the hint if the map isn't empty, since the keys
+        // may already be present.
+        let hint = iter.size_hint().0;
+        self.try_reserve(if self.is_empty() { hint } else { (hint + 1) / 2 })?;
+        for (k, v) in iter {
+            self.try_reserve_and_insert(k, v)?;
+        }
+        Ok(())
+    }
+
     /// Removes a key from the map, returning the value at the key if the key
     /// was previously in the map.
     ///
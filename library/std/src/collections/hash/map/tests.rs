@@ -832,6 +832,76 @@ fn test_try_reserve() {
     }
 }
 
+#[test]
+fn test_try_with_capacity() {
+    let map: HashMap<u8, u8> = HashMap::try_with_capacity(10).unwrap();
+    assert!(map.is_empty());
+    assert!(map.capacity() >= 10);
+
+    if let Err(CapacityOverflow) = HashMap::<u8, u8>::try_with_capacity(usize::MAX) {
+    } else {
+        panic!("usize::MAX should trigger an overflow!");
+    }
+
+    if let Err(AllocError { .. }) = HashMap::<u8, u8>::try_with_capacity(usize::MAX / 8) {
+    } else {
+        panic!("usize::MAX / 8 should trigger an OOM!")
+    }
+}
+
+#[test]
+fn test_try_reserve_and_insert() {
+    let mut map = HashMap::new();
+    assert_eq!(map.try_reserve_and_insert(1, 2), Ok(None));
+    assert_eq!(map.try_reserve_and_insert(1, 3), Ok(Some(2)));
+    assert_eq!(map.try_reserve_and_insert(2, 4), Ok(None));
+    assert_eq!(map.len(), 2);
+    assert_eq!(map[&1], 3);
+
+    // Replacing a value in a full map doesn't grow it, adding a key does.
+    let mut map = HashMap::new();
+    while map.len() < map.capacity() || map.is_empty() {
+        map.try_reserve_and_insert(map.len(), 0).unwrap();
+    }
+    let capacity = map.capacity();
+    assert_eq!(map.try_reserve_and_insert(0, 1), Ok(Some(0)));
+    assert_eq!(map.capacity(), capacity);
+    assert_eq!(map.try_reserve_and_insert(capacity, 1), Ok(None));
+    assert!(map.capacity() > capacity);
+    assert_eq!(map.len(), capacity + 1);
+    assert!((0..=capacity).all(|i| map.contains_key(&i)));
+}
+
+#[test]
+fn test_try_extend() {
+    /// An iterator claiming more elements than can ever be allocated.
+    struct Huge;
+
+    impl Iterator for Huge {
+        type Item = (u8, u8);
+
+        fn next(&mut self) -> Option<(u8, u8)> {
+            None
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (usize::MAX, None)
+        }
+    }
+
+    let mut map = HashMap::new();
+    map.try_extend((0..10).map(|i| (i, i))).unwrap();
+    map.try_extend((5..15).map(|i| (i, i + 1))).unwrap();
+    assert_eq!(map.len(), 15);
+    assert_eq!(map[&4], 4);
+    assert_eq!(map[&5], 6);
+
+    if let Err(CapacityOverflow) = HashMap::new().try_extend(Huge) {
+    } else {
+        panic!("usize::MAX should trigger an overflow!");
+    }
+}
+
 #[test]
 fn test_raw_entry() {
     use super::RawEntryMut::{Occupied, Vacant};
@@ -150,6 +150,35 @@ pub fn new() -> HashSet<T, RandomState> {
     pub fn with_capacity(capacity: usize) -> HashSet<T, RandomState> {
         HashSet { base: base::HashSet::with_capacity_and_hasher(capacity, Default::default()) }
     }
+
+    /// Creates an empty `HashSet` with the specified capacity, returning an
+    /// error instead of panicking or aborting if the allocation fails.
+    ///
+    /// The hash set will be able to hold at least `capacity` elements without
+    /// reallocating. If `capacity` is 0, the hash set will not allocate.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(try_with_capacity)]
+    /// use std::collections::HashSet;
+    ///
+    /// let set: HashSet<i32> = HashSet::try_with_capacity(10).expect("out of memory");
+    /// assert!(set.capacity() >= 10);
+    /// ```
+    #[inline]
+    #[unstable(feature = "try_with_capacity", issue = "none")]
+    pub fn try_with_capacity(capacity: usize) -> Result<HashSet<T, RandomState>, TryReserveError>
+    where
+        T: Eq + Hash,
+    {
+        HashSet::try_with_capacity_and_hasher(capacity, Default::default())
+    }
 }
 
 impl<T, S> HashSet<T, S> {
@@ -368,6 +397,44 @@ pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> HashSet<T, S> {
         HashSet { base: base::HashSet::with_capacity_and_hasher(capacity, hasher) }
     }
 
+    /// Creates an empty `HashSet` with the specified capacity, using
+    /// `hasher` to hash the keys, returning an error instead of panicking or
+    /// aborting if the allocation fails.
+    ///
+    /// The hash set will be able to hold at least `capacity` elements without
+    /// reallocating. If `capacity` is 0, the hash set will not allocate.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(try_with_capacity)]
+    /// use std::collections::HashSet;
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let s = RandomState::new();
+    /// let mut set = HashSet::try_with_capacity_and_hasher(10, s).expect("out of memory");
+    /// set.insert(1);
+    /// ```
+    #[inline]
+    #[unstable(feature = "try_with_capacity", issue = "none")]
+    pub fn try_with_capacity_and_hasher(
+        capacity: usize,
+        hasher: S,
+    ) -> Result<HashSet<T, S>, TryReserveError>
+    where
+        T: Eq + Hash,
+        S: BuildHasher,
+    {
+        let mut set = HashSet::with_hasher(hasher);
+        set.try_reserve(capacity)?;
+        Ok(set)
+    }
+
     /// Returns a reference to the set's [`BuildHasher`].
     ///
     /// # Examples
@@ -836,6 +903,83 @@ pub fn insert(&mut self, value: T) -> bool {
         self.base.insert(value)
     }
 
+    /// Adds a value to the set, returning an error instead of panicking or
+    /// aborting if the set needs to grow and the allocation fails.
+    ///
+    /// Otherwise this behaves like [`insert`]: `Ok(true)` is returned if the set
+    /// did not have this value present, `Ok(false)` if it did. The set is left
+    /// unchanged on error.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(try_reserve)]
+    /// use std::collections::HashSet;
+    ///
+    /// let mut set = HashSet::new();
+    /// assert_eq!(set.try_reserve_and_insert(2).expect("out of memory"), true);
+    /// assert_eq!(set.try_reserve_and_insert(2).expect("out of memory"), false);
+    /// ```
+    ///
+    /// [`insert`]: HashSet::insert
+    #[inline]
+    #[unstable(feature = "try_reserve", reason = "new API", issue = "48043")]
+    pub fn try_reserve_and_insert(&mut self, value: T) -> Result<bool, TryReserveError> {
+        // Adding a value only allocates if the table is full, so only then look it up before
+        // reserving room for it: otherwise `insert` does the only lookup.
+        if self.base.len() == self.base.capacity() {
+            if self.base.contains(&value) {
+                return Ok(false);
+            }
+            self.try_reserve(1)?;
+        }
+        Ok(self.base.insert(value))
+    }
+
+    /// Adds the values of `iter` to the set, returning an error instead of
+    /// panicking or aborting if the set needs to grow and the allocation fails.
+    ///
+    /// This is the fallible counterpart of [`Extend::extend`], and of
+    /// [`collect`] when called on an empty set. On error, the values added
+    /// before the failure are kept in the set, and the rest of `iter` isn't
+    /// consumed.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(try_reserve)]
+    /// use std::collections::HashSet;
+    ///
+    /// let mut set = HashSet::new();
+    /// set.try_extend(vec![1, 2, 2]).expect("out of memory");
+    /// assert_eq!(set.len(), 2);
+    /// ```
+    ///
+    /// [`collect`]: Iterator::collect
+    #[unstable(feature = "try_reserve", reason = "new API", issue = "48043")]
+    pub fn try_extend<I: IntoIterator<Item = T>>(
+        &mut self,
+        iter: I,
+    ) -> Result<(), TryReserveError> {
+        let iter = iter.into_iter();
+        let hint = iter.size_hint().0;
+        self.try_reserve(if self.is_empty() { hint } else { (hint + 1) / 2 })?;
+        for value in iter {
+            self.try_reserve_and_insert(value)?;
+        }
+        Ok(())
+    }
+
     /// Adds a value to the set, replacing the existing value, if any, that is equal to the given
     /// one. Returns the replaced value.
     ///
@@ -484,3 +484,20 @@ fn drop(&mut self) {
     assert_eq!(DROPS.load(Ordering::SeqCst), 3);
     assert_eq!(set.len(), 0);
 }
+
+#[test]
+fn test_try_reserve_and_insert() {
+    let mut set = HashSet::new();
+    while set.len() < set.capacity() || set.is_empty() {
+        assert_eq!(set.try_reserve_and_insert(set.len()), Ok(true));
+    }
+    let capacity = set.capacity();
+    assert_eq!(set.try_reserve_and_insert(0), Ok(false));
+    assert_eq!(set.capacity(), capacity);
+    assert_eq!(set.try_reserve_and_insert(capacity), Ok(true));
+    assert_eq!(set.len(), capacity + 1);
+
+    let mut set = HashSet::new();
+    set.try_extend(vec![1, 2, 2, 3]).unwrap();
+    assert_eq!(set.len(), 3);
+}
@@ -21,11 +21,12 @@
 
 use crate::alloc::{AllocError, LayoutError};
 use crate::any::TypeId;
-use crate::backtrace::Backtrace;
+use crate::backtrace::{Backtrace, BacktraceStatus};
 use crate::borrow::Cow;
 use crate::cell;
 use crate::char;
-use crate::fmt::{self, Debug, Display};
+use crate::fmt::{self, Debug, Display, Write};
+use crate::marker::PhantomData;
 use crate::mem::transmute;
 use crate::num;
 use crate::str;
@@ -135,6 +136,56 @@ fn backtrace(&self) -> Option<&Backtrace> {
         None
     }
 
+    /// Provides type based access to context intended for error reports.
+    ///
+    /// Used in conjunction with [`request_ref`] and [`request_value`] to
+    /// extract references to member variables, or values computed from them,
+    /// from `dyn Error` trait objects. Implementations answer a [`Request`] by
+    /// calling its `provide_*` methods; only the first value of the requested
+    /// type is kept, so errors that wrap another error should provide their own
+    /// members before forwarding the request to the inner error.
+    ///
+    /// [`request_ref`]: trait.Error.html#method.request_ref
+    /// [`request_value`]: trait.Error.html#method.request_value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(error_generic_member_access)]
+    /// use std::error::{Error, Request};
+    /// use std::fmt;
+    ///
+    /// #[derive(Debug)]
+    /// struct Status(u16);
+    ///
+    /// #[derive(Debug)]
+    /// struct HttpError {
+    ///     status: Status,
+    ///     url: String,
+    /// }
+    ///
+    /// impl fmt::Display for HttpError {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         write!(f, "request to {} failed", self.url)
+    ///     }
+    /// }
+    ///
+    /// impl Error for HttpError {
+    ///     fn provide<'a>(&'a self, request: &mut Request<'a>) {
+    ///         request.provide_ref::<Status>(&self.status).provide_ref::<str>(&self.url);
+    ///     }
+    /// }
+    ///
+    /// let error = HttpError { status: Status(404), url: "https://example.com".to_string() };
+    /// let error = &error as &(dyn Error + 'static);
+    /// assert_eq!(error.request_ref::<Status>().unwrap().0, 404);
+    /// assert_eq!(error.request_ref::<str>(), Some("https://example.com"));
+    /// assert!(error.request_value::<u16>().is_none());
+    /// ```
+    #[unstable(feature = "error_generic_member_access", issue = "none")]
+    #[allow(unused_variables)]
+    fn provide<'a>(&'a self, request: &mut Request<'a>) {}
+
     /// ```
     /// if let Err(e) = "xc".parse::<u32>() {
     ///     // Print `e` itself, no need for description().
@@ -510,6 +561,10 @@ fn cause(&self) -> Option<&dyn Error> {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         Error::source(&**self)
     }
+
+    fn provide<'a>(&'a self, request: &mut Request<'a>) {
+        Error::provide(&**self, request)
+    }
 }
 
 #[stable(feature = "error_by_ref", since = "1.51.0")]
@@ -531,6 +586,10 @@ fn source(&self) -> Option<&(dyn Error + 'static)> {
     fn backtrace(&self) -> Option<&Backtrace> {
         Error::backtrace(&**self)
     }
+
+    fn provide<'b>(&'b self, request: &mut Request<'b>) {
+        Error::provide(&**self, request)
+    }
 }
 
 #[stable(feature = "arc_error", since = "1.52.0")]
@@ -552,6 +611,10 @@ fn source(&self) -> Option<&(dyn Error + 'static)> {
     fn backtrace(&self) -> Option<&Backtrace> {
         Error::backtrace(&**self)
     }
+
+    fn provide<'a>(&'a self, request: &mut Request<'a>) {
+        Error::provide(&**self, request)
+    }
 }
 
 #[stable(feature = "fmt_error", since = "1.11.0")]
@@ -636,6 +699,26 @@ pub fn downcast_mut<T: Error + 'static>(&mut self) -> Option<&mut T> {
             None
         }
     }
+
+    /// Requests a reference of type `T` as context for this error.
+    ///
+    /// Returns `None` if the error does not [provide](Error::provide) a
+    /// reference of that type. Only this error is asked, not its sources.
+    #[unstable(feature = "error_generic_member_access", issue = "none")]
+    #[inline]
+    pub fn request_ref<T: ?Sized + 'static>(&self) -> Option<&T> {
+        request_ref(self)
+    }
+
+    /// Requests a value of type `T` as context for this error.
+    ///
+    /// Returns `None` if the error does not [provide](Error::provide) a value
+    /// of that type. Only this error is asked, not its sources.
+    #[unstable(feature = "error_generic_member_access", issue = "none")]
+    #[inline]
+    pub fn request_value<T: 'static>(&self) -> Option<T> {
+        request_value(self)
+    }
 }
 
 impl dyn Error + 'static + Send {
@@ -659,6 +742,20 @@ pub fn downcast_ref<T: Error + 'static>(&self) -> Option<&T> {
     pub fn downcast_mut<T: Error + 'static>(&mut self) -> Option<&mut T> {
         <dyn Error + 'static>::downcast_mut::<T>(self)
     }
+
+    /// Forwards to the method defined on the type `dyn Error`.
+    #[unstable(feature = "error_generic_member_access", issue = "none")]
+    #[inline]
+    pub fn request_ref<T: ?Sized + 'static>(&self) -> Option<&T> {
+        <dyn Error + 'static>::request_ref::<T>(self)
+    }
+
+    /// Forwards to the method defined on the type `dyn Error`.
+    #[unstable(feature = "error_generic_member_access", issue = "none")]
+    #[inline]
+    pub fn request_value<T: 'static>(&self) -> Option<T> {
+        <dyn Error + 'static>::request_value::<T>(self)
+    }
 }
 
 impl dyn Error + 'static + Send + Sync {
@@ -682,6 +779,20 @@ pub fn downcast_ref<T: Error + 'static>(&self) -> Option<&T> {
     pub fn downcast_mut<T: Error + 'static>(&mut self) -> Option<&mut T> {
         <dyn Error + 'static>::downcast_mut::<T>(self)
     }
+
+    /// Forwards to the method defined on the type `dyn Error`.
+    #[unstable(feature = "error_generic_member_access", issue = "none")]
+    #[inline]
+    pub fn request_ref<T: ?Sized + 'static>(&self) -> Option<&T> {
+        <dyn Error + 'static>::request_ref::<T>(self)
+    }
+
+    /// Forwards to the method defined on the type `dyn Error`.
+    #[unstable(feature = "error_generic_member_access", issue = "none")]
+    #[inline]
+    pub fn request_value<T: 'static>(&self) -> Option<T> {
+        <dyn Error + 'static>::request_value::<T>(self)
+    }
 }
 
 impl dyn Error {
@@ -778,6 +889,115 @@ fn next(&mut self) -> Option<Self::Item> {
     }
 }
 
+/// A request for context of a particular type, answered by
+/// [`Error::provide`].
+///
+/// Requests are made through [`request_ref`] and [`request_value`]. Each one
+/// asks for a single type, either as a reference borrowed from the error for
+/// `'a` or as an owned value, and keeps the first answer of that type.
+///
+/// [`request_ref`]: trait.Error.html#method.request_ref
+/// [`request_value`]: trait.Error.html#method.request_value
+#[unstable(feature = "error_generic_member_access", issue = "none")]
+pub struct Request<'a> {
+    // The `TypeId` of `RefTag<T>` or `ValueTag<T>` for the requested `T`.
+    tag: TypeId,
+    // Points to the `Option<&'a T>` or `Option<T>` the answer is written to.
+    slot: *mut (),
+    // `'a` is invariant, so a provider can only answer with references that
+    // live exactly as long as the requester expects.
+    _marker: PhantomData<fn(&'a ()) -> &'a ()>,
+}
+
+// Marker types whose `TypeId`s identify what a `Request` asks for.
+struct RefTag<T: ?Sized>(PhantomData<T>);
+struct ValueTag<T>(PhantomData<T>);
+
+impl<'a> Request<'a> {
+    /// Provides a reference of type `T`.
+    ///
+    /// Does nothing if the request is for another type, or has already been
+    /// answered.
+    #[unstable(feature = "error_generic_member_access", issue = "none")]
+    pub fn provide_ref<T: ?Sized + 'static>(&mut self, value: &'a T) -> &mut Self {
+        // SAFETY: requests tagged with `RefTag<T>` are answered in an `Option<&'a T>`.
+        if let Some(slot) = unsafe { self.slot::<RefTag<T>, &'a T>() } {
+            slot.get_or_insert(value);
+        }
+        self
+    }
+
+    /// Provides a value of type `T`.
+    ///
+    /// Does nothing if the request is for another type, or has already been
+    /// answered.
+    #[unstable(feature = "error_generic_member_access", issue = "none")]
+    pub fn provide_value<T: 'static>(&mut self, value: T) -> &mut Self {
+        self.provide_value_with(|| value)
+    }
+
+    /// Provides a value of type `T`, computed by `f` only if it is requested.
+    #[unstable(feature = "error_generic_member_access", issue = "none")]
+    pub fn provide_value_with<T: 'static>(&mut self, f: impl FnOnce() -> T) -> &mut Self {
+        // SAFETY: requests tagged with `ValueTag<T>` are answered in an `Option<T>`.
+        if let Some(slot) = unsafe { self.slot::<ValueTag<T>, T>() } {
+            if slot.is_none() {
+                *slot = Some(f());
+            }
+        }
+        self
+    }
+
+    /// Returns the slot the answer is written to if the request is tagged
+    /// with `Tag`.
+    ///
+    /// # Safety
+    ///
+    /// Requests tagged with `Tag` must be answered in an `Option<T>`.
+    unsafe fn slot<Tag: ?Sized + 'static, T>(&mut self) -> Option<&mut Option<T>> {
+        if self.tag == TypeId::of::<Tag>() {
+            // The caller guarantees that `slot` points to an `Option<T>`, which
+            // `request_ref` or `request_value` keeps alive for the whole request.
+            Some(&mut *(self.slot as *mut Option<T>))
+        } else {
+            None
+        }
+    }
+}
+
+#[unstable(feature = "error_generic_member_access", issue = "none")]
+impl fmt::Debug for Request<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Request").finish_non_exhaustive()
+    }
+}
+
+fn request_ref<'a, T, E>(error: &'a E) -> Option<&'a T>
+where
+    T: ?Sized + 'static,
+    E: Error + ?Sized,
+{
+    let mut slot: Option<&'a T> = None;
+    let mut request = Request {
+        tag: TypeId::of::<RefTag<T>>(),
+        slot: &mut slot as *mut Option<&'a T> as *mut (),
+        _marker: PhantomData,
+    };
+    error.provide(&mut request);
+    slot
+}
+
+fn request_value<T: 'static, E: Error + ?Sized>(error: &E) -> Option<T> {
+    let mut slot: Option<T> = None;
+    let mut request = Request {
+        tag: TypeId::of::<ValueTag<T>>(),
+        slot: &mut slot as *mut Option<T> as *mut (),
+        _marker: PhantomData,
+    };
+    error.provide(&mut request);
+    slot
+}
+
 impl dyn Error + Send {
     #[inline]
     #[stable(feature = "error_downcast", since = "1.3.0")]
@@ -803,3 +1023,272 @@ pub fn downcast<T: Error + 'static>(self: Box<Self>) -> Result<Box<T>, Box<Self>
         })
     }
 }
+
+/// An error reporter that prints an error and its sources.
+///
+/// `Report` formats the error, followed by the chain of errors returned by
+/// [`Error::source`]. By default the whole chain is printed on a single line,
+/// separated by colons:
+///
+/// ```text
+/// could not load config: failed to read `app.toml`: permission denied
+/// ```
+///
+/// With [`pretty`](Report::pretty) enabled every source gets its own line,
+/// numbered if there is more than one:
+///
+/// ```text
+/// could not load config
+///
+/// Caused by:
+///    0: failed to read `app.toml`
+///    1: permission denied
+/// ```
+///
+/// With [`show_backtrace`](Report::show_backtrace) enabled as well, the
+/// pretty report ends with the first captured [`Backtrace`] found in the
+/// chain, either returned by [`Error::backtrace`] or [provided](Error::provide)
+/// by one of the errors.
+///
+/// The [`Debug`] representation of a `Report` is the same as its [`Display`]
+/// representation, so returning `Result<(), Report>` from `main` prints the
+/// report when `main` fails.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(error_reporter)]
+/// use std::error::{Error, Report};
+/// use std::fmt;
+///
+/// #[derive(Debug)]
+/// struct ConfigError {
+///     source: std::io::Error,
+/// }
+///
+/// impl fmt::Display for ConfigError {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         f.write_str("could not load config")
+///     }
+/// }
+///
+/// impl Error for ConfigError {
+///     fn source(&self) -> Option<&(dyn Error + 'static)> {
+///         Some(&self.source)
+///     }
+/// }
+///
+/// let error = ConfigError {
+///     source: std::io::Error::new(std::io::ErrorKind::Other, "permission denied"),
+/// };
+/// let report = Report::new(error);
+/// assert_eq!(report.to_string(), "could not load config: permission denied");
+///
+/// let report = report.pretty(true);
+/// assert_eq!(report.to_string(), "could not load config\n\nCaused by:\n      permission denied");
+/// ```
+///
+/// Using `Report` as the error type of `main`:
+///
+/// ```should_panic
+/// #![feature(error_reporter)]
+/// use std::error::Report;
+///
+/// fn main() -> Result<(), Report> {
+///     let _: u32 = "NaN".parse()?;
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "error_reporter", issue = "none")]
+pub struct Report<E = Box<dyn Error>> {
+    error: E,
+    show_backtrace: bool,
+    pretty: bool,
+}
+
+impl<E> Report<E>
+where
+    Report<E>: From<E>,
+{
+    /// Creates a new `Report` from an input error.
+    #[unstable(feature = "error_reporter", issue = "none")]
+    pub fn new(error: E) -> Report<E> {
+        Self::from(error)
+    }
+}
+
+impl<E> Report<E> {
+    /// Enables or disables the multi-line format.
+    #[unstable(feature = "error_reporter", issue = "none")]
+    pub fn pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+
+    /// Enables or disables printing the backtrace of the error.
+    ///
+    /// The backtrace is only printed in the multi-line format, and only if it
+    /// was actually captured.
+    #[unstable(feature = "error_reporter", issue = "none")]
+    pub fn show_backtrace(mut self, show_backtrace: bool) -> Self {
+        self.show_backtrace = show_backtrace;
+        self
+    }
+
+    fn fmt_error(&self, error: &dyn Error, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.pretty { self.fmt_multiline(error, f) } else { self.fmt_singleline(error, f) }
+    }
+
+    fn fmt_singleline(&self, error: &dyn Error, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", error)?;
+
+        let sources = error.source().into_iter().flat_map(<dyn Error>::chain);
+        for source in sources {
+            write!(f, ": {}", source)?;
+        }
+
+        Ok(())
+    }
+
+    fn fmt_multiline(&self, error: &dyn Error, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", error)?;
+
+        if let Some(source) = error.source() {
+            f.write_str("\n\nCaused by:")?;
+
+            let multiple = source.source().is_some();
+            for (index, source) in source.chain().enumerate() {
+                f.write_str("\n")?;
+                let mut indented = Indented { inner: f };
+                if multiple {
+                    write!(indented, "{: >4}: {}", index, source)?;
+                } else {
+                    write!(indented, "      {}", source)?;
+                }
+            }
+        }
+
+        if self.show_backtrace {
+            if let Some(backtrace) = find_backtrace(error) {
+                let backtrace = backtrace.to_string();
+                f.write_str("\n\nStack backtrace:\n")?;
+                f.write_str(backtrace.trim_end())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Returns the first captured backtrace of `error` or one of its sources.
+fn find_backtrace<'a>(error: &'a dyn Error) -> Option<&'a Backtrace> {
+    // `error` itself may not be `'static`, so it can't be part of the `chain`.
+    let backtrace = |error: &'a dyn Error| {
+        error
+            .backtrace()
+            .or_else(|| request_ref::<Backtrace, _>(error))
+            .filter(|backtrace| backtrace.status() == BacktraceStatus::Captured)
+    };
+    backtrace(error).or_else(|| {
+        let sources = error.source().into_iter().flat_map(<dyn Error>::chain);
+        sources.find_map(|source| backtrace(source))
+    })
+}
+
+#[unstable(feature = "error_reporter", issue = "none")]
+impl<E> From<E> for Report<E>
+where
+    E: Error,
+{
+    fn from(error: E) -> Self {
+        Report { error, show_backtrace: false, pretty: false }
+    }
+}
+
+#[unstable(feature = "error_reporter", issue = "none")]
+impl<'a, E> From<E> for Report<Box<dyn Error + 'a>>
+where
+    E: Error + 'a,
+{
+    fn from(error: E) -> Self {
+        Report { error: Box::new(error), show_backtrace: false, pretty: false }
+    }
+}
+
+#[unstable(feature = "error_reporter", issue = "none")]
+impl<'a, E> From<E> for Report<Box<dyn Error + Send + Sync + 'a>>
+where
+    E: Error + Send + Sync + 'a,
+{
+    fn from(error: E) -> Self {
+        Report { error: Box::new(error), show_backtrace: false, pretty: false }
+    }
+}
+
+#[unstable(feature = "error_reporter", issue = "none")]
+impl<'a> From<Box<dyn Error + 'a>> for Report<Box<dyn Error + 'a>> {
+    fn from(error: Box<dyn Error + 'a>) -> Self {
+        Report { error, show_backtrace: false, pretty: false }
+    }
+}
+
+#[unstable(feature = "error_reporter", issue = "none")]
+impl<'a> From<Box<dyn Error + Send + Sync + 'a>> for Report<Box<dyn Error + Send + Sync + 'a>> {
+    fn from(error: Box<dyn Error + Send + Sync + 'a>) -> Self {
+        Report { error, show_backtrace: false, pretty: false }
+    }
+}
+
+#[unstable(feature = "error_reporter", issue = "none")]
+impl<E> fmt::Display for Report<E>
+where
+    E: Error,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_error(&self.error, f)
+    }
+}
+
+#[unstable(feature = "error_reporter", issue = "none")]
+impl fmt::Display for Report<Box<dyn Error + '_>> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_error(&*self.error, f)
+    }
+}
+
+#[unstable(feature = "error_reporter", issue = "none")]
+impl fmt::Display for Report<Box<dyn Error + Send + Sync + '_>> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_error(&*self.error, f)
+    }
+}
+
+// This type intentionally outputs the same format for `Display` and `Debug`,
+// for situations where you unwrap a `Report` or return it from main.
+#[unstable(feature = "error_reporter", issue = "none")]
+impl<E> fmt::Debug for Report<E>
+where
+    Report<E>: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Indents every line written through it but the first one, to line up the
+/// continuation lines of multi-line error messages in a pretty `Report`.
+struct Indented<'a, D> {
+    inner: &'a mut D,
+}
+
+impl<D: fmt::Write> fmt::Write for Indented<'_, D> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for (i, line) in s.split('\n').enumerate() {
+            if i > 0 {
+                self.inner.write_str("\n      ")?;
+            }
+            self.inner.write_str(line)?;
+        }
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
-use super::Error;
+use super::{Error, Report, Request};
+use crate::backtrace::{Backtrace, BacktraceStatus};
 use crate::fmt;
 
 #[derive(Debug, PartialEq)]
@@ -35,3 +36,178 @@ fn downcasting() {
         Err(e) => assert_eq!(*e.downcast::<A>().unwrap(), A),
     }
 }
+
+#[derive(Debug)]
+struct Layer {
+    message: &'static str,
+    source: Option<Box<Layer>>,
+    backtrace: Option<Backtrace>,
+    status: u16,
+}
+
+impl Layer {
+    fn new(message: &'static str, source: Option<Layer>) -> Layer {
+        Layer { message, source: source.map(Box::new), backtrace: None, status: 500 }
+    }
+}
+
+impl fmt::Display for Layer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message)
+    }
+}
+
+impl Error for Layer {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source.as_ref().map(|source| &**source as &(dyn Error + 'static))
+    }
+
+    fn provide<'a>(&'a self, request: &mut Request<'a>) {
+        request.provide_value(self.status).provide_ref::<str>(self.message);
+        if let Some(backtrace) = &self.backtrace {
+            request.provide_ref(backtrace);
+        }
+    }
+}
+
+fn three_layers() -> Layer {
+    let inner = Layer::new("permission denied", None);
+    let middle = Layer::new("failed to read `app.toml`", Some(inner));
+    Layer::new("could not load config", Some(middle))
+}
+
+#[test]
+fn request_members() {
+    let error = three_layers();
+    let error = &error as &(dyn Error + 'static);
+    assert_eq!(error.request_value::<u16>(), Some(500));
+    assert_eq!(error.request_ref::<str>(), Some("could not load config"));
+    assert_eq!(error.request_ref::<u16>(), None);
+    assert_eq!(error.request_value::<String>(), None);
+    assert!(error.request_ref::<Backtrace>().is_none());
+
+    // Wrappers forward the request to the error they wrap.
+    let boxed: Box<dyn Error + Send + Sync> = Box::new(Box::new(three_layers()));
+    assert_eq!(boxed.request_value::<u16>(), Some(500));
+}
+
+#[test]
+fn request_keeps_first_answer() {
+    #[derive(Debug)]
+    struct Outer(Layer);
+
+    impl fmt::Display for Outer {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("outer")
+        }
+    }
+
+    impl Error for Outer {
+        fn provide<'a>(&'a self, request: &mut Request<'a>) {
+            request.provide_value::<u16>(404).provide_value_with::<u32>(|| panic!("not requested"));
+            self.0.provide(request);
+        }
+    }
+
+    let error = Outer(three_layers());
+    let error = &error as &(dyn Error + 'static);
+    assert_eq!(error.request_value::<u16>(), Some(404));
+    assert_eq!(error.request_ref::<str>(), Some("could not load config"));
+}
+
+#[test]
+fn single_line_report() {
+    let report = Report::new(three_layers());
+    let expected = "could not load config: failed to read `app.toml`: permission denied";
+    assert_eq!(report.to_string(), expected);
+    assert_eq!(format!("{:?}", report), expected);
+
+    let report = Report::new(Layer::new("could not load config", None));
+    assert_eq!(report.to_string(), "could not load config");
+}
+
+#[test]
+fn pretty_report() {
+    let report = Report::new(three_layers()).pretty(true);
+    let expected = "\
+could not load config
+
+Caused by:
+   0: failed to read `app.toml`
+   1: permission denied";
+    assert_eq!(report.to_string(), expected);
+
+    let inner = Layer::new("permission denied", None);
+    let report = Report::new(Layer::new("could not load config", Some(inner))).pretty(true);
+    let expected = "\
+could not load config
+
+Caused by:
+      permission denied";
+    assert_eq!(report.to_string(), expected);
+}
+
+#[test]
+fn pretty_report_indents_multi_line_sources() {
+    let inner = Layer::new("permission denied\nwhile opening `app.toml`", None);
+    let middle = Layer::new("failed to read config", Some(inner));
+    let report = Report::new(Layer::new("could not start", Some(middle))).pretty(true);
+    let expected = "\
+could not start
+
+Caused by:
+   0: failed to read config
+   1: permission denied
+      while opening `app.toml`";
+    assert_eq!(report.to_string(), expected);
+}
+
+#[test]
+fn report_backtrace() {
+    let mut inner = Layer::new("permission denied", None);
+    let backtrace = Backtrace::force_capture();
+    let captured = backtrace.status() == BacktraceStatus::Captured;
+    inner.backtrace = Some(backtrace);
+    let error = Layer::new("could not load config", Some(inner));
+
+    let report = Report::new(error).pretty(true).show_backtrace(true);
+    let expected = "\
+could not load config
+
+Caused by:
+      permission denied
+
+Stack backtrace:
+";
+    if captured {
+        assert!(report.to_string().starts_with(expected));
+    } else {
+        // Without backtrace support on this platform, there is nothing to show.
+        assert_eq!(report.to_string(), expected.trim_end_matches("\n\nStack backtrace:\n"));
+    }
+
+    // Backtraces are only part of the pretty format, and only if captured.
+    let report = report.pretty(false);
+    assert_eq!(report.to_string(), "could not load config: permission denied");
+
+    let mut error = Layer::new("could not load config", None);
+    error.backtrace = Some(Backtrace::disabled());
+    let report = Report::new(error).pretty(true).show_backtrace(true);
+    assert_eq!(report.to_string(), "could not load config");
+}
+
+#[test]
+fn boxed_report() {
+    fn fails() -> Result<(), Report> {
+        Err(three_layers())?;
+        Ok(())
+    }
+
+    let report = fails().unwrap_err();
+    let expected = "could not load config: failed to read `app.toml`: permission denied";
+    assert_eq!(report.to_string(), expected);
+
+    let error: Box<dyn Error + Send + Sync> = "something went wrong".into();
+    let report = Report::new(error).pretty(true);
+    assert_eq!(report.to_string(), "something went wrong");
+}
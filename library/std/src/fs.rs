@@ -13,7 +13,7 @@
 
 use crate::ffi::OsString;
 use crate::fmt;
-use crate::io::{self, Initializer, IoSlice, IoSliceMut, Read, Seek, SeekFrom, Write};
+use crate::io::{self, IoSlice, IoSliceMut, Read, ReadBuf, Seek, SeekFrom, Write};
 use crate::path::{Path, PathBuf};
 use crate::sys::fs as fs_imp;
 use crate::sys_common::{AsInner, AsInnerMut, FromInner, IntoInner};
@@ -447,6 +447,217 @@ pub fn sync_data(&self) -> io::Result<()> {
         self.inner.datasync()
     }
 
+    /// Acquires an exclusive advisory lock on the file, blocking until it can
+    /// be acquired.
+    ///
+    /// This acquires an exclusive lock; no other file handle to this file may
+    /// acquire another lock. The lock belongs to this file handle: it is held
+    /// until it's released with [`unlock`], or until this handle and all the
+    /// handles [cloned][`try_clone`] from it are closed.
+    ///
+    /// The lock is advisory on Unix: it only excludes the processes which use
+    /// these locking methods (or `flock`) on the file, and doesn't prevent
+    /// reading or writing it. On Windows it is mandatory: the other handles
+    /// can't write the file while a shared lock is held, nor read or write it
+    /// while an exclusive lock is held.
+    ///
+    /// If this file handle, or a clone of it, already holds a lock, the exact
+    /// behavior is unspecified and platform dependent, including the
+    /// possibility that it will deadlock. However, if this method returns,
+    /// then an exclusive lock is held.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix
+    /// with the `LOCK_EX` flag, and the `LockFileEx` function on Windows with
+    /// the `LOCKFILE_EXCLUSIVE_LOCK` flag. Note that, this
+    /// [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error of kind
+    /// [`Unsupported`](io::ErrorKind::Unsupported) on platforms which have no
+    /// file locking with these semantics, such as the Unix platforms without
+    /// `flock`.
+    ///
+    /// [`unlock`]: File::unlock
+    /// [`try_clone`]: File::try_clone
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::create("foo.txt")?;
+    ///     f.lock()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    pub fn lock(&self) -> io::Result<()> {
+        self.inner.lock()
+    }
+
+    /// Acquires a shared advisory lock on the file, blocking until it can be
+    /// acquired.
+    ///
+    /// This acquires a shared lock; more than one file handle may hold a
+    /// shared lock, but none may hold an exclusive lock at the same time. The
+    /// lock is released like the one acquired by [`lock`].
+    ///
+    /// If this file handle, or a clone of it, already holds a lock, the exact
+    /// behavior is unspecified and platform dependent, including the
+    /// possibility that it will deadlock. However, if this method returns,
+    /// then a shared lock is held.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix
+    /// with the `LOCK_SH` flag, and the `LockFileEx` function on Windows. Note
+    /// that, this [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    ///
+    /// [`lock`]: File::lock
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::open("foo.txt")?;
+    ///     f.lock_shared()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    pub fn lock_shared(&self) -> io::Result<()> {
+        self.inner.lock_shared()
+    }
+
+    /// Tries to acquire an exclusive advisory lock on the file, without
+    /// blocking.
+    ///
+    /// Returns `Ok(false)` if another file handle holds a lock on the file,
+    /// and `Ok(true)` once the lock is acquired, in which case it is released
+    /// like the one acquired by [`lock`].
+    ///
+    /// If this file handle, or a clone of it, already holds a lock, the exact
+    /// behavior is unspecified and platform dependent. However, if this method
+    /// returns `Ok(true)`, then it holds an exclusive lock.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix
+    /// with the `LOCK_EX` and `LOCK_NB` flags, and the `LockFileEx` function
+    /// on Windows with the `LOCKFILE_EXCLUSIVE_LOCK` and
+    /// `LOCKFILE_FAIL_IMMEDIATELY` flags. Note that, this
+    /// [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    ///
+    /// [`lock`]: File::lock
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::create("foo.txt")?;
+    ///     if !f.try_lock()? {
+    ///         println!("foo.txt is locked by someone else");
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    pub fn try_lock(&self) -> io::Result<bool> {
+        self.inner.try_lock()
+    }
+
+    /// Tries to acquire a shared advisory lock on the file, without blocking.
+    ///
+    /// Returns `Ok(false)` if another file handle holds an exclusive lock on
+    /// the file, and `Ok(true)` once the lock is acquired, in which case it is
+    /// released like the one acquired by [`lock`].
+    ///
+    /// If this file handle, or a clone of it, already holds a lock, the exact
+    /// behavior is unspecified and platform dependent. However, if this method
+    /// returns `Ok(true)`, then it holds a shared lock.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix
+    /// with the `LOCK_SH` and `LOCK_NB` flags, and the `LockFileEx` function
+    /// on Windows with the `LOCKFILE_FAIL_IMMEDIATELY` flag. Note that, this
+    /// [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    ///
+    /// [`lock`]: File::lock
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::open("foo.txt")?;
+    ///     if !f.try_lock_shared()? {
+    ///         println!("foo.txt is being written by someone else");
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        self.inner.try_lock_shared()
+    }
+
+    /// Releases the lock held by this file handle.
+    ///
+    /// Locks are also released when this handle and all the handles
+    /// [cloned][`try_clone`] from it are closed, so this is only needed to
+    /// release a lock before then.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix
+    /// with the `LOCK_UN` flag, and the `UnlockFile` function on Windows. On
+    /// Windows, a handle holding several locks needs one call for each of
+    /// them. Note that, this [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    ///
+    /// [`try_clone`]: File::try_clone
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::open("foo.txt")?;
+    ///     f.lock()?;
+    ///     f.unlock()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    pub fn unlock(&self) -> io::Result<()> {
+        self.inner.unlock()
+    }
+
     /// Truncates or extends the underlying file, updating the size of
     /// this file to become `size`.
     ///
@@ -600,6 +811,8 @@ fn into_inner(self) -> fs_imp::File {
     }
 }
 
+impl crate::sealed::Sealed for File {}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl fmt::Debug for File {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -613,6 +826,10 @@ fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         self.inner.read(buf)
     }
 
+    fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        self.inner.read_buf(buf)
+    }
+
     fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         self.inner.read_vectored(bufs)
     }
@@ -621,12 +838,6 @@ fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
     fn is_read_vectored(&self) -> bool {
         self.inner.is_read_vectored()
     }
-
-    #[inline]
-    unsafe fn initializer(&self) -> Initializer {
-        // SAFETY: Read is guaranteed to work on uninitialized memory
-        unsafe { Initializer::nop() }
-    }
 }
 #[stable(feature = "rust1", since = "1.0.0")]
 impl Write for File {
@@ -659,6 +870,10 @@ fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         self.inner.read(buf)
     }
 
+    fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        self.inner.read_buf(buf)
+    }
+
     fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         self.inner.read_vectored(bufs)
     }
@@ -667,12 +882,6 @@ fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
     fn is_read_vectored(&self) -> bool {
         self.inner.is_read_vectored()
     }
-
-    #[inline]
-    unsafe fn initializer(&self) -> Initializer {
-        // SAFETY: Read is guaranteed to work on uninitialized memory
-        unsafe { Initializer::nop() }
-    }
 }
 #[stable(feature = "rust1", since = "1.0.0")]
 impl Write for &File {
@@ -252,6 +252,93 @@ fn file_test_io_eof() {
     check!(fs::remove_file(&filename));
 }
 
+#[test]
+#[cfg(any(
+    windows,
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+fn file_lock_multiple_shared() {
+    let tmpdir = tmpdir();
+    let filename = tmpdir.join("file_lock_multiple_shared_test.txt");
+    let f1 = check!(File::create(&filename));
+    let f2 = check!(OpenOptions::new().write(true).open(&filename));
+
+    // Check that we can acquire concurrent shared locks
+    check!(f1.lock_shared());
+    check!(f2.lock_shared());
+    check!(f1.unlock());
+    check!(f2.unlock());
+    assert!(check!(f1.try_lock_shared()));
+    assert!(check!(f2.try_lock_shared()));
+}
+
+#[test]
+#[cfg(any(
+    windows,
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+fn file_lock_blocking() {
+    let tmpdir = tmpdir();
+    let filename = tmpdir.join("file_lock_blocking_test.txt");
+    let f1 = check!(File::create(&filename));
+    let f2 = check!(OpenOptions::new().write(true).open(&filename));
+
+    // Check that shared locks block exclusive locks
+    check!(f1.lock_shared());
+    assert!(!check!(f2.try_lock()));
+    check!(f1.unlock());
+
+    // Check that exclusive locks block shared and exclusive locks
+    check!(f1.lock());
+    assert!(!check!(f2.try_lock()));
+    assert!(!check!(f2.try_lock_shared()));
+    check!(f1.unlock());
+
+    // Check that the lock is available again once released
+    assert!(check!(f2.try_lock()));
+}
+
+#[test]
+#[cfg(any(
+    windows,
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+fn file_lock_drop() {
+    let tmpdir = tmpdir();
+    let filename = tmpdir.join("file_lock_drop_test.txt");
+    let f1 = check!(File::create(&filename));
+    let f2 = check!(OpenOptions::new().write(true).open(&filename));
+
+    // Check that locks are released when the last handle holding them is closed
+    check!(f1.lock());
+    let f3 = check!(f1.try_clone());
+    drop(f1);
+    assert!(!check!(f2.try_lock()));
+    drop(f3);
+    assert!(check!(f2.try_lock()));
+}
+
 #[test]
 #[cfg(unix)]
 fn file_test_io_read_write_at() {
@@ -308,6 +395,44 @@ fn file_test_io_read_write_at() {
     check!(fs::remove_file(&filename));
 }
 
+#[test]
+#[cfg(unix)]
+fn file_test_io_read_write_vectored_at() {
+    use crate::io::{IoSlice, IoSliceMut};
+    use crate::os::unix::fs::FileExt;
+
+    let tmpdir = tmpdir();
+    let filename = tmpdir.join("file_rt_io_file_test_read_write_vectored_at.txt");
+    {
+        let oo = OpenOptions::new().create_new(true).write(true).read(true).clone();
+        let mut rw = check!(oo.open(&filename));
+        let bufs = &[IoSlice::new(b"asdf"), IoSlice::new(b""), IoSlice::new(b"-zxcv")];
+        let n = check!(rw.write_vectored_at(bufs, 5));
+        // Platforms without `pwritev` only write the first non-empty buffer.
+        assert!(n == 4 || n == 9);
+        if n == 4 {
+            check!(rw.write_all_at(b"-zxcv", 9));
+        }
+        check!(rw.write_at(b"qwer-", 0));
+        assert_eq!(check!(rw.seek(SeekFrom::Current(0))), 0);
+    }
+    {
+        let mut read = check!(File::open(&filename));
+        let mut head = [0; 5];
+        let mut tail = [0; 32];
+        let mut bufs = [IoSliceMut::new(&mut head), IoSliceMut::new(&mut tail)];
+        let n = check!(read.read_vectored_at(&mut bufs, 0));
+        assert!(n == 5 || n == 14);
+        assert_eq!(&head, b"qwer-");
+        if n == 14 {
+            assert_eq!(&tail[..9], b"asdf-zxcv");
+        }
+        assert_eq!(check!(read.seek(SeekFrom::Current(0))), 0);
+        assert_eq!(check!(read.read_vectored_at(&mut [IoSliceMut::new(&mut head)], 14)), 0);
+    }
+    check!(fs::remove_file(&filename));
+}
+
 #[test]
 #[cfg(unix)]
 fn set_get_unix_permissions() {
@@ -0,0 +1,267 @@
+//! Asynchronous counterparts of the `Read` and `Write` traits.
+
+#![unstable(feature = "async_io", issue = "none")]
+
+use super::reactor::{poll_io, AsSource, Interest, Reactor, Registration};
+use crate::fmt;
+use crate::fs::File;
+use crate::io::{self, Read, Write};
+use crate::ops::DerefMut;
+use crate::pin::Pin;
+use crate::task::{Context, Poll};
+
+/// The asynchronous counterpart of [`Read`](io::Read).
+///
+/// Instead of blocking the thread until data is available, `poll_read`
+/// returns `Poll::Pending` and arranges for the current task to be woken once
+/// the source may be read again.
+#[unstable(feature = "async_io", issue = "none")]
+pub trait AsyncRead {
+    /// Attempts to read from the source into `buf`.
+    ///
+    /// On success, returns `Poll::Ready(Ok(n))` with the number of bytes read,
+    /// following the same conventions as [`Read::read`](io::Read::read). If no
+    /// data is available yet, returns `Poll::Pending` and arranges for the
+    /// waker of `cx` to be woken when the source becomes readable.
+    #[unstable(feature = "async_io", issue = "none")]
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>>;
+}
+
+/// The asynchronous counterpart of [`Write`](io::Write).
+///
+/// Instead of blocking the thread until the sink can accept data, the methods
+/// of this trait return `Poll::Pending` and arrange for the current task to
+/// be woken once the sink may be written to again.
+#[unstable(feature = "async_io", issue = "none")]
+pub trait AsyncWrite {
+    /// Attempts to write `buf` into the sink.
+    ///
+    /// On success, returns `Poll::Ready(Ok(n))` with the number of bytes
+    /// written, following the same conventions as
+    /// [`Write::write`](io::Write::write). If the sink cannot accept data yet,
+    /// returns `Poll::Pending` and arranges for the waker of `cx` to be woken
+    /// when it becomes writable.
+    #[unstable(feature = "async_io", issue = "none")]
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>>;
+
+    /// Attempts to flush buffered data to its destination.
+    #[unstable(feature = "async_io", issue = "none")]
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>>;
+
+    /// Attempts to shut down the write half of the sink.
+    ///
+    /// Flushes any buffered data first. Once this returns `Poll::Ready(Ok(()))`
+    /// no more data may be written.
+    #[unstable(feature = "async_io", issue = "none")]
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>>;
+}
+
+#[unstable(feature = "async_io", issue = "none")]
+impl<R: AsyncRead + Unpin + ?Sized> AsyncRead for &mut R {
+    #[inline]
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut **self).poll_read(cx, buf)
+    }
+}
+
+#[unstable(feature = "async_io", issue = "none")]
+impl<R: AsyncRead + Unpin + ?Sized> AsyncRead for Box<R> {
+    #[inline]
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut **self).poll_read(cx, buf)
+    }
+}
+
+#[unstable(feature = "async_io", issue = "none")]
+impl<P> AsyncRead for Pin<P>
+where
+    P: DerefMut + Unpin,
+    P::Target: AsyncRead,
+{
+    #[inline]
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        self.get_mut().as_mut().poll_read(cx, buf)
+    }
+}
+
+#[unstable(feature = "async_io", issue = "none")]
+impl<W: AsyncWrite + Unpin + ?Sized> AsyncWrite for &mut W {
+    #[inline]
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut **self).poll_write(cx, buf)
+    }
+
+    #[inline]
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut **self).poll_flush(cx)
+    }
+
+    #[inline]
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut **self).poll_shutdown(cx)
+    }
+}
+
+#[unstable(feature = "async_io", issue = "none")]
+impl<W: AsyncWrite + Unpin + ?Sized> AsyncWrite for Box<W> {
+    #[inline]
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut **self).poll_write(cx, buf)
+    }
+
+    #[inline]
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut **self).poll_flush(cx)
+    }
+
+    #[inline]
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut **self).poll_shutdown(cx)
+    }
+}
+
+#[unstable(feature = "async_io", issue = "none")]
+impl<P> AsyncWrite for Pin<P>
+where
+    P: DerefMut + Unpin,
+    P::Target: AsyncWrite,
+{
+    #[inline]
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.get_mut().as_mut().poll_write(cx, buf)
+    }
+
+    #[inline]
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.get_mut().as_mut().poll_flush(cx)
+    }
+
+    #[inline]
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.get_mut().as_mut().poll_shutdown(cx)
+    }
+}
+
+/// A [`File`] driven by a [`Reactor`].
+///
+/// Only files that support readiness notifications can be used
+/// asynchronously: pipes, FIFOs, terminals and character devices. Regular
+/// files are always reported as ready by the operating system, so most
+/// reactors refuse to register them and [`AsyncFile::new`] returns an error.
+///
+/// # Platform-specific behavior
+///
+/// This type is only useful on Unix. On Windows, readiness is only reported
+/// for sockets, and on other platforms there is nothing a reactor could
+/// monitor, so [`AsyncFile::new`] always fails with
+/// [`ErrorKind::Unsupported`](io::ErrorKind::Unsupported) there.
+#[unstable(feature = "async_io", issue = "none")]
+pub struct AsyncFile {
+    // Declared before `inner` so the file is deregistered before it's closed.
+    registration: Box<dyn Registration>,
+    inner: File,
+}
+
+impl AsyncFile {
+    /// Puts `file` in nonblocking mode and registers it with `reactor`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be switched to nonblocking mode,
+    /// or if `reactor` cannot monitor it.
+    #[unstable(feature = "async_io", issue = "none")]
+    pub fn new<R: Reactor + ?Sized>(file: File, reactor: &R) -> io::Result<AsyncFile> {
+        let source = file.nonblocking_source()?;
+        let registration = reactor.register(source, Interest::READABLE | Interest::WRITABLE)?;
+        Ok(AsyncFile { registration, inner: file })
+    }
+
+    /// Returns a reference to the underlying file.
+    #[unstable(feature = "async_io", issue = "none")]
+    pub fn get_ref(&self) -> &File {
+        &self.inner
+    }
+
+    /// Deregisters the file from its reactor and returns it.
+    ///
+    /// The returned file is still in nonblocking mode.
+    #[unstable(feature = "async_io", issue = "none")]
+    pub fn into_inner(self) -> File {
+        let AsyncFile { registration, inner } = self;
+        drop(registration);
+        inner
+    }
+}
+
+#[unstable(feature = "async_io", issue = "none")]
+impl AsyncRead for AsyncFile {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let file = &mut this.inner;
+        poll_io(&*this.registration, cx, Interest::READABLE, || file.read(buf))
+    }
+}
+
+#[unstable(feature = "async_io", issue = "none")]
+impl AsyncWrite for AsyncFile {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let file = &mut this.inner;
+        poll_io(&*this.registration, cx, Interest::WRITABLE, || file.write(buf))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(self.get_mut().inner.flush())
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.poll_flush(cx)
+    }
+}
+
+#[unstable(feature = "async_io", issue = "none")]
+impl fmt::Debug for AsyncFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncFile").field("inner", &self.inner).finish_non_exhaustive()
+    }
+}
@@ -5,7 +5,7 @@
 use crate::cmp;
 use crate::fmt;
 use crate::io::{
-    self, BufRead, Error, ErrorKind, Initializer, IoSlice, IoSliceMut, Read, Seek, SeekFrom, Write,
+    self, BufRead, Error, ErrorKind, IoSlice, IoSliceMut, Read, ReadBuf, Seek, SeekFrom, Write,
 };
 use crate::mem;
 
@@ -30,8 +30,8 @@ fn is_read_vectored(&self) -> bool {
     }
 
     #[inline]
-    unsafe fn initializer(&self) -> Initializer {
-        (**self).initializer()
+    fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        (**self).read_buf(buf)
     }
 
     #[inline]
@@ -134,8 +134,8 @@ fn is_read_vectored(&self) -> bool {
     }
 
     #[inline]
-    unsafe fn initializer(&self) -> Initializer {
-        (**self).initializer()
+    fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        (**self).read_buf(buf)
     }
 
     #[inline]
@@ -266,8 +266,14 @@ fn is_read_vectored(&self) -> bool {
     }
 
     #[inline]
-    unsafe fn initializer(&self) -> Initializer {
-        Initializer::nop()
+    fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        let amt = cmp::min(buf.remaining(), self.len());
+        let (a, b) = self.split_at(amt);
+
+        buf.append(a);
+
+        *self = b;
+        Ok(())
     }
 
     #[inline]
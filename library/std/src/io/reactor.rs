@@ -0,0 +1,250 @@
+//! A runtime-agnostic interface between asynchronous I/O types and the event
+//! loop driving them.
+//!
+//! The standard library does not ship an event loop. Instead, the
+//! asynchronous I/O types (such as [`AsyncTcpStream`]) put their OS resource in
+//! nonblocking mode and hand it to a [`Reactor`] supplied by the caller, which
+//! wakes the owning task once the resource becomes ready. Any async runtime can
+//! implement [`Reactor`] on top of its own event loop, so libraries written
+//! against these types work with every runtime.
+//!
+//! [`AsyncTcpStream`]: crate::net::AsyncTcpStream
+
+#![unstable(feature = "async_io", issue = "none")]
+
+use crate::fmt;
+use crate::io;
+use crate::ops;
+use crate::task::{Context, Poll};
+
+pub(crate) use crate::sys::reactor::AsSource;
+
+/// The kinds of readiness a [`Source`] is registered for.
+///
+/// Interests are combined with the `|` operator.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(async_io)]
+/// use std::io::Interest;
+///
+/// let both = Interest::READABLE | Interest::WRITABLE;
+/// assert!(both.is_readable());
+/// assert!(both.is_writable());
+/// assert!(!Interest::READABLE.is_writable());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[unstable(feature = "async_io", issue = "none")]
+pub struct Interest(u8);
+
+impl Interest {
+    /// Interest in the source becoming readable.
+    #[unstable(feature = "async_io", issue = "none")]
+    pub const READABLE: Interest = Interest(0b01);
+
+    /// Interest in the source becoming writable.
+    #[unstable(feature = "async_io", issue = "none")]
+    pub const WRITABLE: Interest = Interest(0b10);
+
+    /// Returns `true` if `self` includes [`Interest::READABLE`].
+    #[inline]
+    #[unstable(feature = "async_io", issue = "none")]
+    pub const fn is_readable(self) -> bool {
+        self.0 & Interest::READABLE.0 != 0
+    }
+
+    /// Returns `true` if `self` includes [`Interest::WRITABLE`].
+    #[inline]
+    #[unstable(feature = "async_io", issue = "none")]
+    pub const fn is_writable(self) -> bool {
+        self.0 & Interest::WRITABLE.0 != 0
+    }
+
+    /// Returns the union of `self` and `other`.
+    ///
+    /// This is the `const` equivalent of `self | other`.
+    #[inline]
+    #[unstable(feature = "async_io", issue = "none")]
+    pub const fn add(self, other: Interest) -> Interest {
+        Interest(self.0 | other.0)
+    }
+}
+
+#[unstable(feature = "async_io", issue = "none")]
+impl ops::BitOr for Interest {
+    type Output = Interest;
+
+    #[inline]
+    fn bitor(self, other: Interest) -> Interest {
+        self.add(other)
+    }
+}
+
+#[unstable(feature = "async_io", issue = "none")]
+impl ops::BitOrAssign for Interest {
+    #[inline]
+    fn bitor_assign(&mut self, other: Interest) {
+        *self = self.add(other);
+    }
+}
+
+#[unstable(feature = "async_io", issue = "none")]
+impl fmt::Debug for Interest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.is_readable(), self.is_writable()) {
+            (true, true) => f.write_str("READABLE | WRITABLE"),
+            (true, false) => f.write_str("READABLE"),
+            (false, true) => f.write_str("WRITABLE"),
+            (false, false) => f.write_str("(empty)"),
+        }
+    }
+}
+
+/// An OS resource handed to a [`Reactor`] for registration.
+///
+/// On Unix this is a borrowed file descriptor, available through
+/// [`AsFd`](crate::os::unix::io::AsFd) and
+/// [`AsRawFd`](crate::os::unix::io::AsRawFd). On Windows it is a borrowed
+/// socket, available through
+/// [`AsSocket`](crate::os::windows::io::AsSocket) and
+/// [`AsRawSocket`](crate::os::windows::io::AsRawSocket). Other platforms
+/// have nothing a reactor could monitor, so no `Source` is ever created
+/// there.
+///
+/// The resource is guaranteed to stay open until the [`Registration`] created
+/// for it is dropped.
+#[unstable(feature = "async_io", issue = "none")]
+pub use crate::sys::reactor::Source;
+
+/// An event loop that monitors OS resources for readiness.
+///
+/// A reactor is the one piece an async runtime has to provide for the
+/// asynchronous I/O types of the standard library to work with it. Each
+/// asynchronous I/O object registers its resource once, when it is created,
+/// and keeps the returned [`Registration`] for as long as it lives.
+///
+/// The model is readiness based: the I/O object attempts an operation, and
+/// only when the operation fails with [`ErrorKind::WouldBlock`] does it ask
+/// the reactor to wake it once the resource is ready again. Completion based
+/// event loops can implement this trait by emulating readiness, like they do
+/// for nonblocking sockets.
+///
+/// [`ErrorKind::WouldBlock`]: io::ErrorKind::WouldBlock
+///
+/// # Examples
+///
+/// A reactor that reports every resource as always ready. Tasks driven by it
+/// busy-poll their I/O, which is only sensible for tests.
+///
+/// ```
+/// #![feature(async_io)]
+/// use std::io::{self, Interest, Reactor, Registration, Source};
+/// use std::task::{Context, Poll};
+///
+/// struct Spin;
+///
+/// impl Reactor for Spin {
+///     fn register(&self, _: Source<'_>, _: Interest) -> io::Result<Box<dyn Registration>> {
+///         Ok(Box::new(Spin))
+///     }
+/// }
+///
+/// impl Registration for Spin {
+///     fn poll_ready(&self, _: &mut Context<'_>, _: Interest) -> Poll<io::Result<()>> {
+///         Poll::Ready(Ok(()))
+///     }
+///
+///     fn clear_ready(&self, _: Interest) {}
+/// }
+/// ```
+#[unstable(feature = "async_io", issue = "none")]
+pub trait Reactor: Send + Sync {
+    /// Starts monitoring `source` for the readiness kinds in `interest`.
+    ///
+    /// The source stays registered until the returned [`Registration`] is
+    /// dropped, which always happens before the source is closed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source cannot be monitored by this reactor,
+    /// for example because it refers to a regular file and the underlying
+    /// event notification mechanism only supports sockets and pipes.
+    #[unstable(feature = "async_io", issue = "none")]
+    fn register(&self, source: Source<'_>, interest: Interest)
+    -> io::Result<Box<dyn Registration>>;
+}
+
+/// A resource registered with a [`Reactor`].
+///
+/// Dropping the registration deregisters the resource.
+#[unstable(feature = "async_io", issue = "none")]
+pub trait Registration: Send + Sync {
+    /// Polls whether the resource may be ready for `interest`.
+    ///
+    /// Returns `Poll::Ready(Ok(()))` if the reactor has seen the resource
+    /// become ready since readiness was last cleared with
+    /// [`clear_ready`](Registration::clear_ready). Otherwise arranges for
+    /// the waker of `cx` to be woken when it becomes ready, and returns
+    /// `Poll::Pending`.
+    ///
+    /// Readiness is only a hint: the operation the caller attempts next may
+    /// still fail with [`ErrorKind::WouldBlock`](io::ErrorKind::WouldBlock).
+    #[unstable(feature = "async_io", issue = "none")]
+    fn poll_ready(&self, cx: &mut Context<'_>, interest: Interest) -> Poll<io::Result<()>>;
+
+    /// Clears the readiness previously reported for `interest`.
+    ///
+    /// This is called after an operation failed with
+    /// [`ErrorKind::WouldBlock`](io::ErrorKind::WouldBlock), right before the
+    /// caller polls for readiness again.
+    #[unstable(feature = "async_io", issue = "none")]
+    fn clear_ready(&self, interest: Interest);
+}
+
+#[unstable(feature = "async_io", issue = "none")]
+impl<R: Reactor + ?Sized> Reactor for &R {
+    #[inline]
+    fn register(
+        &self,
+        source: Source<'_>,
+        interest: Interest,
+    ) -> io::Result<Box<dyn Registration>> {
+        (**self).register(source, interest)
+    }
+}
+
+#[unstable(feature = "async_io", issue = "none")]
+impl<R: Reactor + ?Sized> Reactor for crate::sync::Arc<R> {
+    #[inline]
+    fn register(
+        &self,
+        source: Source<'_>,
+        interest: Interest,
+    ) -> io::Result<Box<dyn Registration>> {
+        (**self).register(source, interest)
+    }
+}
+
+/// Runs the nonblocking operation `op` until it doesn't fail with
+/// `WouldBlock`, waiting on `registration` for readiness in between.
+pub(crate) fn poll_io<T>(
+    registration: &dyn Registration,
+    cx: &mut Context<'_>,
+    interest: Interest,
+    mut op: impl FnMut() -> io::Result<T>,
+) -> Poll<io::Result<T>> {
+    loop {
+        match registration.poll_ready(cx, interest) {
+            Poll::Ready(Ok(())) => {}
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            Poll::Pending => return Poll::Pending,
+        }
+        match op() {
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
+                registration.clear_ready(interest);
+            }
+            res => return Poll::Ready(res),
+        }
+    }
+}
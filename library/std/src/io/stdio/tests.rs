@@ -45,3 +45,11 @@ fn panic_doesnt_poison() {
     let _a = stderr();
     let _a = _a.lock();
 }
+
+#[test]
+#[cfg_attr(target_os = "emscripten", ignore)]
+fn regular_file_is_not_terminal() {
+    let tmpdir = crate::sys_common::io::test::tmpdir();
+    let file = File::create(tmpdir.join("not_a_terminal.txt")).unwrap();
+    assert!(!file.is_terminal());
+}
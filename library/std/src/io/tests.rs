@@ -1,7 +1,8 @@
 use super::{repeat, Cursor, SeekFrom};
 use crate::cmp::{self, min};
 use crate::io::{self, IoSlice, IoSliceMut};
-use crate::io::{BufRead, BufReader, Read, Seek, Write};
+use crate::io::{BufRead, BufReader, Read, ReadBuf, Seek, Write};
+use crate::mem::MaybeUninit;
 use crate::ops::Deref;
 
 #[test]
@@ -146,6 +147,69 @@ fn read_exact_slice() {
     assert_eq!(c, b"9");
 }
 
+#[test]
+fn read_buf_exact() {
+    let mut buf = [0; 4];
+    let mut buf = ReadBuf::new(&mut buf);
+
+    let mut c = Cursor::new(&b""[..]);
+    assert_eq!(c.read_buf_exact(&mut buf).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+
+    let mut c = Cursor::new(&b"123456789"[..]);
+    c.read_buf_exact(&mut buf).unwrap();
+    assert_eq!(buf.filled(), b"1234");
+
+    buf.clear();
+
+    c.read_buf_exact(&mut buf).unwrap();
+    assert_eq!(buf.filled(), b"5678");
+
+    buf.clear();
+
+    assert_eq!(c.read_buf_exact(&mut buf).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn read_buf_uninit() {
+    let mut buf = [MaybeUninit::uninit(); 8];
+    let mut buf = ReadBuf::uninit(&mut buf);
+
+    let mut c = &b"hello"[..];
+    c.read_buf(&mut buf).unwrap();
+    assert_eq!(buf.filled(), b"hello");
+    // The slice reader only initializes what it fills.
+    assert_eq!(buf.initialized_len(), 5);
+    assert!(c.is_empty());
+
+    // Readers which only implement `read` initialize the rest of the buffer.
+    let mut r = repeat(b'x').take(2);
+    r.read_buf(&mut buf).unwrap();
+    assert_eq!(buf.filled(), b"hellox");
+}
+
+#[test]
+fn take_read_buf() {
+    let mut buf = [MaybeUninit::uninit(); 16];
+    let mut buf = ReadBuf::uninit(&mut buf);
+
+    // A limit smaller than the buffer only fills up to the limit.
+    let mut t = (&b"0123456789"[..]).take(4);
+    t.read_buf(&mut buf).unwrap();
+    assert_eq!(buf.filled(), b"0123");
+    assert_eq!(buf.initialized_len(), 4);
+    assert_eq!(t.limit(), 0);
+
+    t.read_buf(&mut buf).unwrap();
+    assert_eq!(buf.filled(), b"0123");
+
+    // A limit larger than the buffer is reduced by what was read.
+    buf.clear();
+    let mut t = (&b"0123456789"[..]).take(100);
+    t.read_buf(&mut buf).unwrap();
+    assert_eq!(buf.filled(), b"0123456789");
+    assert_eq!(t.limit(), 90);
+}
+
 #[test]
 fn take_eof() {
     struct R;
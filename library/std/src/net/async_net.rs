@@ -0,0 +1,308 @@
+#![unstable(feature = "async_io", issue = "none")]
+
+#[cfg(all(test, not(target_os = "emscripten")))]
+mod tests;
+
+use crate::io::prelude::*;
+
+use crate::fmt;
+use crate::io::reactor::{poll_io, AsSource};
+use crate::io::{self, AsyncRead, AsyncWrite, Interest, Reactor, Registration};
+use crate::net::{Shutdown, SocketAddr, TcpListener, TcpStream, UdpSocket};
+use crate::pin::Pin;
+use crate::task::{Context, Poll};
+
+/// A [`TcpStream`] driven by a [`Reactor`].
+///
+/// The stream is put in nonblocking mode and registered with the reactor when
+/// it's created. Reads and writes go through the [`AsyncRead`] and
+/// [`AsyncWrite`] traits, which are also implemented for `&AsyncTcpStream` so
+/// that one task can read while another writes.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(async_io)]
+/// use std::io::{self, AsyncRead, Reactor};
+/// use std::net::{AsyncTcpStream, TcpStream};
+/// use std::pin::Pin;
+/// use std::task::{Context, Poll};
+///
+/// fn poll_greeting(
+///     stream: &mut AsyncTcpStream,
+///     cx: &mut Context<'_>,
+///     buf: &mut [u8],
+/// ) -> Poll<io::Result<usize>> {
+///     Pin::new(stream).poll_read(cx, buf)
+/// }
+///
+/// fn wrap(reactor: &dyn Reactor) -> io::Result<AsyncTcpStream> {
+///     let stream = TcpStream::connect("127.0.0.1:8080")?;
+///     AsyncTcpStream::new(stream, reactor)
+/// }
+/// ```
+#[unstable(feature = "async_io", issue = "none")]
+pub struct AsyncTcpStream {
+    // Declared before `inner` so the socket is deregistered before it's closed.
+    registration: Box<dyn Registration>,
+    inner: TcpStream,
+}
+
+/// A [`TcpListener`] driven by a [`Reactor`].
+#[unstable(feature = "async_io", issue = "none")]
+pub struct AsyncTcpListener {
+    registration: Box<dyn Registration>,
+    inner: TcpListener,
+}
+
+/// A [`UdpSocket`] driven by a [`Reactor`].
+#[unstable(feature = "async_io", issue = "none")]
+pub struct AsyncUdpSocket {
+    registration: Box<dyn Registration>,
+    inner: UdpSocket,
+}
+
+impl AsyncTcpStream {
+    /// Puts `stream` in nonblocking mode and registers it with `reactor`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the socket cannot be switched to nonblocking mode,
+    /// or if `reactor` cannot monitor it.
+    #[unstable(feature = "async_io", issue = "none")]
+    pub fn new<R: Reactor + ?Sized>(stream: TcpStream, reactor: &R) -> io::Result<AsyncTcpStream> {
+        let source = stream.nonblocking_source()?;
+        let registration = reactor.register(source, Interest::READABLE | Interest::WRITABLE)?;
+        Ok(AsyncTcpStream { registration, inner: stream })
+    }
+
+    /// Attempts to receive data from the socket without removing it from the
+    /// queue, like [`TcpStream::peek`].
+    #[unstable(feature = "async_io", issue = "none")]
+    pub fn poll_peek(&self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        poll_io(&*self.registration, cx, Interest::READABLE, || self.inner.peek(buf))
+    }
+
+    /// Shuts down the read, write, or both halves of this connection.
+    ///
+    /// See [`TcpStream::shutdown`] for details.
+    #[unstable(feature = "async_io", issue = "none")]
+    pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
+        self.inner.shutdown(how)
+    }
+
+    /// Returns a reference to the underlying stream.
+    #[unstable(feature = "async_io", issue = "none")]
+    pub fn get_ref(&self) -> &TcpStream {
+        &self.inner
+    }
+
+    /// Deregisters the stream from its reactor and returns it.
+    ///
+    /// The returned stream is still in nonblocking mode.
+    #[unstable(feature = "async_io", issue = "none")]
+    pub fn into_inner(self) -> TcpStream {
+        let AsyncTcpStream { registration, inner } = self;
+        drop(registration);
+        inner
+    }
+
+    fn poll_read_priv(&self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        poll_io(&*self.registration, cx, Interest::READABLE, || (&self.inner).read(buf))
+    }
+
+    fn poll_write_priv(&self, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        poll_io(&*self.registration, cx, Interest::WRITABLE, || (&self.inner).write(buf))
+    }
+}
+
+#[unstable(feature = "async_io", issue = "none")]
+impl AsyncRead for AsyncTcpStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        self.poll_read_priv(cx, buf)
+    }
+}
+
+#[unstable(feature = "async_io", issue = "none")]
+impl AsyncRead for &AsyncTcpStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        self.poll_read_priv(cx, buf)
+    }
+}
+
+#[unstable(feature = "async_io", issue = "none")]
+impl AsyncWrite for AsyncTcpStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.poll_write_priv(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(self.shutdown(Shutdown::Write))
+    }
+}
+
+#[unstable(feature = "async_io", issue = "none")]
+impl AsyncWrite for &AsyncTcpStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.poll_write_priv(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(self.shutdown(Shutdown::Write))
+    }
+}
+
+#[unstable(feature = "async_io", issue = "none")]
+impl fmt::Debug for AsyncTcpStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncTcpStream").field("inner", &self.inner).finish_non_exhaustive()
+    }
+}
+
+impl AsyncTcpListener {
+    /// Puts `listener` in nonblocking mode and registers it with `reactor`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the socket cannot be switched to nonblocking mode,
+    /// or if `reactor` cannot monitor it.
+    #[unstable(feature = "async_io", issue = "none")]
+    pub fn new<R: Reactor + ?Sized>(
+        listener: TcpListener,
+        reactor: &R,
+    ) -> io::Result<AsyncTcpListener> {
+        let source = listener.nonblocking_source()?;
+        let registration = reactor.register(source, Interest::READABLE)?;
+        Ok(AsyncTcpListener { registration, inner: listener })
+    }
+
+    /// Attempts to accept a new incoming connection.
+    ///
+    /// Whether the returned stream inherits the nonblocking mode of the
+    /// listener is platform dependent. Wrapping it in an [`AsyncTcpStream`]
+    /// puts it in nonblocking mode either way.
+    #[unstable(feature = "async_io", issue = "none")]
+    pub fn poll_accept(&self, cx: &mut Context<'_>) -> Poll<io::Result<(TcpStream, SocketAddr)>> {
+        poll_io(&*self.registration, cx, Interest::READABLE, || self.inner.accept())
+    }
+
+    /// Returns a reference to the underlying listener.
+    #[unstable(feature = "async_io", issue = "none")]
+    pub fn get_ref(&self) -> &TcpListener {
+        &self.inner
+    }
+
+    /// Deregisters the listener from its reactor and returns it.
+    ///
+    /// The returned listener is still in nonblocking mode.
+    #[unstable(feature = "async_io", issue = "none")]
+    pub fn into_inner(self) -> TcpListener {
+        let AsyncTcpListener { registration, inner } = self;
+        drop(registration);
+        inner
+    }
+}
+
+#[unstable(feature = "async_io", issue = "none")]
+impl fmt::Debug for AsyncTcpListener {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncTcpListener").field("inner", &self.inner).finish_non_exhaustive()
+    }
+}
+
+impl AsyncUdpSocket {
+    /// Puts `socket` in nonblocking mode and registers it with `reactor`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the socket cannot be switched to nonblocking mode,
+    /// or if `reactor` cannot monitor it.
+    #[unstable(feature = "async_io", issue = "none")]
+    pub fn new<R: Reactor + ?Sized>(socket: UdpSocket, reactor: &R) -> io::Result<AsyncUdpSocket> {
+        let source = socket.nonblocking_source()?;
+        let registration = reactor.register(source, Interest::READABLE | Interest::WRITABLE)?;
+        Ok(AsyncUdpSocket { registration, inner: socket })
+    }
+
+    /// Attempts to receive a single datagram, like [`UdpSocket::recv_from`].
+    #[unstable(feature = "async_io", issue = "none")]
+    pub fn poll_recv_from(
+        &self,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<(usize, SocketAddr)>> {
+        poll_io(&*self.registration, cx, Interest::READABLE, || self.inner.recv_from(buf))
+    }
+
+    /// Attempts to send a datagram to `addr`, like [`UdpSocket::send_to`].
+    #[unstable(feature = "async_io", issue = "none")]
+    pub fn poll_send_to(
+        &self,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+        addr: SocketAddr,
+    ) -> Poll<io::Result<usize>> {
+        poll_io(&*self.registration, cx, Interest::WRITABLE, || self.inner.send_to(buf, addr))
+    }
+
+    /// Attempts to receive a single datagram from the connected peer, like
+    /// [`UdpSocket::recv`].
+    #[unstable(feature = "async_io", issue = "none")]
+    pub fn poll_recv(&self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        poll_io(&*self.registration, cx, Interest::READABLE, || self.inner.recv(buf))
+    }
+
+    /// Attempts to send a datagram to the connected peer, like
+    /// [`UdpSocket::send`].
+    #[unstable(feature = "async_io", issue = "none")]
+    pub fn poll_send(&self, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        poll_io(&*self.registration, cx, Interest::WRITABLE, || self.inner.send(buf))
+    }
+
+    /// Returns a reference to the underlying socket.
+    #[unstable(feature = "async_io", issue = "none")]
+    pub fn get_ref(&self) -> &UdpSocket {
+        &self.inner
+    }
+
+    /// Deregisters the socket from its reactor and returns it.
+    ///
+    /// The returned socket is still in nonblocking mode.
+    #[unstable(feature = "async_io", issue = "none")]
+    pub fn into_inner(self) -> UdpSocket {
+        let AsyncUdpSocket { registration, inner } = self;
+        drop(registration);
+        inner
+    }
+}
+
+#[unstable(feature = "async_io", issue = "none")]
+impl fmt::Debug for AsyncUdpSocket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncUdpSocket").field("inner", &self.inner).finish_non_exhaustive()
+    }
+}
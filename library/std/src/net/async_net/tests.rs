@@ -0,0 +1,153 @@
+use crate::io::{self, AsyncRead, AsyncWrite, Interest, Reactor, Registration, Source};
+use crate::net::test::{next_test_ip4, next_test_ip6};
+use crate::net::*;
+use crate::pin::Pin;
+use crate::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use crate::sync::Arc;
+use crate::task::{Context, Poll, Wake, Waker};
+use crate::thread;
+
+macro_rules! t {
+    ($e:expr) => {
+        match $e {
+            Ok(t) => t,
+            Err(e) => panic!("received error for `{}`: {}", stringify!($e), e),
+        }
+    };
+}
+
+fn each_ip(f: &mut dyn FnMut(SocketAddr)) {
+    f(next_test_ip4());
+    f(next_test_ip6());
+}
+
+/// A reactor without an event loop: readiness cleared after `WouldBlock` is
+/// only restored by `block_on` before it polls again.
+#[derive(Default)]
+struct TestReactor {
+    ready: Arc<AtomicBool>,
+    registered: Arc<AtomicUsize>,
+    cleared: Arc<AtomicUsize>,
+}
+
+struct TestRegistration {
+    ready: Arc<AtomicBool>,
+    registered: Arc<AtomicUsize>,
+    cleared: Arc<AtomicUsize>,
+}
+
+impl Reactor for TestReactor {
+    fn register(&self, _: Source<'_>, _: Interest) -> io::Result<Box<dyn Registration>> {
+        self.ready.store(true, Ordering::SeqCst);
+        self.registered.fetch_add(1, Ordering::SeqCst);
+        Ok(Box::new(TestRegistration {
+            ready: self.ready.clone(),
+            registered: self.registered.clone(),
+            cleared: self.cleared.clone(),
+        }))
+    }
+}
+
+impl Registration for TestRegistration {
+    fn poll_ready(&self, _: &mut Context<'_>, _: Interest) -> Poll<io::Result<()>> {
+        if self.ready.load(Ordering::SeqCst) { Poll::Ready(Ok(())) } else { Poll::Pending }
+    }
+
+    fn clear_ready(&self, _: Interest) {
+        self.ready.store(false, Ordering::SeqCst);
+        self.cleared.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+impl Drop for TestRegistration {
+    fn drop(&mut self) {
+        self.registered.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+struct NoopWaker;
+
+impl Wake for NoopWaker {
+    fn wake(self: Arc<Self>) {}
+}
+
+fn block_on<T>(reactor: &TestReactor, mut f: impl FnMut(&mut Context<'_>) -> Poll<T>) -> T {
+    let waker = Waker::from(Arc::new(NoopWaker));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(t) = f(&mut cx) {
+            return t;
+        }
+        thread::yield_now();
+        reactor.ready.store(true, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn tcp_read_write() {
+    each_ip(&mut |addr| {
+        let reactor = TestReactor::default();
+        let listener = t!(AsyncTcpListener::new(t!(TcpListener::bind(&addr)), &reactor));
+
+        let client = thread::spawn(move || {
+            let mut stream = t!(TcpStream::connect(&addr));
+            t!(io::Write::write_all(&mut stream, b"ping"));
+            let mut buf = [0; 4];
+            t!(io::Read::read_exact(&mut stream, &mut buf));
+            assert_eq!(&buf, b"pong");
+        });
+
+        let (stream, _) = t!(block_on(&reactor, |cx| listener.poll_accept(cx)));
+        let mut stream = t!(AsyncTcpStream::new(stream, &reactor));
+        let mut buf = [0; 4];
+        let mut read = 0;
+        while read < buf.len() {
+            let n =
+                t!(block_on(&reactor, |cx| Pin::new(&mut stream).poll_read(cx, &mut buf[read..])));
+            assert!(n > 0);
+            read += n;
+        }
+        assert_eq!(&buf, b"ping");
+        let n = t!(block_on(&reactor, |cx| Pin::new(&mut &stream).poll_write(cx, b"pong")));
+        assert_eq!(n, 4);
+        t!(block_on(&reactor, |cx| Pin::new(&mut stream).poll_shutdown(cx)));
+        client.join().unwrap();
+    })
+}
+
+#[test]
+fn would_block_clears_readiness() {
+    each_ip(&mut |addr| {
+        let reactor = TestReactor::default();
+        let socket = t!(AsyncUdpSocket::new(t!(UdpSocket::bind(&addr)), &reactor));
+        let waker = Waker::from(Arc::new(NoopWaker));
+        let mut cx = Context::from_waker(&waker);
+        let mut buf = [0; 4];
+
+        assert!(socket.poll_recv_from(&mut cx, &mut buf).is_pending());
+        assert_eq!(reactor.cleared.load(Ordering::SeqCst), 1);
+        // Without new readiness the socket isn't read again.
+        assert!(socket.poll_recv_from(&mut cx, &mut buf).is_pending());
+        assert_eq!(reactor.cleared.load(Ordering::SeqCst), 1);
+
+        t!(block_on(&reactor, |cx| socket.poll_send_to(cx, b"data", addr)));
+        let (n, from) = t!(block_on(&reactor, |cx| socket.poll_recv_from(cx, &mut buf)));
+        assert_eq!(n, 4);
+        assert_eq!(from, addr);
+        assert_eq!(&buf, b"data");
+    })
+}
+
+#[test]
+fn drop_deregisters() {
+    let reactor = TestReactor::default();
+    let socket = t!(AsyncUdpSocket::new(t!(UdpSocket::bind(next_test_ip4())), &reactor));
+    let listener = t!(AsyncTcpListener::new(t!(TcpListener::bind(next_test_ip4())), &reactor));
+    assert_eq!(reactor.registered.load(Ordering::SeqCst), 2);
+
+    drop(socket);
+    assert_eq!(reactor.registered.load(Ordering::SeqCst), 1);
+    let listener = listener.into_inner();
+    assert_eq!(reactor.registered.load(Ordering::SeqCst), 0);
+    t!(listener.local_addr());
+}
@@ -21,16 +21,21 @@
 
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::addr::{SocketAddr, SocketAddrV4, SocketAddrV6, ToSocketAddrs};
+#[unstable(feature = "async_io", issue = "none")]
+pub use self::async_net::{AsyncTcpListener, AsyncTcpStream, AsyncUdpSocket};
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::ip::{IpAddr, Ipv4Addr, Ipv6Addr, Ipv6MulticastScope};
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::parser::AddrParseError;
+#[unstable(feature = "net_socket_options", issue = "none")]
+pub use self::tcp::TcpSocket;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::tcp::{Incoming, TcpListener, TcpStream};
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::udp::UdpSocket;
 
 mod addr;
+mod async_net;
 mod ip;
 mod parser;
 mod tcp;
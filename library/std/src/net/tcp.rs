@@ -6,8 +6,8 @@
 use crate::io::prelude::*;
 
 use crate::fmt;
-use crate::io::{self, Initializer, IoSlice, IoSliceMut};
-use crate::net::{Shutdown, SocketAddr, ToSocketAddrs};
+use crate::io::{self, IoSlice, IoSliceMut, ReadBuf};
+use crate::net::{Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr, ToSocketAddrs};
 use crate::sys_common::net as net_imp;
 use crate::sys_common::{AsInner, FromInner, IntoInner};
 use crate::time::Duration;
@@ -442,6 +442,187 @@ pub fn nodelay(&self) -> io::Result<bool> {
         self.0.nodelay()
     }
 
+    /// Sets the value of the `SO_LINGER` option on this socket.
+    ///
+    /// This value controls how the socket is closed when data remains
+    /// to be sent. If `SO_LINGER` is set, the socket will remain open
+    /// for the specified duration as the system attempts to send pending data.
+    /// Otherwise, the system may close the socket immediately, or wait for a
+    /// default timeout.
+    ///
+    /// The duration is rounded down to whole seconds, and longer durations than
+    /// the platform supports are clamped to its maximum.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_options)]
+    ///
+    /// use std::net::TcpStream;
+    /// use std::time::Duration;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// stream.set_linger(Some(Duration::from_secs(0))).expect("set_linger call failed");
+    /// ```
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn set_linger(&self, linger: Option<Duration>) -> io::Result<()> {
+        self.0.set_linger(linger)
+    }
+
+    /// Gets the value of the `SO_LINGER` option on this socket.
+    ///
+    /// For more information about this option, see [`TcpStream::set_linger`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_options)]
+    ///
+    /// use std::net::TcpStream;
+    /// use std::time::Duration;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// stream.set_linger(Some(Duration::from_secs(0))).expect("set_linger call failed");
+    /// assert_eq!(stream.linger().unwrap(), Some(Duration::from_secs(0)));
+    /// ```
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn linger(&self) -> io::Result<Option<Duration>> {
+        self.0.linger()
+    }
+
+    /// Sets the value of the `SO_KEEPALIVE` option on this socket.
+    ///
+    /// If set, the system periodically probes an otherwise idle connection and
+    /// closes it if the peer stops responding. The timing of the probes is
+    /// platform-specific; on Linux it can be configured with the
+    /// `std::os::linux::net::TcpStreamExt` trait.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_options)]
+    ///
+    /// use std::net::TcpStream;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// stream.set_keepalive(true).expect("set_keepalive call failed");
+    /// ```
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn set_keepalive(&self, keepalive: bool) -> io::Result<()> {
+        self.0.set_keepalive(keepalive)
+    }
+
+    /// Gets the value of the `SO_KEEPALIVE` option on this socket.
+    ///
+    /// For more information about this option, see [`TcpStream::set_keepalive`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_options)]
+    ///
+    /// use std::net::TcpStream;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// stream.set_keepalive(true).expect("set_keepalive call failed");
+    /// assert_eq!(stream.keepalive().unwrap_or(false), true);
+    /// ```
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn keepalive(&self) -> io::Result<bool> {
+        self.0.keepalive()
+    }
+
+    /// Sets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// This is a hint for the size of the buffer the system uses for incoming
+    /// data. The system may round or clamp the value, and Linux doubles it to
+    /// leave room for bookkeeping, so [`TcpStream::recv_buffer_size`] may
+    /// report a different value.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_options)]
+    ///
+    /// use std::net::TcpStream;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// stream.set_recv_buffer_size(64 * 1024).expect("set_recv_buffer_size call failed");
+    /// ```
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_recv_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`TcpStream::set_recv_buffer_size`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_options)]
+    ///
+    /// use std::net::TcpStream;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// println!("receive buffer: {} bytes", stream.recv_buffer_size().unwrap());
+    /// ```
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.0.recv_buffer_size()
+    }
+
+    /// Sets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// This is a hint for the size of the buffer the system uses for outgoing
+    /// data. As with [`TcpStream::set_recv_buffer_size`], the system may adjust
+    /// the value.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_options)]
+    ///
+    /// use std::net::TcpStream;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// stream.set_send_buffer_size(64 * 1024).expect("set_send_buffer_size call failed");
+    /// ```
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_send_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`TcpStream::set_send_buffer_size`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_options)]
+    ///
+    /// use std::net::TcpStream;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// println!("send buffer: {} bytes", stream.send_buffer_size().unwrap());
+    /// ```
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.0.send_buffer_size()
+    }
+
     /// Sets the value for the `IP_TTL` option on this socket.
     ///
     /// This value sets the time-to-live field that is used in every packet sent
@@ -552,6 +733,10 @@ fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         self.0.read(buf)
     }
 
+    fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        self.0.read_buf(buf)
+    }
+
     fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         self.0.read_vectored(bufs)
     }
@@ -560,12 +745,6 @@ fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
     fn is_read_vectored(&self) -> bool {
         self.0.is_read_vectored()
     }
-
-    #[inline]
-    unsafe fn initializer(&self) -> Initializer {
-        // SAFETY: Read is guaranteed to work on uninitialized memory
-        unsafe { Initializer::nop() }
-    }
 }
 #[stable(feature = "rust1", since = "1.0.0")]
 impl Write for TcpStream {
@@ -592,6 +771,10 @@ fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         self.0.read(buf)
     }
 
+    fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        self.0.read_buf(buf)
+    }
+
     fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         self.0.read_vectored(bufs)
     }
@@ -600,12 +783,6 @@ fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
     fn is_read_vectored(&self) -> bool {
         self.0.is_read_vectored()
     }
-
-    #[inline]
-    unsafe fn initializer(&self) -> Initializer {
-        // SAFETY: Read is guaranteed to work on uninitialized memory
-        unsafe { Initializer::nop() }
-    }
 }
 #[stable(feature = "rust1", since = "1.0.0")]
 impl Write for &TcpStream {
@@ -645,6 +822,8 @@ fn into_inner(self) -> net_imp::TcpStream {
     }
 }
 
+impl crate::sealed::Sealed for TcpStream {}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl fmt::Debug for TcpStream {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -940,3 +1119,363 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.0.fmt(f)
     }
 }
+
+/// A TCP socket that has not yet been converted to a [`TcpStream`] or
+/// [`TcpListener`].
+///
+/// `TcpSocket` gives control over the socket before it is connected or starts
+/// listening: it can be bound to a specific local address before connecting,
+/// and options that only take effect at that point, such as
+/// [`set_reuse_address`] or the buffer sizes, can be configured. The socket is
+/// then turned into a [`TcpStream`] with [`connect`] or into a [`TcpListener`]
+/// with [`listen`].
+///
+/// Unlike [`TcpListener::bind`], `TcpSocket` does not set any options on its
+/// own, so on Unix platforms `SO_REUSEADDR` has to be enabled explicitly if it
+/// is wanted.
+///
+/// The socket will be closed when the value is dropped.
+///
+/// [`connect`]: TcpSocket::connect
+/// [`listen`]: TcpSocket::listen
+/// [`set_reuse_address`]: TcpSocket::set_reuse_address
+///
+/// # Examples
+///
+/// Connecting from a specific local address:
+///
+/// ```no_run
+/// #![feature(net_socket_options)]
+///
+/// use std::net::TcpSocket;
+///
+/// fn main() -> std::io::Result<()> {
+///     let socket = TcpSocket::new_v4()?;
+///     socket.bind("192.168.0.2:0".parse().unwrap())?;
+///     let stream = socket.connect("192.168.0.1:8080".parse().unwrap())?;
+///     Ok(())
+/// }
+/// ```
+///
+/// Listening with a larger backlog and receive buffer:
+///
+/// ```no_run
+/// #![feature(net_socket_options)]
+///
+/// use std::net::TcpSocket;
+///
+/// fn main() -> std::io::Result<()> {
+///     let socket = TcpSocket::new_v4()?;
+///     socket.set_reuse_address(true)?;
+///     socket.set_recv_buffer_size(256 * 1024)?;
+///     socket.bind("127.0.0.1:8080".parse().unwrap())?;
+///     let listener = socket.listen(1024)?;
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "net_socket_options", issue = "none")]
+pub struct TcpSocket(net_imp::TcpSocket);
+
+impl TcpSocket {
+    /// Creates a new IPv4 TCP socket.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_options)]
+    ///
+    /// use std::net::TcpSocket;
+    ///
+    /// let socket = TcpSocket::new_v4().expect("couldn't create socket");
+    /// ```
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn new_v4() -> io::Result<TcpSocket> {
+        net_imp::TcpSocket::new(&SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0))).map(TcpSocket)
+    }
+
+    /// Creates a new IPv6 TCP socket.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_options)]
+    ///
+    /// use std::net::TcpSocket;
+    ///
+    /// let socket = TcpSocket::new_v6().expect("couldn't create socket");
+    /// ```
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn new_v6() -> io::Result<TcpSocket> {
+        net_imp::TcpSocket::new(&SocketAddr::from((Ipv6Addr::UNSPECIFIED, 0))).map(TcpSocket)
+    }
+
+    /// Binds this socket to the specified local address.
+    ///
+    /// The address must belong to the same family as the socket. Binding to
+    /// port 0 will request that the OS assigns a port, which can be queried
+    /// with [`TcpSocket::local_addr`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_options)]
+    ///
+    /// use std::net::TcpSocket;
+    ///
+    /// let socket = TcpSocket::new_v4().expect("couldn't create socket");
+    /// socket.bind("127.0.0.1:0".parse().unwrap()).expect("couldn't bind to address");
+    /// ```
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn bind(&self, addr: SocketAddr) -> io::Result<()> {
+        self.0.bind(&addr)
+    }
+
+    /// Connects this socket to a remote address, turning it into a
+    /// [`TcpStream`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_options)]
+    ///
+    /// use std::net::TcpSocket;
+    ///
+    /// let socket = TcpSocket::new_v4().expect("couldn't create socket");
+    /// let stream = socket.connect("127.0.0.1:8080".parse().unwrap())
+    ///                    .expect("Couldn't connect to the server...");
+    /// ```
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn connect(self, addr: SocketAddr) -> io::Result<TcpStream> {
+        self.0.connect(&addr).map(TcpStream)
+    }
+
+    /// Connects this socket to a remote address with a timeout, turning it
+    /// into a [`TcpStream`].
+    ///
+    /// See [`TcpStream::connect_timeout`] for details on how the timeout is
+    /// applied.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_options)]
+    ///
+    /// use std::net::TcpSocket;
+    /// use std::time::Duration;
+    ///
+    /// let socket = TcpSocket::new_v4().expect("couldn't create socket");
+    /// let stream = socket.connect_timeout("127.0.0.1:8080".parse().unwrap(),
+    ///                                     Duration::from_secs(1))
+    ///                    .expect("Couldn't connect to the server...");
+    /// ```
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn connect_timeout(self, addr: SocketAddr, timeout: Duration) -> io::Result<TcpStream> {
+        self.0.connect_timeout(&addr, timeout).map(TcpStream)
+    }
+
+    /// Starts listening for incoming connections, turning this socket into a
+    /// [`TcpListener`].
+    ///
+    /// `backlog` is the maximum number of pending connections that have not
+    /// been [`accept`]ed yet. The system may silently clamp it.
+    ///
+    /// [`accept`]: TcpListener::accept
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_options)]
+    ///
+    /// use std::net::TcpSocket;
+    ///
+    /// let socket = TcpSocket::new_v4().expect("couldn't create socket");
+    /// socket.bind("127.0.0.1:80".parse().unwrap()).expect("couldn't bind to address");
+    /// let listener = socket.listen(1024).expect("couldn't listen");
+    /// ```
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn listen(self, backlog: u32) -> io::Result<TcpListener> {
+        self.0.listen(backlog).map(TcpListener)
+    }
+
+    /// Returns the local socket address of this socket.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_options)]
+    ///
+    /// use std::net::TcpSocket;
+    ///
+    /// let socket = TcpSocket::new_v4().expect("couldn't create socket");
+    /// socket.bind("127.0.0.1:0".parse().unwrap()).expect("couldn't bind to address");
+    /// println!("bound to {}", socket.local_addr().unwrap());
+    /// ```
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.0.socket_addr()
+    }
+
+    /// Sets the value of the `SO_REUSEADDR` option on this socket.
+    ///
+    /// On platforms with Berkeley-derived sockets, this allows binding to an
+    /// address whose previous socket is still in the `TIME_WAIT` state. On
+    /// Windows, it allows binding to an address that is actively in use by
+    /// another socket, which is rarely what is wanted.
+    ///
+    /// This option must be set before calling [`TcpSocket::bind`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_options)]
+    ///
+    /// use std::net::TcpSocket;
+    ///
+    /// let socket = TcpSocket::new_v4().expect("couldn't create socket");
+    /// socket.set_reuse_address(true).expect("set_reuse_address call failed");
+    /// ```
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn set_reuse_address(&self, reuse: bool) -> io::Result<()> {
+        self.0.set_reuse_address(reuse)
+    }
+
+    /// Gets the value of the `SO_REUSEADDR` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`TcpSocket::set_reuse_address`].
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn reuse_address(&self) -> io::Result<bool> {
+        self.0.reuse_address()
+    }
+
+    /// Sets the value for the `IPV6_V6ONLY` option on this socket.
+    ///
+    /// If this is set to `true` then the socket is restricted to sending and
+    /// receiving IPv6 packets only. In this case two IPv4 and IPv6 applications
+    /// can bind the same port at the same time.
+    ///
+    /// This option must be set before calling [`TcpSocket::bind`] and has no
+    /// effect on IPv4 sockets.
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn set_only_v6(&self, only_v6: bool) -> io::Result<()> {
+        self.0.set_only_v6(only_v6)
+    }
+
+    /// Gets the value of the `IPV6_V6ONLY` option for this socket.
+    ///
+    /// For more information about this option, see [`TcpSocket::set_only_v6`].
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn only_v6(&self) -> io::Result<bool> {
+        self.0.only_v6()
+    }
+
+    /// Sets the value of the `TCP_NODELAY` option on this socket.
+    ///
+    /// For more information about this option, see [`TcpStream::set_nodelay`].
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn set_nodelay(&self, nodelay: bool) -> io::Result<()> {
+        self.0.set_nodelay(nodelay)
+    }
+
+    /// Gets the value of the `TCP_NODELAY` option on this socket.
+    ///
+    /// For more information about this option, see [`TcpStream::set_nodelay`].
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn nodelay(&self) -> io::Result<bool> {
+        self.0.nodelay()
+    }
+
+    /// Sets the value of the `SO_LINGER` option on this socket.
+    ///
+    /// For more information about this option, see [`TcpStream::set_linger`].
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn set_linger(&self, linger: Option<Duration>) -> io::Result<()> {
+        self.0.set_linger(linger)
+    }
+
+    /// Gets the value of the `SO_LINGER` option on this socket.
+    ///
+    /// For more information about this option, see [`TcpStream::set_linger`].
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn linger(&self) -> io::Result<Option<Duration>> {
+        self.0.linger()
+    }
+
+    /// Sets the value of the `SO_KEEPALIVE` option on this socket.
+    ///
+    /// For more information about this option, see [`TcpStream::set_keepalive`].
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn set_keepalive(&self, keepalive: bool) -> io::Result<()> {
+        self.0.set_keepalive(keepalive)
+    }
+
+    /// Gets the value of the `SO_KEEPALIVE` option on this socket.
+    ///
+    /// For more information about this option, see [`TcpStream::set_keepalive`].
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn keepalive(&self) -> io::Result<bool> {
+        self.0.keepalive()
+    }
+
+    /// Sets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`TcpStream::set_recv_buffer_size`]. On a listening socket, the value
+    /// is inherited by accepted connections, and it has to be set before
+    /// [`TcpSocket::listen`] for the TCP window scale to take it into account.
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_recv_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`TcpStream::set_recv_buffer_size`].
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.0.recv_buffer_size()
+    }
+
+    /// Sets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`TcpStream::set_send_buffer_size`].
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_send_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`TcpStream::set_send_buffer_size`].
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.0.send_buffer_size()
+    }
+
+    /// Gets the value of the `SO_ERROR` option on this socket.
+    ///
+    /// This will retrieve the stored error in the underlying socket, clearing
+    /// the field in the process.
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        self.0.take_error()
+    }
+}
+
+impl AsInner<net_imp::TcpSocket> for TcpSocket {
+    fn as_inner(&self) -> &net_imp::TcpSocket {
+        &self.0
+    }
+}
+
+impl crate::sealed::Sealed for TcpSocket {}
+
+#[unstable(feature = "net_socket_options", issue = "none")]
+impl fmt::Debug for TcpSocket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
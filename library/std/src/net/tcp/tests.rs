@@ -782,6 +782,107 @@ fn nodelay() {
     assert_eq!(false, t!(stream.nodelay()));
 }
 
+#[test]
+#[cfg_attr(target_env = "sgx", ignore)]
+fn linger() {
+    let addr = next_test_ip4();
+    let _listener = t!(TcpListener::bind(&addr));
+
+    let stream = t!(TcpStream::connect(&("localhost", addr.port())));
+
+    assert_eq!(None, t!(stream.linger()));
+    t!(stream.set_linger(Some(Duration::from_secs(1))));
+    assert_eq!(Some(Duration::from_secs(1)), t!(stream.linger()));
+    t!(stream.set_linger(None));
+    assert_eq!(None, t!(stream.linger()));
+}
+
+#[test]
+#[cfg_attr(target_env = "sgx", ignore)]
+fn keepalive() {
+    let addr = next_test_ip4();
+    let _listener = t!(TcpListener::bind(&addr));
+
+    let stream = t!(TcpStream::connect(&("localhost", addr.port())));
+
+    assert_eq!(false, t!(stream.keepalive()));
+    t!(stream.set_keepalive(true));
+    assert_eq!(true, t!(stream.keepalive()));
+    t!(stream.set_keepalive(false));
+    assert_eq!(false, t!(stream.keepalive()));
+}
+
+#[test]
+#[cfg_attr(target_env = "sgx", ignore)]
+fn buffer_sizes() {
+    let addr = next_test_ip4();
+    let _listener = t!(TcpListener::bind(&addr));
+
+    let stream = t!(TcpStream::connect(&("localhost", addr.port())));
+
+    // The system is free to round the requested sizes up (Linux doubles
+    // them, for instance), so only check that they are not shrunk.
+    let size = 8192;
+    t!(stream.set_recv_buffer_size(size));
+    assert!(t!(stream.recv_buffer_size()) >= size);
+
+    t!(stream.set_send_buffer_size(size));
+    assert!(t!(stream.send_buffer_size()) >= size);
+}
+
+#[test]
+#[cfg_attr(target_env = "sgx", ignore)]
+fn tcp_socket_listen_connect() {
+    each_ip(&mut |addr| {
+        let socket = match addr {
+            SocketAddr::V4(..) => t!(TcpSocket::new_v4()),
+            SocketAddr::V6(..) => t!(TcpSocket::new_v6()),
+        };
+        t!(socket.set_reuse_address(true));
+        assert_eq!(true, t!(socket.reuse_address()));
+        t!(socket.bind(addr));
+        assert_eq!(addr, t!(socket.local_addr()));
+        let listener = t!(socket.listen(16));
+
+        // Bind the client to an OS-assigned port on the same interface
+        // before connecting.
+        let client = match addr {
+            SocketAddr::V4(..) => t!(TcpSocket::new_v4()),
+            SocketAddr::V6(..) => t!(TcpSocket::new_v6()),
+        };
+        let mut local = addr;
+        local.set_port(0);
+        t!(client.bind(local));
+        let client_addr = t!(client.local_addr());
+        assert_ne!(client_addr.port(), 0);
+
+        let t = thread::spawn(move || {
+            let mut stream = t!(client.connect(addr));
+            t!(stream.write(&[99]));
+        });
+
+        let (mut stream, peer) = t!(listener.accept());
+        assert_eq!(peer, client_addr);
+        let mut buf = [0];
+        t!(stream.read(&mut buf));
+        assert_eq!(buf[0], 99);
+        t.join().ok().expect("thread panicked");
+    })
+}
+
+#[test]
+#[cfg_attr(target_env = "sgx", ignore)]
+fn tcp_socket_bind_in_use() {
+    let addr = next_test_ip4();
+    let _listener = t!(TcpListener::bind(&addr));
+
+    let socket = t!(TcpSocket::new_v4());
+    match socket.bind(addr) {
+        Ok(..) => panic!(),
+        Err(e) => assert_eq!(e.kind(), ErrorKind::AddrInUse),
+    }
+}
+
 #[test]
 #[cfg_attr(target_env = "sgx", ignore)]
 fn ttl() {
@@ -860,3 +961,30 @@ fn connect_timeout_valid() {
     let addr = listener.local_addr().unwrap();
     TcpStream::connect_timeout(&addr, Duration::from_secs(2)).unwrap();
 }
+
+#[test]
+#[cfg(target_os = "linux")]
+fn send_file() {
+    use crate::fs::File;
+    use crate::os::linux::net::TcpStreamExt;
+    use crate::sys_common::io::test::tmpdir;
+
+    let tmpdir = tmpdir();
+    let path = tmpdir.join("send_file.txt");
+    let mut file = t!(File::create(&path));
+    t!(file.write_all(b"hello, world"));
+    let file = t!(File::open(&path));
+
+    let addr = next_test_ip4();
+    let listener = t!(TcpListener::bind(&addr));
+    let stream = t!(TcpStream::connect(&addr));
+    let (mut peer, _) = t!(listener.accept());
+
+    assert_eq!(t!(stream.send_file(&file, 7, 100)), 5);
+    assert_eq!(t!(stream.send_file(&file, 12, 100)), 0);
+    let mut buf = [0; 5];
+    t!(peer.read_exact(&mut buf));
+    assert_eq!(&buf, b"world");
+    // The file cursor is left untouched.
+    assert_eq!(t!((&file).seek(crate::io::SeekFrom::Current(0))), 0);
+}
@@ -516,6 +516,90 @@ pub fn multicast_loop_v6(&self) -> io::Result<bool> {
         self.0.multicast_loop_v6()
     }
 
+    /// Sets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// This is a hint for the size of the buffer the system uses for incoming
+    /// datagrams. Datagrams that arrive while the buffer is full are dropped.
+    /// The system may round or clamp the value, and Linux doubles it to leave
+    /// room for bookkeeping, so [`UdpSocket::recv_buffer_size`] may report a
+    /// different value.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_options)]
+    ///
+    /// use std::net::UdpSocket;
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:34254").expect("couldn't bind to address");
+    /// socket.set_recv_buffer_size(1 << 20).expect("set_recv_buffer_size call failed");
+    /// ```
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_recv_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`UdpSocket::set_recv_buffer_size`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_options)]
+    ///
+    /// use std::net::UdpSocket;
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:34254").expect("couldn't bind to address");
+    /// println!("receive buffer: {} bytes", socket.recv_buffer_size().unwrap());
+    /// ```
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.0.recv_buffer_size()
+    }
+
+    /// Sets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// This is a hint for the size of the buffer the system uses for outgoing
+    /// datagrams. As with [`UdpSocket::set_recv_buffer_size`], the system may
+    /// adjust the value.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_options)]
+    ///
+    /// use std::net::UdpSocket;
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:34254").expect("couldn't bind to address");
+    /// socket.set_send_buffer_size(1 << 20).expect("set_send_buffer_size call failed");
+    /// ```
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_send_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`UdpSocket::set_send_buffer_size`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_options)]
+    ///
+    /// use std::net::UdpSocket;
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:34254").expect("couldn't bind to address");
+    /// println!("send buffer: {} bytes", socket.send_buffer_size().unwrap());
+    /// ```
+    #[unstable(feature = "net_socket_options", issue = "none")]
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.0.send_buffer_size()
+    }
+
     /// Sets the value for the `IP_TTL` option on this socket.
     ///
     /// This value sets the time-to-live field that is used in every packet sent
@@ -343,6 +343,22 @@ fn ttl() {
     assert_eq!(ttl, t!(stream.ttl()));
 }
 
+#[test]
+fn buffer_sizes() {
+    let addr = next_test_ip4();
+
+    let socket = t!(UdpSocket::bind(&addr));
+
+    // The system is free to round the requested sizes up (Linux doubles
+    // them, for instance), so only check that they are not shrunk.
+    let size = 8192;
+    t!(socket.set_recv_buffer_size(size));
+    assert!(t!(socket.recv_buffer_size()) >= size);
+
+    t!(socket.set_send_buffer_size(size));
+    assert!(t!(socket.send_buffer_size()) >= size);
+}
+
 #[test]
 fn set_nonblocking() {
     each_ip(&mut |addr, _| {
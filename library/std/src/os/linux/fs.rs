@@ -2,7 +2,9 @@
 
 #![stable(feature = "metadata_ext", since = "1.1.0")]
 
-use crate::fs::Metadata;
+use crate::fs::{File, Metadata};
+use crate::io;
+use crate::sealed::Sealed;
 use crate::sys_common::AsInner;
 
 #[allow(deprecated)]
@@ -378,3 +380,75 @@ fn st_blocks(&self) -> u64 {
         self.as_inner().as_inner().st_blocks as u64
     }
 }
+
+/// Linux-specific extensions to [`fs::File`] for copying data between files.
+///
+/// [`fs::File`]: crate::fs::File
+#[unstable(feature = "linux_zero_copy", issue = "none")]
+pub trait FileCopyExt: Sealed {
+    /// Copies up to `len` bytes from this file, starting at `offset`, into
+    /// `dst` at `dst_offset`, using the `copy_file_range` system call.
+    ///
+    /// Returns the number of bytes copied, which may be less than `len`. A
+    /// return value of 0 means that `offset` is at or past the end of this
+    /// file.
+    ///
+    /// The data is copied inside the kernel without passing through userspace
+    /// buffers, and on filesystems that support it (e.g. Btrfs or XFS) the
+    /// copy may share the underlying storage between both files. The file
+    /// cursors of both files are not affected by this function.
+    ///
+    /// Unlike [`io::copy`], this method never falls back to reading and
+    /// writing. If the kernel cannot copy between the two files, the error is
+    /// returned as is, e.g. `EXDEV` for files on different filesystems on
+    /// kernels before 5.3, `EINVAL` if either file is not a regular file, or
+    /// `ENOSYS` if the kernel doesn't support `copy_file_range` at all (Linux
+    /// 4.5 and later do).
+    ///
+    /// [`io::copy`]: crate::io::copy
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(linux_zero_copy)]
+    /// use std::fs::File;
+    /// use std::io;
+    /// use std::os::linux::fs::FileCopyExt;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let src = File::open("foo.txt")?;
+    ///     let dst = File::create("bar.txt")?;
+    ///
+    ///     // Copy 4096 bytes from offset 10 of foo.txt to the start of bar.txt.
+    ///     let mut copied = 0;
+    ///     while copied < 4096 {
+    ///         let n = src.copy_range_to(10 + copied, &dst, copied, 4096 - copied as usize)?;
+    ///         if n == 0 {
+    ///             break;
+    ///         }
+    ///         copied += n as u64;
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    fn copy_range_to(
+        &self,
+        offset: u64,
+        dst: &File,
+        dst_offset: u64,
+        len: usize,
+    ) -> io::Result<usize>;
+}
+
+#[unstable(feature = "linux_zero_copy", issue = "none")]
+impl FileCopyExt for File {
+    fn copy_range_to(
+        &self,
+        offset: u64,
+        dst: &File,
+        dst_offset: u64,
+        len: usize,
+    ) -> io::Result<usize> {
+        self.as_inner().copy_range_to(offset, dst.as_inner(), dst_offset, len)
+    }
+}
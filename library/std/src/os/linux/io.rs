@@ -0,0 +1,109 @@
+//! Linux-specific extensions to general I/O primitives.
+
+#![unstable(feature = "linux_zero_copy", issue = "none")]
+
+use crate::io::{self, Read, Write};
+
+/// Describes how the data of a [`copy_with_report`] call was transferred.
+///
+/// On Linux, [`io::copy`] offloads copies between file descriptors to the
+/// kernel when it can, using `copy_file_range`, `sendfile` or `splice`. When
+/// these are unavailable for the particular pair of file descriptors, or stop
+/// working part-way through, it falls back to a read-write loop through a
+/// userspace buffer. A single copy may therefore use several methods, and the
+/// report accounts for the bytes moved by each of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CopyReport {
+    copy_file_range: u64,
+    sendfile: u64,
+    splice: u64,
+    read_write: u64,
+}
+
+impl CopyReport {
+    /// Returns the total number of bytes copied.
+    ///
+    /// This is the value [`io::copy`] would have returned.
+    pub fn bytes(&self) -> u64 {
+        self.copy_file_range + self.sendfile + self.splice + self.read_write
+    }
+
+    /// Returns the number of bytes copied with `copy_file_range`.
+    pub fn copy_file_range_bytes(&self) -> u64 {
+        self.copy_file_range
+    }
+
+    /// Returns the number of bytes copied with `sendfile`.
+    pub fn sendfile_bytes(&self) -> u64 {
+        self.sendfile
+    }
+
+    /// Returns the number of bytes copied with `splice`.
+    pub fn splice_bytes(&self) -> u64 {
+        self.splice
+    }
+
+    /// Returns the number of bytes that were read into and written from a
+    /// userspace buffer.
+    ///
+    /// This includes data that was already buffered by wrappers such as
+    /// [`BufReader`](io::BufReader) before the copy could be handed to the
+    /// kernel.
+    pub fn read_write_bytes(&self) -> u64 {
+        self.read_write
+    }
+
+    /// Returns `true` if no data was moved through userspace buffers.
+    pub fn is_offloaded(&self) -> bool {
+        self.read_write == 0
+    }
+}
+
+/// Copies the entire contents of a reader into a writer, like [`io::copy`],
+/// and reports which methods were used to transfer the data.
+///
+/// The copy itself behaves exactly like [`io::copy`], including its choice of
+/// system calls and fallbacks. This function only makes that choice
+/// observable, e.g. to verify that a file server actually serves files
+/// without copying them through userspace.
+///
+/// # Errors
+///
+/// This function returns the same errors as [`io::copy`].
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(linux_zero_copy)]
+/// use std::fs::File;
+/// use std::io;
+/// use std::net::TcpStream;
+/// use std::os::linux::io::copy_with_report;
+///
+/// fn main() -> io::Result<()> {
+///     let mut file = File::open("index.html")?;
+///     let mut stream = TcpStream::connect("127.0.0.1:8080")?;
+///
+///     let report = copy_with_report(&mut file, &mut stream)?;
+///     if !report.is_offloaded() {
+///         eprintln!("{} bytes were copied through userspace", report.read_write_bytes());
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn copy_with_report<R: ?Sized, W: ?Sized>(
+    reader: &mut R,
+    writer: &mut W,
+) -> io::Result<CopyReport>
+where
+    R: Read,
+    W: Write,
+{
+    let stats = crate::sys::kernel_copy::copy_spec_stats(reader, writer)?;
+    Ok(CopyReport {
+        copy_file_range: stats.copy_file_range,
+        sendfile: stats.sendfile,
+        splice: stats.splice,
+        read_write: stats.read_write,
+    })
+}
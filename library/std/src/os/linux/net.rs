@@ -0,0 +1,228 @@
+//! Linux-specific extensions to primitives in the `std::net` module.
+
+#![unstable(feature = "net_socket_options", issue = "none")]
+
+use crate::fs::File;
+use crate::io;
+use crate::net;
+use crate::sealed::Sealed;
+use crate::sys_common::AsInner;
+use crate::time::Duration;
+
+/// Linux-specific extensions for [`TcpStream`].
+///
+/// [`TcpStream`]: net::TcpStream
+pub trait TcpStreamExt: Sealed {
+    /// Sets the value of the `TCP_KEEPIDLE` option on this socket.
+    ///
+    /// This is how long the connection has to be idle before the first
+    /// keepalive probe is sent, rounded down to whole seconds. It only has an
+    /// effect if keepalive is enabled with [`TcpStream::set_keepalive`].
+    ///
+    /// [`TcpStream::set_keepalive`]: net::TcpStream::set_keepalive
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_options)]
+    /// use std::net::TcpStream;
+    /// use std::os::linux::net::TcpStreamExt;
+    /// use std::time::Duration;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// stream.set_keepalive(true).expect("set_keepalive call failed");
+    /// stream.set_keepalive_time(Duration::from_secs(60)).expect("set_keepalive_time call failed");
+    /// ```
+    fn set_keepalive_time(&self, time: Duration) -> io::Result<()>;
+
+    /// Gets the value of the `TCP_KEEPIDLE` option on this socket.
+    ///
+    /// For more information about this option, see [`set_keepalive_time`].
+    ///
+    /// [`set_keepalive_time`]: TcpStreamExt::set_keepalive_time
+    fn keepalive_time(&self) -> io::Result<Duration>;
+
+    /// Sets the value of the `TCP_KEEPINTVL` option on this socket.
+    ///
+    /// This is the time between two keepalive probes, rounded down to whole
+    /// seconds.
+    fn set_keepalive_interval(&self, interval: Duration) -> io::Result<()>;
+
+    /// Gets the value of the `TCP_KEEPINTVL` option on this socket.
+    ///
+    /// For more information about this option, see [`set_keepalive_interval`].
+    ///
+    /// [`set_keepalive_interval`]: TcpStreamExt::set_keepalive_interval
+    fn keepalive_interval(&self) -> io::Result<Duration>;
+
+    /// Sets the value of the `TCP_KEEPCNT` option on this socket.
+    ///
+    /// This is the number of unanswered keepalive probes after which the
+    /// connection is dropped.
+    fn set_keepalive_retries(&self, retries: u32) -> io::Result<()>;
+
+    /// Gets the value of the `TCP_KEEPCNT` option on this socket.
+    ///
+    /// For more information about this option, see [`set_keepalive_retries`].
+    ///
+    /// [`set_keepalive_retries`]: TcpStreamExt::set_keepalive_retries
+    fn keepalive_retries(&self) -> io::Result<u32>;
+
+    /// Sets the value of the `TCP_USER_TIMEOUT` option on this socket.
+    ///
+    /// This is the maximum amount of time that transmitted data may remain
+    /// unacknowledged before the connection is forcibly closed. If `None` is
+    /// passed, the system default is used.
+    ///
+    /// An [`Err`] is returned if the zero [`Duration`] is passed to this
+    /// method.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_options)]
+    /// use std::net::TcpStream;
+    /// use std::os::linux::net::TcpStreamExt;
+    /// use std::time::Duration;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// stream.set_user_timeout(Some(Duration::from_secs(30)))
+    ///       .expect("set_user_timeout call failed");
+    /// ```
+    fn set_user_timeout(&self, timeout: Option<Duration>) -> io::Result<()>;
+
+    /// Gets the value of the `TCP_USER_TIMEOUT` option on this socket.
+    ///
+    /// For more information about this option, see [`set_user_timeout`].
+    ///
+    /// [`set_user_timeout`]: TcpStreamExt::set_user_timeout
+    fn user_timeout(&self) -> io::Result<Option<Duration>>;
+
+    /// Sends up to `count` bytes of `file`, starting at `offset`, over this
+    /// stream using the `sendfile` system call.
+    ///
+    /// Returns the number of bytes sent, which may be less than `count`, and
+    /// is 0 if `offset` is at or past the end of the file.
+    ///
+    /// The data is passed to the socket inside the kernel, without being
+    /// copied through userspace buffers. The cursor of `file` is not affected
+    /// by this function. Unlike [`io::copy`], this method never falls back to
+    /// reading and writing: if `sendfile` can't be used with `file`, the error
+    /// is returned.
+    ///
+    /// [`io::copy`]: crate::io::copy
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(linux_zero_copy)]
+    /// use std::fs::File;
+    /// use std::io;
+    /// use std::net::TcpStream;
+    /// use std::os::linux::net::TcpStreamExt;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let file = File::open("index.html")?;
+    ///     let len = file.metadata()?.len();
+    ///     let stream = TcpStream::connect("127.0.0.1:8080")?;
+    ///
+    ///     let mut sent = 0;
+    ///     while sent < len {
+    ///         let n = stream.send_file(&file, sent, (len - sent) as usize)?;
+    ///         if n == 0 {
+    ///             break;
+    ///         }
+    ///         sent += n as u64;
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "linux_zero_copy", issue = "none")]
+    fn send_file(&self, file: &File, offset: u64, count: usize) -> io::Result<usize>;
+}
+
+impl TcpStreamExt for net::TcpStream {
+    fn set_keepalive_time(&self, time: Duration) -> io::Result<()> {
+        self.as_inner().socket().set_keepalive_time(time)
+    }
+
+    fn keepalive_time(&self) -> io::Result<Duration> {
+        self.as_inner().socket().keepalive_time()
+    }
+
+    fn set_keepalive_interval(&self, interval: Duration) -> io::Result<()> {
+        self.as_inner().socket().set_keepalive_interval(interval)
+    }
+
+    fn keepalive_interval(&self) -> io::Result<Duration> {
+        self.as_inner().socket().keepalive_interval()
+    }
+
+    fn set_keepalive_retries(&self, retries: u32) -> io::Result<()> {
+        self.as_inner().socket().set_keepalive_retries(retries)
+    }
+
+    fn keepalive_retries(&self) -> io::Result<u32> {
+        self.as_inner().socket().keepalive_retries()
+    }
+
+    fn set_user_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.as_inner().socket().set_user_timeout(timeout)
+    }
+
+    fn user_timeout(&self) -> io::Result<Option<Duration>> {
+        self.as_inner().socket().user_timeout()
+    }
+
+    fn send_file(&self, file: &File, offset: u64, count: usize) -> io::Result<usize> {
+        self.as_inner().socket().send_file(file.as_inner(), offset, count)
+    }
+}
+
+/// Linux-specific extensions for [`TcpSocket`].
+///
+/// [`TcpSocket`]: net::TcpSocket
+pub trait TcpSocketExt: Sealed {
+    /// Sets the value of the `SO_REUSEPORT` option on this socket.
+    ///
+    /// If set on every socket, this allows several sockets to bind to the
+    /// same address and port, with the kernel distributing incoming
+    /// connections between the listeners.
+    ///
+    /// This option must be set before calling [`TcpSocket::bind`].
+    ///
+    /// [`TcpSocket::bind`]: net::TcpSocket::bind
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_options)]
+    /// use std::net::TcpSocket;
+    /// use std::os::linux::net::TcpSocketExt;
+    ///
+    /// let socket = TcpSocket::new_v4().expect("couldn't create socket");
+    /// socket.set_reuse_port(true).expect("set_reuse_port call failed");
+    /// socket.bind("0.0.0.0:8080".parse().unwrap()).expect("couldn't bind to address");
+    /// let listener = socket.listen(1024).expect("couldn't listen");
+    /// ```
+    fn set_reuse_port(&self, reuse: bool) -> io::Result<()>;
+
+    /// Gets the value of the `SO_REUSEPORT` option on this socket.
+    ///
+    /// For more information about this option, see [`set_reuse_port`].
+    ///
+    /// [`set_reuse_port`]: TcpSocketExt::set_reuse_port
+    fn reuse_port(&self) -> io::Result<bool>;
+}
+
+impl TcpSocketExt for net::TcpSocket {
+    fn set_reuse_port(&self, reuse: bool) -> io::Result<()> {
+        self.as_inner().socket().set_reuse_port(reuse)
+    }
+
+    fn reuse_port(&self) -> io::Result<bool> {
+        self.as_inner().socket().reuse_port()
+    }
+}
@@ -49,6 +49,47 @@ pub trait FileExt {
     #[stable(feature = "file_offset", since = "1.15.0")]
     fn read_at(&self, buf: &mut [u8], offset: u64) -> io::Result<usize>;
 
+    /// Like [`read_at`], except that it reads into a slice of buffers.
+    ///
+    /// Data is copied to fill each buffer in order, with the final buffer
+    /// written to possibly being only partially filled. This method must behave
+    /// equivalently to a single call to [`read_at`] with concatenated buffers.
+    ///
+    /// The offset is relative to the start of the file and thus independent
+    /// from the current cursor. The current file cursor is not affected by this
+    /// function.
+    ///
+    /// The default implementation calls [`read_at`] with the first non-empty
+    /// buffer. On most platforms, the implementation for [`fs::File`] uses
+    /// `preadv` to fill all buffers with a single system call.
+    ///
+    /// [`read_at`]: FileExt::read_at
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_file_vectored_at)]
+    /// use std::fs::File;
+    /// use std::io::{self, IoSliceMut};
+    /// use std::os::unix::prelude::FileExt;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let file = File::open("foo.txt")?;
+    ///     let mut header = [0u8; 4];
+    ///     let mut body = [0u8; 64];
+    ///
+    ///     // Read the header and the start of the body from offset 10.
+    ///     let bufs = &mut [IoSliceMut::new(&mut header), IoSliceMut::new(&mut body)];
+    ///     let num_bytes_read = file.read_vectored_at(bufs, 10)?;
+    ///     println!("read {} bytes", num_bytes_read);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "unix_file_vectored_at", issue = "none")]
+    fn read_vectored_at(&self, bufs: &mut [io::IoSliceMut<'_>], offset: u64) -> io::Result<usize> {
+        io::default_read_vectored(|b| self.read_at(b, offset), bufs)
+    }
+
     /// Reads the exact number of byte required to fill `buf` from the given offset.
     ///
     /// The offset is relative to the start of the file and thus independent
@@ -150,6 +191,44 @@ fn read_exact_at(&self, mut buf: &mut [u8], mut offset: u64) -> io::Result<()> {
     #[stable(feature = "file_offset", since = "1.15.0")]
     fn write_at(&self, buf: &[u8], offset: u64) -> io::Result<usize>;
 
+    /// Like [`write_at`], except that it writes from a slice of buffers.
+    ///
+    /// Data is copied from each buffer in order, with the final buffer read
+    /// from possibly being only partially consumed. This method must behave as
+    /// a call to [`write_at`] with the buffers concatenated would.
+    ///
+    /// The offset is relative to the start of the file and thus independent
+    /// from the current cursor. The current file cursor is not affected by this
+    /// function.
+    ///
+    /// The default implementation calls [`write_at`] with the first non-empty
+    /// buffer. On most platforms, the implementation for [`fs::File`] uses
+    /// `pwritev` to write all buffers with a single system call.
+    ///
+    /// [`write_at`]: FileExt::write_at
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_file_vectored_at)]
+    /// use std::fs::File;
+    /// use std::io::{self, IoSlice};
+    /// use std::os::unix::prelude::FileExt;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let file = File::create("foo.txt")?;
+    ///
+    ///     // Write a header and a body at offset 10.
+    ///     let bufs = &[IoSlice::new(b"HEAD"), IoSlice::new(b"sushi")];
+    ///     file.write_vectored_at(bufs, 10)?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "unix_file_vectored_at", issue = "none")]
+    fn write_vectored_at(&self, bufs: &[io::IoSlice<'_>], offset: u64) -> io::Result<usize> {
+        io::default_write_vectored(|b| self.write_at(b, offset), bufs)
+    }
+
     /// Attempts to write an entire buffer starting from a given offset.
     ///
     /// The offset is relative to the start of the file and thus independent
@@ -213,9 +292,15 @@ impl FileExt for fs::File {
     fn read_at(&self, buf: &mut [u8], offset: u64) -> io::Result<usize> {
         self.as_inner().read_at(buf, offset)
     }
+    fn read_vectored_at(&self, bufs: &mut [io::IoSliceMut<'_>], offset: u64) -> io::Result<usize> {
+        self.as_inner().read_vectored_at(bufs, offset)
+    }
     fn write_at(&self, buf: &[u8], offset: u64) -> io::Result<usize> {
         self.as_inner().write_at(buf, offset)
     }
+    fn write_vectored_at(&self, bufs: &[io::IoSlice<'_>], offset: u64) -> io::Result<usize> {
+        self.as_inner().write_vectored_at(bufs, offset)
+    }
 }
 
 /// Unix-specific extensions to [`fs::Permissions`].
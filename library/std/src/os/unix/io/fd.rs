@@ -0,0 +1,264 @@
+//! Owned and borrowed file descriptors.
+
+#![unstable(feature = "io_safety", issue = "none")]
+#![deny(unsafe_op_in_unsafe_fn)]
+
+use super::raw::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use crate::fmt;
+use crate::fs;
+use crate::io;
+use crate::marker::PhantomData;
+use crate::mem::{forget, ManuallyDrop};
+use crate::net;
+use crate::sys;
+use crate::sys_common::AsInner;
+
+#[cfg(test)]
+mod tests;
+
+/// A borrowed file descriptor.
+///
+/// This has a lifetime parameter to tie it to the lifetime of something that
+/// owns the file descriptor.
+///
+/// This uses `repr(transparent)` and has the representation of a host file
+/// descriptor, so it can be used in FFI in places where a file descriptor is
+/// passed as an argument, it is not captured or consumed, and it never has the
+/// value `-1`.
+#[derive(Copy, Clone)]
+#[repr(transparent)]
+#[rustc_layout_scalar_valid_range_start(0)]
+// libstd/os/raw/mod.rs assures that every libstd-supported platform has a
+// 32-bit c_int. Below is -2, in two's complement, but that only works out
+// because c_int is 32 bits.
+#[rustc_layout_scalar_valid_range_end(0xFF_FF_FF_FE)]
+#[rustc_nonnull_optimization_guaranteed]
+#[unstable(feature = "io_safety", issue = "none")]
+pub struct BorrowedFd<'fd> {
+    fd: RawFd,
+    _phantom: PhantomData<&'fd OwnedFd>,
+}
+
+/// An owned file descriptor.
+///
+/// This closes the file descriptor on drop.
+///
+/// This uses `repr(transparent)` and has the representation of a host file
+/// descriptor, so it can be used in FFI in places where a file descriptor is
+/// passed as a consumed argument or returned as an owned value, and it never
+/// has the value `-1`.
+#[repr(transparent)]
+#[rustc_layout_scalar_valid_range_start(0)]
+// libstd/os/raw/mod.rs assures that every libstd-supported platform has a
+// 32-bit c_int. Below is -2, in two's complement, but that only works out
+// because c_int is 32 bits.
+#[rustc_layout_scalar_valid_range_end(0xFF_FF_FF_FE)]
+#[rustc_nonnull_optimization_guaranteed]
+#[unstable(feature = "io_safety", issue = "none")]
+pub struct OwnedFd {
+    fd: RawFd,
+}
+
+impl BorrowedFd<'_> {
+    /// Returns a `BorrowedFd` holding the given raw file descriptor.
+    ///
+    /// # Safety
+    ///
+    /// The resource pointed to by `fd` must remain open for the duration of
+    /// the returned `BorrowedFd`, and it must not have the value `-1`.
+    #[inline]
+    #[unstable(feature = "io_safety", issue = "none")]
+    pub unsafe fn borrow_raw_fd(fd: RawFd) -> Self {
+        assert_ne!(fd, u32::MAX as RawFd);
+        // SAFETY: we just asserted that the value is in the valid range and
+        // isn't `-1` (the only value bigger than `0xFF_FF_FF_FE` unsigned).
+        unsafe { Self { fd, _phantom: PhantomData } }
+    }
+}
+
+impl OwnedFd {
+    /// Creates a new `OwnedFd` instance that shares the same underlying file
+    /// description as the existing `OwnedFd` instance.
+    #[unstable(feature = "io_safety", issue = "none")]
+    pub fn try_clone(&self) -> io::Result<Self> {
+        // `FileDesc` knows how to duplicate a file descriptor on every
+        // platform, but it would also close this one when dropped.
+        let fd = ManuallyDrop::new(sys::fd::FileDesc::new(self.fd));
+        let duplicate = fd.duplicate()?;
+        // SAFETY: `duplicate` is a new file descriptor, which nothing else
+        // owns.
+        Ok(unsafe { Self::from_raw_fd(duplicate.into_raw()) })
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl AsRawFd for BorrowedFd<'_> {
+    #[inline]
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl AsRawFd for OwnedFd {
+    #[inline]
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl IntoRawFd for OwnedFd {
+    #[inline]
+    fn into_raw_fd(self) -> RawFd {
+        let fd = self.fd;
+        forget(self);
+        fd
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl FromRawFd for OwnedFd {
+    /// Constructs a new instance of `Self` from the given raw file descriptor.
+    ///
+    /// # Safety
+    ///
+    /// The resource pointed to by `fd` must be open and suitable for assuming
+    /// ownership. The resource must not require any cleanup other than `close`.
+    #[inline]
+    unsafe fn from_raw_fd(fd: RawFd) -> Self {
+        assert_ne!(fd, u32::MAX as RawFd);
+        // SAFETY: we just asserted that the value is in the valid range and
+        // isn't `-1` (the only value bigger than `0xFF_FF_FF_FE` unsigned).
+        unsafe { Self { fd } }
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl Drop for OwnedFd {
+    #[inline]
+    fn drop(&mut self) {
+        // Closing goes through `FileDesc`, which ignores errors like the rest
+        // of the standard library does, and handles platform specifics.
+        drop(sys::fd::FileDesc::new(self.fd));
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl fmt::Debug for BorrowedFd<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BorrowedFd").field("fd", &self.fd).finish()
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl fmt::Debug for OwnedFd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedFd").field("fd", &self.fd).finish()
+    }
+}
+
+/// A trait to borrow the file descriptor from an underlying object.
+///
+/// This is only available on unix platforms and must be imported in order to
+/// call the method. Windows platforms have a corresponding `AsHandle` and
+/// `AsSocket` set of traits.
+#[unstable(feature = "io_safety", issue = "none")]
+pub trait AsFd {
+    /// Borrows the file descriptor.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// #![feature(io_safety)]
+    /// use std::fs::File;
+    /// # use std::io;
+    /// use std::os::unix::io::{AsFd, BorrowedFd};
+    ///
+    /// let f = File::open("foo.txt")?;
+    /// let borrowed_fd: BorrowedFd<'_> = f.as_fd();
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    #[unstable(feature = "io_safety", issue = "none")]
+    fn as_fd(&self) -> BorrowedFd<'_>;
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl AsFd for BorrowedFd<'_> {
+    #[inline]
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        *self
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl AsFd for OwnedFd {
+    #[inline]
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        // SAFETY: `OwnedFd` and `BorrowedFd` have the same validity
+        // invariants, and the `BorrowedFd` is bounded by the lifetime
+        // of `&self`.
+        unsafe { BorrowedFd::borrow_raw_fd(self.as_raw_fd()) }
+    }
+}
+
+// The standard library types implement `AsFd` and the conversions from and to
+// `OwnedFd` on top of their raw file descriptor traits, which already have the
+// same ownership semantics. The types of `os::unix::net` and `process` do the
+// same next to their raw file descriptor traits.
+macro_rules! impl_as_fd {
+    ($($t:ty),*) => {$(
+        #[unstable(feature = "io_safety", issue = "none")]
+        impl AsFd for $t {
+            #[inline]
+            fn as_fd(&self) -> BorrowedFd<'_> {
+                // SAFETY: the file descriptor is owned by `self`, so it stays
+                // open as long as it's borrowed.
+                unsafe { BorrowedFd::borrow_raw_fd(self.as_raw_fd()) }
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_into_owned_fd {
+    ($($t:ty),*) => {$(
+        #[unstable(feature = "io_safety", issue = "none")]
+        impl From<$t> for OwnedFd {
+            #[inline]
+            fn from(owner: $t) -> OwnedFd {
+                // SAFETY: `into_raw_fd` transfers the ownership of its file
+                // descriptor, which is open.
+                unsafe { OwnedFd::from_raw_fd(owner.into_raw_fd()) }
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_from_owned_fd {
+    ($($t:ty),*) => {$(
+        #[unstable(feature = "io_safety", issue = "none")]
+        impl From<OwnedFd> for $t {
+            #[inline]
+            fn from(owned_fd: OwnedFd) -> Self {
+                // SAFETY: `OwnedFd` guarantees that its file descriptor is
+                // open, and its ownership is transferred here.
+                unsafe { Self::from_raw_fd(owned_fd.into_raw_fd()) }
+            }
+        }
+    )*};
+}
+
+impl_as_fd!(fs::File, net::TcpStream, net::TcpListener, net::UdpSocket);
+impl_into_owned_fd!(fs::File, net::TcpStream, net::TcpListener, net::UdpSocket);
+impl_from_owned_fd!(fs::File, net::TcpStream, net::TcpListener, net::UdpSocket);
+
+impl_as_fd!(io::Stdin, io::Stdout, io::Stderr);
+impl_as_fd!(io::StdinLock<'_>, io::StdoutLock<'_>, io::StderrLock<'_>);
+
+#[unstable(feature = "async_io", issue = "none")]
+impl AsFd for io::Source<'_> {
+    #[inline]
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        *self.as_inner()
+    }
+}
@@ -193,3 +193,11 @@ fn as_raw_fd(&self) -> RawFd {
         libc::STDERR_FILENO
     }
 }
+
+#[unstable(feature = "async_io", issue = "none")]
+impl AsRawFd for io::Source<'_> {
+    #[inline]
+    fn as_raw_fd(&self) -> RawFd {
+        self.as_inner().as_raw_fd()
+    }
+}
@@ -1,5 +1,5 @@
 use super::{sockaddr_un, SocketAddr, UnixStream};
-use crate::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use crate::os::unix::io::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd};
 use crate::path::Path;
 use crate::sys::cvt;
 use crate::sys::net::Socket;
@@ -320,3 +320,30 @@ fn size_hint(&self) -> (usize, Option<usize>) {
         (usize::MAX, None)
     }
 }
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl AsFd for UnixListener {
+    #[inline]
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        // SAFETY: the file descriptor is owned by `self`.
+        unsafe { BorrowedFd::borrow_raw_fd(self.as_raw_fd()) }
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl From<UnixListener> for OwnedFd {
+    #[inline]
+    fn from(unix_listener: UnixListener) -> OwnedFd {
+        // SAFETY: `into_raw_fd` transfers the ownership of the file descriptor.
+        unsafe { OwnedFd::from_raw_fd(unix_listener.into_raw_fd()) }
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl From<OwnedFd> for UnixListener {
+    #[inline]
+    fn from(owned: OwnedFd) -> Self {
+        // SAFETY: `OwnedFd` transfers the ownership of its open file descriptor.
+        unsafe { Self::from_raw_fd(owned.into_raw_fd()) }
+    }
+}
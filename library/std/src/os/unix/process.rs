@@ -4,7 +4,7 @@
 
 use crate::ffi::OsStr;
 use crate::io;
-use crate::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use crate::os::unix::io::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd};
 use crate::process;
 use crate::sealed::Sealed;
 use crate::sys;
@@ -147,6 +147,59 @@ fn before_exec<F>(&mut self, f: F) -> &mut process::Command
     fn arg0<S>(&mut self, arg: S) -> &mut process::Command
     where
         S: AsRef<OsStr>;
+
+    /// Sets the process group ID (PGID) of the child process. Equivalent to a
+    /// `setpgid` call in the child process, but may be more efficient.
+    ///
+    /// Process groups determine which processes receive signals.
+    ///
+    /// A `pgroup` of 0 puts the child in a new process group, whose ID is the
+    /// child's process ID. This makes it possible to signal the child along
+    /// with all the processes it spawns, by sending a signal to the group,
+    /// without signaling the parent. It also keeps the child from receiving the
+    /// signals sent to the parent's process group, such as the `SIGINT` sent
+    /// to the foreground group of a terminal when the user presses `Ctrl-C`.
+    ///
+    /// # Examples
+    ///
+    /// Pressing `Ctrl-C` in a terminal will send `SIGINT` to all processes in
+    /// the current foreground process group. By spawning the `sleep`
+    /// subprocess in a new process group, it will not receive `SIGINT` from
+    /// the terminal.
+    ///
+    /// The parent process could install a signal handler and manage the
+    /// subprocess on its own terms.
+    ///
+    /// ```no_run
+    /// #![feature(process_set_process_group)]
+    ///
+    /// use std::process::Command;
+    /// use std::os::unix::process::CommandExt;
+    ///
+    /// Command::new("sleep")
+    ///     .arg("10")
+    ///     .process_group(0)
+    ///     .spawn()?
+    ///     .wait()?;
+    /// #
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[unstable(feature = "process_set_process_group", issue = "none")]
+    fn process_group(&mut self, pgroup: i32) -> &mut process::Command;
+
+    /// Sets whether the child process should start a new session. Equivalent
+    /// to a `setsid` call in the child process.
+    ///
+    /// The child becomes the leader of a new session and of a new process
+    /// group in it, and has no controlling terminal. This detaches it from the
+    /// terminal of the parent, along with the processes it spawns.
+    ///
+    /// A session leader can't change its process group, so this shouldn't be
+    /// combined with [`process_group`]: the spawn would fail.
+    ///
+    /// [`process_group`]: CommandExt::process_group
+    #[unstable(feature = "process_setsid", issue = "none")]
+    fn setsid(&mut self, setsid: bool) -> &mut process::Command;
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -199,6 +252,75 @@ fn arg0<S>(&mut self, arg: S) -> &mut process::Command
         self.as_inner_mut().set_arg_0(arg.as_ref());
         self
     }
+
+    fn process_group(&mut self, pgroup: i32) -> &mut process::Command {
+        self.as_inner_mut().pgroup(pgroup);
+        self
+    }
+
+    fn setsid(&mut self, setsid: bool) -> &mut process::Command {
+        self.as_inner_mut().setsid(setsid);
+        self
+    }
+}
+
+/// Unix-specific extensions to [`process::Child`].
+///
+/// This trait is sealed: it cannot be implemented outside the standard library.
+/// This is so that future additional methods are not breaking changes.
+#[unstable(feature = "unix_send_signal", issue = "none")]
+pub trait ChildExt: Sealed {
+    /// Sends a signal to a child process.
+    ///
+    /// Like [`Child::kill`], this returns an [`InvalidInput`] error if the
+    /// child was already waited for, as its process ID may then belong to
+    /// another process. On Linux, the signal is sent through the child's pidfd
+    /// if it has one, which can't refer to another process. Otherwise, as on
+    /// other platforms, it is sent to the raw process ID, which could have been
+    /// reused if the child was reaped by other means, e.g. by a `waitpid` call
+    /// outside of std.
+    ///
+    /// This only signals the child itself. To also reach the processes it
+    /// spawned, put it in its own process group with
+    /// [`CommandExt::process_group`]`(0)` and signal that group with
+    /// `libc::killpg`, passing the child's [`id`]: std has no API to signal a
+    /// process group.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the signal is invalid. The integer
+    /// values associated with signals are implementation-specific, so it's
+    /// encouraged to use a crate that provides posix bindings.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #![feature(unix_send_signal)]
+    ///
+    /// use std::{io, os::unix::process::ChildExt, process::{Command, Stdio}};
+    ///
+    /// const SIGTERM: i32 = 15;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut child = Command::new("cat").stdin(Stdio::piped()).spawn()?;
+    ///     child.send_signal(SIGTERM)?;
+    ///     child.wait()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [`Child::kill`]: process::Child::kill
+    /// [`id`]: process::Child::id
+    /// [`InvalidInput`]: io::ErrorKind::InvalidInput
+    #[unstable(feature = "unix_send_signal", issue = "none")]
+    fn send_signal(&self, signal: i32) -> io::Result<()>;
+}
+
+#[unstable(feature = "unix_send_signal", issue = "none")]
+impl ChildExt for process::Child {
+    fn send_signal(&self, signal: i32) -> io::Result<()> {
+        self.as_inner().send_signal(signal)
+    }
 }
 
 /// Unix-specific extensions to [`process::ExitStatus`] and
@@ -375,6 +497,69 @@ fn into_raw_fd(self) -> RawFd {
     }
 }
 
+#[unstable(feature = "io_safety", issue = "none")]
+impl From<OwnedFd> for process::Stdio {
+    #[inline]
+    fn from(fd: OwnedFd) -> process::Stdio {
+        // SAFETY: `OwnedFd` transfers the ownership of its open file descriptor.
+        unsafe { process::Stdio::from_raw_fd(fd.into_raw_fd()) }
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl AsFd for process::ChildStdin {
+    #[inline]
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        // SAFETY: the file descriptor is owned by `self`.
+        unsafe { BorrowedFd::borrow_raw_fd(self.as_raw_fd()) }
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl From<process::ChildStdin> for OwnedFd {
+    #[inline]
+    fn from(child_stdio: process::ChildStdin) -> OwnedFd {
+        // SAFETY: `into_raw_fd` transfers the ownership of the file descriptor.
+        unsafe { OwnedFd::from_raw_fd(child_stdio.into_raw_fd()) }
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl AsFd for process::ChildStdout {
+    #[inline]
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        // SAFETY: the file descriptor is owned by `self`.
+        unsafe { BorrowedFd::borrow_raw_fd(self.as_raw_fd()) }
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl From<process::ChildStdout> for OwnedFd {
+    #[inline]
+    fn from(child_stdio: process::ChildStdout) -> OwnedFd {
+        // SAFETY: `into_raw_fd` transfers the ownership of the file descriptor.
+        unsafe { OwnedFd::from_raw_fd(child_stdio.into_raw_fd()) }
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl AsFd for process::ChildStderr {
+    #[inline]
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        // SAFETY: the file descriptor is owned by `self`.
+        unsafe { BorrowedFd::borrow_raw_fd(self.as_raw_fd()) }
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl From<process::ChildStderr> for OwnedFd {
+    #[inline]
+    fn from(child_stdio: process::ChildStderr) -> OwnedFd {
+        // SAFETY: `into_raw_fd` transfers the ownership of the file descriptor.
+        unsafe { OwnedFd::from_raw_fd(child_stdio.into_raw_fd()) }
+    }
+}
+
 /// Returns the OS-assigned process identifier associated with this process's parent.
 #[stable(feature = "unix_ppid", since = "1.27.0")]
 pub fn parent_id() -> u32 {
@@ -0,0 +1,368 @@
+//! Owned and borrowed OS handles.
+
+#![unstable(feature = "io_safety", issue = "none")]
+#![deny(unsafe_op_in_unsafe_fn)]
+
+use super::raw::{AsRawHandle, FromRawHandle, IntoRawHandle, RawHandle};
+use crate::convert::TryFrom;
+use crate::ffi::c_void;
+use crate::fmt;
+use crate::fs;
+use crate::io;
+use crate::marker::PhantomData;
+use crate::mem::{forget, ManuallyDrop};
+use crate::process;
+use crate::ptr::NonNull;
+use crate::sys;
+use crate::sys::c;
+
+#[cfg(test)]
+mod tests;
+
+/// A borrowed handle.
+///
+/// This has a lifetime parameter to tie it to the lifetime of something that
+/// owns the handle.
+///
+/// This uses `repr(transparent)` and has the representation of a host handle,
+/// so it can be used in FFI in places where a handle is passed as an argument,
+/// it is not captured or consumed, and it is never null.
+///
+/// Note that it *may* have the value `INVALID_HANDLE_VALUE` (-1), which is
+/// sometimes a valid handle value. See [here] for the full story.
+///
+/// [here]: https://devblogs.microsoft.com/oldnewthing/20040302-00/?p=40443
+#[derive(Copy, Clone)]
+#[repr(transparent)]
+#[unstable(feature = "io_safety", issue = "none")]
+pub struct BorrowedHandle<'handle> {
+    handle: NonNull<c_void>,
+    _phantom: PhantomData<&'handle OwnedHandle>,
+}
+
+/// An owned handle.
+///
+/// This closes the handle on drop.
+///
+/// This uses `repr(transparent)` and has the representation of a host handle,
+/// so it can be used in FFI in places where a handle is passed as a consumed
+/// argument or returned as an owned value, and is never null.
+///
+/// Note that it *may* have the value `INVALID_HANDLE_VALUE` (-1), which is
+/// sometimes a valid handle value. See [here] for the full story. For APIs
+/// like `CreateFileW` which report errors with `INVALID_HANDLE_VALUE` instead
+/// of null, use [`HandleOrInvalid`] instead of `Option<OwnedHandle>`.
+///
+/// `OwnedHandle` uses [`CloseHandle`] to close its handle on drop. As such,
+/// it must not be used with handles to open registry keys which need to be
+/// closed with [`RegCloseKey`] instead.
+///
+/// [`CloseHandle`]: https://docs.microsoft.com/en-us/windows/win32/api/handleapi/nf-handleapi-closehandle
+/// [`RegCloseKey`]: https://docs.microsoft.com/en-us/windows/win32/api/winreg/nf-winreg-regclosekey
+///
+/// [here]: https://devblogs.microsoft.com/oldnewthing/20040302-00/?p=40443
+#[repr(transparent)]
+#[unstable(feature = "io_safety", issue = "none")]
+pub struct OwnedHandle {
+    handle: NonNull<c_void>,
+}
+
+/// FFI type for handles in return values or out parameters, where
+/// `INVALID_HANDLE_VALUE` is used as a sentry value to indicate errors, such
+/// as in the return value of `CreateFileW`. This uses `repr(transparent)` and
+/// has the representation of a host handle, so that it can be used in such FFI
+/// declarations.
+///
+/// The only thing you can usefully do with a `HandleOrInvalid` is to convert
+/// it into an `OwnedHandle` using its [`TryFrom`] implementation; this
+/// conversion takes care of the check for `INVALID_HANDLE_VALUE`. This ensures
+/// that such FFI calls cannot start using the handle without checking for
+/// `INVALID_HANDLE_VALUE` first.
+///
+/// If this holds a valid handle, it will close the handle on drop.
+#[repr(transparent)]
+#[unstable(feature = "io_safety", issue = "none")]
+#[derive(Debug)]
+pub struct HandleOrInvalid(Option<OwnedHandle>);
+
+// The Windows [`HANDLE`] type may be transferred across and shared between
+// thread boundaries (despite containing a `*mut void`, which in general isn't
+// `Send` or `Sync`).
+//
+// [`HANDLE`]: std::os::windows::raw::HANDLE
+#[unstable(feature = "io_safety", issue = "none")]
+unsafe impl Send for OwnedHandle {}
+#[unstable(feature = "io_safety", issue = "none")]
+unsafe impl Send for HandleOrInvalid {}
+#[unstable(feature = "io_safety", issue = "none")]
+unsafe impl Send for BorrowedHandle<'_> {}
+#[unstable(feature = "io_safety", issue = "none")]
+unsafe impl Sync for OwnedHandle {}
+#[unstable(feature = "io_safety", issue = "none")]
+unsafe impl Sync for HandleOrInvalid {}
+#[unstable(feature = "io_safety", issue = "none")]
+unsafe impl Sync for BorrowedHandle<'_> {}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl BorrowedHandle<'_> {
+    /// Returns a `BorrowedHandle` holding the given raw handle.
+    ///
+    /// # Safety
+    ///
+    /// The resource pointed to by `handle` must be a valid open handle, it
+    /// must remain open for the duration of the returned `BorrowedHandle`, and
+    /// it must not be null.
+    ///
+    /// Note that it *may* have the value `INVALID_HANDLE_VALUE` (-1), which is
+    /// sometimes a valid handle value. See [here] for the full story.
+    ///
+    /// [here]: https://devblogs.microsoft.com/oldnewthing/20040302-00/?p=40443
+    #[inline]
+    #[unstable(feature = "io_safety", issue = "none")]
+    pub unsafe fn borrow_raw_handle(handle: RawHandle) -> Self {
+        assert!(!handle.is_null());
+        // SAFETY: we just asserted that the handle isn't null.
+        Self { handle: unsafe { NonNull::new_unchecked(handle) }, _phantom: PhantomData }
+    }
+}
+
+impl OwnedHandle {
+    /// Creates a new `OwnedHandle` instance that refers to the same kernel
+    /// object as the existing `OwnedHandle` instance.
+    #[unstable(feature = "io_safety", issue = "none")]
+    pub fn try_clone(&self) -> io::Result<Self> {
+        // `Handle` knows how to duplicate a handle, but it would also close
+        // this one when dropped.
+        let handle = ManuallyDrop::new(sys::handle::Handle::new(self.as_raw_handle()));
+        let duplicate = handle.duplicate(0, false, c::DUPLICATE_SAME_ACCESS)?;
+        // SAFETY: `duplicate` is a new handle, which nothing else owns.
+        Ok(unsafe { Self::from_raw_handle(duplicate.into_raw()) })
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl TryFrom<HandleOrInvalid> for OwnedHandle {
+    type Error = ();
+
+    #[inline]
+    fn try_from(handle_or_invalid: HandleOrInvalid) -> Result<Self, ()> {
+        // In theory, we ought to be able to assume that the pointer here is
+        // never null, use `OwnedHandle` rather than `Option<OwnedHandle>`, and
+        // obviate the the panic path here. Unfortunately, Win32 documentation
+        // doesn't explicitly guarantee this anywhere.
+        //
+        // APIs like [`CreateFileW`] itself have `HANDLE` arguments where a
+        // null handle indicates an absent value, which wouldn't work if null
+        // were a valid handle value, so it seems very unlikely that it could
+        // ever return null. But who knows?
+        //
+        // [`CreateFileW`]: https://docs.microsoft.com/en-us/windows/win32/api/fileapi/nf-fileapi-createfilew
+        let owned_handle = handle_or_invalid.0.expect("A `HandleOrInvalid` was null!");
+        if owned_handle.handle.as_ptr() == c::INVALID_HANDLE_VALUE {
+            forget(owned_handle);
+            Err(())
+        } else {
+            Ok(owned_handle)
+        }
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl AsRawHandle for BorrowedHandle<'_> {
+    #[inline]
+    fn as_raw_handle(&self) -> RawHandle {
+        self.handle.as_ptr()
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl AsRawHandle for OwnedHandle {
+    #[inline]
+    fn as_raw_handle(&self) -> RawHandle {
+        self.handle.as_ptr()
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl IntoRawHandle for OwnedHandle {
+    #[inline]
+    fn into_raw_handle(self) -> RawHandle {
+        let handle = self.handle.as_ptr();
+        forget(self);
+        handle
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl FromRawHandle for OwnedHandle {
+    /// Constructs a new instance of `Self` from the given raw handle.
+    ///
+    /// Use `HandleOrInvalid` instead of `Option<OwnedHandle>` for APIs that
+    /// use `INVALID_HANDLE_VALUE` to indicate failure.
+    ///
+    /// # Safety
+    ///
+    /// The resource pointed to by `handle` must be open and suitable for
+    /// assuming ownership. The resource must not require any cleanup other
+    /// than `CloseHandle`.
+    ///
+    /// In particular, it must not be used with handles to open registry
+    /// keys which need to be closed with [`RegCloseKey`] instead.
+    ///
+    /// Note that it *may* have the value `INVALID_HANDLE_VALUE` (-1), which is
+    /// sometimes a valid handle value. See [here] for the full story.
+    ///
+    /// [`RegCloseKey`]: https://docs.microsoft.com/en-us/windows/win32/api/winreg/nf-winreg-regclosekey
+    /// [here]: https://devblogs.microsoft.com/oldnewthing/20040302-00/?p=40443
+    #[inline]
+    unsafe fn from_raw_handle(handle: RawHandle) -> Self {
+        assert!(!handle.is_null());
+        // SAFETY: we just asserted that the handle isn't null.
+        Self { handle: unsafe { NonNull::new_unchecked(handle) } }
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl FromRawHandle for HandleOrInvalid {
+    /// Constructs a new instance of `Self` from the given `RawHandle` returned
+    /// from a Windows API that uses `INVALID_HANDLE_VALUE` to indicate
+    /// failure, such as `CreateFileW`.
+    ///
+    /// Use `Option<OwnedHandle>` instead of `HandleOrInvalid` for APIs that
+    /// use null to indicate failure.
+    ///
+    /// # Safety
+    ///
+    /// The resource pointed to by `handle` must be either open and otherwise
+    /// unowned, or equal to `INVALID_HANDLE_VALUE` (-1). It must not be null.
+    /// Note that not all Windows APIs use `INVALID_HANDLE_VALUE` for errors;
+    /// see [here] for the full story.
+    ///
+    /// [here]: https://devblogs.microsoft.com/oldnewthing/20040302-00/?p=40443
+    #[inline]
+    unsafe fn from_raw_handle(handle: RawHandle) -> Self {
+        // We require non-null here to catch errors earlier.
+        Self(Some(unsafe { OwnedHandle::from_raw_handle(handle) }))
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl Drop for OwnedHandle {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let _ = c::CloseHandle(self.handle.as_ptr());
+        }
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl fmt::Debug for BorrowedHandle<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BorrowedHandle").field("handle", &self.handle).finish()
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl fmt::Debug for OwnedHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedHandle").field("handle", &self.handle).finish()
+    }
+}
+
+/// A trait to borrow the handle from an underlying object.
+///
+/// The standard streams don't implement this trait: their handle may be null,
+/// for example in a process without a console.
+#[unstable(feature = "io_safety", issue = "none")]
+pub trait AsHandle {
+    /// Borrows the handle.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// #![feature(io_safety)]
+    /// use std::fs::File;
+    /// # use std::io;
+    /// use std::os::windows::io::{AsHandle, BorrowedHandle};
+    ///
+    /// let f = File::open("foo.txt")?;
+    /// let borrowed_handle: BorrowedHandle<'_> = f.as_handle();
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    #[unstable(feature = "io_safety", issue = "none")]
+    fn as_handle(&self) -> BorrowedHandle<'_>;
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl AsHandle for BorrowedHandle<'_> {
+    #[inline]
+    fn as_handle(&self) -> BorrowedHandle<'_> {
+        *self
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl AsHandle for OwnedHandle {
+    #[inline]
+    fn as_handle(&self) -> BorrowedHandle<'_> {
+        // SAFETY: `OwnedHandle` and `BorrowedHandle` have the same validity
+        // invariants, and the `BorrowedHandle` is bounded by the lifetime
+        // of `&self`.
+        unsafe { BorrowedHandle::borrow_raw_handle(self.as_raw_handle()) }
+    }
+}
+
+// The standard library types implement `AsHandle` and the conversions from
+// and to `OwnedHandle` on top of their raw handle traits, which already have
+// the same ownership semantics.
+macro_rules! impl_as_handle {
+    ($($t:ty),*) => {$(
+        #[unstable(feature = "io_safety", issue = "none")]
+        impl AsHandle for $t {
+            #[inline]
+            fn as_handle(&self) -> BorrowedHandle<'_> {
+                // SAFETY: the handle is owned by `self`, so it stays open as
+                // long as it's borrowed.
+                unsafe { BorrowedHandle::borrow_raw_handle(self.as_raw_handle()) }
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_into_owned_handle {
+    ($($t:ty),*) => {$(
+        #[unstable(feature = "io_safety", issue = "none")]
+        impl From<$t> for OwnedHandle {
+            #[inline]
+            fn from(owner: $t) -> OwnedHandle {
+                // SAFETY: `into_raw_handle` transfers the ownership of its
+                // handle, which is open.
+                unsafe { OwnedHandle::from_raw_handle(owner.into_raw_handle()) }
+            }
+        }
+    )*};
+}
+
+impl_as_handle!(fs::File, process::Child, process::ChildStdin);
+impl_as_handle!(process::ChildStdout, process::ChildStderr);
+impl_into_owned_handle!(fs::File, process::Child, process::ChildStdin);
+impl_into_owned_handle!(process::ChildStdout, process::ChildStderr);
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl From<OwnedHandle> for fs::File {
+    #[inline]
+    fn from(owned: OwnedHandle) -> Self {
+        // SAFETY: `OwnedHandle` transfers the ownership of its open handle.
+        unsafe { Self::from_raw_handle(owned.into_raw_handle()) }
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl From<OwnedHandle> for process::Stdio {
+    #[inline]
+    fn from(owned: OwnedHandle) -> Self {
+        // SAFETY: `OwnedHandle` transfers the ownership of its open handle.
+        unsafe { Self::from_raw_handle(owned.into_raw_handle()) }
+    }
+}
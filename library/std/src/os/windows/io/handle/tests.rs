@@ -0,0 +1,51 @@
+use crate::io::prelude::*;
+
+use crate::fs::File;
+use crate::io::SeekFrom;
+use crate::mem::size_of;
+use crate::os::windows::io::{
+    AsHandle, AsRawHandle, BorrowedHandle, FromRawHandle, IntoRawHandle, OwnedHandle, RawHandle,
+};
+use crate::sys_common::io::test::tmpdir;
+
+#[test]
+fn test_handle() {
+    let dir = tmpdir();
+    let file = File::create(dir.path().join("handle")).unwrap();
+    let raw_handle: RawHandle = file.as_raw_handle();
+
+    let owned_handle: OwnedHandle = file.into();
+    assert_eq!(owned_handle.as_raw_handle(), raw_handle);
+    let handle: BorrowedHandle<'_> = owned_handle.as_handle();
+    assert_eq!(handle.as_raw_handle(), raw_handle);
+
+    let file = File::from(owned_handle);
+    assert_eq!(file.as_handle().as_raw_handle(), raw_handle);
+    let raw_handle = file.into_raw_handle();
+    let owned_handle = unsafe { OwnedHandle::from_raw_handle(raw_handle) };
+    assert_eq!(owned_handle.as_raw_handle(), raw_handle);
+}
+
+#[test]
+fn test_niche_optimizations() {
+    assert_eq!(size_of::<Option<OwnedHandle>>(), size_of::<RawHandle>());
+    assert_eq!(size_of::<Option<BorrowedHandle<'static>>>(), size_of::<RawHandle>());
+}
+
+#[test]
+fn test_try_clone() {
+    let dir = tmpdir();
+    let mut file = File::create(dir.path().join("try_clone")).unwrap();
+    let owned_handle = OwnedHandle::from(file.try_clone().unwrap());
+    let clone = owned_handle.try_clone().unwrap();
+    assert_ne!(clone.as_raw_handle(), owned_handle.as_raw_handle());
+
+    // Both handles refer to the same file, and share its cursor.
+    File::from(clone).write_all(b"hello").unwrap();
+    assert_eq!(file.seek(SeekFrom::Current(0)).unwrap(), 5);
+    file.write_all(b" world").unwrap();
+    drop(file);
+    let mut contents = String::new();
+    File::open(dir.path().join("try_clone")).unwrap().read_to_string(&mut contents).unwrap();
+    assert_eq!(contents, "hello world");
+}
@@ -232,3 +232,11 @@ fn into_raw_socket(self) -> RawSocket {
         self.into_inner().into_socket().into_inner()
     }
 }
+
+#[unstable(feature = "async_io", issue = "none")]
+impl AsRawSocket for io::Source<'_> {
+    #[inline]
+    fn as_raw_socket(&self) -> RawSocket {
+        self.as_inner().as_raw_socket()
+    }
+}
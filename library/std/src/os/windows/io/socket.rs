@@ -0,0 +1,224 @@
+//! Owned and borrowed OS sockets.
+
+#![unstable(feature = "io_safety", issue = "none")]
+#![deny(unsafe_op_in_unsafe_fn)]
+
+use super::raw::{AsRawSocket, FromRawSocket, IntoRawSocket, RawSocket};
+use crate::fmt;
+use crate::io;
+use crate::marker::PhantomData;
+use crate::mem::forget;
+use crate::net;
+use crate::sys::c;
+use crate::sys_common::AsInner;
+
+#[cfg(test)]
+mod tests;
+
+/// A borrowed socket.
+///
+/// This has a lifetime parameter to tie it to the lifetime of something that
+/// owns the socket.
+///
+/// This uses `repr(transparent)` and has the representation of a host socket,
+/// so it can be used in FFI in places where a socket is passed as an argument,
+/// it is not captured or consumed, and it never has the value
+/// `INVALID_SOCKET`.
+#[derive(Copy, Clone)]
+#[repr(transparent)]
+#[rustc_layout_scalar_valid_range_start(0)]
+// This is -2, in two's complement. -1 is `INVALID_SOCKET`.
+#[cfg_attr(target_pointer_width = "32", rustc_layout_scalar_valid_range_end(0xFF_FF_FF_FE))]
+#[cfg_attr(
+    target_pointer_width = "64",
+    rustc_layout_scalar_valid_range_end(0xFF_FF_FF_FF_FF_FF_FF_FE)
+)]
+#[rustc_nonnull_optimization_guaranteed]
+#[unstable(feature = "io_safety", issue = "none")]
+pub struct BorrowedSocket<'socket> {
+    socket: RawSocket,
+    _phantom: PhantomData<&'socket OwnedSocket>,
+}
+
+/// An owned socket.
+///
+/// This closes the socket on drop.
+///
+/// This uses `repr(transparent)` and has the representation of a host socket,
+/// so it can be used in FFI in places where a socket is passed as a consumed
+/// argument or returned as an owned value, and it never has the value
+/// `INVALID_SOCKET`.
+#[repr(transparent)]
+#[rustc_layout_scalar_valid_range_start(0)]
+// This is -2, in two's complement. -1 is `INVALID_SOCKET`.
+#[cfg_attr(target_pointer_width = "32", rustc_layout_scalar_valid_range_end(0xFF_FF_FF_FE))]
+#[cfg_attr(
+    target_pointer_width = "64",
+    rustc_layout_scalar_valid_range_end(0xFF_FF_FF_FF_FF_FF_FF_FE)
+)]
+#[rustc_nonnull_optimization_guaranteed]
+#[unstable(feature = "io_safety", issue = "none")]
+pub struct OwnedSocket {
+    socket: RawSocket,
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl BorrowedSocket<'_> {
+    /// Returns a `BorrowedSocket` holding the given raw socket.
+    ///
+    /// # Safety
+    ///
+    /// The resource pointed to by `socket` must remain open for the duration
+    /// of the returned `BorrowedSocket`, and it must not have the value
+    /// `INVALID_SOCKET`.
+    #[inline]
+    #[unstable(feature = "io_safety", issue = "none")]
+    pub unsafe fn borrow_raw_socket(socket: RawSocket) -> Self {
+        assert!(socket != c::INVALID_SOCKET);
+        // SAFETY: we just asserted that the value is in the valid range and
+        // isn't `INVALID_SOCKET`.
+        unsafe { Self { socket, _phantom: PhantomData } }
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl AsRawSocket for BorrowedSocket<'_> {
+    #[inline]
+    fn as_raw_socket(&self) -> RawSocket {
+        self.socket
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl AsRawSocket for OwnedSocket {
+    #[inline]
+    fn as_raw_socket(&self) -> RawSocket {
+        self.socket
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl IntoRawSocket for OwnedSocket {
+    #[inline]
+    fn into_raw_socket(self) -> RawSocket {
+        let socket = self.socket;
+        forget(self);
+        socket
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl FromRawSocket for OwnedSocket {
+    /// Constructs a new instance of `Self` from the given raw socket.
+    ///
+    /// # Safety
+    ///
+    /// The resource pointed to by `socket` must be open and suitable for
+    /// assuming ownership. The resource must not require cleanup other than
+    /// `closesocket`.
+    #[inline]
+    unsafe fn from_raw_socket(socket: RawSocket) -> Self {
+        assert!(socket != c::INVALID_SOCKET);
+        // SAFETY: we just asserted that the value is in the valid range and
+        // isn't `INVALID_SOCKET`.
+        unsafe { Self { socket } }
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl Drop for OwnedSocket {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let _ = c::closesocket(self.socket);
+        }
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl fmt::Debug for BorrowedSocket<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BorrowedSocket").field("socket", &self.socket).finish()
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl fmt::Debug for OwnedSocket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedSocket").field("socket", &self.socket).finish()
+    }
+}
+
+/// A trait to borrow the socket from an underlying object.
+#[unstable(feature = "io_safety", issue = "none")]
+pub trait AsSocket {
+    /// Borrows the socket.
+    #[unstable(feature = "io_safety", issue = "none")]
+    fn as_socket(&self) -> BorrowedSocket<'_>;
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl AsSocket for BorrowedSocket<'_> {
+    #[inline]
+    fn as_socket(&self) -> BorrowedSocket<'_> {
+        *self
+    }
+}
+
+#[unstable(feature = "io_safety", issue = "none")]
+impl AsSocket for OwnedSocket {
+    #[inline]
+    fn as_socket(&self) -> BorrowedSocket<'_> {
+        // SAFETY: `OwnedSocket` and `BorrowedSocket` have the same validity
+        // invariants, and the `BorrowedSocket` is bounded by the lifetime
+        // of `&self`.
+        unsafe { BorrowedSocket::borrow_raw_socket(self.as_raw_socket()) }
+    }
+}
+
+// The socket types of `net` implement `AsSocket` and the conversions from and
+// to `OwnedSocket` on top of their raw socket traits, which already have the
+// same ownership semantics.
+macro_rules! impl_socket {
+    ($($t:ty),*) => {$(
+        #[unstable(feature = "io_safety", issue = "none")]
+        impl AsSocket for $t {
+            #[inline]
+            fn as_socket(&self) -> BorrowedSocket<'_> {
+                // SAFETY: the socket is owned by `self`, so it stays open as
+                // long as it's borrowed.
+                unsafe { BorrowedSocket::borrow_raw_socket(self.as_raw_socket()) }
+            }
+        }
+
+        #[unstable(feature = "io_safety", issue = "none")]
+        impl From<$t> for OwnedSocket {
+            #[inline]
+            fn from(owner: $t) -> OwnedSocket {
+                // SAFETY: `into_raw_socket` transfers the ownership of its
+                // socket, which is open.
+                unsafe { OwnedSocket::from_raw_socket(owner.into_raw_socket()) }
+            }
+        }
+
+        #[unstable(feature = "io_safety", issue = "none")]
+        impl From<OwnedSocket> for $t {
+            #[inline]
+            fn from(owned: OwnedSocket) -> Self {
+                // SAFETY: `OwnedSocket` transfers the ownership of its open
+                // socket.
+                unsafe { Self::from_raw_socket(owned.into_raw_socket()) }
+            }
+        }
+    )*};
+}
+
+impl_socket!(net::TcpStream, net::TcpListener, net::UdpSocket);
+
+#[unstable(feature = "async_io", issue = "none")]
+impl AsSocket for io::Source<'_> {
+    #[inline]
+    fn as_socket(&self) -> BorrowedSocket<'_> {
+        *self.as_inner()
+    }
+}
@@ -0,0 +1,33 @@
+use crate::mem::size_of;
+use crate::net::{TcpListener, TcpStream};
+use crate::os::windows::io::{
+    AsRawSocket, AsSocket, BorrowedSocket, FromRawSocket, IntoRawSocket, OwnedSocket, RawSocket,
+};
+
+#[test]
+fn test_socket() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let raw_socket: RawSocket = listener.as_raw_socket();
+
+    let owned_socket: OwnedSocket = listener.into();
+    assert_eq!(owned_socket.as_raw_socket(), raw_socket);
+    let socket: BorrowedSocket<'_> = owned_socket.as_socket();
+    assert_eq!(socket.as_raw_socket(), raw_socket);
+
+    // The socket is still usable after the round trip.
+    let listener = TcpListener::from(owned_socket);
+    assert_eq!(listener.as_socket().as_raw_socket(), raw_socket);
+    let _stream = TcpStream::connect(addr).unwrap();
+    listener.accept().unwrap();
+
+    let raw_socket = listener.into_raw_socket();
+    let owned_socket = unsafe { OwnedSocket::from_raw_socket(raw_socket) };
+    assert_eq!(TcpListener::from(owned_socket).local_addr().unwrap(), addr);
+}
+
+#[test]
+fn test_niche_optimizations() {
+    assert_eq!(size_of::<Option<OwnedSocket>>(), size_of::<RawSocket>());
+    assert_eq!(size_of::<Option<BorrowedSocket<'static>>>(), size_of::<RawSocket>());
+}
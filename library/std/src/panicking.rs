@@ -18,7 +18,7 @@
 use crate::process;
 use crate::sync::atomic::{AtomicBool, Ordering};
 use crate::sys::stdio::panic_output;
-use crate::sys_common::backtrace::{self, RustBacktrace};
+use crate::sys_common::backtrace::{self, BacktraceStyle, RustBacktrace};
 use crate::sys_common::rwlock::StaticRWLock;
 use crate::sys_common::thread_info;
 use crate::thread;
@@ -177,11 +177,75 @@ pub fn take_hook() -> Box<dyn Fn(&PanicInfo<'_>) + 'static + Sync + Send> {
     }
 }
 
+/// Atomically installs a panic hook that wraps the current one.
+///
+/// *See also the functions [`set_hook`] and [`take_hook`].*
+///
+/// [`set_hook`]: ./fn.set_hook.html
+/// [`take_hook`]: ./fn.take_hook.html
+///
+/// `hook_fn` is called with the hook that was registered before, which is the
+/// default hook if no custom hook was registered, and the `PanicInfo` of the
+/// panic. It decides whether and when to run the previous hook. Unlike taking
+/// the previous hook with `take_hook` and then calling `set_hook`, no panic can
+/// observe the default hook in between.
+///
+/// # Panics
+///
+/// Panics if called from a panicking thread.
+///
+/// # Examples
+///
+/// Emitting panics as structured log records before printing them as usual:
+///
+/// ```should_panic
+/// #![feature(panic_update_hook, panic_info_message)]
+/// use std::panic;
+/// use std::thread;
+///
+/// panic::update_hook(|prev, info| {
+///     let thread = thread::current();
+///     eprintln!(
+///         "panic thread={:?} thread_id={:?} location={} message={:?}",
+///         thread.name().unwrap_or("<unnamed>"),
+///         thread.id(),
+///         info.location().unwrap(),
+///         info.message().map(|message| message.to_string()),
+///     );
+///     prev(info);
+/// });
+///
+/// panic!("Normal panic");
+/// ```
+#[unstable(feature = "panic_update_hook", issue = "none")]
+pub fn update_hook<F>(hook_fn: F)
+where
+    F: Fn(&(dyn Fn(&PanicInfo<'_>) + Send + Sync + 'static), &PanicInfo<'_>)
+        + Sync
+        + Send
+        + 'static,
+{
+    if thread::panicking() {
+        panic!("cannot modify the panic hook from a panicking thread");
+    }
+
+    unsafe {
+        let guard = HOOK_LOCK.write();
+        let prev: Box<dyn Fn(&PanicInfo<'_>) + 'static + Sync + Send> = match HOOK {
+            Hook::Default => Box::new(default_hook),
+            Hook::Custom(ptr) => Box::from_raw(ptr),
+        };
+        let hook = move |info: &PanicInfo<'_>| hook_fn(&*prev, info);
+        HOOK = Hook::Custom(Box::into_raw(Box::new(hook)));
+        drop(guard);
+    }
+}
+
 fn default_hook(info: &PanicInfo<'_>) {
     // If this is a double panic, make sure that we print a backtrace
     // for this panic. Otherwise only print it if logging is enabled.
     let backtrace_env = if panic_count::get_count() >= 2 {
-        RustBacktrace::Print(crate::backtrace_rs::PrintFmt::Full)
+        RustBacktrace::Print(BacktraceStyle::Full)
     } else {
         backtrace::rust_backtrace_env()
     };
@@ -453,7 +517,7 @@ pub fn begin_panic_fmt(msg: &fmt::Arguments<'_>) -> ! {
         intrinsics::abort()
     }
 
-    let info = PanicInfo::internal_constructor(Some(msg), Location::caller());
+    let info = PanicInfo::internal_constructor(Some(msg), Location::caller(), true);
     begin_panic_handler(&info)
 }
 
@@ -539,7 +603,13 @@ pub fn begin_panic<M: Any + Send>(msg: M) -> ! {
 
     let loc = Location::caller();
     return crate::sys_common::backtrace::__rust_end_short_backtrace(move || {
-        rust_panic_with_hook(&mut PanicPayload::new(msg), None, loc)
+        // Make string payloads available to the panic hook as the message too.
+        match (&msg as &dyn Any).downcast_ref::<&'static str>().copied() {
+            Some(s) => {
+                rust_panic_with_hook(&mut PanicPayload::new(msg), Some(&format_args!("{}", s)), loc)
+            }
+            None => rust_panic_with_hook(&mut PanicPayload::new(msg), None, loc),
+        }
     });
 
     struct PanicPayload<A> {
@@ -600,14 +670,15 @@ fn rust_panic_with_hook(
         } else {
             // Unfortunately, this does not print a backtrace, because creating
             // a `Backtrace` will allocate, which we must to avoid here.
-            let panicinfo = PanicInfo::internal_constructor(message, location);
+            let panicinfo = PanicInfo::internal_constructor(message, location, false);
             rtprintpanic!("{}\npanicked after panic::always_abort(), aborting.\n", panicinfo);
         }
         intrinsics::abort()
     }
 
     unsafe {
-        let mut info = PanicInfo::internal_constructor(message, location);
+        // Panicking while panicking aborts right after running the hook.
+        let mut info = PanicInfo::internal_constructor(message, location, panics < 2);
         let _guard = HOOK_LOCK.read();
         match HOOK {
             // Some platforms (like wasm) know that printing to stderr won't ever actually
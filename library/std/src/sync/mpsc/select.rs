@@ -0,0 +1,323 @@
+//! Selection over a set of receivers.
+//!
+//! Selection follows the same blocking protocol as a single `recv`, just
+//! spread over every receiver at once: a signal token for the current thread
+//! is installed in each channel, the thread goes to sleep, and on wakeup the
+//! token is taken back out of each channel again. Taking the token back out
+//! ("aborting" the selection) is also what tells us which channels are ready.
+
+#![unstable(feature = "mpsc_select", issue = "none")]
+
+#[cfg(all(test, not(target_os = "emscripten")))]
+mod tests;
+
+use crate::fmt;
+use crate::ptr;
+use crate::time::{Duration, Instant};
+
+use super::blocking::{self, SignalToken};
+use super::Receiver;
+
+/// The result of installing a signal token in a channel.
+#[derive(PartialEq, Eq)]
+pub(super) enum StartResult {
+    /// The token was installed, the channel will use it to wake us up.
+    Installed,
+    /// The channel can already be received from, the token was dropped.
+    Abort,
+}
+
+/// The operations a receiver needs to support to take part in a selection.
+pub(super) trait Packet {
+    /// Returns whether a `try_recv` on this receiver would not return
+    /// `Empty`.
+    fn can_recv(&self) -> bool;
+
+    /// Installs `token` to be signaled when this receiver becomes ready.
+    fn start_selection(&self, token: SignalToken) -> StartResult;
+
+    /// Removes a previously installed token, returning whether this receiver
+    /// is ready.
+    fn abort_selection(&self) -> bool;
+}
+
+/// Waits on several [`Receiver`]s at once.
+///
+/// Receivers are added to a `Select` with [`recv`], which returns the index
+/// that identifies the receiver. Each of the selection methods then waits
+/// until at least one of the receivers is *ready* and returns its index. A
+/// receiver is ready if receiving from it will not block, that is, if it has
+/// a message waiting or if all of its senders have disconnected. Selecting
+/// does not receive the message itself: call [`Receiver::try_recv`] or
+/// [`Receiver::recv`] on the chosen receiver to get it.
+///
+/// When several receivers are ready at the same time, the one after the
+/// previously selected receiver is preferred, so a busy channel cannot starve
+/// the others.
+///
+/// [`recv`]: Select::recv
+///
+/// # Examples
+///
+/// ```
+/// #![feature(mpsc_select)]
+///
+/// use std::sync::mpsc::{channel, Select};
+/// use std::thread;
+///
+/// let (tx1, rx1) = channel::<i32>();
+/// let (tx2, rx2) = channel::<&str>();
+///
+/// thread::spawn(move || tx2.send("hello").unwrap());
+///
+/// let mut sel = Select::new();
+/// let numbers = sel.recv(&rx1);
+/// let words = sel.recv(&rx2);
+///
+/// match sel.select() {
+///     i if i == numbers => println!("number: {:?}", rx1.try_recv()),
+///     i if i == words => assert_eq!(rx2.try_recv(), Ok("hello")),
+///     _ => unreachable!(),
+/// }
+/// # drop(tx1);
+/// ```
+pub struct Select<'a> {
+    handles: Vec<&'a dyn Packet>,
+    // Index at which the search for a ready receiver starts.
+    next: usize,
+}
+
+impl<'a> Select<'a> {
+    /// Creates an empty `Select`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpsc_select)]
+    ///
+    /// use std::sync::mpsc::Select;
+    ///
+    /// let mut sel = Select::new();
+    /// assert_eq!(sel.try_select(), None);
+    /// ```
+    pub fn new() -> Select<'a> {
+        Select { handles: Vec::new(), next: 0 }
+    }
+
+    /// Adds a receiver to the set, returning the index identifying it.
+    ///
+    /// Indices are handed out in order, starting at zero. Adding a receiver
+    /// that is already in the set returns its existing index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpsc_select)]
+    ///
+    /// use std::sync::mpsc::{channel, Select};
+    ///
+    /// let (_tx1, rx1) = channel::<i32>();
+    /// let (_tx2, rx2) = channel::<i32>();
+    ///
+    /// let mut sel = Select::new();
+    /// assert_eq!(sel.recv(&rx1), 0);
+    /// assert_eq!(sel.recv(&rx2), 1);
+    /// assert_eq!(sel.recv(&rx1), 0);
+    /// ```
+    pub fn recv<T>(&mut self, rx: &'a Receiver<T>) -> usize {
+        // Installing two tokens in the same channel would break its blocking
+        // protocol, so duplicates are folded into one entry.
+        let addr = rx as *const Receiver<T> as *const u8;
+        let existing = self.handles.iter().position(|h| ptr::eq(*h as *const _ as *const u8, addr));
+        match existing {
+            Some(index) => index,
+            None => {
+                self.handles.push(rx);
+                self.handles.len() - 1
+            }
+        }
+    }
+
+    /// Returns the number of receivers in the set.
+    pub fn len(&self) -> usize {
+        self.handles.len()
+    }
+
+    /// Returns `true` if no receivers have been added.
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
+
+    /// Returns the index of a ready receiver without blocking.
+    ///
+    /// Returns `None` if none of the receivers is ready.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpsc_select)]
+    ///
+    /// use std::sync::mpsc::{channel, Select};
+    ///
+    /// let (tx1, rx1) = channel();
+    /// let (_tx2, rx2) = channel::<i32>();
+    ///
+    /// let mut sel = Select::new();
+    /// let first = sel.recv(&rx1);
+    /// sel.recv(&rx2);
+    ///
+    /// assert_eq!(sel.try_select(), None);
+    /// tx1.send(1).unwrap();
+    /// assert_eq!(sel.try_select(), Some(first));
+    /// ```
+    pub fn try_select(&mut self) -> Option<usize> {
+        let handles = &self.handles;
+        let index = rotation(self.next, handles.len()).find(|&i| handles[i].can_recv())?;
+        Some(self.selected(index))
+    }
+
+    /// Blocks until one of the receivers is ready and returns its index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no receivers have been added, as this would block forever.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpsc_select)]
+    ///
+    /// use std::sync::mpsc::{channel, Select};
+    /// use std::thread;
+    ///
+    /// let (_tx1, rx1) = channel::<i32>();
+    /// let (tx2, rx2) = channel();
+    ///
+    /// let mut sel = Select::new();
+    /// sel.recv(&rx1);
+    /// let second = sel.recv(&rx2);
+    ///
+    /// thread::spawn(move || tx2.send(2).unwrap());
+    ///
+    /// assert_eq!(sel.select(), second);
+    /// assert_eq!(rx2.recv(), Ok(2));
+    /// ```
+    pub fn select(&mut self) -> usize {
+        assert!(!self.handles.is_empty(), "no receivers to select on");
+        self.wait(None).expect("woke up without a ready receiver")
+    }
+
+    /// Blocks until one of the receivers is ready or `timeout` has elapsed.
+    ///
+    /// Returns the index of the ready receiver, or `None` on timeout.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpsc_select)]
+    ///
+    /// use std::sync::mpsc::{channel, Select};
+    /// use std::time::Duration;
+    ///
+    /// let (_tx, rx) = channel::<i32>();
+    ///
+    /// let mut sel = Select::new();
+    /// sel.recv(&rx);
+    ///
+    /// assert_eq!(sel.select_timeout(Duration::from_millis(100)), None);
+    /// ```
+    pub fn select_timeout(&mut self, timeout: Duration) -> Option<usize> {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.select_deadline(deadline),
+            // So far in the future that it's practically the same as waiting indefinitely.
+            None => self.wait(None),
+        }
+    }
+
+    /// Blocks until one of the receivers is ready or `deadline` is reached.
+    ///
+    /// Returns the index of the ready receiver, or `None` on timeout.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpsc_select)]
+    ///
+    /// use std::sync::mpsc::{channel, Select};
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let (tx, rx) = channel();
+    /// tx.send(1).unwrap();
+    ///
+    /// let mut sel = Select::new();
+    /// let index = sel.recv(&rx);
+    ///
+    /// let deadline = Instant::now() + Duration::from_millis(100);
+    /// assert_eq!(sel.select_deadline(deadline), Some(index));
+    /// ```
+    pub fn select_deadline(&mut self, deadline: Instant) -> Option<usize> {
+        self.wait(Some(deadline))
+    }
+
+    fn wait(&mut self, deadline: Option<Instant>) -> Option<usize> {
+        // Stage 1: preflight checks. Look for any ready receiver before going
+        // through the expensive blocking protocol.
+        if let Some(index) = self.try_select() {
+            return Some(index);
+        }
+
+        // Stage 2: begin the blocking process. Install our token in every
+        // receiver. If one of them turns out to have become ready in the
+        // meantime, take the token back out of the ones we've already
+        // installed it in and return that one.
+        let (wait_token, signal_token) = blocking::tokens();
+        let aborted = self
+            .handles
+            .iter()
+            .position(|handle| handle.start_selection(signal_token.clone()) == StartResult::Abort);
+        drop(signal_token);
+        if let Some(index) = aborted {
+            for handle in &self.handles[..index] {
+                handle.abort_selection();
+            }
+            return Some(self.selected(index));
+        }
+
+        // Stage 3: no receiver was ready, so go to sleep until one of them
+        // signals us.
+        match deadline {
+            Some(deadline) => {
+                wait_token.wait_max_until(deadline);
+            }
+            None => wait_token.wait(),
+        }
+
+        // Stage 4: there *must* be a ready receiver if we were woken up, unless
+        // we timed out. Take our token back out of every receiver, picking the
+        // first ready one in search order.
+        let mut ready = None;
+        for i in rotation(self.next, self.handles.len()) {
+            if self.handles[i].abort_selection() && ready.is_none() {
+                ready = Some(i);
+            }
+        }
+        ready.map(|i| self.selected(i))
+    }
+
+    // Records `index` as selected, so the next search starts after it.
+    fn selected(&mut self, index: usize) -> usize {
+        self.next = (index + 1) % self.handles.len();
+        index
+    }
+}
+
+// Iterates over the indices `0..len`, starting at `start` and wrapping around.
+fn rotation(start: usize, len: usize) -> impl Iterator<Item = usize> {
+    (start..len).chain(0..start)
+}
+
+impl fmt::Debug for Select<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Select").field("len", &self.handles.len()).finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,198 @@
+use super::Select;
+use crate::sync::mpsc::{channel, sync_channel, TryRecvError};
+use crate::thread;
+use crate::time::{Duration, Instant};
+
+#[test]
+fn smoke() {
+    let (tx1, rx1) = channel::<i32>();
+    let (tx2, rx2) = channel::<i32>();
+    tx1.send(1).unwrap();
+
+    let mut sel = Select::new();
+    let a = sel.recv(&rx1);
+    let b = sel.recv(&rx2);
+    assert_eq!(sel.select(), a);
+    assert_eq!(rx1.try_recv(), Ok(1));
+
+    tx2.send(2).unwrap();
+    assert_eq!(sel.select(), b);
+    assert_eq!(rx2.try_recv(), Ok(2));
+}
+
+#[test]
+fn empty() {
+    let mut sel = Select::new();
+    assert!(sel.is_empty());
+    assert_eq!(sel.try_select(), None);
+    assert_eq!(sel.select_timeout(Duration::from_millis(1)), None);
+}
+
+#[test]
+#[should_panic]
+fn empty_select_panics() {
+    Select::new().select();
+}
+
+#[test]
+fn duplicate() {
+    let (tx, rx) = channel::<i32>();
+    let (_tx2, rx2) = channel::<i32>();
+
+    let mut sel = Select::new();
+    assert_eq!(sel.recv(&rx), 0);
+    assert_eq!(sel.recv(&rx2), 1);
+    assert_eq!(sel.recv(&rx), 0);
+    assert_eq!(sel.len(), 2);
+
+    let _t = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(10));
+        tx.send(1).unwrap();
+    });
+    assert_eq!(sel.select(), 0);
+    assert_eq!(rx.recv(), Ok(1));
+}
+
+#[test]
+fn disconnected_is_ready() {
+    let (tx1, rx1) = channel::<i32>();
+    let (_tx2, rx2) = channel::<i32>();
+    drop(tx1);
+
+    let mut sel = Select::new();
+    let a = sel.recv(&rx1);
+    sel.recv(&rx2);
+    assert_eq!(sel.select(), a);
+    assert_eq!(rx1.try_recv(), Err(TryRecvError::Disconnected));
+}
+
+#[test]
+fn blocking_all_flavors() {
+    for flavor in 0..4 {
+        let (_tx1, rx1) = channel::<i32>();
+        let (tx2, rx2) = channel::<i32>();
+        let (stx, srx) = sync_channel::<i32>(0);
+
+        match flavor {
+            // stream
+            1 => {
+                tx2.send(0).unwrap();
+                assert_eq!(rx2.recv(), Ok(0));
+            }
+            // shared
+            2 => drop(tx2.clone()),
+            _ => {}
+        }
+
+        let mut sel = Select::new();
+        sel.recv(&rx1);
+        let b = sel.recv(&rx2);
+        let c = sel.recv(&srx);
+
+        let expected = if flavor == 3 { c } else { b };
+        // Hand the senders back so that neither channel disconnects early.
+        let t = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(10));
+            if flavor == 3 {
+                stx.send(3).unwrap();
+            } else {
+                tx2.send(flavor).unwrap();
+            }
+            (tx2, stx)
+        });
+        assert_eq!(sel.select(), expected);
+        if flavor == 3 {
+            assert_eq!(srx.try_recv(), Ok(3));
+        } else {
+            assert_eq!(rx2.try_recv(), Ok(flavor));
+        }
+        t.join().unwrap();
+    }
+}
+
+#[test]
+fn upgrade_while_blocked() {
+    // The first send on a fresh channel uses the oneshot flavor, the second
+    // one upgrades the receiver while the selecting thread is asleep.
+    let (tx, rx) = channel::<i32>();
+    let (_tx2, rx2) = channel::<i32>();
+
+    let mut sel = Select::new();
+    let a = sel.recv(&rx);
+    sel.recv(&rx2);
+
+    let _t = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(10));
+        let tx3 = tx.clone();
+        tx.send(1).unwrap();
+        tx3.send(2).unwrap();
+    });
+    assert_eq!(sel.select(), a);
+    assert_eq!(rx.recv(), Ok(1));
+    assert_eq!(rx.recv(), Ok(2));
+}
+
+#[test]
+fn timeout() {
+    let (_tx, rx) = channel::<i32>();
+    let mut sel = Select::new();
+    sel.recv(&rx);
+
+    let start = Instant::now();
+    assert_eq!(sel.select_timeout(Duration::from_millis(10)), None);
+    assert!(start.elapsed() >= Duration::from_millis(10));
+
+    // The receiver must still work normally after an aborted selection.
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+}
+
+#[test]
+fn fairness() {
+    let (tx1, rx1) = channel::<i32>();
+    let (tx2, rx2) = channel::<i32>();
+    for i in 0..2 {
+        tx1.send(i).unwrap();
+        tx2.send(i).unwrap();
+    }
+
+    let mut sel = Select::new();
+    let a = sel.recv(&rx1);
+    let b = sel.recv(&rx2);
+    assert_eq!(sel.try_select(), Some(a));
+    // Not receiving from `rx1` leaves it ready, but `rx2` gets its turn first.
+    assert_eq!(sel.try_select(), Some(b));
+    assert_eq!(sel.try_select(), Some(a));
+}
+
+#[test]
+fn stress() {
+    const AMT: i32 = 1000;
+    let (tx1, rx1) = channel::<i32>();
+    let (tx2, rx2) = sync_channel::<i32>(1);
+
+    let t = thread::spawn(move || {
+        for i in 0..AMT {
+            if i % 2 == 0 {
+                tx1.send(i).unwrap();
+            } else {
+                tx2.send(i).unwrap();
+            }
+        }
+    });
+
+    let mut sel = Select::new();
+    let a = sel.recv(&rx1);
+    let b = sel.recv(&rx2);
+    let mut received = 0;
+    while received < AMT {
+        let i = sel.select();
+        let msg = if i == a { rx1.try_recv() } else { rx2.try_recv() };
+        assert!(i == a || i == b);
+        match msg {
+            Ok(_) => received += 1,
+            Err(TryRecvError::Disconnected) => {}
+            Err(TryRecvError::Empty) => panic!("selected receiver {} was not ready", i),
+        }
+    }
+    t.join().unwrap();
+}
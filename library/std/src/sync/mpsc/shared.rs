@@ -8,7 +8,6 @@
 /// module. You'll also note that the implementation of the shared and stream
 /// channels are quite similar, and this is no coincidence!
 pub use self::Failure::*;
-use self::StartResult::*;
 
 use core::cmp;
 use core::intrinsics::abort;
@@ -18,6 +17,7 @@
 use crate::sync::atomic::{AtomicBool, AtomicIsize, AtomicUsize, Ordering};
 use crate::sync::mpsc::blocking::{self, SignalToken};
 use crate::sync::mpsc::mpsc_queue as mpsc;
+use crate::sync::mpsc::select::StartResult::{self, *};
 use crate::sync::{Mutex, MutexGuard};
 use crate::thread;
 use crate::time::Instant;
@@ -54,12 +54,6 @@ pub enum Failure {
     Disconnected,
 }
 
-#[derive(PartialEq, Eq)]
-enum StartResult {
-    Installed,
-    Abort,
-}
-
 impl<T> Packet<T> {
     // Creation of a packet *must* be followed by a call to postinit_lock
     // and later by inherit_blocker
@@ -420,6 +414,17 @@ fn take_to_wake(&self) -> SignalToken {
     // select implementation
     ////////////////////////////////////////////////////////////////////////////
 
+    // Helper function for select, tests whether this port can receive without
+    // blocking (obviously not an atomic decision).
+    //
+    // This is different than the stream version because there's no need to peek
+    // at the queue, we can just look at the local count.
+    pub fn can_recv(&self) -> bool {
+        let cnt = self.cnt.load(Ordering::SeqCst);
+        let steals = unsafe { *self.steals.get() };
+        cnt == DISCONNECTED || cnt - steals > 0
+    }
+
     // increment the count on the channel (used for selection)
     fn bump(&self, amt: isize) -> isize {
         match self.cnt.fetch_add(amt, Ordering::SeqCst) {
@@ -431,6 +436,22 @@ fn bump(&self, amt: isize) -> isize {
         }
     }
 
+    // Inserts the signal token for selection on this port, returning true if
+    // blocking should proceed.
+    //
+    // The code here is the same as in stream.rs, except that it doesn't need to
+    // peek at the channel to see if an upgrade is pending.
+    pub fn start_selection(&self, token: SignalToken) -> StartResult {
+        match self.decrement(token) {
+            Installed => Installed,
+            Abort => {
+                let prev = self.bump(1);
+                assert!(prev == DISCONNECTED || prev >= 0);
+                Abort
+            }
+        }
+    }
+
     // Cancels a previous thread waiting on this port, returning whether there's
     // data on the port.
     //
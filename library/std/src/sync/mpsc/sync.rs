@@ -31,6 +31,7 @@
 
 use crate::sync::atomic::{AtomicUsize, Ordering};
 use crate::sync::mpsc::blocking::{self, SignalToken, WaitToken};
+use crate::sync::mpsc::select::StartResult::{self, *};
 use crate::sync::{Mutex, MutexGuard};
 use crate::time::Instant;
 
@@ -416,6 +417,42 @@ pub fn drop_port(&self) {
             token.signal();
         }
     }
+
+    ////////////////////////////////////////////////////////////////////////////
+    // select implementation
+    ////////////////////////////////////////////////////////////////////////////
+
+    // Tests whether this port can receive without blocking, either because
+    // there's buffered data or because the channel is disconnected.
+    pub fn can_recv(&self) -> bool {
+        let guard = self.lock.lock().unwrap();
+        guard.disconnected || guard.buf.size() > 0
+    }
+
+    // Attempts to start selection on this port. This can either succeed or fail
+    // because there is data waiting.
+    pub fn start_selection(&self, token: SignalToken) -> StartResult {
+        let mut guard = self.lock.lock().unwrap();
+        if guard.disconnected || guard.buf.size() > 0 {
+            Abort
+        } else {
+            match mem::replace(&mut guard.blocker, BlockedReceiver(token)) {
+                NoneBlocked => {}
+                BlockedSender(..) => unreachable!(),
+                BlockedReceiver(..) => unreachable!(),
+            }
+            Installed
+        }
+    }
+
+    // Remove a previous selecting thread from this port. This ensures that the
+    // blocked thread will no longer be visible to any other threads.
+    //
+    // The return value indicates whether there's data on this port.
+    pub fn abort_selection(&self) -> bool {
+        let mut guard = self.lock.lock().unwrap();
+        abort_selection(&mut guard)
+    }
 }
 
 impl<T> Drop for Packet<T> {
@@ -15,6 +15,25 @@ fn smoke() {
     drop(m.lock().unwrap());
 }
 
+#[test]
+fn const_static() {
+    static M: Mutex<u32> = Mutex::new(0);
+    static C: Condvar = Condvar::new();
+
+    let t = thread::spawn(|| {
+        *M.lock().unwrap() += 1;
+        C.notify_one();
+    });
+
+    let mut g = M.lock().unwrap();
+    while *g == 0 {
+        g = C.wait(g).unwrap();
+    }
+    assert_eq!(*g, 1);
+    drop(g);
+    t.join().unwrap();
+}
+
 #[test]
 fn lots_and_lots() {
     const J: u32 = 1000;
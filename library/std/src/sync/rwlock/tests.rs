@@ -16,6 +16,33 @@ fn smoke() {
     drop(l.write().unwrap());
 }
 
+#[test]
+fn const_static() {
+    static L: RwLock<u32> = RwLock::new(0);
+    *L.write().unwrap() += 1;
+    assert_eq!(*L.read().unwrap(), 1);
+}
+
+#[test]
+#[cfg_attr(not(any(target_os = "linux", target_os = "android")), ignore)]
+fn writer_preferred() {
+    let l = Arc::new(RwLock::new(()));
+    let r = l.read().unwrap();
+
+    let l2 = l.clone();
+    let t = thread::spawn(move || drop(l2.write().unwrap()));
+
+    // Once a writer is waiting, new readers have to wait behind it.
+    while l.try_read().is_ok() {
+        thread::yield_now();
+    }
+    assert!(matches!(l.try_read(), Err(TryLockError::WouldBlock)));
+
+    drop(r);
+    t.join().unwrap();
+    drop(l.read().unwrap());
+}
+
 #[test]
 fn frob() {
     const N: u32 = 10;
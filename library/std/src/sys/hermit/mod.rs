@@ -38,6 +38,8 @@
 pub mod pipe;
 #[path = "../unsupported/process.rs"]
 pub mod process;
+#[path = "../unsupported/reactor.rs"]
+pub mod reactor;
 pub mod rwlock;
 pub mod stdio;
 pub mod thread;
@@ -6,6 +6,7 @@
 use crate::ptr;
 use crate::sync::atomic::{AtomicUsize, Ordering};
 use crate::sys::hermit::abi;
+use crate::sys_common::lazy_box::{LazyBox, LazyInit};
 
 /// This type provides a lock based on busy waiting to realize mutual exclusion
 ///
@@ -156,7 +157,13 @@ pub struct Mutex {
     inner: Spinlock<MutexInner>,
 }
 
-pub type MovableMutex = Box<Mutex>;
+pub(crate) type MovableMutex = LazyBox<Mutex>;
+
+impl LazyInit for Mutex {
+    fn init() -> Box<Self> {
+        Box::new(Self::new())
+    }
+}
 
 unsafe impl Send for Mutex {}
 unsafe impl Sync for Mutex {}
@@ -166,11 +173,6 @@ pub const fn new() -> Mutex {
         Mutex { inner: Spinlock::new(MutexInner::new()) }
     }
 
-    #[inline]
-    pub unsafe fn init(&mut self) {
-        self.inner = Spinlock::new(MutexInner::new());
-    }
-
     #[inline]
     pub unsafe fn lock(&self) {
         loop {
@@ -1,6 +1,6 @@
 use crate::convert::TryFrom;
 use crate::fmt;
-use crate::io::{self, ErrorKind, IoSlice, IoSliceMut};
+use crate::io::{self, ErrorKind, IoSlice, IoSliceMut, ReadBuf};
 use crate::net::{IpAddr, Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr};
 use crate::str;
 use crate::sync::Arc;
@@ -109,6 +109,10 @@ pub fn read(&self, buffer: &mut [u8]) -> io::Result<usize> {
         self.read_vectored(&mut [IoSliceMut::new(buffer)])
     }
 
+    pub fn read_buf(&self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        crate::io::default_read_buf(|buf| self.read(buf), buf)
+    }
+
     pub fn read_vectored(&self, ioslice: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         let mut size: usize = 0;
 
@@ -188,6 +192,38 @@ pub fn nodelay(&self) -> io::Result<bool> {
             .map_err(|_| io::Error::new_const(ErrorKind::Other, &"nodelay failed"))
     }
 
+    pub fn set_linger(&self, _: Option<Duration>) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn linger(&self) -> io::Result<Option<Duration>> {
+        unsupported()
+    }
+
+    pub fn set_keepalive(&self, _: bool) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn keepalive(&self) -> io::Result<bool> {
+        unsupported()
+    }
+
+    pub fn set_recv_buffer_size(&self, _: usize) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        unsupported()
+    }
+
+    pub fn set_send_buffer_size(&self, _: usize) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        unsupported()
+    }
+
     pub fn set_ttl(&self, tll: u32) -> io::Result<()> {
         abi::tcpstream::set_tll(*self.0.as_inner(), tll)
             .map_err(|_| io::Error::new_const(ErrorKind::Other, &"unable to set TTL"))
@@ -277,6 +313,11 @@ fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+#[path = "../unsupported/tcp_socket.rs"]
+mod tcp_socket;
+
+pub use self::tcp_socket::TcpSocket;
+
 pub struct UdpSocket(abi::Handle);
 
 impl UdpSocket {
@@ -372,6 +413,22 @@ pub fn leave_multicast_v6(&self, _: &Ipv6Addr, _: u32) -> io::Result<()> {
         unsupported()
     }
 
+    pub fn set_recv_buffer_size(&self, _: usize) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        unsupported()
+    }
+
+    pub fn set_send_buffer_size(&self, _: usize) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        unsupported()
+    }
+
     pub fn set_ttl(&self, _: u32) -> io::Result<()> {
         unsupported()
     }
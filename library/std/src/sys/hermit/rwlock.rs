@@ -1,6 +1,7 @@
 use crate::cell::UnsafeCell;
 use crate::sys::condvar::Condvar;
 use crate::sys::mutex::Mutex;
+use crate::sys_common::lazy_box::{LazyBox, LazyInit};
 
 pub struct RWLock {
     lock: Mutex,
@@ -8,7 +9,17 @@ pub struct RWLock {
     state: UnsafeCell<State>,
 }
 
-pub type MovableRWLock = Box<RWLock>;
+pub(crate) type MovableRWLock = LazyBox<RWLock>;
+
+impl LazyInit for RWLock {
+    fn init() -> Box<Self> {
+        Box::new(Self::new())
+    }
+
+    fn destroy(rwlock: Box<Self>) {
+        unsafe { rwlock.destroy() };
+    }
+}
 
 enum State {
     Unlocked,
@@ -3,8 +3,10 @@
 use crate::ffi::CStr;
 use crate::io;
 use crate::mem;
+use crate::num::NonZeroUsize;
 use crate::sys::hermit::abi;
 use crate::sys::hermit::thread_local_dtor::run_dtors;
+use crate::sys::unsupported;
 use crate::time::Duration;
 
 pub type Tid = abi::Tid;
@@ -95,6 +97,10 @@ pub fn into_id(self) -> Tid {
     }
 }
 
+pub fn available_parallelism() -> io::Result<NonZeroUsize> {
+    unsupported()
+}
+
 pub mod guard {
     pub type Guard = !;
     pub unsafe fn current() -> Option<Guard> {
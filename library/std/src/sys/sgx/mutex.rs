@@ -16,9 +16,6 @@ pub const fn new() -> Mutex {
         Mutex { inner: SpinMutex::new(WaitVariable::new(false)) }
     }
 
-    #[inline]
-    pub unsafe fn init(&mut self) {}
-
     #[inline]
     pub unsafe fn lock(&self) {
         let mut guard = self.inner.lock();
@@ -55,9 +52,6 @@ pub unsafe fn try_lock(&self) -> bool {
             true
         }
     }
-
-    #[inline]
-    pub unsafe fn destroy(&self) {}
 }
 
 struct ReentrantLock {
@@ -1,7 +1,7 @@
 use crate::convert::TryFrom;
 use crate::error;
 use crate::fmt;
-use crate::io::{self, IoSlice, IoSliceMut};
+use crate::io::{self, IoSlice, IoSliceMut, ReadBuf};
 use crate::net::{Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr, ToSocketAddrs};
 use crate::sync::Arc;
 use crate::sys::fd::FileDesc;
@@ -145,6 +145,10 @@ pub fn read(&self, buf: &mut [u8]) -> io::Result<usize> {
         self.inner.inner.read(buf)
     }
 
+    pub fn read_buf(&self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        crate::io::default_read_buf(|buf| self.read(buf), buf)
+    }
+
     pub fn read_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         self.inner.inner.read_vectored(bufs)
     }
@@ -191,6 +195,38 @@ pub fn nodelay(&self) -> io::Result<bool> {
         sgx_ineffective(false)
     }
 
+    pub fn set_linger(&self, _: Option<Duration>) -> io::Result<()> {
+        sgx_ineffective(())
+    }
+
+    pub fn linger(&self) -> io::Result<Option<Duration>> {
+        sgx_ineffective(None)
+    }
+
+    pub fn set_keepalive(&self, _: bool) -> io::Result<()> {
+        sgx_ineffective(())
+    }
+
+    pub fn keepalive(&self) -> io::Result<bool> {
+        sgx_ineffective(false)
+    }
+
+    pub fn set_recv_buffer_size(&self, _: usize) -> io::Result<()> {
+        sgx_ineffective(())
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        unsupported()
+    }
+
+    pub fn set_send_buffer_size(&self, _: usize) -> io::Result<()> {
+        sgx_ineffective(())
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        unsupported()
+    }
+
     pub fn set_ttl(&self, _: u32) -> io::Result<()> {
         sgx_ineffective(())
     }
@@ -310,6 +346,11 @@ fn from_inner(inner: Socket) -> TcpListener {
     }
 }
 
+#[path = "../unsupported/tcp_socket.rs"]
+mod tcp_socket;
+
+pub use self::tcp_socket::TcpSocket;
+
 pub struct UdpSocket(!);
 
 impl UdpSocket {
@@ -405,6 +446,22 @@ pub fn leave_multicast_v6(&self, _: &Ipv6Addr, _: u32) -> io::Result<()> {
         self.0
     }
 
+    pub fn set_recv_buffer_size(&self, _: usize) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.0
+    }
+
+    pub fn set_send_buffer_size(&self, _: usize) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.0
+    }
+
     pub fn set_ttl(&self, _: u32) -> io::Result<()> {
         self.0
     }
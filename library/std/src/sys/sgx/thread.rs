@@ -1,9 +1,11 @@
 #![cfg_attr(test, allow(dead_code))] // why is this necessary?
 use crate::ffi::CStr;
 use crate::io;
+use crate::num::NonZeroUsize;
 use crate::time::Duration;
 
 use super::abi::usercalls;
+use super::unsupported;
 
 pub struct Thread(task_queue::JoinHandle);
 
@@ -135,6 +137,10 @@ pub fn join(self) {
     }
 }
 
+pub fn available_parallelism() -> io::Result<NonZeroUsize> {
+    unsupported()
+}
+
 pub mod guard {
     pub type Guard = !;
     pub unsafe fn current() -> Option<Guard> {
@@ -1,13 +1,16 @@
 #![unstable(reason = "not public", issue = "none", feature = "fd")]
 
 #[cfg(target_os = "espidf")]
-use crate::{collections::HashMap, lazy::SyncLazy, sync::Mutex};
+use crate::{
+    collections::HashMap,
+    sync::{LazyLock, Mutex},
+};
 
 #[cfg(test)]
 mod tests;
 
 use crate::cmp;
-use crate::io::{self, Initializer, IoSlice, IoSliceMut, Read};
+use crate::io::{self, IoSlice, IoSliceMut, Read, ReadBuf};
 use crate::mem;
 use crate::sys::cvt;
 use crate::sys_common::AsInner;
@@ -71,7 +74,7 @@ const fn max_iov() -> usize {
 
 // ESP-IDF does not (yet) support duplication of file descriptors, so we are emulating it here
 #[cfg(target_os = "espidf")]
-static MAP: SyncLazy<Mutex<HashMap<c_int, u32>>> = SyncLazy::new(|| Mutex::new(HashMap::new()));
+static MAP: LazyLock<Mutex<HashMap<c_int, u32>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
 
 impl FileDesc {
     pub fn new(fd: c_int) -> FileDesc {
@@ -99,6 +102,23 @@ pub fn read(&self, buf: &mut [u8]) -> io::Result<usize> {
         Ok(ret as usize)
     }
 
+    pub fn read_buf(&self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        let ret = cvt(unsafe {
+            libc::read(
+                self.fd,
+                buf.unfilled_mut().as_mut_ptr() as *mut c_void,
+                cmp::min(buf.remaining(), READ_LIMIT),
+            )
+        })?;
+
+        // Safety: `ret` bytes were written to the initialized portion of the buffer
+        unsafe {
+            buf.assume_init(ret as usize);
+        }
+        buf.add_filled(ret as usize);
+        Ok(())
+    }
+
     #[cfg(not(target_os = "espidf"))]
     pub fn read_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         let ret = cvt(unsafe {
@@ -155,6 +175,45 @@ unsafe fn cvt_pread64(
         }
     }
 
+    #[cfg(any(
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "fuchsia",
+        target_os = "illumos",
+        target_os = "linux",
+        target_os = "netbsd",
+        target_os = "openbsd",
+    ))]
+    pub fn read_vectored_at(&self, bufs: &mut [IoSliceMut<'_>], offset: u64) -> io::Result<usize> {
+        #[cfg(not(target_os = "linux"))]
+        use libc::preadv as preadv64;
+        #[cfg(target_os = "linux")]
+        use libc::preadv64;
+
+        let ret = cvt(unsafe {
+            preadv64(
+                self.fd,
+                bufs.as_ptr() as *const libc::iovec,
+                cmp::min(bufs.len(), max_iov()) as c_int,
+                offset as i64,
+            )
+        })?;
+        Ok(ret as usize)
+    }
+
+    #[cfg(not(any(
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "fuchsia",
+        target_os = "illumos",
+        target_os = "linux",
+        target_os = "netbsd",
+        target_os = "openbsd",
+    )))]
+    pub fn read_vectored_at(&self, bufs: &mut [IoSliceMut<'_>], offset: u64) -> io::Result<usize> {
+        crate::io::default_read_vectored(|b| self.read_at(b, offset), bufs)
+    }
+
     pub fn write(&self, buf: &[u8]) -> io::Result<usize> {
         let ret = cvt(unsafe {
             libc::write(self.fd, buf.as_ptr() as *const c_void, cmp::min(buf.len(), READ_LIMIT))
@@ -213,6 +272,45 @@ unsafe fn cvt_pwrite64(
         }
     }
 
+    #[cfg(any(
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "fuchsia",
+        target_os = "illumos",
+        target_os = "linux",
+        target_os = "netbsd",
+        target_os = "openbsd",
+    ))]
+    pub fn write_vectored_at(&self, bufs: &[IoSlice<'_>], offset: u64) -> io::Result<usize> {
+        #[cfg(not(target_os = "linux"))]
+        use libc::pwritev as pwritev64;
+        #[cfg(target_os = "linux")]
+        use libc::pwritev64;
+
+        let ret = cvt(unsafe {
+            pwritev64(
+                self.fd,
+                bufs.as_ptr() as *const libc::iovec,
+                cmp::min(bufs.len(), max_iov()) as c_int,
+                offset as i64,
+            )
+        })?;
+        Ok(ret as usize)
+    }
+
+    #[cfg(not(any(
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "fuchsia",
+        target_os = "illumos",
+        target_os = "linux",
+        target_os = "netbsd",
+        target_os = "openbsd",
+    )))]
+    pub fn write_vectored_at(&self, bufs: &[IoSlice<'_>], offset: u64) -> io::Result<usize> {
+        crate::io::default_write_vectored(|b| self.write_at(b, offset), bufs)
+    }
+
     #[cfg(target_os = "linux")]
     pub fn get_cloexec(&self) -> io::Result<bool> {
         unsafe { Ok((cvt(libc::fcntl(self.fd, libc::F_GETFD))? & libc::FD_CLOEXEC) != 0) }
@@ -350,9 +448,8 @@ fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         (**self).read(buf)
     }
 
-    #[inline]
-    unsafe fn initializer(&self) -> Initializer {
-        Initializer::nop()
+    fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        (**self).read_buf(buf)
     }
 }
 
@@ -2,7 +2,7 @@
 
 use crate::ffi::{CStr, CString, OsStr, OsString};
 use crate::fmt;
-use crate::io::{self, Error, IoSlice, IoSliceMut, SeekFrom};
+use crate::io::{self, Error, IoSlice, IoSliceMut, ReadBuf, SeekFrom};
 use crate::mem;
 use crate::path::{Path, PathBuf};
 use crate::ptr;
@@ -813,6 +813,26 @@ unsafe fn os_datasync(fd: c_int) -> c_int {
         }
     }
 
+    pub fn lock(&self) -> io::Result<()> {
+        flock(self.0.raw(), true, true).map(drop)
+    }
+
+    pub fn lock_shared(&self) -> io::Result<()> {
+        flock(self.0.raw(), false, true).map(drop)
+    }
+
+    pub fn try_lock(&self) -> io::Result<bool> {
+        flock(self.0.raw(), true, false)
+    }
+
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        flock(self.0.raw(), false, false)
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        funlock(self.0.raw())
+    }
+
     pub fn truncate(&self, size: u64) -> io::Result<()> {
         #[cfg(target_os = "android")]
         return crate::sys::android::ftruncate64(self.0.raw(), size);
@@ -830,6 +850,10 @@ pub fn read(&self, buf: &mut [u8]) -> io::Result<usize> {
         self.0.read(buf)
     }
 
+    pub fn read_buf(&self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        self.0.read_buf(buf)
+    }
+
     pub fn read_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         self.0.read_vectored(bufs)
     }
@@ -856,10 +880,46 @@ pub fn is_write_vectored(&self) -> bool {
         self.0.is_write_vectored()
     }
 
+    pub fn read_vectored_at(&self, bufs: &mut [IoSliceMut<'_>], offset: u64) -> io::Result<usize> {
+        self.0.read_vectored_at(bufs, offset)
+    }
+
     pub fn write_at(&self, buf: &[u8], offset: u64) -> io::Result<usize> {
         self.0.write_at(buf, offset)
     }
 
+    pub fn write_vectored_at(&self, bufs: &[IoSlice<'_>], offset: u64) -> io::Result<usize> {
+        self.0.write_vectored_at(bufs, offset)
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn copy_range_to(
+        &self,
+        offset: u64,
+        dst: &File,
+        dst_offset: u64,
+        len: usize,
+    ) -> io::Result<usize> {
+        syscall! {
+            fn copy_file_range(
+                fd_in: libc::c_int,
+                off_in: *mut libc::loff_t,
+                fd_out: libc::c_int,
+                off_out: *mut libc::loff_t,
+                len: libc::size_t,
+                flags: libc::c_uint
+            ) -> libc::ssize_t
+        }
+
+        // Passing explicit offsets leaves the file cursors of both files untouched.
+        let mut off_in = offset as libc::loff_t;
+        let mut off_out = dst_offset as libc::loff_t;
+        let ret = cvt(unsafe {
+            copy_file_range(self.0.raw(), &mut off_in, dst.0.raw(), &mut off_out, len, 0)
+        })?;
+        Ok(ret as usize)
+    }
+
     pub fn flush(&self) -> io::Result<()> {
         Ok(())
     }
@@ -894,6 +954,80 @@ pub fn set_permissions(&self, perm: FilePermissions) -> io::Result<()> {
     }
 }
 
+/// Locks the whole file with `flock`, returning `false` if `blocking` is unset
+/// and the lock is held by another open file description.
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+fn flock(fd: c_int, exclusive: bool, blocking: bool) -> io::Result<bool> {
+    let mut operation = if exclusive { libc::LOCK_EX } else { libc::LOCK_SH };
+    if !blocking {
+        operation |= libc::LOCK_NB;
+    }
+    match cvt_r(|| unsafe { libc::flock(fd, operation) }) {
+        Ok(_) => Ok(true),
+        Err(ref e) if !blocking && e.raw_os_error() == Some(libc::EWOULDBLOCK) => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+fn funlock(fd: c_int) -> io::Result<()> {
+    cvt(unsafe { libc::flock(fd, libc::LOCK_UN) })?;
+    Ok(())
+}
+
+// The other platforms only have `fcntl` record locks, which belong to the
+// process rather than to the open file description and are all released as
+// soon as *any* descriptor of the file is closed, so they can't provide the
+// semantics documented on `fs::File::lock`.
+#[cfg(not(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "openbsd"
+)))]
+fn flock(_fd: c_int, _exclusive: bool, _blocking: bool) -> io::Result<bool> {
+    Err(io::Error::new_const(
+        io::ErrorKind::Unsupported,
+        &"file locking is not supported on this platform",
+    ))
+}
+
+#[cfg(not(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "openbsd"
+)))]
+fn funlock(fd: c_int) -> io::Result<()> {
+    flock(fd, false, false).map(drop)
+}
+
 impl DirBuilder {
     pub fn new() -> DirBuilder {
         DirBuilder { mode: 0o777 }
@@ -1,4 +1,5 @@
 use crate::marker::PhantomData;
+use crate::os::unix::io::AsRawFd;
 use crate::slice;
 
 use libc::{c_void, iovec};
@@ -74,3 +75,7 @@ pub fn as_mut_slice(&mut self) -> &mut [u8] {
         unsafe { slice::from_raw_parts_mut(self.vec.iov_base as *mut u8, self.vec.iov_len) }
     }
 }
+
+pub fn is_terminal(fd: &impl AsRawFd) -> bool {
+    unsafe { libc::isatty(fd.as_raw_fd()) != 0 }
+}
@@ -70,10 +70,38 @@ pub(crate) fn copy_spec<R: Read + ?Sized, W: Write + ?Sized>(
     read: &mut R,
     write: &mut W,
 ) -> Result<u64> {
+    copy_spec_stats(read, write).map(|stats| stats.total())
+}
+
+/// Like `copy_spec`, but reports how many bytes were moved by each of the copy methods.
+pub(crate) fn copy_spec_stats<R: Read + ?Sized, W: Write + ?Sized>(
+    read: &mut R,
+    write: &mut W,
+) -> Result<CopyStats> {
     let copier = Copier { read, write };
     SpecCopy::copy(copier)
 }
 
+/// Number of bytes transferred by each of the methods a single `io::copy` can make use of.
+///
+/// A copy can use several methods in sequence, e.g. when `copy_file_range` stops working
+/// part-way through and the remainder is copied by the generic read-write loop.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct CopyStats {
+    pub(crate) copy_file_range: u64,
+    pub(crate) sendfile: u64,
+    pub(crate) splice: u64,
+    /// Bytes moved through userspace buffers, either by the generic copy loop or while
+    /// draining wrapper buffers before switching to one of the syscalls above.
+    pub(crate) read_write: u64,
+}
+
+impl CopyStats {
+    pub(crate) fn total(&self) -> u64 {
+        self.copy_file_range + self.sendfile + self.splice + self.read_write
+    }
+}
+
 /// This type represents either the inferred `FileType` of a `RawFd` based on the source
 /// type from which it was extracted or the actual metadata
 ///
@@ -135,17 +163,18 @@ struct Copier<'a, 'b, R: Read + ?Sized, W: Write + ?Sized> {
 }
 
 trait SpecCopy {
-    fn copy(self) -> Result<u64>;
+    fn copy(self) -> Result<CopyStats>;
 }
 
 impl<R: Read + ?Sized, W: Write + ?Sized> SpecCopy for Copier<'_, '_, R, W> {
-    default fn copy(self) -> Result<u64> {
-        generic_copy(self.read, self.write)
+    default fn copy(self) -> Result<CopyStats> {
+        let read_write = generic_copy(self.read, self.write)?;
+        Ok(CopyStats { read_write, ..CopyStats::default() })
     }
 }
 
 impl<R: CopyRead, W: CopyWrite> SpecCopy for Copier<'_, '_, R, W> {
-    fn copy(self) -> Result<u64> {
+    fn copy(self) -> Result<CopyStats> {
         let (reader, writer) = (self.read, self.write);
         let r_cfg = reader.properties();
         let w_cfg = writer.properties();
@@ -158,12 +187,12 @@ fn copy(self) -> Result<u64> {
             Ok(bytes)
         };
 
-        let mut written = 0u64;
+        let mut stats = CopyStats::default();
 
         if let (CopyParams(input_meta, Some(readfd)), CopyParams(output_meta, Some(writefd))) =
             (r_cfg, w_cfg)
         {
-            written += flush()?;
+            stats.read_write += flush()?;
             let max_write = reader.min_limit();
 
             if input_meta.copy_file_range_candidate() && output_meta.copy_file_range_candidate() {
@@ -171,9 +200,12 @@ fn copy(self) -> Result<u64> {
                 result.update_take(reader);
 
                 match result {
-                    CopyResult::Ended(bytes_copied) => return Ok(bytes_copied + written),
+                    CopyResult::Ended(bytes_copied) => {
+                        stats.copy_file_range += bytes_copied;
+                        return Ok(stats);
+                    }
                     CopyResult::Error(e, _) => return Err(e),
-                    CopyResult::Fallback(bytes) => written += bytes,
+                    CopyResult::Fallback(bytes) => stats.copy_file_range += bytes,
                 }
             }
 
@@ -187,9 +219,12 @@ fn copy(self) -> Result<u64> {
                 result.update_take(reader);
 
                 match result {
-                    CopyResult::Ended(bytes_copied) => return Ok(bytes_copied + written),
+                    CopyResult::Ended(bytes_copied) => {
+                        stats.sendfile += bytes_copied;
+                        return Ok(stats);
+                    }
                     CopyResult::Error(e, _) => return Err(e),
-                    CopyResult::Fallback(bytes) => written += bytes,
+                    CopyResult::Fallback(bytes) => stats.sendfile += bytes,
                 }
             }
 
@@ -198,7 +233,10 @@ fn copy(self) -> Result<u64> {
                 result.update_take(reader);
 
                 match result {
-                    CopyResult::Ended(bytes_copied) => return Ok(bytes_copied + written),
+                    CopyResult::Ended(bytes_copied) => {
+                        stats.splice += bytes_copied;
+                        return Ok(stats);
+                    }
                     CopyResult::Error(e, _) => return Err(e),
                     CopyResult::Fallback(0) => { /* use the fallback below */ }
                     CopyResult::Fallback(_) => {
@@ -209,10 +247,8 @@ fn copy(self) -> Result<u64> {
         }
 
         // fallback if none of the more specialized syscalls wants to work with these file descriptors
-        match generic_copy(reader, writer) {
-            Ok(bytes) => Ok(bytes + written),
-            err => err,
-        }
+        stats.read_write += generic_copy(reader, writer)?;
+        Ok(stats)
     }
 }
 
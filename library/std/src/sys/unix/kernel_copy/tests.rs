@@ -83,6 +83,83 @@ fn copies_append_mode_sink() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn copy_reports_methods() -> Result<()> {
+    use super::{copy_regular_files, copy_spec_stats, sendfile_splice, CopyResult, SpliceMode};
+
+    // Slices and vectors have no file descriptors, so the generic loop is the only option.
+    let stats = copy_spec_stats(&mut &b"hello"[..], &mut Vec::new())?;
+    assert_eq!(stats.read_write, 5);
+    assert_eq!(stats.total(), 5);
+
+    let tmp_path = tmpdir();
+    let source_path = tmp_path.join("copy-report.source");
+    let sink_path = tmp_path.join("copy-report.sink");
+    let mut source =
+        OpenOptions::new().create(true).truncate(true).write(true).read(true).open(&source_path)?;
+    source.write_all(&[1u8; 4096])?;
+    source.seek(SeekFrom::Start(0))?;
+    let mut sink = OpenOptions::new().create(true).truncate(true).write(true).open(&sink_path)?;
+
+    let stats = copy_spec_stats(&mut source, &mut sink)?;
+    assert_eq!(stats.total(), 4096);
+    assert_eq!(sink.metadata()?.len(), 4096);
+
+    // A copy between regular files is offloaded to the kernel, with `copy_file_range` or with
+    // `sendfile` as a fallback, unless both are unavailable, e.g. on old kernels or because of
+    // seccomp filters. Probe them by copying a single byte to another file.
+    let probe_path = tmp_path.join("copy-report.probe");
+    let probe = OpenOptions::new().create(true).truncate(true).write(true).open(&probe_path)?;
+    source.seek(SeekFrom::Start(0))?;
+    let (source_fd, probe_fd) = (source.as_raw_fd(), probe.as_raw_fd());
+    let offloaded = matches!(copy_regular_files(source_fd, probe_fd, 1), CopyResult::Ended(1))
+        || matches!(
+            sendfile_splice(SpliceMode::Sendfile, source_fd, probe_fd, 1),
+            CopyResult::Ended(1)
+        );
+    if offloaded {
+        assert_eq!(stats.read_write, 0);
+        assert_eq!(stats.copy_file_range + stats.sendfile, 4096);
+    } else {
+        eprintln!("copy_file_range and sendfile are unavailable, skipping the offload check");
+    }
+
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn copy_range_to() -> Result<()> {
+    use crate::os::linux::fs::FileCopyExt;
+
+    let tmp_path = tmpdir();
+    let source_path = tmp_path.join("copy-range.source");
+    let sink_path = tmp_path.join("copy-range.sink");
+    let mut source =
+        OpenOptions::new().create(true).truncate(true).write(true).read(true).open(&source_path)?;
+    source.write_all(b"abcdefghiklmnopqr")?;
+    let mut sink =
+        OpenOptions::new().create(true).truncate(true).write(true).read(true).open(&sink_path)?;
+    sink.write_all(b"0000")?;
+
+    let copied = match source.copy_range_to(2, &sink, 1, 5) {
+        // copy_file_range is unavailable or blocked, nothing to test here.
+        Err(e) if matches!(e.raw_os_error(), Some(libc::ENOSYS | libc::EPERM)) => return Ok(()),
+        result => result?,
+    };
+    assert_eq!(copied, 5);
+    assert_eq!(source.seek(SeekFrom::Current(0))?, 17, "source cursor is unchanged");
+    assert_eq!(sink.seek(SeekFrom::Current(0))?, 4, "sink cursor is unchanged");
+    assert_eq!(source.copy_range_to(17, &sink, 0, 5)?, 0, "nothing to copy at EOF");
+
+    sink.seek(SeekFrom::Start(0))?;
+    let mut contents = Vec::new();
+    sink.read_to_end(&mut contents)?;
+    assert_eq!(&contents, b"0cdefg");
+
+    Ok(())
+}
+
 #[bench]
 fn bench_file_to_file_copy(b: &mut test::Bencher) {
     const BYTES: usize = 128 * 1024;
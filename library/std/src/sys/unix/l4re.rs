@@ -11,7 +11,7 @@ pub mod net {
     #![allow(warnings)]
     use crate::convert::TryFrom;
     use crate::fmt;
-    use crate::io::{self, IoSlice, IoSliceMut};
+    use crate::io::{self, IoSlice, IoSliceMut, ReadBuf};
     use crate::net::{Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr};
     use crate::sys::fd::FileDesc;
     use crate::sys_common::{AsInner, FromInner, IntoInner};
@@ -54,6 +54,10 @@ pub fn read(&self, _: &mut [u8]) -> io::Result<usize> {
             unimpl!();
         }
 
+        pub fn read_buf(&self, _: &mut ReadBuf<'_>) -> io::Result<()> {
+            unimpl!();
+        }
+
         pub fn read_vectored(&self, _: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
             unimpl!();
         }
@@ -178,6 +182,10 @@ pub fn read(&self, _: &mut [u8]) -> io::Result<usize> {
             unimpl!();
         }
 
+        pub fn read_buf(&self, _: &mut ReadBuf<'_>) -> io::Result<()> {
+            unimpl!();
+        }
+
         pub fn read_vectored(&self, _: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
             unimpl!();
         }
@@ -222,6 +230,38 @@ pub fn nodelay(&self) -> io::Result<bool> {
             unimpl!();
         }
 
+        pub fn set_linger(&self, _: Option<Duration>) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn linger(&self) -> io::Result<Option<Duration>> {
+            unimpl!();
+        }
+
+        pub fn set_keepalive(&self, _: bool) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn keepalive(&self) -> io::Result<bool> {
+            unimpl!();
+        }
+
+        pub fn set_recv_buffer_size(&self, _: usize) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn recv_buffer_size(&self) -> io::Result<usize> {
+            unimpl!();
+        }
+
+        pub fn set_send_buffer_size(&self, _: usize) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn send_buffer_size(&self) -> io::Result<usize> {
+            unimpl!();
+        }
+
         pub fn set_ttl(&self, _: u32) -> io::Result<()> {
             unimpl!();
         }
@@ -317,6 +357,11 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         }
     }
 
+    #[path = "../../../unsupported/tcp_socket.rs"]
+    mod tcp_socket;
+
+    pub use self::tcp_socket::TcpSocket;
+
     pub struct UdpSocket {
         inner: Socket,
     }
@@ -422,6 +467,22 @@ pub fn leave_multicast_v6(&self, _: &Ipv6Addr, _: u32) -> io::Result<()> {
             unimpl!();
         }
 
+        pub fn set_recv_buffer_size(&self, _: usize) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn recv_buffer_size(&self) -> io::Result<usize> {
+            unimpl!();
+        }
+
+        pub fn set_send_buffer_size(&self, _: usize) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn send_buffer_size(&self) -> io::Result<usize> {
+            unimpl!();
+        }
+
         pub fn set_ttl(&self, _: u32) -> io::Result<()> {
             unimpl!();
         }
@@ -0,0 +1,24 @@
+cfg_if::cfg_if! {
+    if #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+    ))] {
+        #[path = "futex_condvar.rs"]
+        pub mod condvar;
+        #[path = "futex_mutex.rs"]
+        pub mod mutex;
+        #[path = "futex_rwlock.rs"]
+        pub mod rwlock;
+    } else {
+        #[path = "pthread_condvar.rs"]
+        pub mod condvar;
+        #[path = "pthread_mutex.rs"]
+        pub mod mutex;
+        #[path = "pthread_rwlock.rs"]
+        pub mod rwlock;
+    }
+}
+
+// Reentrant mutexes are always implemented on top of pthread mutexes, as
+// they need to know which thread owns them.
+mod pthread_remutex;
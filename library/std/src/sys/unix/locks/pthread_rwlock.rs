@@ -1,5 +1,6 @@
 use crate::cell::UnsafeCell;
 use crate::sync::atomic::{AtomicUsize, Ordering};
+use crate::sys_common::lazy_box::{LazyBox, LazyInit};
 
 pub struct RWLock {
     inner: UnsafeCell<libc::pthread_rwlock_t>,
@@ -7,7 +8,22 @@ pub struct RWLock {
     num_readers: AtomicUsize,
 }
 
-pub type MovableRWLock = Box<RWLock>;
+pub(crate) type MovableRWLock = LazyBox<RWLock>;
+
+impl LazyInit for RWLock {
+    fn init() -> Box<Self> {
+        Box::new(Self::new())
+    }
+
+    fn destroy(rwlock: Box<Self>) {
+        unsafe { rwlock.destroy() };
+    }
+
+    fn cancel_init(_: Box<Self>) {
+        // In this case, we can just drop it without any checks,
+        // since it cannot have been locked yet.
+    }
+}
 
 unsafe impl Send for RWLock {}
 unsafe impl Sync for RWLock {}
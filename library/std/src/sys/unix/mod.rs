@@ -14,7 +14,6 @@
 pub mod args;
 #[path = "../unix/cmath.rs"]
 pub mod cmath;
-pub mod condvar;
 pub mod env;
 pub mod fd;
 pub mod fs;
@@ -24,8 +23,8 @@
 pub mod kernel_copy;
 #[cfg(target_os = "l4re")]
 mod l4re;
+pub mod locks;
 pub mod memchr;
-pub mod mutex;
 #[cfg(not(target_os = "l4re"))]
 pub mod net;
 #[cfg(target_os = "l4re")]
@@ -35,7 +34,7 @@
 pub mod pipe;
 pub mod process;
 pub mod rand;
-pub mod rwlock;
+pub mod reactor;
 pub mod stack_overflow;
 pub mod stdio;
 pub mod thread;
@@ -44,6 +43,7 @@
 pub mod time;
 
 pub use crate::sys_common::os_str_bytes as os_str;
+pub use self::locks::{condvar, mutex, rwlock};
 
 #[cfg(target_os = "espidf")]
 pub fn init(argc: isize, argv: *const *const u8) {
@@ -1,6 +1,6 @@
 use crate::cmp;
 use crate::ffi::CStr;
-use crate::io::{self, IoSlice, IoSliceMut};
+use crate::io::{self, IoSlice, IoSliceMut, ReadBuf};
 use crate::mem;
 use crate::net::{Shutdown, SocketAddr};
 use crate::str;
@@ -18,6 +18,16 @@
 
 pub type wrlen_t = size_t;
 
+cfg_if::cfg_if! {
+    if #[cfg(any(target_os = "ios", target_os = "macos"))] {
+        // `SO_LINGER` is measured in clock ticks rather than seconds on Apple
+        // platforms, `SO_LINGER_SEC` is the equivalent of the POSIX option.
+        use libc::SO_LINGER_SEC as SO_LINGER;
+    } else {
+        use libc::SO_LINGER;
+    }
+}
+
 pub struct Socket(FileDesc);
 
 pub fn init() {}
@@ -229,19 +239,37 @@ pub fn duplicate(&self) -> io::Result<Socket> {
         self.0.duplicate().map(Socket)
     }
 
-    fn recv_with_flags(&self, buf: &mut [u8], flags: c_int) -> io::Result<usize> {
+    fn recv_with_flags(&self, buf: &mut ReadBuf<'_>, flags: c_int) -> io::Result<()> {
         let ret = cvt(unsafe {
-            libc::recv(self.0.raw(), buf.as_mut_ptr() as *mut c_void, buf.len(), flags)
+            libc::recv(
+                self.0.raw(),
+                buf.unfilled_mut().as_mut_ptr() as *mut c_void,
+                buf.remaining(),
+                flags,
+            )
         })?;
-        Ok(ret as usize)
+        // SAFETY: `ret` bytes were written to the unfilled portion of the buffer.
+        unsafe {
+            buf.assume_init(ret as usize);
+        }
+        buf.add_filled(ret as usize);
+        Ok(())
     }
 
     pub fn read(&self, buf: &mut [u8]) -> io::Result<usize> {
-        self.recv_with_flags(buf, 0)
+        let mut buf = ReadBuf::new(buf);
+        self.recv_with_flags(&mut buf, 0)?;
+        Ok(buf.filled_len())
     }
 
     pub fn peek(&self, buf: &mut [u8]) -> io::Result<usize> {
-        self.recv_with_flags(buf, MSG_PEEK)
+        let mut buf = ReadBuf::new(buf);
+        self.recv_with_flags(&mut buf, MSG_PEEK)?;
+        Ok(buf.filled_len())
+    }
+
+    pub fn read_buf(&self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        self.recv_with_flags(buf, 0)
     }
 
     pub fn read_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
@@ -373,6 +401,22 @@ pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
         Ok(())
     }
 
+    pub fn set_linger(&self, linger: Option<Duration>) -> io::Result<()> {
+        let secs = linger.unwrap_or_default().as_secs();
+        let linger = libc::linger {
+            l_onoff: linger.is_some() as libc::c_int,
+            l_linger: cmp::min(secs, c_int::MAX as u64) as c_int,
+        };
+
+        setsockopt(self, libc::SOL_SOCKET, SO_LINGER, linger)
+    }
+
+    pub fn linger(&self) -> io::Result<Option<Duration>> {
+        let val: libc::linger = getsockopt(self, libc::SOL_SOCKET, SO_LINGER)?;
+
+        Ok((val.l_onoff != 0).then(|| Duration::from_secs(val.l_linger as u64)))
+    }
+
     pub fn set_nodelay(&self, nodelay: bool) -> io::Result<()> {
         setsockopt(self, libc::IPPROTO_TCP, libc::TCP_NODELAY, nodelay as c_int)
     }
@@ -382,6 +426,93 @@ pub fn nodelay(&self) -> io::Result<bool> {
         Ok(raw != 0)
     }
 
+    #[cfg(target_os = "linux")]
+    pub fn set_keepalive_time(&self, time: Duration) -> io::Result<()> {
+        let secs = cmp::min(time.as_secs(), c_int::MAX as u64) as c_int;
+        setsockopt(self, libc::IPPROTO_TCP, libc::TCP_KEEPIDLE, secs)
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn keepalive_time(&self) -> io::Result<Duration> {
+        let raw: c_int = getsockopt(self, libc::IPPROTO_TCP, libc::TCP_KEEPIDLE)?;
+        Ok(Duration::from_secs(raw as u64))
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn set_keepalive_interval(&self, interval: Duration) -> io::Result<()> {
+        let secs = cmp::min(interval.as_secs(), c_int::MAX as u64) as c_int;
+        setsockopt(self, libc::IPPROTO_TCP, libc::TCP_KEEPINTVL, secs)
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn keepalive_interval(&self) -> io::Result<Duration> {
+        let raw: c_int = getsockopt(self, libc::IPPROTO_TCP, libc::TCP_KEEPINTVL)?;
+        Ok(Duration::from_secs(raw as u64))
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn set_keepalive_retries(&self, retries: u32) -> io::Result<()> {
+        let retries = cmp::min(retries, c_int::MAX as u32) as c_int;
+        setsockopt(self, libc::IPPROTO_TCP, libc::TCP_KEEPCNT, retries)
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn keepalive_retries(&self) -> io::Result<u32> {
+        let raw: c_int = getsockopt(self, libc::IPPROTO_TCP, libc::TCP_KEEPCNT)?;
+        Ok(raw as u32)
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn set_user_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        let millis = match timeout {
+            Some(timeout) if timeout.as_secs() == 0 && timeout.subsec_nanos() == 0 => {
+                return Err(io::Error::new_const(
+                    io::ErrorKind::InvalidInput,
+                    &"cannot set a 0 duration timeout",
+                ));
+            }
+            Some(timeout) => {
+                cmp::min(timeout.as_millis(), libc::c_uint::MAX as u128) as libc::c_uint
+            }
+            None => 0,
+        };
+        setsockopt(self, libc::IPPROTO_TCP, libc::TCP_USER_TIMEOUT, millis)
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn user_timeout(&self) -> io::Result<Option<Duration>> {
+        let raw: libc::c_uint = getsockopt(self, libc::IPPROTO_TCP, libc::TCP_USER_TIMEOUT)?;
+        Ok((raw != 0).then(|| Duration::from_millis(raw as u64)))
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn send_file(
+        &self,
+        file: &crate::sys::fs::File,
+        offset: u64,
+        count: usize,
+    ) -> io::Result<usize> {
+        // The file offset is passed explicitly so that the file cursor is left untouched.
+        let mut offset = offset as libc::off64_t;
+        // `sendfile` transfers at most 0x7ffff000 bytes per call anyway.
+        let count = cmp::min(count, 0x7ffff000);
+        let ret = cvt(unsafe {
+            libc::sendfile64(*self.as_inner(), file.fd().raw(), &mut offset, count)
+        })?;
+        Ok(ret as usize)
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn set_reuse_port(&self, reuse: bool) -> io::Result<()> {
+        setsockopt(self, libc::SOL_SOCKET, libc::SO_REUSEPORT, reuse as c_int)
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn reuse_port(&self) -> io::Result<bool> {
+        let raw: c_int = getsockopt(self, libc::SOL_SOCKET, libc::SO_REUSEPORT)?;
+        Ok(raw != 0)
+    }
+
     #[cfg(any(target_os = "android", target_os = "linux",))]
     pub fn set_passcred(&self, passcred: bool) -> io::Result<()> {
         setsockopt(self, libc::SOL_SOCKET, libc::SO_PASSCRED, passcred as libc::c_int)
@@ -17,7 +17,7 @@
 #[cfg(not(target_os = "fuchsia"))]
 use crate::sys::fs::OpenOptions;
 
-use libc::{c_char, c_int, gid_t, uid_t, EXIT_FAILURE, EXIT_SUCCESS};
+use libc::{c_char, c_int, gid_t, pid_t, uid_t, EXIT_FAILURE, EXIT_SUCCESS};
 
 cfg_if::cfg_if! {
     if #[cfg(target_os = "fuchsia")] {
@@ -76,6 +76,10 @@ pub struct Command {
     saw_nul: bool,
     closures: Vec<Box<dyn FnMut() -> io::Result<()> + Send + Sync>>,
     groups: Option<Box<[gid_t]>>,
+    pgroup: Option<pid_t>,
+    setsid: bool,
+    #[cfg(target_os = "linux")]
+    create_pidfd: bool,
     stdin: Option<Stdio>,
     stdout: Option<Stdio>,
     stderr: Option<Stdio>,
@@ -138,6 +142,10 @@ pub fn new(program: &OsStr) -> Command {
             saw_nul,
             closures: Vec::new(),
             groups: None,
+            pgroup: None,
+            setsid: false,
+            #[cfg(target_os = "linux")]
+            create_pidfd: false,
             stdin: None,
             stdout: None,
             stderr: None,
@@ -176,6 +184,16 @@ pub fn gid(&mut self, id: gid_t) {
     pub fn groups(&mut self, groups: &[gid_t]) {
         self.groups = Some(Box::from(groups));
     }
+    pub fn pgroup(&mut self, pgroup: pid_t) {
+        self.pgroup = Some(pgroup);
+    }
+    pub fn setsid(&mut self, setsid: bool) {
+        self.setsid = setsid;
+    }
+    #[cfg(target_os = "linux")]
+    pub fn create_pidfd(&mut self, create_pidfd: bool) {
+        self.create_pidfd = create_pidfd;
+    }
 
     pub fn saw_nul(&self) -> bool {
         self.saw_nul
@@ -223,6 +241,18 @@ pub fn get_gid(&self) -> Option<gid_t> {
     pub fn get_groups(&self) -> Option<&[gid_t]> {
         self.groups.as_deref()
     }
+    #[allow(dead_code)]
+    pub fn get_pgroup(&self) -> Option<pid_t> {
+        self.pgroup
+    }
+    #[allow(dead_code)]
+    pub fn get_setsid(&self) -> bool {
+        self.setsid
+    }
+    #[cfg(target_os = "linux")]
+    pub fn get_create_pidfd(&self) -> bool {
+        self.create_pidfd
+    }
 
     pub fn get_closures(&mut self) -> &mut Vec<Box<dyn FnMut() -> io::Result<()> + Send + Sync>> {
         &mut self.closures
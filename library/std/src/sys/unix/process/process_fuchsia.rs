@@ -161,6 +161,14 @@ pub fn kill(&mut self) -> io::Result<()> {
         Ok(())
     }
 
+    pub fn send_signal(&self, _signal: i32) -> io::Result<()> {
+        // Zircon processes have no signals, only termination.
+        Err(io::Error::new_const(
+            io::ErrorKind::Unsupported,
+            &"signals are not supported on this platform",
+        ))
+    }
+
     pub fn wait(&mut self) -> io::Result<ExitStatus> {
         use crate::default::Default;
         use crate::sys::process::zircon::*;
@@ -15,6 +15,9 @@
 #[cfg(not(target_os = "vxworks"))]
 use libc::{c_int, gid_t, pid_t, uid_t};
 
+#[cfg(target_os = "linux")]
+use crate::os::linux::process::PidFd;
+
 ////////////////////////////////////////////////////////////////////////////////
 // Command
 ////////////////////////////////////////////////////////////////////////////////
@@ -53,7 +56,7 @@ pub fn spawn(
         // a lock any more because the parent won't do anything and the child is
         // in its own process. Thus the parent drops the lock guard while the child
         // forgets it to avoid unlocking it on a new thread, which would be invalid.
-        let (env_lock, pid) = unsafe { (sys::os::env_read_lock(), cvt(libc::fork())?) };
+        let (env_lock, (pid, pidfd)) = unsafe { (sys::os::env_read_lock(), self.do_fork()?) };
 
         if pid == 0 {
             crate::panic::always_abort();
@@ -82,7 +85,8 @@ pub fn spawn(
         drop(env_lock);
         drop(output);
 
-        let mut p = Process { pid, status: None };
+        // SAFETY: `pidfd` is either -1 or a new file descriptor owned by nothing else.
+        let mut p = unsafe { Process::new(pid, pidfd) };
         let mut bytes = [0; 8];
 
         // loop to handle EINTR
@@ -114,6 +118,84 @@ pub fn spawn(
         }
     }
 
+    // Creates the child with `fork`, returning its pid, or 0 in the child, and
+    // its pidfd, or -1 if none was created.
+    #[cfg(not(target_os = "linux"))]
+    unsafe fn do_fork(&mut self) -> io::Result<(pid_t, c_int)> {
+        cvt(libc::fork()).map(|pid| (pid, -1))
+    }
+
+    // Creates the child with `clone3` when a pidfd was requested, which creates
+    // the pidfd atomically along with the child, and with `fork` otherwise.
+    //
+    // Bypassing libc for `clone3` leaves its internal state (such as the cached
+    // thread ID) stale in the child, which is fine for the async-signal-safe
+    // calls done until `exec`, but still a reason to only do it when needed.
+    // If `clone3` isn't available, the pidfd is opened with `pidfd_open` right
+    // after `fork` instead: this is race-free too, since the child can't be
+    // reaped, and its pid reused, until we wait for it.
+    #[cfg(target_os = "linux")]
+    unsafe fn do_fork(&mut self) -> io::Result<(pid_t, c_int)> {
+        use crate::sync::atomic::{AtomicBool, Ordering};
+
+        static HAS_CLONE3: AtomicBool = AtomicBool::new(true);
+        const CLONE_PIDFD: u64 = 0x00001000;
+
+        #[repr(C)]
+        struct clone_args {
+            flags: u64,
+            pidfd: u64,
+            child_tid: u64,
+            parent_tid: u64,
+            exit_signal: u64,
+            stack: u64,
+            stack_size: u64,
+            tls: u64,
+            set_tid: u64,
+            set_tid_size: u64,
+            cgroup: u64,
+        }
+
+        if !self.get_create_pidfd() {
+            return cvt(libc::fork()).map(|pid| (pid, -1));
+        }
+
+        let mut pidfd: c_int = -1;
+        if HAS_CLONE3.load(Ordering::Relaxed) {
+            let mut args = clone_args {
+                flags: CLONE_PIDFD,
+                pidfd: &mut pidfd as *mut c_int as u64,
+                child_tid: 0,
+                parent_tid: 0,
+                exit_signal: libc::SIGCHLD as u64,
+                stack: 0,
+                stack_size: 0,
+                tls: 0,
+                set_tid: 0,
+                set_tid_size: 0,
+                cgroup: 0,
+            };
+            let args = &mut args as *mut clone_args;
+            match cvt(libc::syscall(libc::SYS_clone3, args, mem::size_of::<clone_args>())) {
+                Ok(pid) => return Ok((pid as pid_t, pidfd)),
+                // Several threads may race to store this, which is harmless.
+                Err(ref e) if e.raw_os_error() == Some(libc::ENOSYS) => {
+                    HAS_CLONE3.store(false, Ordering::Relaxed)
+                }
+                // `clone3` may be blocked by a seccomp filter.
+                Err(ref e) if e.raw_os_error() == Some(libc::EPERM) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        let pid = cvt(libc::fork())?;
+        if pid > 0 {
+            // If this fails too, the child just doesn't get a pidfd.
+            pidfd = libc::syscall(libc::SYS_pidfd_open, pid, 0) as c_int;
+        }
+        Ok((pid, pidfd))
+    }
+
     pub fn exec(&mut self, default: Stdio) -> io::Error {
         let envp = self.capture_env();
 
@@ -216,6 +298,13 @@ unsafe fn do_exec(
             cvt(libc::chdir(cwd.as_ptr()))?;
         }
 
+        if self.get_setsid() {
+            cvt(libc::setsid())?;
+        }
+        if let Some(pgroup) = self.get_pgroup() {
+            cvt(libc::setpgid(0, pgroup))?;
+        }
+
         // emscripten has no signal support.
         #[cfg(not(target_os = "emscripten"))]
         {
@@ -300,10 +389,17 @@ fn posix_spawn(
             || (self.env_saw_path() && !self.program_is_path())
             || !self.get_closures().is_empty()
             || self.get_groups().is_some()
+            || self.get_setsid()
         {
             return Ok(None);
         }
 
+        // A pidfd can only be created along with the child by `do_fork`.
+        #[cfg(target_os = "linux")]
+        if self.get_create_pidfd() {
+            return Ok(None);
+        }
+
         // Only glibc 2.24+ posix_spawn() supports returning ENOENT directly.
         #[cfg(all(target_os = "linux", target_env = "gnu"))]
         {
@@ -344,7 +440,8 @@ fn posix_spawn_file_actions_addchdir_np(
             None => None,
         };
 
-        let mut p = Process { pid: 0, status: None };
+        // SAFETY: -1 isn't a file descriptor.
+        let mut p = unsafe { Process::new(0, -1) };
 
         struct PosixSpawnFileActions<'a>(&'a mut MaybeUninit<libc::posix_spawn_file_actions_t>);
 
@@ -406,7 +503,11 @@ fn drop(&mut self) {
             cvt(sigaddset(set.as_mut_ptr(), libc::SIGPIPE))?;
             cvt_nz(libc::posix_spawnattr_setsigdefault(attrs.0.as_mut_ptr(), set.as_ptr()))?;
 
-            let flags = libc::POSIX_SPAWN_SETSIGDEF | libc::POSIX_SPAWN_SETSIGMASK;
+            let mut flags = libc::POSIX_SPAWN_SETSIGDEF | libc::POSIX_SPAWN_SETSIGMASK;
+            if let Some(pgroup) = self.get_pgroup() {
+                flags |= libc::POSIX_SPAWN_SETPGROUP;
+                cvt_nz(libc::posix_spawnattr_setpgroup(attrs.0.as_mut_ptr(), pgroup))?;
+            }
             cvt_nz(libc::posix_spawnattr_setflags(attrs.0.as_mut_ptr(), flags as _))?;
 
             // Make sure we synchronize access to the global `environ` resource
@@ -433,25 +534,59 @@ fn drop(&mut self) {
 pub struct Process {
     pid: pid_t,
     status: Option<ExitStatus>,
+    // The pidfd of the child on Linux, if one was requested and could be
+    // created. It's then used instead of the pid, which may be reused by
+    // another process once the child has been reaped.
+    #[cfg(target_os = "linux")]
+    pidfd: Option<PidFd>,
 }
 
 impl Process {
+    // Safety: `pidfd` must either be -1 or a valid file descriptor which isn't
+    // owned by anything else.
+    #[cfg(target_os = "linux")]
+    unsafe fn new(pid: pid_t, pidfd: c_int) -> Self {
+        use crate::sys_common::FromInner;
+
+        let pidfd =
+            if pidfd >= 0 { Some(PidFd::from_inner(sys::fd::FileDesc::new(pidfd))) } else { None };
+        Process { pid, status: None, pidfd }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    unsafe fn new(pid: pid_t, _pidfd: c_int) -> Self {
+        Process { pid, status: None }
+    }
+
     pub fn id(&self) -> u32 {
         self.pid as u32
     }
 
     pub fn kill(&mut self) -> io::Result<()> {
+        self.send_signal(libc::SIGKILL)
+    }
+
+    pub fn send_signal(&self, signal: i32) -> io::Result<()> {
         // If we've already waited on this process then the pid can be recycled
         // and used for another process, and we probably shouldn't be killing
         // random processes, so just return an error.
         if self.status.is_some() {
-            Err(Error::new_const(
+            return Err(Error::new_const(
                 ErrorKind::InvalidInput,
                 &"invalid argument: can't kill an exited process",
-            ))
-        } else {
-            cvt(unsafe { libc::kill(self.pid, libc::SIGKILL) }).map(drop)
+            ));
         }
+        #[cfg(target_os = "linux")]
+        if let Some(pidfd) = &self.pidfd {
+            use crate::sys_common::AsInner;
+
+            let fd = pidfd.as_inner().raw();
+            return cvt(unsafe {
+                libc::syscall(libc::SYS_pidfd_send_signal, fd, signal, ptr::null::<()>(), 0)
+            })
+            .map(drop);
+        }
+        cvt(unsafe { libc::kill(self.pid, signal) }).map(drop)
     }
 
     pub fn wait(&mut self) -> io::Result<ExitStatus> {
@@ -459,6 +594,12 @@ pub fn wait(&mut self) -> io::Result<ExitStatus> {
         if let Some(status) = self.status {
             return Ok(status);
         }
+        #[cfg(target_os = "linux")]
+        if self.pidfd.is_some() {
+            let status = self.waitid_pidfd(0)?.expect("waitid() returned without a status");
+            self.status = Some(status);
+            return Ok(status);
+        }
         let mut status = 0 as c_int;
         cvt_r(|| unsafe { libc::waitpid(self.pid, &mut status, 0) })?;
         self.status = Some(ExitStatus::new(status));
@@ -469,6 +610,12 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         if let Some(status) = self.status {
             return Ok(Some(status));
         }
+        #[cfg(target_os = "linux")]
+        if self.pidfd.is_some() {
+            let status = self.waitid_pidfd(libc::WNOHANG)?;
+            self.status = status;
+            return Ok(status);
+        }
         let mut status = 0 as c_int;
         let pid = cvt(unsafe { libc::waitpid(self.pid, &mut status, libc::WNOHANG) })?;
         if pid == 0 {
@@ -478,6 +625,44 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
             Ok(Some(ExitStatus::new(status)))
         }
     }
+
+    // Waits for the child to exit through its pidfd, which can't refer to
+    // another process, even if the child was already reaped elsewhere.
+    #[cfg(target_os = "linux")]
+    fn waitid_pidfd(&self, options: c_int) -> io::Result<Option<ExitStatus>> {
+        use crate::sys::cvt_r;
+        use crate::sys_common::AsInner;
+
+        // Not yet in `libc`, see `include/uapi/linux/wait.h`.
+        const P_PIDFD: libc::idtype_t = 3;
+
+        let fd = self.pidfd.as_ref().unwrap().as_inner().raw();
+        let mut siginfo: libc::siginfo_t = unsafe { mem::zeroed() };
+        cvt_r(|| unsafe {
+            libc::waitid(P_PIDFD, fd as libc::id_t, &mut siginfo, libc::WEXITED | options)
+        })?;
+        // With `WNOHANG`, the `siginfo_t` is left zeroed if the child hasn't
+        // exited yet.
+        if unsafe { siginfo.si_pid() } == 0 {
+            Ok(None)
+        } else {
+            Ok(Some(ExitStatus::from_waitid_siginfo(&siginfo)))
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn pidfd(&self) -> io::Result<&PidFd> {
+        self.pidfd.as_ref().ok_or_else(|| {
+            Error::new_const(ErrorKind::Other, &"no pidfd was created for this process")
+        })
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn take_pidfd(&mut self) -> io::Result<PidFd> {
+        self.pidfd.take().ok_or_else(|| {
+            Error::new_const(ErrorKind::Other, &"no pidfd was created for this process")
+        })
+    }
 }
 
 /// Unix exit statuses
@@ -489,6 +674,21 @@ pub fn new(status: c_int) -> ExitStatus {
         ExitStatus(status)
     }
 
+    // Builds the wait status equivalent to the `siginfo_t` filled in by
+    // `waitid`, as encoded by Linux.
+    #[cfg(target_os = "linux")]
+    fn from_waitid_siginfo(siginfo: &libc::siginfo_t) -> ExitStatus {
+        let status = unsafe { siginfo.si_status() };
+        match siginfo.si_code {
+            libc::CLD_EXITED => ExitStatus((status & 0xff) << 8),
+            libc::CLD_KILLED => ExitStatus(status),
+            libc::CLD_DUMPED => ExitStatus(status | 0x80),
+            libc::CLD_CONTINUED => ExitStatus(0xffff),
+            libc::CLD_STOPPED | libc::CLD_TRAPPED => ExitStatus(((status & 0xff) << 8) | 0x7f),
+            _ => unreachable!("waitid() should only return the above codes"),
+        }
+    }
+
     fn exited(&self) -> bool {
         libc::WIFEXITED(self.0)
     }
@@ -55,3 +55,73 @@ fn test_command_fork_no_unwind() {
     let signal = status.signal().expect("expected child process to die of signal");
     assert!(signal == libc::SIGABRT || signal == libc::SIGILL || signal == libc::SIGTRAP);
 }
+
+#[test]
+#[cfg_attr(target_os = "emscripten", ignore)]
+fn test_command_process_group() {
+    use crate::process::Stdio;
+
+    // The group is set before `spawn` returns, and a new group gets the
+    // child's pid as ID.
+    let mut child = Command::new("cat").stdin(Stdio::piped()).process_group(0).spawn().unwrap();
+    let pid = child.id() as libc::pid_t;
+    assert_eq!(unsafe { libc::getpgid(pid) }, pid);
+    assert_ne!(unsafe { libc::getpgid(0) }, pid);
+    child.kill().unwrap();
+    child.wait().unwrap();
+}
+
+#[test]
+#[cfg_attr(target_os = "emscripten", ignore)]
+fn test_send_signal() {
+    use crate::os::unix::process::ChildExt;
+    use crate::process::Stdio;
+
+    let mut child = Command::new("cat").stdin(Stdio::piped()).spawn().unwrap();
+    child.send_signal(libc::SIGTERM).unwrap();
+    let status = child.wait().unwrap();
+    assert_eq!(status.signal(), Some(libc::SIGTERM));
+
+    // The pid may already belong to another process.
+    assert!(child.send_signal(libc::SIGTERM).is_err());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_command_pidfd() {
+    use crate::os::linux::process::{ChildExt, CommandExt};
+    use crate::os::unix::io::AsRawFd;
+    use crate::process::Stdio;
+
+    // The kernel may not support pidfds (before Linux 5.3), which is fine.
+    let pidfd_open_available = unsafe { libc::syscall(libc::SYS_pidfd_open, 0, 0) } != -1
+        || crate::io::Error::last_os_error().raw_os_error() != Some(libc::ENOSYS);
+
+    // `create_pidfd(false)` is the default and never creates a pidfd.
+    let mut child = Command::new("true").create_pidfd(false).spawn().unwrap();
+    assert!(child.pidfd().is_err());
+    child.wait().unwrap();
+
+    let mut child = Command::new("cat").stdin(Stdio::piped()).create_pidfd(true).spawn().unwrap();
+    if pidfd_open_available {
+        let flags = unsafe { libc::fcntl(child.pidfd().unwrap().as_raw_fd(), libc::F_GETFD) };
+        assert!(flags != -1 && flags & libc::FD_CLOEXEC != 0);
+    }
+
+    // Signals and waits go through the pidfd, if there is one.
+    child.kill().unwrap();
+    let status = child.wait().unwrap();
+    assert_eq!(status.signal(), Some(libc::SIGKILL));
+    assert_eq!(child.try_wait().unwrap(), Some(status));
+
+    let mut child = Command::new("sh").arg("-c").arg("exit 3").create_pidfd(true).spawn().unwrap();
+    if pidfd_open_available {
+        child.pidfd().unwrap();
+    }
+    assert_eq!(child.wait().unwrap().code(), Some(3));
+    if pidfd_open_available {
+        // The pidfd can still be taken after waiting.
+        child.take_pidfd().unwrap();
+        assert!(child.pidfd().is_err());
+    }
+}
@@ -47,6 +47,10 @@ pub fn kill(&mut self) -> io::Result<()> {
         unsupported()
     }
 
+    pub fn send_signal(&self, _signal: i32) -> io::Result<()> {
+        unsupported()
+    }
+
     pub fn wait(&mut self) -> io::Result<ExitStatus> {
         unsupported()
     }
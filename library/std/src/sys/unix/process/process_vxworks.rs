@@ -138,6 +138,10 @@ pub fn id(&self) -> u32 {
     }
 
     pub fn kill(&mut self) -> io::Result<()> {
+        self.send_signal(libc::SIGKILL)
+    }
+
+    pub fn send_signal(&self, signal: i32) -> io::Result<()> {
         // If we've already waited on this process then the pid can be recycled
         // and used for another process, and we probably shouldn't be killing
         // random processes, so just return an error.
@@ -147,7 +151,7 @@ pub fn kill(&mut self) -> io::Result<()> {
                 &"invalid argument: can't kill an exited process",
             ))
         } else {
-            cvt(unsafe { libc::kill(self.pid, libc::SIGKILL) }).map(drop)
+            cvt(unsafe { libc::kill(self.pid, signal) }).map(drop)
         }
     }
 
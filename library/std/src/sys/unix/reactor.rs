@@ -0,0 +1,55 @@
+//! Registration of file descriptors with a `Reactor`.
+
+use crate::fmt;
+use crate::fs::File;
+use crate::io;
+use crate::net::{TcpListener, TcpStream, UdpSocket};
+use crate::os::unix::io::{AsFd, AsRawFd, BorrowedFd};
+use crate::sys_common::AsInner;
+
+#[derive(Clone, Copy)]
+#[unstable(feature = "async_io", issue = "none")]
+pub struct Source<'a> {
+    fd: BorrowedFd<'a>,
+}
+
+impl<'a> AsInner<BorrowedFd<'a>> for Source<'a> {
+    #[inline]
+    fn as_inner(&self) -> &BorrowedFd<'a> {
+        &self.fd
+    }
+}
+
+#[unstable(feature = "async_io", issue = "none")]
+impl fmt::Debug for Source<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Source").field("fd", &self.fd.as_raw_fd()).finish_non_exhaustive()
+    }
+}
+
+/// An I/O object that can be driven by a `Reactor`.
+pub trait AsSource {
+    /// Puts the object in nonblocking mode and borrows its file descriptor
+    /// for registration.
+    fn nonblocking_source(&self) -> io::Result<Source<'_>>;
+}
+
+impl AsSource for File {
+    fn nonblocking_source(&self) -> io::Result<Source<'_>> {
+        self.as_inner().fd().set_nonblocking(true)?;
+        Ok(Source { fd: self.as_fd() })
+    }
+}
+
+macro_rules! impl_as_source {
+    ($($t:ty),*) => {$(
+        impl AsSource for $t {
+            fn nonblocking_source(&self) -> io::Result<Source<'_>> {
+                self.set_nonblocking(true)?;
+                Ok(Source { fd: self.as_fd() })
+            }
+        }
+    )*};
+}
+
+impl_as_source!(TcpStream, TcpListener, UdpSocket);
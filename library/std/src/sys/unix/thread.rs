@@ -2,10 +2,14 @@
 use crate::ffi::CStr;
 use crate::io;
 use crate::mem;
+use crate::num::NonZeroUsize;
 use crate::ptr;
 use crate::sys::{os, stack_overflow};
 use crate::time::Duration;
 
+#[cfg(all(test, target_os = "linux"))]
+mod tests;
+
 #[cfg(not(any(target_os = "l4re", target_os = "vxworks", target_os = "espidf")))]
 pub const DEFAULT_MIN_STACK_SIZE: usize = 2 * 1024 * 1024;
 #[cfg(target_os = "l4re")]
@@ -222,6 +226,297 @@ fn drop(&mut self) {
     }
 }
 
+pub fn available_parallelism() -> io::Result<NonZeroUsize> {
+    cfg_if::cfg_if! {
+        if #[cfg(any(
+            target_os = "android",
+            target_os = "emscripten",
+            target_os = "fuchsia",
+            target_os = "ios",
+            target_os = "linux",
+            target_os = "macos",
+            target_os = "solaris",
+            target_os = "illumos",
+        ))] {
+            #[cfg(target_os = "linux")]
+            let quota = cgroups::quota().max(1);
+            #[cfg(not(target_os = "linux"))]
+            let quota = usize::MAX;
+
+            #[cfg(target_os = "linux")]
+            {
+                let mut set: libc::cpu_set_t = unsafe { mem::zeroed() };
+                // This fails if the kernel supports more CPUs than `cpu_set_t`
+                // can hold, in which case the online CPUs are counted instead.
+                if unsafe { libc::sched_getaffinity(0, mem::size_of::<libc::cpu_set_t>(), &mut set) }
+                    == 0
+                {
+                    let count = unsafe { libc::CPU_COUNT(&set) } as usize;
+                    // The affinity mask of a running thread can't be empty.
+                    if let Some(count) = NonZeroUsize::new(count.min(quota)) {
+                        return Ok(count);
+                    }
+                }
+            }
+
+            match unsafe { libc::sysconf(libc::_SC_NPROCESSORS_ONLN) } {
+                -1 => Err(io::Error::last_os_error()),
+                0 => Err(io::Error::new_const(io::ErrorKind::NotFound, &"The number of hardware threads is not known for the target platform")),
+                cpus => Ok(unsafe { NonZeroUsize::new_unchecked((cpus as usize).min(quota)) }),
+            }
+        } else if #[cfg(any(target_os = "freebsd", target_os = "dragonfly", target_os = "netbsd"))] {
+            use crate::ptr;
+
+            let mut cpus: libc::c_uint = 0;
+            let mut cpus_size = crate::mem::size_of_val(&cpus);
+
+            unsafe {
+                cpus = libc::sysconf(libc::_SC_NPROCESSORS_ONLN) as libc::c_uint;
+            }
+
+            // Fallback approach in case of errors or no hardware threads.
+            if cpus < 1 {
+                let mut mib = [libc::CTL_HW, libc::HW_NCPU, 0, 0];
+                let res = unsafe {
+                    libc::sysctl(
+                        mib.as_mut_ptr(),
+                        2,
+                        &mut cpus as *mut _ as *mut _,
+                        &mut cpus_size as *mut _ as *mut _,
+                        ptr::null_mut(),
+                        0,
+                    )
+                };
+
+                // Handle errors if any.
+                if res == -1 {
+                    return Err(io::Error::last_os_error());
+                } else if cpus == 0 {
+                    return Err(io::Error::new_const(io::ErrorKind::NotFound, &"The number of hardware threads is not known for the target platform"));
+                }
+            }
+            Ok(unsafe { NonZeroUsize::new_unchecked(cpus as usize) })
+        } else if #[cfg(target_os = "openbsd")] {
+            use crate::ptr;
+
+            let mut cpus: libc::c_uint = 0;
+            let mut cpus_size = crate::mem::size_of_val(&cpus);
+            let mut mib = [libc::CTL_HW, libc::HW_NCPU, 0, 0];
+
+            let res = unsafe {
+                libc::sysctl(
+                    mib.as_mut_ptr(),
+                    2,
+                    &mut cpus as *mut _ as *mut _,
+                    &mut cpus_size as *mut _ as *mut _,
+                    ptr::null_mut(),
+                    0,
+                )
+            };
+
+            // Handle errors if any.
+            if res == -1 {
+                return Err(io::Error::last_os_error());
+            } else if cpus == 0 {
+                return Err(io::Error::new_const(io::ErrorKind::NotFound, &"The number of hardware threads is not known for the target platform"));
+            }
+
+            Ok(unsafe { NonZeroUsize::new_unchecked(cpus as usize) })
+        } else {
+            // FIXME: implement on vxWorks, Redox, Haiku, l4re
+            Err(io::Error::new_const(io::ErrorKind::Unsupported, &"Getting the number of hardware threads is not supported on the target platform"))
+        }
+    }
+}
+
+/// Reading of the CPU quota of the cgroup of the current process.
+///
+/// The quota is read from the cgroup v2 hierarchy mounted at `/sys/fs/cgroup`,
+/// or from the cgroup v1 hierarchy with the `cpu` controller, wherever it's
+/// mounted.
+#[cfg(target_os = "linux")]
+mod cgroups {
+    use crate::ffi::OsString;
+    use crate::fs::{self, File};
+    use crate::io::{BufRead, BufReader};
+    use crate::os::unix::ffi::OsStringExt;
+    use crate::path::{Path, PathBuf};
+    use crate::str;
+
+    /// Returns the CPU quota of the current cgroup and its ancestors in CPUs,
+    /// rounded down, or `usize::MAX` if there's none or it can't be read.
+    pub(super) fn quota() -> usize {
+        let own_cgroup = fs::read("/proc/self/cgroup").ok().and_then(|c| parse_cgroup(&c));
+        match own_cgroup {
+            Some((path, true)) => quota_v1(&path),
+            Some((path, false)) => quota_v2(&path),
+            None => usize::MAX,
+        }
+    }
+
+    /// Finds the cgroup of the current process in the contents of
+    /// `/proc/self/cgroup`, relative to the root of its hierarchy, and whether
+    /// it's a v1 cgroup.
+    pub(super) fn parse_cgroup(contents: &[u8]) -> Option<(PathBuf, bool)> {
+        let mut found = None;
+        for line in contents.split(|&c| c == b'\n') {
+            // Each line is `<hierarchy ID>:<controllers>:<path>`, where the
+            // controllers are empty for the unified v2 hierarchy.
+            let mut fields = line.splitn(3, |&c| c == b':');
+            let (controllers, path) = match (fields.nth(1), fields.next()) {
+                (Some(controllers), Some(path)) => (controllers, path),
+                _ => continue,
+            };
+            let path = Path::new(str::from_utf8(path).ok()?.trim_start_matches('/'));
+            if controllers.is_empty() {
+                // A v1 hierarchy with the `cpu` controller takes precedence,
+                // as the controller is then not available in the v2 one.
+                if found.is_none() {
+                    found = Some((path.to_path_buf(), false));
+                }
+            } else if controllers.split(|&c| c == b',').any(|c| c == b"cpu") {
+                return Some((path.to_path_buf(), true));
+            }
+        }
+        found
+    }
+
+    /// Walks up the hierarchy from `group`, reading `cpu.max` in each cgroup.
+    fn quota_v2(group: &Path) -> usize {
+        let root = Path::new("/sys/fs/cgroup");
+        if !root.join("cgroup.controllers").exists() {
+            // Not a cgroup v2 mount.
+            return usize::MAX;
+        }
+        let mut quota = usize::MAX;
+        let mut dir = root.join(group);
+        while dir.starts_with(root) {
+            if let Some(cpus) =
+                fs::read_to_string(dir.join("cpu.max")).ok().and_then(|c| parse_cpu_max(&c))
+            {
+                quota = quota.min(cpus);
+            }
+            if !dir.pop() {
+                break;
+            }
+        }
+        quota
+    }
+
+    /// Parses the contents of a `cpu.max` file, which are the quota and period
+    /// in microseconds, or `max` for the quota if there's none, into CPUs.
+    pub(super) fn parse_cpu_max(contents: &str) -> Option<usize> {
+        let mut fields = contents.split_whitespace();
+        let limit = match fields.next()? {
+            "max" => return None,
+            limit => limit.parse::<usize>().ok()?,
+        };
+        let period = fields.next()?.parse::<usize>().ok()?;
+        limit.checked_div(period)
+    }
+
+    /// Walks up the hierarchy from `group`, reading `cpu.cfs_quota_us` and
+    /// `cpu.cfs_period_us` in each cgroup.
+    fn quota_v1(group: &Path) -> usize {
+        let (mount, group) = match find_v1_mount(group) {
+            Some(found) => found,
+            None => return usize::MAX,
+        };
+        let mut quota = usize::MAX;
+        let mut dir = mount.join(group);
+        while dir.starts_with(&mount) {
+            let limit = fs::read_to_string(dir.join("cpu.cfs_quota_us"));
+            let period = fs::read_to_string(dir.join("cpu.cfs_period_us"));
+            if let (Ok(limit), Ok(period)) = (limit, period) {
+                if let Some(cpus) = parse_cfs_quota(&limit, &period) {
+                    quota = quota.min(cpus);
+                }
+            }
+            if !dir.pop() {
+                break;
+            }
+        }
+        quota
+    }
+
+    /// Parses the contents of the `cpu.cfs_quota_us` and `cpu.cfs_period_us`
+    /// files, in microseconds, into CPUs. The quota is -1 if there's none.
+    pub(super) fn parse_cfs_quota(limit: &str, period: &str) -> Option<usize> {
+        let limit = limit.trim().parse::<isize>().ok()?;
+        let period = period.trim().parse::<usize>().ok()?;
+        if limit < 0 {
+            return None;
+        }
+        (limit as usize).checked_div(period)
+    }
+
+    /// Finds where the v1 hierarchy with the `cpu` controller is mounted, and
+    /// the path of `group` under it.
+    ///
+    /// The usual mount points are tried first, as scanning all of them may be
+    /// slow. In containers, only a subtree of the hierarchy is usually mounted,
+    /// which `/proc/self/mountinfo` tells about.
+    fn find_v1_mount(group: &Path) -> Option<(PathBuf, PathBuf)> {
+        for mount in &["/sys/fs/cgroup/cpu", "/sys/fs/cgroup/cpu,cpuacct"] {
+            let mount = Path::new(mount);
+            if mount.join(group).exists() {
+                return Some((mount.to_path_buf(), group.to_path_buf()));
+            }
+        }
+
+        let reader = BufReader::new(File::open("/proc/self/mountinfo").ok()?);
+        for line in reader.lines() {
+            if let Some(found) = parse_mountinfo_line(&line.ok()?, group) {
+                return Some(found);
+            }
+        }
+        None
+    }
+
+    /// Parses a line of `/proc/self/mountinfo`. If it's a mount of the v1
+    /// hierarchy with the `cpu` controller, and the mounted subtree contains
+    /// `group`, returns the mount point and the path of `group` under it.
+    pub(super) fn parse_mountinfo_line(line: &str, group: &Path) -> Option<(PathBuf, PathBuf)> {
+        // The fields are the mount ID, the parent ID, `major:minor`, the
+        // mounted subtree, the mount point, the mount options, optional
+        // fields, a `-` separator, the filesystem type, the source and the
+        // superblock options.
+        let mut fields = line.split(' ');
+        let subtree = fields.nth(3)?;
+        let mount = fields.next()?;
+        let mut rest = fields.skip_while(|&field| field != "-").skip(1);
+        let fs_type = rest.next()?;
+        let options = rest.nth(1)?;
+        if fs_type != "cgroup" || !options.split(',').any(|option| option == "cpu") {
+            return None;
+        }
+        let subtree = unescape_mountinfo(subtree);
+        let group = group.strip_prefix(subtree.strip_prefix("/").ok()?).ok()?;
+        Some((unescape_mountinfo(mount), group.to_path_buf()))
+    }
+
+    /// Undoes the escaping of paths in `/proc/self/mountinfo`, where spaces,
+    /// tabs, newlines and backslashes are written as `\` and three octal digits.
+    fn unescape_mountinfo(field: &str) -> PathBuf {
+        let mut path = Vec::with_capacity(field.len());
+        let mut rest = field.as_bytes();
+        loop {
+            rest = match rest {
+                [b'\\', a @ b'0'..=b'3', b @ b'0'..=b'7', c @ b'0'..=b'7', rest @ ..] => {
+                    path.push((a - b'0') << 6 | (b - b'0') << 3 | (c - b'0'));
+                    rest
+                }
+                [byte, rest @ ..] => {
+                    path.push(*byte);
+                    rest
+                }
+                [] => break,
+            };
+        }
+        PathBuf::from(OsString::from_vec(path))
+    }
+}
+
 #[cfg(all(
     not(target_os = "linux"),
     not(target_os = "freebsd"),
@@ -0,0 +1,71 @@
+use super::cgroups::{parse_cfs_quota, parse_cgroup, parse_cpu_max, parse_mountinfo_line};
+use crate::path::{Path, PathBuf};
+
+#[test]
+fn cpu_max() {
+    assert_eq!(parse_cpu_max("max 100000\n"), None);
+    assert_eq!(parse_cpu_max("200000 100000\n"), Some(2));
+    assert_eq!(parse_cpu_max("150000 100000\n"), Some(1));
+    assert_eq!(parse_cpu_max("50000 100000\n"), Some(0));
+    assert_eq!(parse_cpu_max("100000 0\n"), None);
+    assert_eq!(parse_cpu_max("100000\n"), None);
+}
+
+#[test]
+fn cfs_quota() {
+    assert_eq!(parse_cfs_quota("-1\n", "100000\n"), None);
+    assert_eq!(parse_cfs_quota("400000\n", "100000\n"), Some(4));
+    assert_eq!(parse_cfs_quota("250000\n", "100000\n"), Some(2));
+    assert_eq!(parse_cfs_quota("100000\n", "0\n"), None);
+    assert_eq!(parse_cfs_quota("\n", "100000\n"), None);
+}
+
+#[test]
+fn cgroup() {
+    let v2 = b"0::/user.slice/session-1.scope\n";
+    assert_eq!(parse_cgroup(v2), Some((PathBuf::from("user.slice/session-1.scope"), false)));
+
+    let v1 = b"4:memory:/docker/abc\n3:cpu,cpuacct:/docker/abc\n0::/\n";
+    assert_eq!(parse_cgroup(v1), Some((PathBuf::from("docker/abc"), true)));
+
+    // Without the `cpu` controller in a v1 hierarchy, the v2 one is used.
+    let hybrid = b"4:memory:/docker/abc\n0::/init.scope\n";
+    assert_eq!(parse_cgroup(hybrid), Some((PathBuf::from("init.scope"), false)));
+
+    // The `cpu` controller is nowhere.
+    assert_eq!(parse_cgroup(b"4:memory:/docker/abc\n2:cpuset:/docker/abc\n"), None);
+    assert_eq!(parse_cgroup(b""), None);
+}
+
+#[test]
+fn mountinfo() {
+    let group = Path::new("docker/abc/def");
+
+    let line = "35 25 0:30 / /sys/fs/cgroup/cpu,cpuacct rw,nosuid shared:16 - cgroup cgroup \
+                rw,cpu,cpuacct";
+    let found = (PathBuf::from("/sys/fs/cgroup/cpu,cpuacct"), PathBuf::from("docker/abc/def"));
+    assert_eq!(parse_mountinfo_line(line, group), Some(found));
+
+    // Only a subtree of the hierarchy is mounted, as in a container.
+    let line = "1 0 0:30 /docker/abc /sys/fs/cgroup/cpu ro - cgroup cgroup ro,cpu";
+    let found = (PathBuf::from("/sys/fs/cgroup/cpu"), PathBuf::from("def"));
+    assert_eq!(parse_mountinfo_line(line, group), Some(found));
+
+    // The mounted subtree doesn't contain the cgroup.
+    let line = "1 0 0:30 /docker/xyz /sys/fs/cgroup/cpu ro - cgroup cgroup ro,cpu";
+    assert_eq!(parse_mountinfo_line(line, group), None);
+
+    // A mount of the hierarchy without the `cpu` controller.
+    let line = "1 0 0:31 / /sys/fs/cgroup/memory rw - cgroup cgroup rw,memory";
+    assert_eq!(parse_mountinfo_line(line, group), None);
+
+    // Not a cgroup v1 mount.
+    let line = "1 0 0:26 / /sys/fs/cgroup rw - cgroup2 cgroup2 rw,nsdelegate";
+    assert_eq!(parse_mountinfo_line(line, group), None);
+
+    // Spaces, tabs, newlines and backslashes are escaped in paths.
+    let group = Path::new("my group/a\\b/c");
+    let line = "1 0 0:30 /my\\040group/a\\134b /mnt/cpu\\011cgroup\\012 rw - cgroup cgroup rw,cpu";
+    let found = (PathBuf::from("/mnt/cpu\tcgroup\n"), PathBuf::from("c"));
+    assert_eq!(parse_mountinfo_line(line, group), Some(found));
+}
@@ -10,9 +10,6 @@ pub const fn new() -> Condvar {
         Condvar {}
     }
 
-    #[inline]
-    pub unsafe fn init(&mut self) {}
-
     #[inline]
     pub unsafe fn notify_one(&self) {}
 
@@ -26,7 +23,4 @@ pub unsafe fn wait(&self, _mutex: &Mutex) {
     pub unsafe fn wait_timeout(&self, _mutex: &Mutex, _dur: Duration) -> bool {
         panic!("condvar wait not supported");
     }
-
-    #[inline]
-    pub unsafe fn destroy(&self) {}
 }
@@ -15,6 +15,7 @@
 pub mod path;
 pub mod pipe;
 pub mod process;
+pub mod reactor;
 pub mod rwlock;
 pub mod stdio;
 pub mod thread;
@@ -0,0 +1,32 @@
+//! Without file descriptors or sockets there is nothing a `Reactor` could
+//! monitor, so no `Source` can ever be created.
+
+use crate::fmt;
+use crate::fs::File;
+use crate::io;
+use crate::marker::PhantomData;
+use crate::net::{TcpListener, TcpStream, UdpSocket};
+use crate::sys::unsupported;
+
+#[derive(Clone, Copy)]
+#[unstable(feature = "async_io", issue = "none")]
+pub struct Source<'a>(!, PhantomData<&'a ()>);
+
+#[unstable(feature = "async_io", issue = "none")]
+impl fmt::Debug for Source<'_> {
+    fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0
+    }
+}
+
+/// An I/O object that can be driven by a `Reactor`.
+pub trait AsSource {
+    fn nonblocking_source(&self) -> io::Result<Source<'_>> {
+        unsupported()
+    }
+}
+
+impl AsSource for File {}
+impl AsSource for TcpStream {}
+impl AsSource for TcpListener {}
+impl AsSource for UdpSocket {}
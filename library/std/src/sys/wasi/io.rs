@@ -1,6 +1,7 @@
 #![deny(unsafe_op_in_unsafe_fn)]
 
 use crate::marker::PhantomData;
+use crate::os::wasi::io::AsRawFd;
 use crate::slice;
 
 #[derive(Copy, Clone)]
@@ -71,3 +72,15 @@ pub fn as_mut_slice(&mut self) -> &mut [u8] {
         unsafe { slice::from_raw_parts_mut(self.vec.buf as *mut u8, self.vec.buf_len) }
     }
 }
+
+pub fn is_terminal(fd: &impl AsRawFd) -> bool {
+    // Like `isatty` in wasi-libc, this considers a character device which
+    // can't be seeked to be a terminal.
+    match unsafe { wasi::fd_fdstat_get(fd.as_raw_fd()) } {
+        Ok(stat) => {
+            stat.fs_filetype == wasi::FILETYPE_CHARACTER_DEVICE
+                && stat.fs_rights_base & (wasi::RIGHTS_FD_SEEK | wasi::RIGHTS_FD_TELL) == 0
+        }
+        Err(_) => false,
+    }
+}
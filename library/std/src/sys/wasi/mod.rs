@@ -39,6 +39,8 @@
 pub mod pipe;
 #[path = "../unsupported/process.rs"]
 pub mod process;
+#[path = "../unsupported/reactor.rs"]
+pub mod reactor;
 #[path = "../unsupported/rwlock.rs"]
 pub mod rwlock;
 pub mod stdio;
@@ -3,7 +3,7 @@
 use super::fd::WasiFd;
 use crate::convert::TryFrom;
 use crate::fmt;
-use crate::io::{self, IoSlice, IoSliceMut};
+use crate::io::{self, IoSlice, IoSliceMut, ReadBuf};
 use crate::net::{Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr};
 use crate::sys::unsupported;
 use crate::sys_common::FromInner;
@@ -46,6 +46,10 @@ pub fn read(&self, _: &mut [u8]) -> io::Result<usize> {
         unsupported()
     }
 
+    pub fn read_buf(&self, _: &mut ReadBuf<'_>) -> io::Result<()> {
+        unsupported()
+    }
+
     pub fn read_vectored(&self, _: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         unsupported()
     }
@@ -90,6 +94,38 @@ pub fn nodelay(&self) -> io::Result<bool> {
         unsupported()
     }
 
+    pub fn set_linger(&self, _: Option<Duration>) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn linger(&self) -> io::Result<Option<Duration>> {
+        unsupported()
+    }
+
+    pub fn set_keepalive(&self, _: bool) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn keepalive(&self) -> io::Result<bool> {
+        unsupported()
+    }
+
+    pub fn set_recv_buffer_size(&self, _: usize) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        unsupported()
+    }
+
+    pub fn set_send_buffer_size(&self, _: usize) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        unsupported()
+    }
+
     pub fn set_ttl(&self, _: u32) -> io::Result<()> {
         unsupported()
     }
@@ -193,6 +229,11 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+#[path = "../unsupported/tcp_socket.rs"]
+mod tcp_socket;
+
+pub use self::tcp_socket::TcpSocket;
+
 pub struct UdpSocket {
     fd: WasiFd,
 }
@@ -290,6 +331,22 @@ pub fn leave_multicast_v6(&self, _: &Ipv6Addr, _: u32) -> io::Result<()> {
         unsupported()
     }
 
+    pub fn set_recv_buffer_size(&self, _: usize) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        unsupported()
+    }
+
+    pub fn set_send_buffer_size(&self, _: usize) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        unsupported()
+    }
+
     pub fn set_ttl(&self, _: u32) -> io::Result<()> {
         unsupported()
     }
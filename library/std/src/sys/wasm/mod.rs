@@ -36,6 +36,8 @@
 pub mod pipe;
 #[path = "../unsupported/process.rs"]
 pub mod process;
+#[path = "../unsupported/reactor.rs"]
+pub mod reactor;
 #[path = "../unsupported/stdio.rs"]
 pub mod stdio;
 #[path = "../unsupported/thread_local_dtor.rs"]
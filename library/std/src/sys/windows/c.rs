@@ -32,6 +32,7 @@
 pub type WORD = u16;
 pub type CHAR = c_char;
 pub type ULONG_PTR = usize;
+pub type DWORD_PTR = ULONG_PTR;
 pub type ULONG = c_ulong;
 pub type NTSTATUS = LONG;
 pub type ACCESS_MASK = DWORD;
@@ -46,6 +47,7 @@
 pub type LPPROCESS_INFORMATION = *mut PROCESS_INFORMATION;
 pub type LPSECURITY_ATTRIBUTES = *mut SECURITY_ATTRIBUTES;
 pub type LPSTARTUPINFO = *mut STARTUPINFO;
+pub type LPSYSTEM_INFO = *mut SYSTEM_INFO;
 pub type LPVOID = *mut c_void;
 pub type LPWCH = *mut WCHAR;
 pub type LPWIN32_FIND_DATAW = *mut WIN32_FIND_DATAW;
@@ -102,6 +104,9 @@
 pub const FILE_FLAG_BACKUP_SEMANTICS: DWORD = 0x02000000;
 pub const SECURITY_SQOS_PRESENT: DWORD = 0x00100000;
 
+pub const LOCKFILE_FAIL_IMMEDIATELY: DWORD = 0x00000001;
+pub const LOCKFILE_EXCLUSIVE_LOCK: DWORD = 0x00000002;
+
 pub const FIONBIO: c_ulong = 0x8004667e;
 
 #[repr(C)]
@@ -162,6 +167,10 @@ fn clone(&self) -> Self {
 pub const STD_OUTPUT_HANDLE: DWORD = -11i32 as DWORD;
 pub const STD_ERROR_HANDLE: DWORD = -12i32 as DWORD;
 
+pub const FILE_TYPE_PIPE: DWORD = 3;
+
+pub const MAX_PATH: usize = 260;
+
 pub const PROGRESS_CONTINUE: DWORD = 0;
 
 // List of Windows system error codes with descriptions:
@@ -174,6 +183,7 @@ fn clone(&self) -> Self {
 pub const ERROR_OUTOFMEMORY: DWORD = 14;
 pub const ERROR_NO_MORE_FILES: DWORD = 18;
 pub const ERROR_SHARING_VIOLATION: u32 = 32;
+pub const ERROR_LOCK_VIOLATION: DWORD = 33;
 pub const ERROR_HANDLE_EOF: DWORD = 38;
 pub const ERROR_FILE_EXISTS: DWORD = 80;
 pub const ERROR_INVALID_PARAMETER: DWORD = 87;
@@ -245,6 +255,11 @@ fn clone(&self) -> Self {
 pub const IPV6_V6ONLY: c_int = 27;
 pub const SO_ERROR: c_int = 0x1007;
 pub const SO_BROADCAST: c_int = 0x0020;
+pub const SO_REUSEADDR: c_int = 0x0004;
+pub const SO_KEEPALIVE: c_int = 0x0008;
+pub const SO_LINGER: c_int = 0x0080;
+pub const SO_SNDBUF: c_int = 0x1001;
+pub const SO_RCVBUF: c_int = 0x1002;
 pub const IP_MULTICAST_LOOP: c_int = 11;
 pub const IPV6_MULTICAST_LOOP: c_int = 11;
 pub const IP_MULTICAST_TTL: c_int = 10;
@@ -254,6 +269,13 @@ fn clone(&self) -> Self {
 pub const IPV6_DROP_MEMBERSHIP: c_int = 13;
 pub const MSG_PEEK: c_int = 0x2;
 
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct linger {
+    pub l_onoff: c_ushort,
+    pub l_linger: c_ushort,
+}
+
 #[repr(C)]
 pub struct ip_mreq {
     pub imr_multiaddr: in_addr,
@@ -532,6 +554,21 @@ pub struct FILETIME {
     pub dwHighDateTime: DWORD,
 }
 
+#[repr(C)]
+pub struct SYSTEM_INFO {
+    pub wProcessorArchitecture: WORD,
+    pub wReserved: WORD,
+    pub dwPageSize: DWORD,
+    pub lpMinimumApplicationAddress: LPVOID,
+    pub lpMaximumApplicationAddress: LPVOID,
+    pub dwActiveProcessorMask: DWORD_PTR,
+    pub dwNumberOfProcessors: DWORD,
+    pub dwProcessorType: DWORD,
+    pub dwAllocationGranularity: DWORD,
+    pub wProcessorLevel: WORD,
+    pub wProcessorRevision: WORD,
+}
+
 #[repr(C)]
 pub struct OVERLAPPED {
     pub Internal: *mut c_ulong,
@@ -778,15 +815,6 @@ pub fn BCryptGenRandom(
             dwFlags: ULONG,
         ) -> LONG;
     }
-    #[link(name = "kernel32")]
-    extern "system" {
-        pub fn GetFileInformationByHandleEx(
-            hFile: HANDLE,
-            fileInfoClass: FILE_INFO_BY_HANDLE_CLASS,
-            lpFileInformation: LPVOID,
-            dwBufferSize: DWORD,
-        ) -> BOOL;
-    }
 }
 }
 
@@ -809,6 +837,13 @@ pub fn GetFileInformationByHandleEx(
     pub fn GetCurrentProcess() -> HANDLE;
     pub fn GetCurrentThread() -> HANDLE;
     pub fn GetStdHandle(which: DWORD) -> HANDLE;
+    pub fn GetFileType(hFile: HANDLE) -> DWORD;
+    pub fn GetFileInformationByHandleEx(
+        hFile: HANDLE,
+        fileInfoClass: FILE_INFO_BY_HANDLE_CLASS,
+        lpFileInformation: LPVOID,
+        dwBufferSize: DWORD,
+    ) -> BOOL;
     pub fn ExitProcess(uExitCode: c_uint) -> !;
     pub fn DeviceIoControl(
         hDevice: HANDLE,
@@ -914,6 +949,21 @@ pub fn SetFilePointerEx(
         dwMoveMethod: DWORD,
     ) -> BOOL;
     pub fn FlushFileBuffers(hFile: HANDLE) -> BOOL;
+    pub fn LockFileEx(
+        hFile: HANDLE,
+        dwFlags: DWORD,
+        dwReserved: DWORD,
+        nNumberOfBytesToLockLow: DWORD,
+        nNumberOfBytesToLockHigh: DWORD,
+        lpOverlapped: LPOVERLAPPED,
+    ) -> BOOL;
+    pub fn UnlockFile(
+        hFile: HANDLE,
+        dwFileOffsetLow: DWORD,
+        dwFileOffsetHigh: DWORD,
+        nNumberOfBytesToUnlockLow: DWORD,
+        nNumberOfBytesToUnlockHigh: DWORD,
+    ) -> BOOL;
     pub fn CreateFileW(
         lpFileName: LPCWSTR,
         dwDesiredAccess: DWORD,
@@ -933,6 +983,7 @@ pub fn CreateFileW(
     pub fn GetModuleHandleW(lpModuleName: LPCWSTR) -> HMODULE;
 
     pub fn GetSystemTimeAsFileTime(lpSystemTimeAsFileTime: LPFILETIME);
+    pub fn GetSystemInfo(lpSystemInfo: LPSYSTEM_INFO);
 
     pub fn CreateEventW(
         lpEventAttributes: LPSECURITY_ATTRIBUTES,
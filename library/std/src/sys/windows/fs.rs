@@ -2,7 +2,7 @@
 
 use crate::ffi::OsString;
 use crate::fmt;
-use crate::io::{self, Error, IoSlice, IoSliceMut, SeekFrom};
+use crate::io::{self, Error, IoSlice, IoSliceMut, ReadBuf, SeekFrom};
 use crate::mem;
 use crate::path::{Path, PathBuf};
 use crate::ptr;
@@ -308,6 +308,54 @@ pub fn datasync(&self) -> io::Result<()> {
         self.fsync()
     }
 
+    pub fn lock(&self) -> io::Result<()> {
+        self.lock_file(c::LOCKFILE_EXCLUSIVE_LOCK).map(drop)
+    }
+
+    pub fn lock_shared(&self) -> io::Result<()> {
+        self.lock_file(0).map(drop)
+    }
+
+    pub fn try_lock(&self) -> io::Result<bool> {
+        self.lock_file(c::LOCKFILE_EXCLUSIVE_LOCK | c::LOCKFILE_FAIL_IMMEDIATELY)
+    }
+
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        self.lock_file(c::LOCKFILE_FAIL_IMMEDIATELY)
+    }
+
+    /// Locks the whole file, returning `false` if `LOCKFILE_FAIL_IMMEDIATELY`
+    /// is set and the lock is held by another handle.
+    fn lock_file(&self, flags: c::DWORD) -> io::Result<bool> {
+        // The range covers the whole file: locks may extend past its end.
+        let mut overlapped: c::OVERLAPPED = unsafe { mem::zeroed() };
+        let result = cvt(unsafe {
+            c::LockFileEx(
+                self.handle.raw(),
+                flags,
+                0,
+                c::DWORD::MAX,
+                c::DWORD::MAX,
+                &mut overlapped,
+            )
+        });
+        match result {
+            Ok(_) => Ok(true),
+            Err(ref e)
+                if flags & c::LOCKFILE_FAIL_IMMEDIATELY != 0
+                    && e.raw_os_error() == Some(c::ERROR_LOCK_VIOLATION as i32) =>
+            {
+                Ok(false)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        cvt(unsafe { c::UnlockFile(self.handle.raw(), 0, 0, c::DWORD::MAX, c::DWORD::MAX) })?;
+        Ok(())
+    }
+
     pub fn truncate(&self, size: u64) -> io::Result<()> {
         let mut info = c::FILE_END_OF_FILE_INFO { EndOfFile: size as c::LARGE_INTEGER };
         let size = mem::size_of_val(&info);
@@ -405,6 +453,10 @@ pub fn read(&self, buf: &mut [u8]) -> io::Result<usize> {
         self.handle.read(buf)
     }
 
+    pub fn read_buf(&self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        self.handle.read_buf(buf)
+    }
+
     pub fn read_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         self.handle.read_vectored(bufs)
     }
@@ -1,4 +1,6 @@
 use crate::marker::PhantomData;
+use crate::mem::size_of;
+use crate::os::windows::io::AsRawHandle;
 use crate::slice;
 use crate::sys::c;
 
@@ -78,3 +80,72 @@ pub fn as_mut_slice(&mut self) -> &mut [u8] {
         unsafe { slice::from_raw_parts_mut(self.vec.buf as *mut u8, self.vec.len as usize) }
     }
 }
+
+pub fn is_terminal(h: &impl AsRawHandle) -> bool {
+    unsafe { handle_is_console(h.as_raw_handle() as c::HANDLE) }
+}
+
+unsafe fn handle_is_console(handle: c::HANDLE) -> bool {
+    // A null handle means the process has no console.
+    if handle.is_null() {
+        return false;
+    }
+
+    #[cfg(not(target_vendor = "uwp"))]
+    {
+        let mut out = 0;
+        if c::GetConsoleMode(handle, &mut out) != 0 {
+            // False positives aren't possible. If we got a console then we
+            // definitely have a console.
+            return true;
+        }
+    }
+
+    // Otherwise, we fall back to an msys hack to see if we can detect the
+    // presence of a pty.
+    msys_tty_on(handle)
+}
+
+unsafe fn msys_tty_on(handle: c::HANDLE) -> bool {
+    // The ptys of msys and cygwin are named pipes.
+    if c::GetFileType(handle) != c::FILE_TYPE_PIPE {
+        return false;
+    }
+
+    /// Mirrors `FILE_NAME_INFO`, giving it a fixed length that we can stack
+    /// allocate.
+    #[repr(C)]
+    #[allow(non_snake_case)]
+    struct FILE_NAME_INFO {
+        FileNameLength: u32,
+        FileName: [u16; c::MAX_PATH],
+    }
+    let mut name_info = FILE_NAME_INFO { FileNameLength: 0, FileName: [0; c::MAX_PATH] };
+    // The buffer length is fixed, so the name is truncated if it's too long,
+    // which doesn't matter as only its start is checked.
+    let res = c::GetFileInformationByHandleEx(
+        handle,
+        c::FileNameInfo,
+        &mut name_info as *mut _ as c::LPVOID,
+        size_of::<FILE_NAME_INFO>() as c::DWORD,
+    );
+    if res == 0 {
+        return false;
+    }
+
+    // Use `get` because `FileNameLength` can be out of range.
+    let s = match name_info.FileName.get(..name_info.FileNameLength as usize / 2) {
+        None => return false,
+        Some(s) => s,
+    };
+    let name = String::from_utf16_lossy(s);
+    // Get the file name only.
+    let name = name.rsplit('\\').next().unwrap_or(&name);
+    // This checks whether 'pty' exists in the file name, which indicates that
+    // a pseudo-terminal is attached. To mitigate against false positives
+    // (e.g., an actual file name that contains 'pty'), we also require that
+    // the file name begins with either the strings 'msys-' or 'cygwin-'.
+    let is_msys = name.starts_with("msys-") || name.starts_with("cygwin-");
+    let is_pty = name.contains("-pty");
+    is_msys && is_pty
+}
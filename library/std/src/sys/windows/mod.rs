@@ -30,6 +30,7 @@
 pub mod pipe;
 pub mod process;
 pub mod rand;
+pub mod reactor;
 pub mod rwlock;
 pub mod thread;
 pub mod thread_local_dtor;
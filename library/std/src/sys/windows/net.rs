@@ -1,7 +1,7 @@
 #![unstable(issue = "none", feature = "windows_net")]
 
 use crate::cmp;
-use crate::io::{self, IoSlice, IoSliceMut, Read};
+use crate::io::{self, IoSlice, IoSliceMut, Read, ReadBuf};
 use crate::mem;
 use crate::net::{Shutdown, SocketAddr};
 use crate::ptr;
@@ -12,7 +12,7 @@
 use crate::sys_common::{AsInner, FromInner, IntoInner};
 use crate::time::Duration;
 
-use libc::{c_int, c_long, c_ulong, c_void};
+use libc::{c_int, c_long, c_ulong, c_ushort, c_void};
 
 pub type wrlen_t = i32;
 
@@ -230,20 +230,34 @@ pub fn duplicate(&self) -> io::Result<Socket> {
         Ok(socket)
     }
 
-    fn recv_with_flags(&self, buf: &mut [u8], flags: c_int) -> io::Result<usize> {
+    fn recv_with_flags(&self, buf: &mut ReadBuf<'_>, flags: c_int) -> io::Result<()> {
         // On unix when a socket is shut down all further reads return 0, so we
         // do the same on windows to map a shut down socket to returning EOF.
-        let len = cmp::min(buf.len(), i32::MAX as usize) as i32;
-        unsafe {
-            match c::recv(self.0, buf.as_mut_ptr() as *mut c_void, len, flags) {
-                -1 if c::WSAGetLastError() == c::WSAESHUTDOWN => Ok(0),
-                -1 => Err(last_error()),
-                n => Ok(n as usize),
+        let len = cmp::min(buf.remaining(), i32::MAX as usize) as i32;
+        let result =
+            unsafe { c::recv(self.0, buf.unfilled_mut().as_mut_ptr() as *mut c_void, len, flags) };
+        match result {
+            -1 if unsafe { c::WSAGetLastError() } == c::WSAESHUTDOWN => Ok(()),
+            -1 => Err(last_error()),
+            n => {
+                // SAFETY: `n` bytes were written to the unfilled portion of
+                // the buffer.
+                unsafe {
+                    buf.assume_init(n as usize);
+                }
+                buf.add_filled(n as usize);
+                Ok(())
             }
         }
     }
 
     pub fn read(&self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut buf = ReadBuf::new(buf);
+        self.recv_with_flags(&mut buf, 0)?;
+        Ok(buf.filled_len())
+    }
+
+    pub fn read_buf(&self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
         self.recv_with_flags(buf, 0)
     }
 
@@ -277,7 +291,9 @@ pub fn is_read_vectored(&self) -> bool {
     }
 
     pub fn peek(&self, buf: &mut [u8]) -> io::Result<usize> {
-        self.recv_with_flags(buf, c::MSG_PEEK)
+        let mut buf = ReadBuf::new(buf);
+        self.recv_with_flags(&mut buf, c::MSG_PEEK)?;
+        Ok(buf.filled_len())
     }
 
     fn recv_from_with_flags(
@@ -394,6 +410,22 @@ pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
         if r == 0 { Ok(()) } else { Err(io::Error::last_os_error()) }
     }
 
+    pub fn set_linger(&self, linger: Option<Duration>) -> io::Result<()> {
+        let secs = linger.unwrap_or_default().as_secs();
+        let linger = c::linger {
+            l_onoff: linger.is_some() as c_ushort,
+            l_linger: cmp::min(secs, c_ushort::MAX as u64) as c_ushort,
+        };
+
+        net::setsockopt(self, c::SOL_SOCKET, c::SO_LINGER, linger)
+    }
+
+    pub fn linger(&self) -> io::Result<Option<Duration>> {
+        let val: c::linger = net::getsockopt(self, c::SOL_SOCKET, c::SO_LINGER)?;
+
+        Ok((val.l_onoff != 0).then(|| Duration::from_secs(val.l_linger as u64)))
+    }
+
     pub fn set_nodelay(&self, nodelay: bool) -> io::Result<()> {
         net::setsockopt(self, c::IPPROTO_TCP, c::TCP_NODELAY, nodelay as c::BYTE)
     }
@@ -0,0 +1,62 @@
+//! Registration of sockets with a `Reactor`.
+
+use crate::fmt;
+use crate::fs::File;
+use crate::io;
+use crate::net::{TcpListener, TcpStream, UdpSocket};
+use crate::os::windows::io::{AsRawSocket, AsSocket, BorrowedSocket};
+use crate::sys_common::AsInner;
+
+#[derive(Clone, Copy)]
+#[unstable(feature = "async_io", issue = "none")]
+pub struct Source<'a> {
+    socket: BorrowedSocket<'a>,
+}
+
+impl<'a> AsInner<BorrowedSocket<'a>> for Source<'a> {
+    #[inline]
+    fn as_inner(&self) -> &BorrowedSocket<'a> {
+        &self.socket
+    }
+}
+
+#[unstable(feature = "async_io", issue = "none")]
+impl fmt::Debug for Source<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Source")
+            .field("socket", &self.socket.as_raw_socket())
+            .finish_non_exhaustive()
+    }
+}
+
+/// An I/O object that can be driven by a `Reactor`.
+pub trait AsSource {
+    /// Puts the object in nonblocking mode and borrows its socket for
+    /// registration.
+    fn nonblocking_source(&self) -> io::Result<Source<'_>>;
+}
+
+impl AsSource for File {
+    fn nonblocking_source(&self) -> io::Result<Source<'_>> {
+        // Readiness is only reported for sockets; file handles can only be
+        // used asynchronously through overlapped I/O, which `Reactor` can't
+        // express.
+        Err(io::Error::new_const(
+            io::ErrorKind::Unsupported,
+            &"files cannot be registered with a reactor on Windows",
+        ))
+    }
+}
+
+macro_rules! impl_as_source {
+    ($($t:ty),*) => {$(
+        impl AsSource for $t {
+            fn nonblocking_source(&self) -> io::Result<Source<'_>> {
+                self.set_nonblocking(true)?;
+                Ok(Source { socket: self.as_socket() })
+            }
+        }
+    )*};
+}
+
+impl_as_source!(TcpStream, TcpListener, UdpSocket);
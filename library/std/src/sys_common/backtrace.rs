@@ -20,7 +20,7 @@ pub unsafe fn lock() -> impl Drop {
 }
 
 /// Prints the current backtrace.
-pub fn print(w: &mut dyn Write, format: PrintFmt) -> io::Result<()> {
+pub fn print(w: &mut dyn Write, style: BacktraceStyle) -> io::Result<()> {
     // There are issues currently linking libbacktrace into tests, and in
     // general during libstd's own unit tests we're not testing this path. In
     // test mode immediately return here to optimize away any references to the
@@ -33,23 +33,24 @@ pub fn print(w: &mut dyn Write, format: PrintFmt) -> io::Result<()> {
     // Some platforms also requires it, like `SymFromAddr` on Windows.
     unsafe {
         let _lock = lock();
-        _print(w, format)
+        _print(w, style)
     }
 }
 
-unsafe fn _print(w: &mut dyn Write, format: PrintFmt) -> io::Result<()> {
+unsafe fn _print(w: &mut dyn Write, style: BacktraceStyle) -> io::Result<()> {
     struct DisplayBacktrace {
-        format: PrintFmt,
+        style: BacktraceStyle,
     }
     impl fmt::Display for DisplayBacktrace {
         fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
-            unsafe { _print_fmt(fmt, self.format) }
+            unsafe { _print_fmt(fmt, self.style) }
         }
     }
-    write!(w, "{}", DisplayBacktrace { format })
+    write!(w, "{}", DisplayBacktrace { style })
 }
 
-unsafe fn _print_fmt(fmt: &mut fmt::Formatter<'_>, print_fmt: PrintFmt) -> fmt::Result {
+unsafe fn _print_fmt(fmt: &mut fmt::Formatter<'_>, style: BacktraceStyle) -> fmt::Result {
+    let print_fmt = style.print_fmt();
     // Always 'fail' to get the cwd when running under Miri -
     // this allows Miri to display backtraces in isolation mode
     let cwd = if !cfg!(miri) { env::current_dir().ok() } else { None };
@@ -61,6 +62,7 @@ unsafe fn _print_fmt(fmt: &mut fmt::Formatter<'_>, print_fmt: PrintFmt) -> fmt::
     let mut bt_fmt = BacktraceFmt::new(fmt, print_fmt, &mut print_path);
     bt_fmt.add_context()?;
     let mut idx = 0;
+    let mut omitted = 0;
     let mut res = Ok(());
     // Start immediately if we're not using a short backtrace.
     let mut start = print_fmt != PrintFmt::Short;
@@ -87,6 +89,12 @@ unsafe fn _print_fmt(fmt: &mut fmt::Formatter<'_>, print_fmt: PrintFmt) -> fmt::
             }
 
             if start {
+                if style == BacktraceStyle::Compact
+                    && symbol.name().map_or(false, |name| is_runtime_symbol(&format!("{:#}", name)))
+                {
+                    omitted += 1;
+                    return;
+                }
                 res = bt_fmt.frame().symbol(frame, symbol);
             }
         });
@@ -104,16 +112,44 @@ unsafe fn _print_fmt(fmt: &mut fmt::Formatter<'_>, print_fmt: PrintFmt) -> fmt::
     });
     res?;
     bt_fmt.finish()?;
-    if print_fmt == PrintFmt::Short {
-        writeln!(
+    match style {
+        BacktraceStyle::Compact if omitted > 0 => writeln!(
+            fmt,
+            "note: {} frames of the standard library are omitted, \
+             run with `RUST_BACKTRACE=1` to show them or `RUST_BACKTRACE=full` \
+             for a verbose backtrace.",
+            omitted
+        )?,
+        BacktraceStyle::Compact | BacktraceStyle::Short => writeln!(
             fmt,
             "note: Some details are omitted, \
              run with `RUST_BACKTRACE=full` for a verbose backtrace."
-        )?;
+        )?,
+        BacktraceStyle::Full => {}
     }
     Ok(())
 }
 
+/// Returns whether the demangled symbol `name` is part of the Rust runtime,
+/// that is the standard library crates or the entry points of the panic
+/// machinery.
+///
+/// These are the frames `RUST_BACKTRACE=compact` omits.
+pub fn is_runtime_symbol(name: &str) -> bool {
+    const RUNTIME_CRATES: &[&str] = &["std", "core", "alloc", "panic_unwind", "panic_abort"];
+
+    if name.starts_with("__rust_") || name == "rust_begin_unwind" || name == "rust_panic" {
+        return true;
+    }
+
+    // Trait impls are demangled as `<Type as Trait>::method`, attribute them
+    // to the crate of the type.
+    let path = name.trim_start_matches('<');
+    RUNTIME_CRATES
+        .iter()
+        .any(|krate| path.strip_prefix(krate).map_or(false, |rest| rest.starts_with("::")))
+}
+
 /// Fixed frame used to clean the backtrace with `RUST_BACKTRACE=1`. Note that
 /// this is only inline(never) when backtraces in libstd are enabled, otherwise
 /// it's fine to optimize away.
@@ -146,8 +182,31 @@ pub fn __rust_end_short_backtrace<F, T>(f: F) -> T
     result
 }
 
+/// The formats backtraces are printed in when a thread panics.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum BacktraceStyle {
+    /// Like `Short`, but also omitting the frames of the standard library.
+    /// Selected by `RUST_BACKTRACE=compact`.
+    Compact,
+    /// Only the frames between `__rust_end_short_backtrace` and
+    /// `__rust_begin_short_backtrace`. Selected by any other value of
+    /// `RUST_BACKTRACE` than `0`, `full` or `compact`.
+    Short,
+    /// Every frame, with addresses. Selected by `RUST_BACKTRACE=full`.
+    Full,
+}
+
+impl BacktraceStyle {
+    fn print_fmt(self) -> PrintFmt {
+        match self {
+            BacktraceStyle::Compact | BacktraceStyle::Short => PrintFmt::Short,
+            BacktraceStyle::Full => PrintFmt::Full,
+        }
+    }
+}
+
 pub enum RustBacktrace {
-    Print(PrintFmt),
+    Print(BacktraceStyle),
     Disabled,
     RuntimeDisabled,
 }
@@ -165,15 +224,16 @@ pub fn rust_backtrace_env() -> RustBacktrace {
     // Setting environment variables for Fuchsia components isn't a standard
     // or easily supported workflow. For now, always display backtraces.
     if cfg!(target_os = "fuchsia") {
-        return RustBacktrace::Print(PrintFmt::Full);
+        return RustBacktrace::Print(BacktraceStyle::Full);
     }
 
     static ENABLED: atomic::AtomicIsize = atomic::AtomicIsize::new(0);
     match ENABLED.load(Ordering::SeqCst) {
         0 => {}
         1 => return RustBacktrace::RuntimeDisabled,
-        2 => return RustBacktrace::Print(PrintFmt::Short),
-        _ => return RustBacktrace::Print(PrintFmt::Full),
+        2 => return RustBacktrace::Print(BacktraceStyle::Short),
+        3 => return RustBacktrace::Print(BacktraceStyle::Full),
+        _ => return RustBacktrace::Print(BacktraceStyle::Compact),
     }
 
     let (format, cache) = env::var_os("RUST_BACKTRACE")
@@ -181,9 +241,11 @@ pub fn rust_backtrace_env() -> RustBacktrace {
             if &x == "0" {
                 (RustBacktrace::RuntimeDisabled, 1)
             } else if &x == "full" {
-                (RustBacktrace::Print(PrintFmt::Full), 3)
+                (RustBacktrace::Print(BacktraceStyle::Full), 3)
+            } else if &x == "compact" {
+                (RustBacktrace::Print(BacktraceStyle::Compact), 4)
             } else {
-                (RustBacktrace::Print(PrintFmt::Short), 2)
+                (RustBacktrace::Print(BacktraceStyle::Short), 2)
             }
         })
         .unwrap_or((RustBacktrace::RuntimeDisabled, 1));
@@ -191,33 +253,42 @@ pub fn rust_backtrace_env() -> RustBacktrace {
     format
 }
 
-/// Prints the filename of the backtrace frame.
+/// Converts the filename of a backtrace frame into a path.
 ///
-/// See also `output`.
-pub fn output_filename(
-    fmt: &mut fmt::Formatter<'_>,
-    bows: BytesOrWideString<'_>,
-    print_fmt: PrintFmt,
-    cwd: Option<&PathBuf>,
-) -> fmt::Result {
-    let file: Cow<'_, Path> = match bows {
+/// Filenames that can't be represented on the current platform are
+/// converted lossily.
+pub fn filename_to_path(bows: BytesOrWideString<'_>) -> Cow<'_, Path> {
+    match bows {
         #[cfg(unix)]
         BytesOrWideString::Bytes(bytes) => {
             use crate::os::unix::prelude::*;
             Path::new(crate::ffi::OsStr::from_bytes(bytes)).into()
         }
         #[cfg(not(unix))]
-        BytesOrWideString::Bytes(bytes) => {
-            Path::new(crate::str::from_utf8(bytes).unwrap_or("<unknown>")).into()
-        }
+        BytesOrWideString::Bytes(bytes) => match String::from_utf8_lossy(bytes) {
+            Cow::Borrowed(s) => Path::new(s).into(),
+            Cow::Owned(s) => Cow::Owned(s.into()),
+        },
         #[cfg(windows)]
         BytesOrWideString::Wide(wide) => {
             use crate::os::windows::prelude::*;
             Cow::Owned(crate::ffi::OsString::from_wide(wide).into())
         }
         #[cfg(not(windows))]
-        BytesOrWideString::Wide(_wide) => Path::new("<unknown>").into(),
-    };
+        BytesOrWideString::Wide(wide) => Cow::Owned(String::from_utf16_lossy(wide).into()),
+    }
+}
+
+/// Prints the filename of the backtrace frame.
+///
+/// See also `output`.
+pub fn output_filename(
+    fmt: &mut fmt::Formatter<'_>,
+    bows: BytesOrWideString<'_>,
+    print_fmt: PrintFmt,
+    cwd: Option<&PathBuf>,
+) -> fmt::Result {
+    let file = filename_to_path(bows);
     if print_fmt == PrintFmt::Short && file.is_absolute() {
         if let Some(cwd) = cwd {
             if let Ok(stripped) = file.strip_prefix(&cwd) {
@@ -5,7 +5,7 @@
 use crate::convert::{TryFrom, TryInto};
 use crate::ffi::CString;
 use crate::fmt;
-use crate::io::{self, Error, ErrorKind, IoSlice, IoSliceMut};
+use crate::io::{self, Error, ErrorKind, IoSlice, IoSliceMut, ReadBuf};
 use crate::mem;
 use crate::net::{Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr};
 use crate::ptr;
@@ -82,6 +82,16 @@ pub fn getsockopt<T: Copy>(sock: &Socket, opt: c_int, val: c_int) -> io::Result<
     }
 }
 
+fn set_buffer_size(sock: &Socket, opt: c_int, size: usize) -> io::Result<()> {
+    let size = cmp::min(size, c_int::MAX as usize) as c_int;
+    setsockopt(sock, c::SOL_SOCKET, opt, size)
+}
+
+fn buffer_size(sock: &Socket, opt: c_int) -> io::Result<usize> {
+    let raw: c_int = getsockopt(sock, c::SOL_SOCKET, opt)?;
+    Ok(raw as usize)
+}
+
 fn sockname<F>(f: F) -> io::Result<SocketAddr>
 where
     F: FnOnce(*mut c::sockaddr, *mut c::socklen_t) -> c_int,
@@ -261,6 +271,10 @@ pub fn read(&self, buf: &mut [u8]) -> io::Result<usize> {
         self.inner.read(buf)
     }
 
+    pub fn read_buf(&self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        self.inner.read_buf(buf)
+    }
+
     pub fn read_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         self.inner.read_vectored(bufs)
     }
@@ -311,6 +325,39 @@ pub fn nodelay(&self) -> io::Result<bool> {
         self.inner.nodelay()
     }
 
+    pub fn set_linger(&self, linger: Option<Duration>) -> io::Result<()> {
+        self.inner.set_linger(linger)
+    }
+
+    pub fn linger(&self) -> io::Result<Option<Duration>> {
+        self.inner.linger()
+    }
+
+    pub fn set_keepalive(&self, keepalive: bool) -> io::Result<()> {
+        setsockopt(&self.inner, c::SOL_SOCKET, c::SO_KEEPALIVE, keepalive as c_int)
+    }
+
+    pub fn keepalive(&self) -> io::Result<bool> {
+        let raw: c_int = getsockopt(&self.inner, c::SOL_SOCKET, c::SO_KEEPALIVE)?;
+        Ok(raw != 0)
+    }
+
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_buffer_size(&self.inner, c::SO_RCVBUF, size)
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        buffer_size(&self.inner, c::SO_RCVBUF)
+    }
+
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_buffer_size(&self.inner, c::SO_SNDBUF, size)
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        buffer_size(&self.inner, c::SO_SNDBUF)
+    }
+
     pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
         setsockopt(&self.inner, c::IPPROTO_IP, c::IP_TTL, ttl as c_int)
     }
@@ -457,6 +504,133 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+////////////////////////////////////////////////////////////////////////////////
+// TCP sockets
+////////////////////////////////////////////////////////////////////////////////
+
+pub struct TcpSocket {
+    inner: Socket,
+}
+
+impl TcpSocket {
+    pub fn new(addr: &SocketAddr) -> io::Result<TcpSocket> {
+        init();
+
+        let sock = Socket::new(addr, c::SOCK_STREAM)?;
+        Ok(TcpSocket { inner: sock })
+    }
+
+    pub fn socket(&self) -> &Socket {
+        &self.inner
+    }
+
+    pub fn into_socket(self) -> Socket {
+        self.inner
+    }
+
+    pub fn bind(&self, addr: &SocketAddr) -> io::Result<()> {
+        let (addrp, len) = addr.into_inner();
+        cvt(unsafe { c::bind(*self.inner.as_inner(), addrp, len as _) }).map(drop)
+    }
+
+    pub fn connect(self, addr: &SocketAddr) -> io::Result<TcpStream> {
+        let (addrp, len) = addr.into_inner();
+        cvt_r(|| unsafe { c::connect(*self.inner.as_inner(), addrp, len) })?;
+        Ok(TcpStream { inner: self.inner })
+    }
+
+    pub fn connect_timeout(self, addr: &SocketAddr, timeout: Duration) -> io::Result<TcpStream> {
+        self.inner.connect_timeout(addr, timeout)?;
+        Ok(TcpStream { inner: self.inner })
+    }
+
+    pub fn listen(self, backlog: u32) -> io::Result<TcpListener> {
+        let backlog = cmp::min(backlog, c_int::MAX as u32) as c_int;
+        cvt(unsafe { c::listen(*self.inner.as_inner(), backlog) })?;
+        Ok(TcpListener { inner: self.inner })
+    }
+
+    pub fn socket_addr(&self) -> io::Result<SocketAddr> {
+        sockname(|buf, len| unsafe { c::getsockname(*self.inner.as_inner(), buf, len) })
+    }
+
+    pub fn set_reuse_address(&self, reuse: bool) -> io::Result<()> {
+        setsockopt(&self.inner, c::SOL_SOCKET, c::SO_REUSEADDR, reuse as c_int)
+    }
+
+    pub fn reuse_address(&self) -> io::Result<bool> {
+        let raw: c_int = getsockopt(&self.inner, c::SOL_SOCKET, c::SO_REUSEADDR)?;
+        Ok(raw != 0)
+    }
+
+    pub fn set_only_v6(&self, only_v6: bool) -> io::Result<()> {
+        setsockopt(&self.inner, c::IPPROTO_IPV6, c::IPV6_V6ONLY, only_v6 as c_int)
+    }
+
+    pub fn only_v6(&self) -> io::Result<bool> {
+        let raw: c_int = getsockopt(&self.inner, c::IPPROTO_IPV6, c::IPV6_V6ONLY)?;
+        Ok(raw != 0)
+    }
+
+    pub fn set_nodelay(&self, nodelay: bool) -> io::Result<()> {
+        self.inner.set_nodelay(nodelay)
+    }
+
+    pub fn nodelay(&self) -> io::Result<bool> {
+        self.inner.nodelay()
+    }
+
+    pub fn set_linger(&self, linger: Option<Duration>) -> io::Result<()> {
+        self.inner.set_linger(linger)
+    }
+
+    pub fn linger(&self) -> io::Result<Option<Duration>> {
+        self.inner.linger()
+    }
+
+    pub fn set_keepalive(&self, keepalive: bool) -> io::Result<()> {
+        setsockopt(&self.inner, c::SOL_SOCKET, c::SO_KEEPALIVE, keepalive as c_int)
+    }
+
+    pub fn keepalive(&self) -> io::Result<bool> {
+        let raw: c_int = getsockopt(&self.inner, c::SOL_SOCKET, c::SO_KEEPALIVE)?;
+        Ok(raw != 0)
+    }
+
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_buffer_size(&self.inner, c::SO_RCVBUF, size)
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        buffer_size(&self.inner, c::SO_RCVBUF)
+    }
+
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_buffer_size(&self.inner, c::SO_SNDBUF, size)
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        buffer_size(&self.inner, c::SO_SNDBUF)
+    }
+
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        self.inner.take_error()
+    }
+}
+
+impl fmt::Debug for TcpSocket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut res = f.debug_struct("TcpSocket");
+
+        if let Ok(addr) = self.socket_addr() {
+            res.field("addr", &addr);
+        }
+
+        let name = if cfg!(windows) { "socket" } else { "fd" };
+        res.field(name, &self.inner.as_inner()).finish()
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // UDP
 ////////////////////////////////////////////////////////////////////////////////
@@ -615,6 +789,22 @@ pub fn leave_multicast_v6(&self, multiaddr: &Ipv6Addr, interface: u32) -> io::Re
         setsockopt(&self.inner, c::IPPROTO_IPV6, IPV6_DROP_MEMBERSHIP, mreq)
     }
 
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_buffer_size(&self.inner, c::SO_RCVBUF, size)
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        buffer_size(&self.inner, c::SO_RCVBUF)
+    }
+
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_buffer_size(&self.inner, c::SO_SNDBUF, size)
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        buffer_size(&self.inner, c::SO_SNDBUF)
+    }
+
     pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
         setsockopt(&self.inner, c::IPPROTO_IP, c::IP_TTL, ttl as c_int)
     }
@@ -61,20 +61,21 @@ fn drop(&mut self) {
 
 /// An OS-based reader-writer lock.
 ///
-/// This rwlock does *not* have a const constructor, cleans up its resources in
-/// its `Drop` implementation and may safely be moved (when not borrowed).
+/// This rwlock has a const constructor, cleans up its resources when dropped
+/// and may safely be moved (when not borrowed).
 ///
 /// This rwlock does not implement poisoning.
 ///
-/// This is either a wrapper around `Box<imp::RWLock>` or `imp::RWLock`,
+/// This is either a wrapper around `LazyBox<imp::RWLock>` or `imp::RWLock`,
 /// depending on the platform. It is boxed on platforms where `imp::RWLock` may
 /// not be moved.
 pub struct MovableRWLock(imp::MovableRWLock);
 
 impl MovableRWLock {
     /// Creates a new reader-writer lock for use.
-    pub fn new() -> Self {
-        Self(imp::MovableRWLock::from(imp::RWLock::new()))
+    #[inline]
+    pub const fn new() -> Self {
+        Self(imp::MovableRWLock::new())
     }
 
     /// Acquires shared access to the underlying lock, blocking the current
@@ -126,9 +127,3 @@ pub unsafe fn write_unlock(&self) {
         self.0.write_unlock()
     }
 }
-
-impl Drop for MovableRWLock {
-    fn drop(&mut self) {
-        unsafe { self.0.destroy() };
-    }
-}
@@ -1,7 +1,9 @@
 use super::Builder;
 use crate::any::Any;
 use crate::mem;
+use crate::panic;
 use crate::result;
+use crate::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use crate::sync::mpsc::{channel, Sender};
 use crate::thread::{self, ThreadId};
 use crate::time::Duration;
@@ -258,5 +260,67 @@ fn test_thread_id_not_equal() {
     assert!(thread::current().id() != spawned_id);
 }
 
+#[test]
+fn test_scoped_threads_borrow() {
+    let mut data = vec![1, 2, 3, 4];
+    let mut sum = 0;
+    thread::scope(|s| {
+        let (left, right) = data.split_at_mut(2);
+        s.spawn(move || left.iter_mut().for_each(|x| *x *= 10));
+        s.spawn(move || right.iter_mut().for_each(|x| *x *= 100));
+        s.spawn(|| sum = 5);
+    });
+    assert_eq!(data, [10, 20, 300, 400]);
+    assert_eq!(sum, 5);
+}
+
+#[test]
+fn test_scoped_threads_join() {
+    let a = vec![1, 2, 3];
+    let (total, panicked) = thread::scope(|s| {
+        let total = s.spawn(|| a.iter().sum::<i32>());
+        let panicked = s.spawn(|| panic!("oops"));
+        (total.join().unwrap(), panicked.join().is_err())
+    });
+    assert_eq!(total, 6);
+    assert!(panicked);
+}
+
+#[test]
+fn test_scoped_threads_nested() {
+    let counter = &AtomicUsize::new(0);
+    thread::scope(|s| {
+        s.spawn(move || {
+            s.spawn(move || counter.fetch_add(1, Ordering::Relaxed));
+            counter.fetch_add(1, Ordering::Relaxed);
+        });
+        Builder::new()
+            .name("scoped".to_string())
+            .spawn_scoped(s, || {
+                assert_eq!(thread::current().name(), Some("scoped"));
+                counter.fetch_add(1, Ordering::Relaxed);
+            })
+            .unwrap();
+    });
+    assert_eq!(counter.load(Ordering::Relaxed), 3);
+}
+
+#[test]
+fn test_scoped_threads_unjoined_panic() {
+    let finished = AtomicBool::new(false);
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        thread::scope(|s| {
+            s.spawn(|| panic!("oops"));
+            s.spawn(|| {
+                thread::sleep(Duration::from_millis(10));
+                finished.store(true, Ordering::Relaxed);
+            });
+        });
+    }));
+    assert!(result.is_err());
+    // The scope waits for every thread before propagating the panic.
+    assert!(finished.load(Ordering::Relaxed));
+}
+
 // NOTE: the corresponding test for stderr is in ui/thread-stderr, due
 // to the test harness apparently interfering with stderr configuration.
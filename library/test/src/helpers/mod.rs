@@ -3,5 +3,4 @@
 
 pub mod concurrency;
 pub mod exit_code;
-pub mod isatty;
 pub mod metrics;
@@ -2092,6 +2092,7 @@ fn run(self, builder: &Builder<'_>) {
         let compiler = builder.compiler_for(builder.top_stage, target, target);
         builder.ensure(compile::Rustc { compiler, target });
 
+        // The `schemars` feature is needed to check that the checked in JSON schema is up to date.
         let mut cargo = tool::prepare_tool_cargo(
             builder,
             compiler,
@@ -2100,7 +2101,7 @@ fn run(self, builder: &Builder<'_>) {
             test_kind.subcommand(),
             "src/rustdoc-json-types",
             SourceType::InTree,
-            &[],
+            &["schemars".to_string()],
         );
         if test_kind.subcommand() == "test" && !builder.fail_fast {
             cargo.arg("--no-fail-fast");
@@ -23,6 +23,7 @@
 use crate::html::markdown::IdMap;
 use crate::html::render::StylePath;
 use crate::html::static_files;
+use crate::json::OLDEST_SUPPORTED_FORMAT_VERSION;
 use crate::opts;
 use crate::passes::{self, Condition, DefaultPassOption};
 use crate::theme;
@@ -31,6 +32,8 @@
 crate enum OutputFormat {
     Json,
     Html,
+    Markdown,
+    Zip,
 }
 
 impl Default for OutputFormat {
@@ -52,6 +55,8 @@ fn try_from(value: &str) -> Result<Self, Self::Error> {
         match value {
             "json" => Ok(OutputFormat::Json),
             "html" => Ok(OutputFormat::Html),
+            "markdown" => Ok(OutputFormat::Markdown),
+            "zip" => Ok(OutputFormat::Zip),
             _ => Err(format!("unknown output format `{}`", value)),
         }
     }
@@ -121,6 +126,9 @@ fn try_from(value: &str) -> Result<Self, Self::Error> {
     crate enable_per_target_ignores: bool,
     /// Do not run doctests, compile them if should_test is active.
     crate no_run: bool,
+    /// Whether to compile compatible doctests into a single binary instead of one binary per
+    /// doctest.
+    crate merge_doctests: bool,
 
     /// The path to a rustc-like binary to build tests with. If not set, we
     /// default to loading from `$sysroot/bin/rustc`.
@@ -197,6 +205,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             .field("runtool", &self.runtool)
             .field("runtool_args", &self.runtool_args)
             .field("enable-per-target-ignores", &self.enable_per_target_ignores)
+            .field("merge_doctests", &self.merge_doctests)
             .field("run_check", &self.run_check)
             .field("no_run", &self.no_run)
             .finish()
@@ -271,6 +280,8 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     crate generate_redirect_map: bool,
     /// Show the memory layout of types in the docs.
     crate show_type_layout: bool,
+    /// The version of the JSON format to emit, if not the latest one.
+    crate json_format_version: Option<u32>,
     crate unstable_features: rustc_feature::UnstableFeatures,
     crate emit: Vec<EmitType>,
 }
@@ -582,9 +593,10 @@ fn println_condition(condition: Condition) {
             Some(s) => match OutputFormat::try_from(s.as_str()) {
                 Ok(out_fmt) => {
                     if !out_fmt.is_json() && show_coverage {
-                        diag.struct_err(
-                            "html output format isn't supported for the --show-coverage option",
-                        )
+                        diag.struct_err(&format!(
+                            "{} output format isn't supported for the --show-coverage option",
+                            s
+                        ))
                         .emit();
                         return Err(1);
                     }
@@ -611,6 +623,12 @@ fn println_condition(condition: Condition) {
         let crate_version = matches.opt_str("crate-version");
         let enable_index_page = matches.opt_present("enable-index-page") || index_page.is_some();
         let static_root_path = matches.opt_str("static-root-path");
+        if static_root_path.is_some() && output_format == OutputFormat::Zip {
+            diag.struct_err("--static-root-path can't be used with --output-format zip")
+                .note("the archive needs to contain the static files to be usable offline")
+                .emit();
+            return Err(1);
+        }
         let generate_search_filter = !matches.opt_present("disable-per-crate-search");
         let test_run_directory = matches.opt_str("test-run-directory").map(PathBuf::from);
         let persist_doctests = matches.opt_str("persist-doctests").map(PathBuf::from);
@@ -622,12 +640,38 @@ fn println_condition(condition: Condition) {
         let runtool = matches.opt_str("runtool");
         let runtool_args = matches.opt_strs("runtool-arg");
         let enable_per_target_ignores = matches.opt_present("enable-per-target-ignores");
+        let merge_doctests = matches.opt_present("merge-doctests");
         let document_private = matches.opt_present("document-private-items");
         let document_hidden = matches.opt_present("document-hidden-items");
         let run_check = matches.opt_present("check");
         let generate_redirect_map = matches.opt_present("generate-redirect-map");
         let show_type_layout = matches.opt_present("show-type-layout");
 
+        let json_format_version = match matches.opt_str("json-format-version") {
+            Some(s) => {
+                let supported =
+                    OLDEST_SUPPORTED_FORMAT_VERSION..=rustdoc_json_types::FORMAT_VERSION;
+                if !output_format.is_json() {
+                    diag.struct_err("--json-format-version requires --output-format json").emit();
+                    return Err(1);
+                }
+                match s.parse::<u32>() {
+                    Ok(version) if supported.contains(&version) => Some(version),
+                    _ => {
+                        diag.struct_err(&format!(
+                            "unsupported JSON format version `{}`, expected one from {} to {}",
+                            s,
+                            supported.start(),
+                            supported.end(),
+                        ))
+                        .emit();
+                        return Err(1);
+                    }
+                }
+            }
+            None => None,
+        };
+
         let (lint_opts, describe_lints, lint_cap, _) =
             get_cmd_lint_options(matches, error_format, &debugging_opts);
 
@@ -662,6 +706,7 @@ fn println_condition(condition: Condition) {
             runtool,
             runtool_args,
             enable_per_target_ignores,
+            merge_doctests,
             test_builder,
             run_check,
             no_run,
@@ -688,6 +733,7 @@ fn println_condition(condition: Condition) {
                 document_hidden,
                 generate_redirect_map,
                 show_type_layout,
+                json_format_version,
                 unstable_features: rustc_feature::UnstableFeatures::from_environment(
                     crate_name.as_deref(),
                 ),
@@ -19,8 +19,9 @@
 
 use std::env;
 use std::io::{self, Write};
+use std::mem;
 use std::panic;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{self, Command, Stdio};
 use std::str;
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -32,6 +33,10 @@
 use crate::lint::init_lints;
 use crate::passes::span_of_attrs;
 
+mod merged;
+
+use self::merged::MergedDoctests;
+
 #[derive(Clone, Default)]
 crate struct TestOptions {
     /// Whether to disable the default `extern crate my_crate;` when creating doctests.
@@ -47,16 +52,18 @@
     let input = config::Input::File(options.input.clone());
 
     let invalid_codeblock_attributes_name = crate::lint::INVALID_CODEBLOCK_ATTRIBUTES.name;
+    let unnecessary_ignore_name = crate::lint::UNNECESSARY_IGNORE.name;
 
     // See core::create_config for what's going on here.
     let allowed_lints = vec![
         invalid_codeblock_attributes_name.to_owned(),
+        unnecessary_ignore_name.to_owned(),
         lint::builtin::UNKNOWN_LINTS.name.to_owned(),
         lint::builtin::RENAMED_AND_REMOVED_LINTS.name.to_owned(),
     ];
 
     let (lint_opts, lint_caps) = init_lints(allowed_lints, options.lint_opts.clone(), |lint| {
-        if lint.name == invalid_codeblock_attributes_name {
+        if lint.name == invalid_codeblock_attributes_name || lint.name == unnecessary_ignore_name {
             None
         } else {
             Some((lint.name_lower(), lint::Allow))
@@ -149,6 +156,7 @@
                         intravisit::walk_crate(this, krate);
                     },
                 );
+                collector.check_ignored_tests(tcx);
 
                 collector
             });
@@ -283,7 +291,7 @@ fn path(&self) -> &std::path::Path {
 // and cargo.
 // We could unify this struct the one in rustc but they have different
 // ownership semantics, so doing so would create wasteful allocations.
-#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
 struct UnusedExterns {
     /// Lint level of the unused_crate_dependencies lint
     lint_level: String,
@@ -291,6 +299,73 @@ struct UnusedExterns {
     unused_extern_names: Vec<String>,
 }
 
+/// Creates the `rustc` invocation shared by every doctest compilation: the crate type, output
+/// path and all the paths, cfgs and codegen flags passed to rustdoc.
+fn doctest_compiler(
+    options: &Options,
+    edition: Edition,
+    target: TargetTriple,
+    output_file: &Path,
+) -> Command {
+    let rustc_binary = options
+        .test_builder
+        .as_deref()
+        .unwrap_or_else(|| rustc_interface::util::rustc_path().expect("found rustc"));
+    let mut compiler = Command::new(&rustc_binary);
+    compiler.arg("--crate-type").arg("bin");
+    for cfg in &options.cfgs {
+        compiler.arg("--cfg").arg(&cfg);
+    }
+    if let Some(ref sysroot) = options.maybe_sysroot {
+        compiler.arg("--sysroot").arg(sysroot);
+    }
+    compiler.arg("--edition").arg(&edition.to_string());
+    compiler.arg("-o").arg(output_file);
+    for lib_str in &options.lib_strs {
+        compiler.arg("-L").arg(&lib_str);
+    }
+    for extern_str in &options.extern_strs {
+        compiler.arg("--extern").arg(&extern_str);
+    }
+    compiler.arg("-Ccodegen-units=1");
+    for codegen_options_str in &options.codegen_options_strs {
+        compiler.arg("-C").arg(&codegen_options_str);
+    }
+    for debugging_option_str in &options.debugging_opts_strs {
+        compiler.arg("-Z").arg(&debugging_option_str);
+    }
+    compiler.arg("--target").arg(match target {
+        TargetTriple::TargetTriple(s) => s,
+        TargetTriple::TargetPath(path) => {
+            path.to_str().expect("target path must be valid unicode").to_string()
+        }
+    });
+    compiler
+}
+
+/// Creates the command running a compiled doctest binary, going through the `--runtool` if one
+/// was given.
+fn doctest_runner(
+    runtool: Option<String>,
+    runtool_args: Vec<String>,
+    output_file: &Path,
+    options: &Options,
+) -> Command {
+    let mut cmd;
+
+    if let Some(tool) = runtool {
+        cmd = Command::new(tool);
+        cmd.args(runtool_args);
+        cmd.arg(output_file);
+    } else {
+        cmd = Command::new(output_file);
+    }
+    if let Some(ref run_directory) = options.test_run_directory {
+        cmd.current_dir(run_directory);
+    }
+    cmd
+}
+
 fn run_test(
     test: &str,
     cratename: &str,
@@ -316,22 +391,9 @@ fn run_test(
 
     let output_file = outdir.path().join("rust_out");
 
-    let rustc_binary = options
-        .test_builder
-        .as_deref()
-        .unwrap_or_else(|| rustc_interface::util::rustc_path().expect("found rustc"));
-    let mut compiler = Command::new(&rustc_binary);
-    compiler.arg("--crate-type").arg("bin");
-    for cfg in &options.cfgs {
-        compiler.arg("--cfg").arg(&cfg);
-    }
-    if let Some(sysroot) = options.maybe_sysroot {
-        compiler.arg("--sysroot").arg(sysroot);
-    }
-    compiler.arg("--edition").arg(&edition.to_string());
+    let mut compiler = doctest_compiler(&options, edition, target, &output_file);
     compiler.env("UNSTABLE_RUSTDOC_TEST_PATH", path);
     compiler.env("UNSTABLE_RUSTDOC_TEST_LINE", format!("{}", line as isize - line_offset as isize));
-    compiler.arg("-o").arg(&output_file);
     if as_test_harness {
         compiler.arg("--test");
     }
@@ -341,28 +403,9 @@ fn run_test(
         compiler.arg("-Z").arg("unstable-options");
         compiler.arg("-W").arg("unused_crate_dependencies");
     }
-    for lib_str in &options.lib_strs {
-        compiler.arg("-L").arg(&lib_str);
-    }
-    for extern_str in &options.extern_strs {
-        compiler.arg("--extern").arg(&extern_str);
-    }
-    compiler.arg("-Ccodegen-units=1");
-    for codegen_options_str in &options.codegen_options_strs {
-        compiler.arg("-C").arg(&codegen_options_str);
-    }
-    for debugging_option_str in &options.debugging_opts_strs {
-        compiler.arg("-Z").arg(&debugging_option_str);
-    }
     if no_run && !compile_fail {
         compiler.arg("--emit=metadata");
     }
-    compiler.arg("--target").arg(match target {
-        TargetTriple::TargetTriple(s) => s,
-        TargetTriple::TargetPath(path) => {
-            path.to_str().expect("target path must be valid unicode").to_string()
-        }
-    });
     if let ErrorOutputType::HumanReadable(kind) = options.error_format {
         let (short, color_config) = kind.unzip();
 
@@ -448,18 +491,7 @@ fn drop(&mut self) {
     }
 
     // Run the code!
-    let mut cmd;
-
-    if let Some(tool) = runtool {
-        cmd = Command::new(tool);
-        cmd.args(runtool_args);
-        cmd.arg(output_file);
-    } else {
-        cmd = Command::new(output_file);
-    }
-    if let Some(run_directory) = options.test_run_directory {
-        cmd.current_dir(run_directory);
-    }
+    let mut cmd = doctest_runner(runtool, runtool_args, &output_file, &options);
 
     match cmd.output() {
         Err(e) => return Err(TestFailure::ExecutionError(e)),
@@ -761,6 +793,25 @@ enum PartitionState {
     (before, after, crates)
 }
 
+/// Creates the directory a doctest binary is compiled into: a subdirectory of the
+/// `--persist-doctests` directory if one was given, or a temporary directory.
+fn make_outdir(options: &Options, name: &str) -> DirState {
+    if let Some(mut path) = options.persist_doctests.clone() {
+        path.push(name);
+
+        std::fs::create_dir_all(&path).expect("Couldn't create directory for doctest executables");
+
+        DirState::Perm(path)
+    } else {
+        DirState::Temp(
+            TempFileBuilder::new()
+                .prefix("rustdoctest")
+                .tempdir()
+                .expect("rustdoc needs a tempdir"),
+        )
+    }
+}
+
 crate trait Tester {
     fn add_test(&mut self, test: String, config: LangString, line: usize);
     fn get_line(&self) -> usize {
@@ -805,6 +856,23 @@ fn register_header(&mut self, _name: &str, _level: u32) {}
     visited_tests: FxHashMap<(String, usize), usize>,
     unused_extern_reports: Arc<Mutex<Vec<UnusedExterns>>>,
     compiling_test_count: AtomicUsize,
+    /// The binary compatible doctests are compiled into with `--merge-doctests`.
+    merged_doctests: Option<Arc<MergedDoctests>>,
+    /// The item whose documentation is being collected and the span of its doc comments, if the
+    /// `unnecessary_ignore` lint is enabled for it.
+    ignore_lint_target: Option<(HirId, Span)>,
+    /// The `ignore`d doctests to check with the `unnecessary_ignore` lint.
+    ignored_tests: Vec<IgnoredTest>,
+}
+
+/// An `ignore`d doctest, which is reported by the `unnecessary_ignore` lint if it compiles.
+struct IgnoredTest {
+    test: String,
+    name: String,
+    edition: Edition,
+    as_test_harness: bool,
+    hir_id: HirId,
+    span: Span,
 }
 
 impl Collector {
@@ -817,6 +885,17 @@ impl Collector {
         filename: Option<PathBuf>,
         enable_per_target_ignores: bool,
     ) -> Collector {
+        let merged_doctests = if options.merge_doctests {
+            let outdir = make_outdir(&options, "merged_doctests");
+            Some(Arc::new(MergedDoctests::new(
+                options.clone(),
+                cratename.clone(),
+                opts.clone(),
+                outdir,
+            )))
+        } else {
+            None
+        };
         Collector {
             tests: Vec::new(),
             names: Vec::new(),
@@ -831,6 +910,9 @@ impl Collector {
             visited_tests: FxHashMap::default(),
             unused_extern_reports: Default::default(),
             compiling_test_count: AtomicUsize::new(0),
+            merged_doctests,
+            ignore_lint_target: None,
+            ignored_tests: Vec::new(),
         }
     }
 
@@ -842,6 +924,65 @@ fn generate_name(&self, line: usize, filename: &FileName) -> String {
         format!("{} - {}(line {})", filename.prefer_local(), item_path, line)
     }
 
+    /// Reports the `ignore`d doctests which compile with the `unnecessary_ignore` lint.
+    ///
+    /// The doctests are only compiled, like `no_run` ones: running them could have side effects,
+    /// which is a valid reason to mark them as `ignore`.
+    fn check_ignored_tests(&mut self, tcx: TyCtxt<'_>) {
+        for ignored in mem::take(&mut self.ignored_tests) {
+            if !self.compiles(&ignored) {
+                continue;
+            }
+            tcx.struct_span_lint_hir(
+                crate::lint::UNNECESSARY_IGNORE,
+                ignored.hir_id,
+                ignored.span,
+                |lint| {
+                    lint.build(&format!("`ignore`d doctest compiles: {}", ignored.name))
+                        .help(
+                            "remove `ignore` to run it as a test, \
+                             or use `no_run` if it should only be compiled",
+                        )
+                        .emit()
+                },
+            );
+        }
+    }
+
+    fn compiles(&self, ignored: &IgnoredTest) -> bool {
+        let (test, _, _) = make_test(
+            &ignored.test,
+            Some(&self.cratename),
+            ignored.as_test_harness,
+            &self.opts,
+            ignored.edition,
+            None,
+        );
+        let outdir = make_outdir(&self.options, "ignored_doctest");
+        let output_file = outdir.path().join("rust_out");
+        let target = self.options.target.clone();
+        let mut compiler = doctest_compiler(&self.options, ignored.edition, target, &output_file);
+        if ignored.as_test_harness {
+            compiler.arg("--test");
+        }
+        compiler.arg("--emit=metadata");
+        compiler.arg("-");
+        compiler.stdin(Stdio::piped());
+        compiler.stdout(Stdio::null());
+        compiler.stderr(Stdio::null());
+
+        let mut child = match compiler.spawn() {
+            Ok(child) => child,
+            Err(e) => {
+                debug!("couldn't run rustc on an ignored doctest: {}", e);
+                return false;
+            }
+        };
+        let written =
+            child.stdin.take().map_or(false, |mut stdin| stdin.write_all(test.as_bytes()).is_ok());
+        child.wait().map_or(false, |status| written && status.success())
+    }
+
     crate fn set_position(&mut self, position: Span) {
         self.position = position;
     }
@@ -881,6 +1022,18 @@ fn add_test(&mut self, test: String, config: LangString, line: usize) {
         let target_str = target.to_string();
         let unused_externs = self.unused_extern_reports.clone();
         let no_run = config.no_run || options.no_run;
+        if let (Ignore::All, Some((hir_id, span))) = (&config.ignore, self.ignore_lint_target) {
+            if !config.compile_fail {
+                self.ignored_tests.push(IgnoredTest {
+                    test: test.clone(),
+                    name: name.clone(),
+                    edition,
+                    as_test_harness: config.test_harness,
+                    hir_id,
+                    span,
+                });
+            }
+        }
         if !config.compile_fail {
             self.compiling_test_count.fetch_add(1, Ordering::SeqCst);
         }
@@ -914,20 +1067,11 @@ fn add_test(&mut self, test: String, config: LangString, line: usize) {
                 self.visited_tests.entry((file.clone(), line)).and_modify(|v| *v += 1).or_insert(0)
             },
         );
-        let outdir = if let Some(mut path) = options.persist_doctests.clone() {
-            path.push(&test_id);
-
-            std::fs::create_dir_all(&path)
-                .expect("Couldn't create directory for doctest executables");
-
-            DirState::Perm(path)
-        } else {
-            DirState::Temp(
-                TempFileBuilder::new()
-                    .prefix("rustdoctest")
-                    .tempdir()
-                    .expect("rustdoc needs a tempdir"),
-            )
+        let merged = match self.merged_doctests {
+            Some(ref merged) if merged.accepts(&test, &config) => {
+                Some((merged.clone(), merged.add(&test, &test_id, &path, line)))
+            }
+            _ => None,
         };
 
         debug!("creating test {}: {}", name, test);
@@ -952,26 +1096,42 @@ fn add_test(&mut self, test: String, config: LangString, line: usize) {
                 let report_unused_externs = |uext| {
                     unused_externs.lock().unwrap().push(uext);
                 };
-                let res = run_test(
-                    &test,
-                    &cratename,
-                    line,
-                    options,
-                    config.should_panic,
-                    no_run,
-                    config.test_harness,
-                    runtool,
-                    runtool_args,
-                    target,
-                    config.compile_fail,
-                    config.error_codes,
-                    &opts,
-                    edition,
-                    outdir,
-                    path,
-                    &test_id,
-                    report_unused_externs,
-                );
+                let merged_res = merged.and_then(|(merged, index)| {
+                    merged.run(
+                        index,
+                        runtool.clone(),
+                        runtool_args.clone(),
+                        target.clone(),
+                        &report_unused_externs,
+                    )
+                });
+                let res = match merged_res {
+                    Some(res) => res,
+                    None => {
+                        // Only tests compiled on their own need a directory of their own.
+                        let outdir = make_outdir(&options, &test_id);
+                        run_test(
+                            &test,
+                            &cratename,
+                            line,
+                            options,
+                            config.should_panic,
+                            no_run,
+                            config.test_harness,
+                            runtool,
+                            runtool_args,
+                            target,
+                            config.compile_fail,
+                            config.error_codes,
+                            &opts,
+                            edition,
+                            outdir,
+                            path,
+                            &test_id,
+                            report_unused_externs,
+                        )
+                    }
+                };
 
                 if let Err(err) = res {
                     match err {
@@ -1128,17 +1288,20 @@ fn visit_testable<F: FnOnce(&mut Self)>(
                 .map(|span| span.ctxt().outer_expn().expansion_cause().unwrap_or(span))
                 .unwrap_or(DUMMY_SP);
             self.collector.set_position(span);
+            let doc_span = span_of_attrs(&attrs).unwrap_or(sp);
+            let (ignore_lint_level, _) =
+                self.tcx.lint_level_at_node(crate::lint::UNNECESSARY_IGNORE, hir_id);
+            if ignore_lint_level != lint::Allow {
+                self.collector.ignore_lint_target = Some((hir_id, doc_span));
+            }
             markdown::find_testable_code(
                 &doc,
                 self.collector,
                 self.codes,
                 self.collector.enable_per_target_ignores,
-                Some(&crate::html::markdown::ExtraInfo::new(
-                    self.tcx,
-                    hir_id,
-                    span_of_attrs(&attrs).unwrap_or(sp),
-                )),
+                Some(&crate::html::markdown::ExtraInfo::new(self.tcx, hir_id, doc_span)),
             );
+            self.collector.ignore_lint_target = None;
         }
 
         nested(self);
@@ -0,0 +1,287 @@
+//! Support for `--merge-doctests`: compiling every compatible doctest of a crate into a single
+//! binary.
+//!
+//! Each merged doctest is wrapped into its own module of a "bundle" crate, whose `main` function
+//! dispatches on the [`MERGED_DOCTEST_ENV`] environment variable. The bundle is compiled once, by
+//! the first merged doctest that runs, and every merged doctest then runs as a separate process
+//! of the bundle, so panics and exit codes stay isolated exactly like for standalone doctests.
+//!
+//! Every doctest module is written to its own file, which is remapped with `--remap-path-prefix`
+//! to the file the doctest comes from, and padded with empty lines so that the doctest code is on
+//! the same lines as in that file. Panic locations, `file!()` and `line!()` thus point to the
+//! documentation of the item like they would for the doctest compiled on its own.
+//!
+//! If the bundle fails to compile (for example because a doctest relies on being the crate root),
+//! every merged doctest falls back to being compiled and run on its own.
+
+use rustc_span::edition::Edition;
+use rustc_target::spec::TargetTriple;
+
+use std::ffi::OsString;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::str;
+use std::sync::Mutex;
+
+use super::{
+    doctest_compiler, doctest_runner, make_test, partition_source, DirState, TestFailure,
+    TestOptions, UnusedExterns,
+};
+use crate::config::Options;
+use crate::html::markdown::{Ignore, LangString};
+
+/// Environment variable telling the bundle binary which doctest to run.
+const MERGED_DOCTEST_ENV: &str = "RUSTDOC_MERGED_DOCTEST";
+
+/// State of the bundle binary.
+enum BundleState {
+    /// Doctests are still being added to the bundle; nothing has been compiled yet.
+    Pending,
+    /// The bundle was compiled successfully into the given binary.
+    Compiled { binary: PathBuf, unused_externs: Option<UnusedExterns> },
+    /// The bundle failed to compile: every doctest must be compiled on its own.
+    Failed,
+}
+
+/// A doctest added to the bundle.
+struct MergedDoctest {
+    /// The file the doctest comes from.
+    path: PathBuf,
+    /// The contents of the module of the doctest.
+    source: String,
+}
+
+crate struct MergedDoctests {
+    options: Options,
+    cratename: String,
+    opts: TestOptions,
+    outdir: DirState,
+    /// The doctests, in the order of their index in the bundle.
+    doctests: Mutex<Vec<MergedDoctest>>,
+    state: Mutex<BundleState>,
+}
+
+impl MergedDoctests {
+    crate fn new(options: Options, cratename: String, opts: TestOptions, outdir: DirState) -> Self {
+        MergedDoctests {
+            options,
+            cratename,
+            opts,
+            outdir,
+            doctests: Mutex::new(Vec::new()),
+            state: Mutex::new(BundleState::Pending),
+        }
+    }
+
+    /// Returns `true` if the given doctest can be compiled as part of the bundle.
+    ///
+    /// Doctests that have to be compiled with specific settings (`compile_fail`, `no_run`,
+    /// `test_harness`, another edition, ...), that expect a specific outcome at runtime
+    /// (`should_panic`) or that set crate-level attributes are always compiled separately.
+    crate fn accepts(&self, test: &str, config: &LangString) -> bool {
+        if config.compile_fail
+            || config.should_panic
+            || config.test_harness
+            || config.no_run
+            || self.options.no_run
+            || config.ignore != Ignore::None
+        {
+            return false;
+        }
+        // Doctests are wrapped into modules, so paths in them only resolve like at the crate root
+        // with the 2018 module system.
+        let edition = config.edition.unwrap_or(self.options.edition);
+        if edition != self.options.edition || edition < Edition::Edition2018 {
+            return false;
+        }
+        let (crate_attrs, _, crates) = partition_source(test);
+        crates.trim().is_empty() && !crate_attrs.lines().any(|l| l.trim().starts_with("#!["))
+    }
+
+    /// Adds a doctest to the bundle and returns its index. `path` and `line` are the location of
+    /// the doctest, like for a doctest run on its own.
+    crate fn add(&self, test: &str, test_id: &str, path: &Path, line: usize) -> usize {
+        // Attributes from `#![doc(test(attr(...)))]` are added once at the root of the bundle,
+        // but like for standalone doctests, their presence disables the `#![allow(unused)]`.
+        let opts = TestOptions {
+            no_crate_inject: self.opts.no_crate_inject,
+            display_warnings: self.opts.display_warnings || !self.opts.attrs.is_empty(),
+            attrs: Vec::new(),
+        };
+        let (program, line_offset, _) = make_test(
+            test,
+            Some(&self.cratename),
+            false,
+            &opts,
+            self.options.edition,
+            Some(test_id),
+        );
+
+        // Same as `UNSTABLE_RUSTDOC_TEST_LINE` for a doctest compiled on its own.
+        let padding = line.saturating_sub(line_offset);
+        let source = format!(
+            "{padding}{program}\n\
+             pub(crate) fn __run() -> i32 {{ super::__RustdocReport::report(main()) }}\n",
+            padding = "\n".repeat(padding),
+            program = program,
+        );
+
+        let mut doctests = self.doctests.lock().unwrap();
+        doctests.push(MergedDoctest { path: path.to_path_buf(), source });
+        doctests.len() - 1
+    }
+
+    /// Runs the doctest at `index` from the bundle, compiling the bundle first if needed.
+    ///
+    /// Returns `None` if the bundle could not be compiled, in which case the doctest needs to be
+    /// run on its own.
+    crate fn run(
+        &self,
+        index: usize,
+        runtool: Option<String>,
+        runtool_args: Vec<String>,
+        target: TargetTriple,
+        report_unused_externs: impl Fn(UnusedExterns),
+    ) -> Option<Result<(), TestFailure>> {
+        let binary = {
+            let mut state = self.state.lock().unwrap();
+            if let BundleState::Pending = *state {
+                *state = self.compile(target);
+            }
+            match *state {
+                BundleState::Compiled { ref binary, ref unused_externs } => {
+                    // Every doctest reports the unused externs of the bundle, so that rustdoc
+                    // gets one report per compiled doctest like without `--merge-doctests`.
+                    if let Some(unused_externs) = unused_externs {
+                        report_unused_externs(unused_externs.clone());
+                    }
+                    binary.clone()
+                }
+                BundleState::Failed => return None,
+                BundleState::Pending => unreachable!(),
+            }
+        };
+
+        let mut cmd = doctest_runner(runtool, runtool_args, &binary, &self.options);
+        cmd.env(MERGED_DOCTEST_ENV, index.to_string());
+        Some(match cmd.output() {
+            Err(e) => Err(TestFailure::ExecutionError(e)),
+            Ok(out) if !out.status.success() => Err(TestFailure::ExecutionFailure(out)),
+            Ok(_) => Ok(()),
+        })
+    }
+
+    fn compile(&self, target: TargetTriple) -> BundleState {
+        let doctests = self.doctests.lock().unwrap();
+        let mut modules = Vec::with_capacity(doctests.len());
+        let mut remaps = Vec::with_capacity(doctests.len());
+        for (index, doctest) in doctests.iter().enumerate() {
+            let dir = self.outdir.path().join(index.to_string());
+            let file_name = doctest.path.file_name().unwrap_or_else(|| "doctest.rs".as_ref());
+            let file = dir.join(file_name);
+            let written = fs::create_dir_all(&dir).and_then(|()| fs::write(&file, &doctest.source));
+            if let Err(e) = written {
+                debug!("couldn't write the merged doctest {}: {}", index, e);
+                return BundleState::Failed;
+            }
+            let mut remap = OsString::from(&dir);
+            remap.push("=");
+            remap.push(doctest.path.parent().unwrap_or_else(|| "".as_ref()));
+            remaps.push(remap);
+            modules.push(file);
+        }
+        drop(doctests);
+
+        let source_file = self.outdir.path().join("merged_doctests.rs");
+        let binary = self.outdir.path().join("merged_doctests");
+        if let Err(e) = fs::write(&source_file, self.bundle_source(&modules)) {
+            debug!("couldn't write the merged doctests: {}", e);
+            return BundleState::Failed;
+        }
+
+        let mut compiler = doctest_compiler(&self.options, self.options.edition, target, &binary);
+        for remap in remaps {
+            compiler.arg("--remap-path-prefix").arg(remap);
+        }
+        if self.options.json_unused_externs {
+            compiler.arg("--error-format=json");
+            compiler.arg("--json").arg("unused-externs");
+            compiler.arg("-Z").arg("unstable-options");
+            compiler.arg("-W").arg("unused_crate_dependencies");
+        }
+        compiler.arg(&source_file);
+        compiler.stdout(Stdio::null());
+        compiler.stderr(Stdio::piped());
+
+        let output = match compiler.output() {
+            Ok(output) => output,
+            Err(e) => {
+                debug!("couldn't run rustc on the merged doctests: {}", e);
+                return BundleState::Failed;
+            }
+        };
+        if !output.status.success() {
+            // The errors will be reported again by the doctest(s) causing them, once compiled on
+            // their own.
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            debug!("merged doctests failed to compile:\n{}", stderr);
+            return BundleState::Failed;
+        }
+
+        let unused_externs = str::from_utf8(&output.stderr)
+            .unwrap_or_default()
+            .lines()
+            .find_map(|l| serde_json::from_str::<UnusedExterns>(l).ok());
+        BundleState::Compiled { binary, unused_externs }
+    }
+
+    /// Returns the source of the crate root of the bundle, given the files of the doctest modules.
+    fn bundle_source(&self, modules: &[PathBuf]) -> String {
+        let mut bundle = String::new();
+
+        for attr in &self.opts.attrs {
+            writeln!(bundle, "#![{}]", attr).unwrap();
+        }
+        for (index, module) in modules.iter().enumerate() {
+            writeln!(bundle, "#[path = {:?}]\nmod __doctest_{};", module, index).unwrap();
+        }
+        // Mirrors what the `Termination` implementations of `()` and `Result` do for the `main`
+        // function of standalone doctests.
+        bundle.push_str(
+            "trait __RustdocReport { fn report(self) -> i32; }
+impl __RustdocReport for () { fn report(self) -> i32 { 0 } }
+impl<E: ::std::fmt::Debug> __RustdocReport for ::std::result::Result<(), E> {
+    fn report(self) -> i32 {
+        match self {
+            ::std::result::Result::Ok(()) => 0,
+            ::std::result::Result::Err(e) => { ::std::eprintln!(\"Error: {:?}\", e); 1 }
+        }
+    }
+}
+fn main() {
+",
+        );
+        writeln!(
+            bundle,
+            "    let test = ::std::env::var({:?}).expect(\"no doctest to run\");",
+            MERGED_DOCTEST_ENV
+        )
+        .unwrap();
+        bundle.push_str("    let code = match &test[..] {\n");
+        for index in 0..modules.len() {
+            writeln!(bundle, "        \"{0}\" => __doctest_{0}::__run(),", index).unwrap();
+        }
+        bundle.push_str(
+            "        _ => ::std::panic!(\"unknown doctest {}\", test),
+    };
+    if code != 0 {
+        ::std::process::exit(code);
+    }
+}
+",
+        );
+        bundle
+    }
+}
@@ -0,0 +1,410 @@
+//! Packing of the HTML documentation into a single zip archive, for `--output-format zip`.
+//!
+//! The documentation is first rendered as HTML into the output directory as usual, and the whole
+//! directory is then written to `<output directory>.zip`. Since every crate of a graph is usually
+//! documented into the same output directory, the archive is updated after each of them and ends
+//! up containing the full graph: the shared search index and the relative links between the
+//! crates keep working once it's extracted or served by an offline documentation viewer.
+//!
+//! Updating the archive only compresses the files written since it was last updated, which are
+//! the ones of the new crate and the shared files. The compressed data of all the other entries is
+//! copied over from the previous archive as is.
+//!
+//! Only the parts of the zip format needed for this are implemented: files are either deflated
+//! or stored, and the zip64 extensions are used when the archive gets too large for the original
+//! format. All the entries get the same timestamp so that the archive is reproducible.
+
+use std::convert::{TryFrom, TryInto};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::mem;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use flate2::write::DeflateEncoder;
+use flate2::{Compression, Crc};
+use rustc_data_structures::flock;
+use rustc_data_structures::fx::FxHashMap;
+
+use crate::docfs::PathError;
+use crate::error::Error;
+
+const LOCAL_FILE_HEADER: u32 = 0x04034b50;
+const CENTRAL_DIRECTORY_HEADER: u32 = 0x02014b50;
+const END_OF_CENTRAL_DIRECTORY: u32 = 0x06054b50;
+const ZIP64_END_OF_CENTRAL_DIRECTORY: u32 = 0x06064b50;
+const ZIP64_END_OF_CENTRAL_DIRECTORY_LOCATOR: u32 = 0x07064b50;
+
+const METHOD_STORED: u16 = 0;
+const METHOD_DEFLATED: u16 = 8;
+/// The names of the entries are encoded in UTF-8.
+const FLAG_UTF8: u16 = 1 << 11;
+const VERSION: u16 = 20;
+const VERSION_ZIP64: u16 = 45;
+/// 1980-01-01 00:00:00, the earliest date which can be represented.
+const DOS_TIME: u16 = 0;
+const DOS_DATE: u16 = (1 << 5) | 1;
+
+/// Returns the path of the archive written for the documentation in `doc_root`.
+crate fn archive_path(doc_root: &Path) -> Result<PathBuf, Error> {
+    let doc_root = try_err!(doc_root.canonicalize(), doc_root);
+    let mut name = doc_root.file_name().unwrap_or_default().to_os_string();
+    name.push(".zip");
+    Ok(doc_root.with_file_name(name))
+}
+
+/// Writes all the files of `doc_root` to the archive at [`archive_path`], replacing it if it
+/// already exists.
+///
+/// The files that weren't modified since the existing archive was written are copied from it
+/// without being compressed again.
+crate fn write_archive(doc_root: &Path) -> Result<(), Error> {
+    let archive = archive_path(doc_root)?;
+
+    // Other rustdoc processes may be writing the shared files of the same directory, so take the
+    // lock used by `write_shared` to avoid packing them halfway through.
+    let lock_file = doc_root.join(".lock");
+    let _lock = try_err!(flock::Lock::new(&lock_file, true, true, true), &lock_file);
+
+    let mut files = Vec::new();
+    collect_files(doc_root, "", &mut files)?;
+    files.sort();
+
+    // An archive that can't be read back is simply written again from scratch.
+    let previous = fs::metadata(&archive).and_then(|m| m.modified()).ok().and_then(|modified| {
+        let buf = fs::read(&archive).ok()?;
+        Some((modified, buf))
+    });
+    let previous_entries: FxHashMap<_, _> = previous
+        .as_ref()
+        .and_then(|(_, buf)| read_entries(buf))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(entry, data)| (entry.name.clone(), (entry, data)))
+        .collect();
+
+    // Write to a temporary file first, so that an archive is never seen half-written.
+    let mut tmp = archive.clone().into_os_string();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    let file = try_err!(File::create(&tmp), &tmp);
+    let mut writer = ZipWriter::new(BufWriter::new(file));
+    for (name, path) in &files {
+        let modified = try_err!(fs::metadata(path).and_then(|m| m.modified()), path);
+        match (&previous, previous_entries.get(name)) {
+            // Files written in the same tick as the previous archive are added again, as they may
+            // have been modified after it.
+            (Some((archived, buf)), Some((entry, data))) if modified < *archived => {
+                try_err!(writer.copy_entry(entry, &buf[data.clone()]), &tmp);
+            }
+            _ => {
+                let contents = try_err!(fs::read(path), path);
+                try_err!(writer.add_file(name, &contents), &tmp);
+            }
+        }
+    }
+    let mut out = try_err!(writer.finish(), &tmp);
+    try_err!(out.flush(), &tmp);
+    drop(out);
+    try_err!(fs::rename(&tmp, &archive), &archive);
+    Ok(())
+}
+
+/// Collects the files under `dir` along with their name in the archive, which is their path
+/// relative to the documentation root, with `/` as separator.
+fn collect_files(
+    dir: &Path,
+    prefix: &str,
+    files: &mut Vec<(String, PathBuf)>,
+) -> Result<(), Error> {
+    for entry in try_err!(fs::read_dir(dir), dir) {
+        let entry = try_err!(entry, dir);
+        let path = entry.path();
+        let file_name = entry.file_name();
+        let file_name = match file_name.to_str() {
+            Some(name) => name,
+            None => return Err(Error::new("file name is not valid UTF-8", &path)),
+        };
+        if prefix.is_empty() && file_name == ".lock" {
+            continue;
+        }
+        let name = format!("{}{}", prefix, file_name);
+        if try_err!(entry.file_type(), &path).is_dir() {
+            collect_files(&path, &format!("{}/", name), files)?;
+        } else {
+            files.push((name, path));
+        }
+    }
+    Ok(())
+}
+
+/// Reads back the entries of an archive written by [`ZipWriter`], along with the range of their
+/// compressed data in `buf`. Returns `None` if `buf` isn't such an archive.
+fn read_entries(buf: &[u8]) -> Option<Vec<(Entry, Range<usize>)>> {
+    // Archives written by `ZipWriter` never have a comment, so the end of central directory
+    // record is always at the very end.
+    let end = buf.len().checked_sub(22)?;
+    if get_u32(buf, end)? != END_OF_CENTRAL_DIRECTORY {
+        return None;
+    }
+    let (count, directory_offset) = if get_u16(buf, end + 10)? == u16::MAX {
+        let locator = end.checked_sub(20)?;
+        if get_u32(buf, locator)? != ZIP64_END_OF_CENTRAL_DIRECTORY_LOCATOR {
+            return None;
+        }
+        let zip64_end = usize::try_from(get_u64(buf, locator + 8)?).ok()?;
+        if get_u32(buf, zip64_end)? != ZIP64_END_OF_CENTRAL_DIRECTORY {
+            return None;
+        }
+        (get_u64(buf, zip64_end + 32)?, get_u64(buf, zip64_end + 48)?)
+    } else {
+        (get_u16(buf, end + 10)? as u64, get_u32(buf, end + 16)? as u64)
+    };
+
+    let mut pos = usize::try_from(directory_offset).ok()?;
+    let mut entries = Vec::new();
+    for _ in 0..count {
+        if get_u32(buf, pos)? != CENTRAL_DIRECTORY_HEADER {
+            return None;
+        }
+        let name_len = get_u16(buf, pos + 28)? as usize;
+        let extra_len = get_u16(buf, pos + 30)? as usize;
+        let comment_len = get_u16(buf, pos + 32)? as usize;
+        let name = String::from_utf8(buf.get(pos + 46..pos + 46 + name_len)?.to_vec()).ok()?;
+        let mut offset = get_u32(buf, pos + 42)? as u64;
+        if offset == u32::MAX as u64 {
+            // The zip64 extended information only ever holds the offset.
+            let extra = pos + 46 + name_len;
+            if get_u16(buf, extra)? != 1 {
+                return None;
+            }
+            offset = get_u64(buf, extra + 4)?;
+        }
+        let entry = Entry {
+            name,
+            method: get_u16(buf, pos + 10)?,
+            crc: get_u32(buf, pos + 16)?,
+            compressed_size: get_u32(buf, pos + 20)?,
+            size: get_u32(buf, pos + 24)?,
+            offset,
+        };
+
+        let header = usize::try_from(offset).ok()?;
+        if get_u32(buf, header)? != LOCAL_FILE_HEADER {
+            return None;
+        }
+        let start = header + 30 + get_u16(buf, header + 26)? as usize;
+        let start = start + get_u16(buf, header + 28)? as usize;
+        let data = start..start.checked_add(entry.compressed_size as usize)?;
+        buf.get(data.clone())?;
+        entries.push((entry, data));
+        pos += 46 + name_len + extra_len + comment_len;
+    }
+    Some(entries)
+}
+
+/// The data needed to write the central directory entry of a file.
+struct Entry {
+    name: String,
+    method: u16,
+    crc: u32,
+    compressed_size: u32,
+    size: u32,
+    offset: u64,
+}
+
+/// A writer of zip archives, which only supports adding files whose contents are entirely known.
+crate struct ZipWriter<W: Write> {
+    out: W,
+    offset: u64,
+    entries: Vec<Entry>,
+}
+
+impl<W: Write> ZipWriter<W> {
+    crate fn new(out: W) -> Self {
+        ZipWriter { out, offset: 0, entries: Vec::new() }
+    }
+
+    /// Adds a file named `name` to the archive, compressing it if it makes it smaller.
+    crate fn add_file(&mut self, name: &str, contents: &[u8]) -> io::Result<()> {
+        let size = to_u32(contents.len())?;
+        let mut crc = Crc::new();
+        crc.update(contents);
+
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(contents)?;
+        let deflated = encoder.finish()?;
+        let (method, data) = if deflated.len() < contents.len() {
+            (METHOD_DEFLATED, &deflated[..])
+        } else {
+            (METHOD_STORED, contents)
+        };
+        self.add_entry(name, method, crc.sum(), size, data)
+    }
+
+    /// Adds an entry read from another archive, along with its compressed data.
+    fn copy_entry(&mut self, entry: &Entry, data: &[u8]) -> io::Result<()> {
+        self.add_entry(&entry.name, entry.method, entry.crc, entry.size, data)
+    }
+
+    fn add_entry(
+        &mut self,
+        name: &str,
+        method: u16,
+        crc: u32,
+        size: u32,
+        data: &[u8],
+    ) -> io::Result<()> {
+        let entry = Entry {
+            name: name.to_string(),
+            method,
+            crc,
+            compressed_size: to_u32(data.len())?,
+            size,
+            offset: self.offset,
+        };
+        let mut header = Vec::with_capacity(30 + name.len());
+        put_u32(&mut header, LOCAL_FILE_HEADER);
+        put_u16(&mut header, VERSION);
+        put_u16(&mut header, FLAG_UTF8);
+        put_u16(&mut header, entry.method);
+        put_u16(&mut header, DOS_TIME);
+        put_u16(&mut header, DOS_DATE);
+        put_u32(&mut header, entry.crc);
+        put_u32(&mut header, entry.compressed_size);
+        put_u32(&mut header, entry.size);
+        put_u16(&mut header, to_u16(name.len())?);
+        put_u16(&mut header, 0);
+        header.extend_from_slice(name.as_bytes());
+
+        self.write(&header)?;
+        self.write(data)?;
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    /// Writes the central directory, which completes the archive, and returns the underlying
+    /// writer.
+    crate fn finish(mut self) -> io::Result<W> {
+        let directory_offset = self.offset;
+        let entries = mem::take(&mut self.entries);
+        for entry in &entries {
+            let zip64 = entry.offset >= u32::MAX as u64;
+            let mut header = Vec::with_capacity(46 + entry.name.len() + 12);
+            put_u32(&mut header, CENTRAL_DIRECTORY_HEADER);
+            put_u16(&mut header, if zip64 { VERSION_ZIP64 } else { VERSION });
+            put_u16(&mut header, if zip64 { VERSION_ZIP64 } else { VERSION });
+            put_u16(&mut header, FLAG_UTF8);
+            put_u16(&mut header, entry.method);
+            put_u16(&mut header, DOS_TIME);
+            put_u16(&mut header, DOS_DATE);
+            put_u32(&mut header, entry.crc);
+            put_u32(&mut header, entry.compressed_size);
+            put_u32(&mut header, entry.size);
+            put_u16(&mut header, to_u16(entry.name.len())?);
+            put_u16(&mut header, if zip64 { 12 } else { 0 });
+            // Comment length, disk number, internal and external attributes.
+            put_u16(&mut header, 0);
+            put_u16(&mut header, 0);
+            put_u16(&mut header, 0);
+            put_u32(&mut header, 0);
+            put_u32(&mut header, if zip64 { u32::MAX } else { entry.offset as u32 });
+            header.extend_from_slice(entry.name.as_bytes());
+            if zip64 {
+                // The zip64 extended information, which only holds the offset here.
+                put_u16(&mut header, 1);
+                put_u16(&mut header, 8);
+                put_u64(&mut header, entry.offset);
+            }
+            self.write(&header)?;
+        }
+        let directory_size = self.offset - directory_offset;
+        let count = entries.len() as u64;
+
+        let mut end = Vec::new();
+        let zip64 = count >= u16::MAX as u64
+            || directory_offset >= u32::MAX as u64
+            || directory_size >= u32::MAX as u64;
+        if zip64 {
+            let zip64_end_offset = self.offset;
+            put_u32(&mut end, ZIP64_END_OF_CENTRAL_DIRECTORY);
+            // The size of the rest of the record.
+            put_u64(&mut end, 44);
+            put_u16(&mut end, VERSION_ZIP64);
+            put_u16(&mut end, VERSION_ZIP64);
+            // The number of this disk, and of the one with the central directory.
+            put_u32(&mut end, 0);
+            put_u32(&mut end, 0);
+            put_u64(&mut end, count);
+            put_u64(&mut end, count);
+            put_u64(&mut end, directory_size);
+            put_u64(&mut end, directory_offset);
+
+            put_u32(&mut end, ZIP64_END_OF_CENTRAL_DIRECTORY_LOCATOR);
+            put_u32(&mut end, 0);
+            put_u64(&mut end, zip64_end_offset);
+            // The total number of disks.
+            put_u32(&mut end, 1);
+        }
+        put_u32(&mut end, END_OF_CENTRAL_DIRECTORY);
+        put_u16(&mut end, 0);
+        put_u16(&mut end, 0);
+        put_u16(&mut end, if zip64 { u16::MAX } else { count as u16 });
+        put_u16(&mut end, if zip64 { u16::MAX } else { count as u16 });
+        put_u32(&mut end, if zip64 { u32::MAX } else { directory_size as u32 });
+        put_u32(&mut end, if zip64 { u32::MAX } else { directory_offset as u32 });
+        // Comment length.
+        put_u16(&mut end, 0);
+        self.write(&end)?;
+        Ok(self.out)
+    }
+
+    fn write(&mut self, data: &[u8]) -> io::Result<()> {
+        self.out.write_all(data)?;
+        self.offset += data.len() as u64;
+        Ok(())
+    }
+}
+
+fn to_u16(n: usize) -> io::Result<u16> {
+    if n < u16::MAX as usize {
+        Ok(n as u16)
+    } else {
+        Err(io::Error::new(io::ErrorKind::Other, "file name too long for a zip archive"))
+    }
+}
+
+fn to_u32(n: usize) -> io::Result<u32> {
+    if n < u32::MAX as usize {
+        Ok(n as u32)
+    } else {
+        Err(io::Error::new(io::ErrorKind::Other, "file too large for a zip archive"))
+    }
+}
+
+fn get_u16(buf: &[u8], pos: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(buf.get(pos..pos.checked_add(2)?)?.try_into().ok()?))
+}
+
+fn get_u32(buf: &[u8], pos: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(buf.get(pos..pos.checked_add(4)?)?.try_into().ok()?))
+}
+
+fn get_u64(buf: &[u8], pos: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(buf.get(pos..pos.checked_add(8)?)?.try_into().ok()?))
+}
+
+fn put_u16(buf: &mut Vec<u8>, n: u16) {
+    buf.extend_from_slice(&n.to_le_bytes());
+}
+
+fn put_u32(buf: &mut Vec<u8>, n: u32) {
+    buf.extend_from_slice(&n.to_le_bytes());
+}
+
+fn put_u64(buf: &mut Vec<u8>, n: u64) {
+    buf.extend_from_slice(&n.to_le_bytes());
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,139 @@
+use std::fs;
+use std::io::Read;
+
+use flate2::read::DeflateDecoder;
+
+use super::{archive_path, read_entries, write_archive, ZipWriter};
+
+fn u16_at(buf: &[u8], pos: usize) -> u16 {
+    u16::from_le_bytes([buf[pos], buf[pos + 1]])
+}
+
+fn u32_at(buf: &[u8], pos: usize) -> u32 {
+    u32::from_le_bytes([buf[pos], buf[pos + 1], buf[pos + 2], buf[pos + 3]])
+}
+
+/// Reads back the files of an archive by following its central directory.
+fn read_archive(buf: &[u8]) -> Vec<(String, Vec<u8>)> {
+    let end = buf.len() - 22;
+    assert_eq!(u32_at(buf, end), 0x06054b50);
+    let count = u16_at(buf, end + 10) as usize;
+    let mut pos = u32_at(buf, end + 16) as usize;
+    let mut files = Vec::new();
+    for _ in 0..count {
+        assert_eq!(u32_at(buf, pos), 0x02014b50);
+        let method = u16_at(buf, pos + 10);
+        let compressed_size = u32_at(buf, pos + 20) as usize;
+        let size = u32_at(buf, pos + 24) as usize;
+        let name_len = u16_at(buf, pos + 28) as usize;
+        let extra_len = u16_at(buf, pos + 30) as usize;
+        let offset = u32_at(buf, pos + 42) as usize;
+        let name = String::from_utf8(buf[pos + 46..pos + 46 + name_len].to_vec()).unwrap();
+
+        assert_eq!(u32_at(buf, offset), 0x04034b50);
+        assert_eq!(&buf[offset + 30..offset + 30 + name_len], name.as_bytes());
+        let start = offset + 30 + name_len;
+        let data = &buf[start..start + compressed_size];
+        let contents = match method {
+            0 => data.to_vec(),
+            8 => {
+                let mut contents = Vec::new();
+                DeflateDecoder::new(data).read_to_end(&mut contents).unwrap();
+                contents
+            }
+            _ => panic!("unexpected compression method {}", method),
+        };
+        assert_eq!(contents.len(), size);
+        let mut crc = flate2::Crc::new();
+        crc.update(&contents);
+        assert_eq!(crc.sum(), u32_at(buf, pos + 16));
+
+        files.push((name, contents));
+        pos += 46 + name_len + extra_len;
+    }
+    files
+}
+
+#[test]
+fn test_zip_roundtrip() {
+    let page = "<html><body>".to_string() + &"<p>hello</p>".repeat(100) + "</body></html>";
+    let files = vec![
+        ("index.html".to_string(), page.into_bytes()),
+        ("foo/struct.Bar.html".to_string(), b"x".to_vec()),
+        ("search-index.js".to_string(), Vec::new()),
+    ];
+
+    let mut writer = ZipWriter::new(Vec::new());
+    for (name, contents) in &files {
+        writer.add_file(name, contents).unwrap();
+    }
+    let archive = writer.finish().unwrap();
+
+    // The page is compressed, the tiny files are stored.
+    assert!(archive.len() < files[0].1.len());
+    assert_eq!(read_archive(&archive), files);
+}
+
+#[test]
+fn test_zip_empty() {
+    let archive = ZipWriter::new(Vec::new()).finish().unwrap();
+    assert_eq!(archive.len(), 22);
+    assert!(read_archive(&archive).is_empty());
+}
+
+#[test]
+fn test_zip_copy_entries() {
+    let page = "<p>hello</p>".repeat(100);
+    let mut writer = ZipWriter::new(Vec::new());
+    writer.add_file("index.html", page.as_bytes()).unwrap();
+    writer.add_file("all.html", b"x").unwrap();
+    let first = writer.finish().unwrap();
+
+    let entries = read_entries(&first).unwrap();
+    assert_eq!(entries.len(), 2);
+    let mut writer = ZipWriter::new(Vec::new());
+    for (entry, data) in &entries {
+        writer.copy_entry(entry, &first[data.clone()]).unwrap();
+    }
+    writer.add_file("search-index.js", b"var searchIndex;").unwrap();
+    let second = writer.finish().unwrap();
+
+    let mut files = read_archive(&first);
+    files.push(("search-index.js".to_string(), b"var searchIndex;".to_vec()));
+    assert_eq!(read_archive(&second), files);
+}
+
+#[test]
+fn test_zip_read_entries_invalid() {
+    assert!(read_entries(b"").is_none());
+    assert!(read_entries(&[0; 64]).is_none());
+
+    let mut writer = ZipWriter::new(Vec::new());
+    writer.add_file("index.html", b"hello").unwrap();
+    let archive = writer.finish().unwrap();
+    assert!(read_entries(&archive[..archive.len() - 1]).is_none());
+    assert!(read_entries(&archive[1..]).is_none());
+}
+
+#[test]
+fn test_write_archive_update() {
+    let dir = tempfile::tempdir().unwrap();
+    let doc_root = dir.path().join("doc");
+    fs::create_dir_all(doc_root.join("foo")).unwrap();
+    fs::write(doc_root.join("foo/index.html"), "foo").unwrap();
+    fs::write(doc_root.join("search-index.js"), "foo").unwrap();
+    write_archive(&doc_root).unwrap();
+
+    fs::create_dir_all(doc_root.join("bar")).unwrap();
+    fs::write(doc_root.join("bar/index.html"), "bar").unwrap();
+    fs::write(doc_root.join("search-index.js"), "foo bar").unwrap();
+    write_archive(&doc_root).unwrap();
+
+    let archive = fs::read(archive_path(&doc_root).unwrap()).unwrap();
+    let files = vec![
+        ("bar/index.html".to_string(), b"bar".to_vec()),
+        ("foo/index.html".to_string(), b"foo".to_vec()),
+        ("search-index.js".to_string(), b"foo bar".to_vec()),
+    ];
+    assert_eq!(read_archive(&archive), files);
+}
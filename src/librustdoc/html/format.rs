@@ -20,10 +20,22 @@
 use crate::clean::{
     self, utils::find_nearest_parent_module, ExternalCrate, FakeDefId, GetDefId, PrimitiveType,
 };
+use crate::formats::cache::Cache;
 use crate::formats::item_type::ItemType;
 use crate::html::escape::Escape;
 use crate::html::render::cache::ExternalLocation;
-use crate::html::render::Context;
+
+/// What the functions of this module need to know about the page they print into.
+///
+/// This is implemented by the HTML [`Context`](crate::html::render::Context), but also by the
+/// renderers which print types and signatures as plain text with `{:#}`, in which case no links
+/// are generated.
+crate trait PrintContext<'tcx> {
+    fn tcx(&self) -> TyCtxt<'tcx>;
+    fn cache(&self) -> &Cache;
+    /// The path of the module the page belongs to, which links are relative to.
+    fn current(&self) -> &[String];
+}
 
 crate trait Print {
     fn print(self, buffer: &mut Buffer);
@@ -132,7 +144,7 @@ fn comma_sep<T: fmt::Display>(items: impl Iterator<Item = T>) -> impl fmt::Displ
 
 crate fn print_generic_bounds<'a, 'tcx: 'a>(
     bounds: &'a [clean::GenericBound],
-    cx: &'a Context<'tcx>,
+    cx: &'a dyn PrintContext<'tcx>,
 ) -> impl fmt::Display + 'a + Captures<'tcx> {
     display_fn(move |f| {
         let mut bounds_dup = FxHashSet::default();
@@ -152,7 +164,7 @@ fn comma_sep<T: fmt::Display>(items: impl Iterator<Item = T>) -> impl fmt::Displ
 impl clean::GenericParamDef {
     crate fn print<'a, 'tcx: 'a>(
         &'a self,
-        cx: &'a Context<'tcx>,
+        cx: &'a dyn PrintContext<'tcx>,
     ) -> impl fmt::Display + 'a + Captures<'tcx> {
         display_fn(move |f| match self.kind {
             clean::GenericParamDefKind::Lifetime => write!(f, "{}", self.name),
@@ -201,7 +213,7 @@ impl clean::GenericParamDef {
 impl clean::Generics {
     crate fn print<'a, 'tcx: 'a>(
         &'a self,
-        cx: &'a Context<'tcx>,
+        cx: &'a dyn PrintContext<'tcx>,
     ) -> impl fmt::Display + 'a + Captures<'tcx> {
         display_fn(move |f| {
             let real_params =
@@ -223,7 +235,7 @@ impl clean::Generics {
 /// * Whether the where-clause needs to add a comma and newline after the last bound.
 crate fn print_where_clause<'a, 'tcx: 'a>(
     gens: &'a clean::Generics,
-    cx: &'a Context<'tcx>,
+    cx: &'a dyn PrintContext<'tcx>,
     indent: usize,
     end_newline: bool,
 ) -> impl fmt::Display + 'a + Captures<'tcx> {
@@ -332,7 +344,7 @@ impl clean::Constant {
 impl clean::PolyTrait {
     fn print<'a, 'tcx: 'a>(
         &'a self,
-        cx: &'a Context<'tcx>,
+        cx: &'a dyn PrintContext<'tcx>,
     ) -> impl fmt::Display + 'a + Captures<'tcx> {
         display_fn(move |f| {
             if !self.generic_params.is_empty() {
@@ -362,7 +374,7 @@ fn print<'a, 'tcx: 'a>(
 impl clean::GenericBound {
     crate fn print<'a, 'tcx: 'a>(
         &'a self,
-        cx: &'a Context<'tcx>,
+        cx: &'a dyn PrintContext<'tcx>,
     ) -> impl fmt::Display + 'a + Captures<'tcx> {
         display_fn(move |f| match self {
             clean::GenericBound::Outlives(lt) => write!(f, "{}", lt.print()),
@@ -385,7 +397,7 @@ impl clean::GenericBound {
 impl clean::GenericArgs {
     fn print<'a, 'tcx: 'a>(
         &'a self,
-        cx: &'a Context<'tcx>,
+        cx: &'a dyn PrintContext<'tcx>,
     ) -> impl fmt::Display + 'a + Captures<'tcx> {
         display_fn(move |f| {
             match self {
@@ -455,9 +467,9 @@ fn print<'a, 'tcx: 'a>(
     }
 }
 
-crate fn href(did: DefId, cx: &Context<'_>) -> Option<(String, ItemType, Vec<String>)> {
+crate fn href(did: DefId, cx: &dyn PrintContext<'_>) -> Option<(String, ItemType, Vec<String>)> {
     let cache = &cx.cache();
-    let relative_to = &cx.current;
+    let relative_to = cx.current();
     fn to_module_fqp(shortty: ItemType, fqp: &[String]) -> &[String] {
         if shortty == ItemType::Module { &fqp[..] } else { &fqp[..fqp.len() - 1] }
     }
@@ -537,7 +549,7 @@ fn resolved_path<'a, 'cx: 'a>(
     path: &clean::Path,
     print_all: bool,
     use_absolute: bool,
-    cx: &'cx Context<'_>,
+    cx: &'cx dyn PrintContext<'_>,
 ) -> fmt::Result {
     let last = path.segments.last().unwrap();
 
@@ -571,14 +583,14 @@ fn primitive_link(
     f: &mut fmt::Formatter<'_>,
     prim: clean::PrimitiveType,
     name: &str,
-    cx: &Context<'_>,
+    cx: &dyn PrintContext<'_>,
 ) -> fmt::Result {
     let m = &cx.cache();
     let mut needs_termination = false;
     if !f.alternate() {
         match m.primitive_locations.get(&prim) {
             Some(&def_id) if def_id.is_local() => {
-                let len = cx.current.len();
+                let len = cx.current().len();
                 let len = if len == 0 { 0 } else { len - 1 };
                 write!(
                     f,
@@ -599,11 +611,11 @@ fn primitive_link(
                     ExternalLocation::Local => {
                         cname_str =
                             ExternalCrate { crate_num: def_id.krate }.name(cx.tcx()).as_str();
-                        Some(if cx.current.first().map(|x| &x[..]) == Some(&cname_str[..]) {
-                            iter::repeat("..").take(cx.current.len() - 1).collect()
+                        Some(if cx.current().first().map(|x| &x[..]) == Some(&cname_str[..]) {
+                            iter::repeat("..").take(cx.current().len() - 1).collect()
                         } else {
                             let cname = iter::once(&cname_str[..]);
-                            iter::repeat("..").take(cx.current.len()).chain(cname).collect()
+                            iter::repeat("..").take(cx.current().len()).chain(cname).collect()
                         })
                     }
                     ExternalLocation::Unknown => None,
@@ -631,7 +643,7 @@ fn primitive_link(
 /// Helper to render type parameters
 fn tybounds<'a, 'tcx: 'a>(
     param_names: &'a Option<Vec<clean::GenericBound>>,
-    cx: &'a Context<'tcx>,
+    cx: &'a dyn PrintContext<'tcx>,
 ) -> impl fmt::Display + 'a + Captures<'tcx> {
     display_fn(move |f| match *param_names {
         Some(ref params) => {
@@ -648,7 +660,7 @@ fn tybounds<'a, 'tcx: 'a>(
 crate fn anchor<'a, 'cx: 'a>(
     did: DefId,
     text: &'a str,
-    cx: &'cx Context<'_>,
+    cx: &'cx dyn PrintContext<'_>,
 ) -> impl fmt::Display + 'a {
     let parts = href(did.into(), cx);
     display_fn(move |f| {
@@ -672,7 +684,7 @@ fn fmt_type<'cx>(
     t: &clean::Type,
     f: &mut fmt::Formatter<'_>,
     use_absolute: bool,
-    cx: &'cx Context<'_>,
+    cx: &'cx dyn PrintContext<'_>,
 ) -> fmt::Result {
     debug!("fmt_type(t = {:?})", t);
 
@@ -909,7 +921,7 @@ fn fmt_type<'cx>(
 impl clean::Type {
     crate fn print<'b, 'a: 'b, 'tcx: 'a>(
         &'a self,
-        cx: &'a Context<'tcx>,
+        cx: &'a dyn PrintContext<'tcx>,
     ) -> impl fmt::Display + 'b + Captures<'tcx> {
         display_fn(move |f| fmt_type(self, f, false, cx))
     }
@@ -919,7 +931,7 @@ impl clean::Impl {
     crate fn print<'a, 'tcx: 'a>(
         &'a self,
         use_absolute: bool,
-        cx: &'a Context<'tcx>,
+        cx: &'a dyn PrintContext<'tcx>,
     ) -> impl fmt::Display + 'a + Captures<'tcx> {
         display_fn(move |f| {
             if f.alternate() {
@@ -951,7 +963,7 @@ impl clean::Impl {
 impl clean::Arguments {
     crate fn print<'a, 'tcx: 'a>(
         &'a self,
-        cx: &'a Context<'tcx>,
+        cx: &'a dyn PrintContext<'tcx>,
     ) -> impl fmt::Display + 'a + Captures<'tcx> {
         display_fn(move |f| {
             for (i, input) in self.values.iter().enumerate() {
@@ -975,7 +987,7 @@ impl clean::Arguments {
 impl clean::FnRetTy {
     crate fn print<'a, 'tcx: 'a>(
         &'a self,
-        cx: &'a Context<'tcx>,
+        cx: &'a dyn PrintContext<'tcx>,
     ) -> impl fmt::Display + 'a + Captures<'tcx> {
         display_fn(move |f| match self {
             clean::Return(clean::Tuple(tys)) if tys.is_empty() => Ok(()),
@@ -991,7 +1003,7 @@ impl clean::FnRetTy {
 impl clean::BareFunctionDecl {
     fn print_hrtb_with_space<'a, 'tcx: 'a>(
         &'a self,
-        cx: &'a Context<'tcx>,
+        cx: &'a dyn PrintContext<'tcx>,
     ) -> impl fmt::Display + 'a + Captures<'tcx> {
         display_fn(move |f| {
             if !self.generic_params.is_empty() {
@@ -1006,7 +1018,7 @@ fn print_hrtb_with_space<'a, 'tcx: 'a>(
 impl clean::FnDecl {
     crate fn print<'b, 'a: 'b, 'tcx: 'a>(
         &'a self,
-        cx: &'a Context<'tcx>,
+        cx: &'a dyn PrintContext<'tcx>,
     ) -> impl fmt::Display + 'b + Captures<'tcx> {
         display_fn(move |f| {
             let ellipsis = if self.c_variadic { ", ..." } else { "" };
@@ -1041,7 +1053,7 @@ impl clean::FnDecl {
         header_len: usize,
         indent: usize,
         asyncness: hir::IsAsync,
-        cx: &'a Context<'tcx>,
+        cx: &'a dyn PrintContext<'tcx>,
     ) -> impl fmt::Display + 'a + Captures<'tcx> {
         display_fn(move |f| self.inner_full_print(header_len, indent, asyncness, f, cx))
     }
@@ -1052,7 +1064,7 @@ fn inner_full_print(
         indent: usize,
         asyncness: hir::IsAsync,
         f: &mut fmt::Formatter<'_>,
-        cx: &Context<'_>,
+        cx: &dyn PrintContext<'_>,
     ) -> fmt::Result {
         let amp = if f.alternate() { "&" } else { "&amp;" };
         let mut args = String::new();
@@ -1136,8 +1148,11 @@ fn inner_full_print(
 
         let declaration_len = header_len + args_plain.len() + arrow_plain.len();
         let output = if declaration_len > 80 {
-            let full_pad = format!("<br>{}", "&nbsp;".repeat(indent + 4));
-            let close_pad = format!("<br>{}", "&nbsp;".repeat(indent));
+            // The alternate output is plain text, where trailing spaces and `&nbsp;` would show.
+            let (args, space) =
+                if f.alternate() { (args.replace(" <br>", "<br>"), " ") } else { (args, "&nbsp;") };
+            let full_pad = format!("<br>{}", space.repeat(indent + 4));
+            let close_pad = format!("<br>{}", space.repeat(indent));
             format!(
                 "({args}{close}){arrow}",
                 args = args.replace("<br>", &full_pad),
@@ -1160,7 +1175,7 @@ impl clean::Visibility {
     crate fn print_with_space<'a, 'tcx: 'a>(
         self,
         item_did: FakeDefId,
-        cx: &'a Context<'tcx>,
+        cx: &'a dyn PrintContext<'tcx>,
     ) -> impl fmt::Display + 'a + Captures<'tcx> {
         let to_print = match self {
             clean::Public => "pub ".to_owned(),
@@ -1283,7 +1298,7 @@ fn print_with_space(&self) -> &str {
 impl clean::Import {
     crate fn print<'a, 'tcx: 'a>(
         &'a self,
-        cx: &'a Context<'tcx>,
+        cx: &'a dyn PrintContext<'tcx>,
     ) -> impl fmt::Display + 'a + Captures<'tcx> {
         display_fn(move |f| match self.kind {
             clean::ImportKind::Simple(name) => {
@@ -1307,7 +1322,7 @@ impl clean::Import {
 impl clean::ImportSource {
     crate fn print<'a, 'tcx: 'a>(
         &'a self,
-        cx: &'a Context<'tcx>,
+        cx: &'a dyn PrintContext<'tcx>,
     ) -> impl fmt::Display + 'a + Captures<'tcx> {
         display_fn(move |f| match self.did {
             Some(did) => resolved_path(f, did, &self.path, true, false, cx),
@@ -1330,7 +1345,7 @@ impl clean::ImportSource {
 impl clean::TypeBinding {
     crate fn print<'a, 'tcx: 'a>(
         &'a self,
-        cx: &'a Context<'tcx>,
+        cx: &'a dyn PrintContext<'tcx>,
     ) -> impl fmt::Display + 'a + Captures<'tcx> {
         display_fn(move |f| {
             f.write_str(&*self.name.as_str())?;
@@ -1374,7 +1389,7 @@ impl clean::TypeBinding {
 impl clean::GenericArg {
     crate fn print<'a, 'tcx: 'a>(
         &'a self,
-        cx: &'a Context<'tcx>,
+        cx: &'a dyn PrintContext<'tcx>,
     ) -> impl fmt::Display + 'a + Captures<'tcx> {
         display_fn(move |f| match self {
             clean::GenericArg::Lifetime(lt) => fmt::Display::fmt(&lt.print(), f),
@@ -117,7 +117,7 @@ impl ErrorCodes {
 /// Controls whether a line will be hidden or shown in HTML output.
 ///
 /// All lines are used in documentation tests.
-enum Line<'a> {
+crate enum Line<'a> {
     Hidden(&'a str),
     Shown(Cow<'a, str>),
 }
@@ -143,7 +143,7 @@ fn for_code(self) -> Cow<'a, str> {
 // is done in the single # case. This inconsistency seems okay, if non-ideal. In
 // order to fix it we'd have to iterate to find the first non-# character, and
 // then reallocate to remove it; which would make us return a String.
-fn map_line(s: &str) -> Line<'_> {
+crate fn map_line(s: &str) -> Line<'_> {
     let trimmed = s.trim();
     if trimmed.starts_with("##") {
         Line::Shown(Cow::Owned(s.replacen("##", "#", 1)))
@@ -775,7 +775,7 @@ fn default() -> Self {
 }
 
 impl LangString {
-    fn parse_without_check(
+    crate fn parse_without_check(
         string: &str,
         allow_error_code_check: ErrorCodes,
         enable_per_target_ignores: bool,
@@ -1235,6 +1235,30 @@ fn push(s: &mut String, text_length: &mut usize, text: &str) {
     links.into_inner()
 }
 
+/// Returns the contents and range of every inline code span in the markdown which isn't part of a
+/// link. Code spans written as shortcut links (`` [`Foo`] ``) are skipped as well, even when the
+/// link is broken: they are intra-doc links, not plain code.
+crate fn markdown_code_spans(md: &str) -> Vec<(String, Range<usize>)> {
+    let mut spans = vec![];
+    let mut link_depth = 0usize;
+
+    for (event, range) in Parser::new_ext(md, opts()).into_offset_iter() {
+        match event {
+            Event::Start(Tag::Link(..)) => link_depth += 1,
+            Event::End(Tag::Link(..)) => link_depth -= 1,
+            Event::Code(code) if link_depth == 0 => {
+                if md[..range.start].ends_with('[') && md[range.end..].starts_with(']') {
+                    continue;
+                }
+                spans.push((code.into_string(), range));
+            }
+            _ => {}
+        }
+    }
+
+    spans
+}
+
 #[derive(Debug)]
 crate struct RustCodeBlock {
     /// The range in the markdown that the code block occupies. Note that this includes the fences
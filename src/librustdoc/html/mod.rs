@@ -1,3 +1,4 @@
+crate mod archive;
 crate mod escape;
 crate mod format;
 crate mod highlight;
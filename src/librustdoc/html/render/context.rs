@@ -27,7 +27,7 @@
 use crate::formats::item_type::ItemType;
 use crate::formats::FormatRenderer;
 use crate::html::escape::Escape;
-use crate::html::format::Buffer;
+use crate::html::format::{Buffer, PrintContext};
 use crate::html::markdown::{self, plain_text_summary, ErrorCodes, IdMap};
 use crate::html::{layout, sources};
 
@@ -348,6 +348,20 @@ pub(super) fn src_href(&self, item: &clean::Item) -> Option<String> {
     }
 }
 
+impl<'tcx> PrintContext<'tcx> for Context<'tcx> {
+    fn tcx(&self) -> TyCtxt<'tcx> {
+        self.shared.tcx
+    }
+
+    fn cache(&self) -> &Cache {
+        &self.cache
+    }
+
+    fn current(&self) -> &[String] {
+        &self.current
+    }
+}
+
 /// Generates the documentation for `crate` into the directory `dst`
 impl<'tcx> FormatRenderer<'tcx> for Context<'tcx> {
     fn descr() -> &'static str {
@@ -0,0 +1,56 @@
+//! Support for emitting older versions of the JSON format, selected with `--json-format-version`.
+//!
+//! Rustdoc always builds a blob of the current [`types::FORMAT_VERSION`]. When an older version is
+//! requested, the serialized blob is converted back one version at a time by [`downgrade`]. Every
+//! time `FORMAT_VERSION` is incremented, a conversion from the new version to the previous one
+//! should be added there, so that consumers get some time to migrate.
+
+use rustdoc_json_types as types;
+use serde_json::Value;
+
+/// The oldest version of the format which rustdoc can still emit.
+crate const OLDEST_SUPPORTED_FORMAT_VERSION: u32 = 6;
+
+/// Converts `blob`, a serialized [`types::Crate`] of the current format version, to `version`.
+///
+/// `version` must be in `OLDEST_SUPPORTED_FORMAT_VERSION..=types::FORMAT_VERSION`, which is
+/// checked when parsing the command line.
+crate fn downgrade(mut blob: Value, version: u32) -> Value {
+    assert!((OLDEST_SUPPORTED_FORMAT_VERSION..=types::FORMAT_VERSION).contains(&version));
+    for current in (version + 1..=types::FORMAT_VERSION).rev() {
+        match current {
+            // Add `N => to_previous_N(&mut blob),` here when bumping the format to version `N`.
+            7 => to_previous_7(&mut blob),
+            _ => unreachable!("no conversion from JSON format version {}", current),
+        }
+    }
+    blob["format_version"] = Value::from(version);
+    blob
+}
+
+/// Version 7 replaced the set of qualifiers in the `header` of functions, methods and function
+/// pointers with a [`types::Header`] struct.
+fn to_previous_7(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            let kind = object.get("kind").and_then(Value::as_str);
+            if let Some("function" | "method" | "function_pointer") = kind {
+                let header = object.get_mut("inner").and_then(|inner| inner.get_mut("header"));
+                if let Some(header) = header {
+                    let qualifiers = ["const", "unsafe", "async"]
+                        .iter()
+                        .filter(|qualifier| header[**qualifier] == Value::Bool(true))
+                        .map(|qualifier| Value::from(*qualifier))
+                        .collect();
+                    *header = Value::Array(qualifiers);
+                }
+            }
+            object.values_mut().for_each(to_previous_7);
+        }
+        Value::Array(values) => values.iter_mut().for_each(to_previous_7),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests;
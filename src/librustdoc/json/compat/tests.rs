@@ -0,0 +1,51 @@
+use serde_json::json;
+
+use super::downgrade;
+
+#[test]
+fn test_downgrade_header() {
+    let blob = json!({
+        "index": {
+            "0:1": {
+                "name": "f",
+                "kind": "function",
+                "inner": {
+                    "header": { "const": true, "unsafe": true, "async": false },
+                    "decl": {
+                        "inputs": [["g", {
+                            "kind": "function_pointer",
+                            "inner": {
+                                "header": { "const": false, "unsafe": false, "async": false },
+                            },
+                        }]],
+                    },
+                },
+            },
+            "0:2": { "name": "header", "kind": "struct", "inner": { "header": true } },
+        },
+        "paths": { "0:1": { "path": ["krate", "f"], "kind": "function" } },
+        "format_version": 7,
+    });
+    let expected = json!({
+        "index": {
+            "0:1": {
+                "name": "f",
+                "kind": "function",
+                "inner": {
+                    "header": ["const", "unsafe"],
+                    "decl": {
+                        "inputs": [["g", {
+                            "kind": "function_pointer",
+                            "inner": { "header": [] },
+                        }]],
+                    },
+                },
+            },
+            "0:2": { "name": "header", "kind": "struct", "inner": { "header": true } },
+        },
+        "paths": { "0:1": { "path": ["krate", "f"], "kind": "function" } },
+        "format_version": 6,
+    });
+    assert_eq!(downgrade(blob.clone(), 6), expected);
+    assert_eq!(downgrade(blob.clone(), 7), blob);
+}
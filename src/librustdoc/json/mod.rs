@@ -4,6 +4,7 @@
 //! output. See [the RFC](https://github.com/rust-lang/rfcs/pull/2963) and the [`types`] module
 //! docs for usage and details.
 
+mod compat;
 mod conversions;
 
 use std::cell::RefCell;
@@ -27,6 +28,8 @@
 use crate::html::render::cache::ExternalLocation;
 use crate::json::conversions::{from_def_id, IntoWithTcx};
 
+crate use compat::OLDEST_SUPPORTED_FORMAT_VERSION;
+
 #[derive(Clone)]
 crate struct JsonRenderer<'tcx> {
     tcx: TyCtxt<'tcx>,
@@ -35,6 +38,8 @@
     index: Rc<RefCell<FxHashMap<types::Id, types::Item>>>,
     /// The directory where the blob will be written to.
     out_path: PathBuf,
+    /// The version of the JSON format to emit, from `--json-format-version`.
+    format_version: u32,
     cache: Rc<Cache>,
 }
 
@@ -144,6 +149,7 @@ fn init(
                 tcx,
                 index: Rc::new(RefCell::new(FxHashMap::default())),
                 out_path: options.output,
+                format_version: options.json_format_version.unwrap_or(types::FORMAT_VERSION),
                 cache: Rc::new(cache),
             },
             krate,
@@ -234,13 +240,19 @@ fn after_krate(&mut self) -> Result<(), Error> {
                     )
                 })
                 .collect(),
-            format_version: 6,
+            format_version: types::FORMAT_VERSION,
         };
         let mut p = self.out_path.clone();
         p.push(output.index.get(&output.root).unwrap().name.clone().unwrap());
         p.set_extension("json");
         let file = File::create(&p).map_err(|error| Error { error: error.to_string(), file: p })?;
-        serde_json::ser::to_writer(&file, &output).unwrap();
+        if self.format_version == types::FORMAT_VERSION {
+            serde_json::ser::to_writer(&file, &output).unwrap();
+        } else {
+            let output = serde_json::to_value(&output).unwrap();
+            let output = compat::downgrade(output, self.format_version);
+            serde_json::ser::to_writer(&file, &output).unwrap();
+        }
         Ok(())
     }
 
@@ -118,6 +118,7 @@ macro_rules! map {
 mod json;
 crate mod lint;
 mod markdown;
+mod md;
 mod passes;
 mod theme;
 mod visit_ast;
@@ -563,6 +564,13 @@ fn opts() -> Vec<RustcOptGroup> {
                 "parse ignore-foo for ignoring doctests on a per-target basis",
             )
         }),
+        unstable("merge-doctests", |o| {
+            o.optflag(
+                "",
+                "merge-doctests",
+                "compile compatible doctests into a single binary to speed up `--test`",
+            )
+        }),
         unstable("runtool", |o| {
             o.optopt(
                 "",
@@ -602,6 +610,14 @@ fn opts() -> Vec<RustcOptGroup> {
         unstable("show-type-layout", |o| {
             o.optflag("", "show-type-layout", "Include the memory layout of types in the docs")
         }),
+        unstable("json-format-version", |o| {
+            o.optopt(
+                "",
+                "json-format-version",
+                "Version of the JSON format to emit with `--output-format json`",
+                "VERSION",
+            )
+        }),
     ]
 }
 
@@ -771,6 +787,27 @@ fn main_options(options: config::Options) -> MainResult {
                     config::OutputFormat::Json => sess.time("render_json", || {
                         run_renderer::<json::JsonRenderer<'_>>(krate, render_opts, cache, tcx)
                     }),
+                    config::OutputFormat::Markdown => sess.time("render_markdown", || {
+                        run_renderer::<md::MarkdownRenderer<'_>>(krate, render_opts, cache, tcx)
+                    }),
+                    config::OutputFormat::Zip => {
+                        let doc_root = render_opts.output.clone();
+                        sess.time("render_html", || {
+                            run_renderer::<html::render::Context<'_>>(
+                                krate,
+                                render_opts,
+                                cache,
+                                tcx,
+                            )
+                        })?;
+                        sess.time("write_archive", || {
+                            html::archive::write_archive(&doc_root).map_err(|e| {
+                                let msg = format!("couldn't write documentation archive: {}", e);
+                                tcx.sess.struct_err(&msg).emit();
+                                ErrorReported
+                            })
+                        })
+                    }
                 }
             })
         })
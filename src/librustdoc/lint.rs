@@ -169,6 +169,28 @@ macro_rules! declare_rustdoc_lint {
    "codeblock could not be parsed as valid Rust or is empty"
 }
 
+declare_rustdoc_lint! {
+    /// The `unnecessary_ignore` lint detects `ignore`d code blocks in
+    /// documentation examples that compile, and could therefore be tested.
+    /// This is a `rustdoc` only lint, see the documentation in the [rustdoc book].
+    ///
+    /// [rustdoc book]: ../../../rustdoc/lints.html#unnecessary_ignore
+    UNNECESSARY_IGNORE,
+    Allow,
+    "detects `ignore`d code blocks that compile"
+}
+
+declare_rustdoc_lint! {
+    /// The `stale_code_references` lint detects paths in inline code spans
+    /// that don't resolve to any item. This is a `rustdoc` only lint, see the
+    /// documentation in the [rustdoc book].
+    ///
+    /// [rustdoc book]: ../../../rustdoc/lints.html#stale_code_references
+    STALE_CODE_REFERENCES,
+    Allow,
+    "detects paths in inline code spans that don't resolve"
+}
+
 crate static RUSTDOC_LINTS: Lazy<Vec<&'static Lint>> = Lazy::new(|| {
     vec![
         BROKEN_INTRA_DOC_LINKS,
@@ -180,6 +202,8 @@ macro_rules! declare_rustdoc_lint {
         INVALID_HTML_TAGS,
         BARE_URLS,
         MISSING_CRATE_LEVEL_DOCS,
+        UNNECESSARY_IGNORE,
+        STALE_CODE_REFERENCES,
     ]
 });
 
@@ -0,0 +1,96 @@
+//! Plain-text rendering of item signatures for the Markdown backend.
+//!
+//! Types, bounds and function declarations are printed by `html::format` in its alternate
+//! (`{:#}`) mode, which doesn't emit any HTML: signatures end up in fenced code blocks, where
+//! Markdown can't render links anyway.
+
+use std::fmt::{self, Write as _};
+
+use crate::clean;
+use crate::html::format::{
+    print_abi_with_space, print_generic_bounds, print_where_clause, PrintContext, PrintWithSpace,
+};
+
+/// Accumulates the plain-text rendering of types, bounds and signatures.
+crate struct Signature<'a, 'tcx> {
+    cx: &'a dyn PrintContext<'tcx>,
+    crate buf: String,
+}
+
+impl<'a, 'tcx> Signature<'a, 'tcx> {
+    crate fn new(cx: &'a dyn PrintContext<'tcx>) -> Self {
+        Signature { cx, buf: String::new() }
+    }
+
+    crate fn push(&mut self, s: &str) -> &mut Self {
+        self.buf.push_str(s);
+        self
+    }
+
+    /// Pushes the plain-text rendering of `d`, one of the printers of `html::format`.
+    fn push_display(&mut self, d: impl fmt::Display) -> &mut Self {
+        write!(self.buf, "{:#}", d).unwrap();
+        self
+    }
+
+    /// Prints the visibility of `item`, followed by a space if it isn't inherited.
+    crate fn visibility(&mut self, item: &clean::Item) -> &mut Self {
+        let tcx = self.cx.tcx();
+        self.push_display(item.visibility.to_src_with_space(tcx, item.def_id.expect_real()))
+    }
+
+    crate fn ty(&mut self, ty: &clean::Type) -> &mut Self {
+        let cx = self.cx;
+        self.push_display(ty.print(cx))
+    }
+
+    crate fn bounds(&mut self, bounds: &[clean::GenericBound]) -> &mut Self {
+        let cx = self.cx;
+        self.push_display(print_generic_bounds(bounds, cx))
+    }
+
+    crate fn generics(&mut self, generics: &clean::Generics) -> &mut Self {
+        let cx = self.cx;
+        self.push_display(generics.print(cx))
+    }
+
+    /// Prints the `where` clause of `generics`, if there is one.
+    crate fn where_clause(&mut self, generics: &clean::Generics) -> &mut Self {
+        let cx = self.cx;
+        self.push_display(print_where_clause(generics, cx, 0, false))
+    }
+
+    /// Prints the full declaration of a function or method `item` named `name`.
+    crate fn function(
+        &mut self,
+        item: &clean::Item,
+        name: &str,
+        func: &clean::Function,
+    ) -> &mut Self {
+        let cx = self.cx;
+        let header = &func.header;
+        self.visibility(item)
+            .push(header.constness.print_with_space())
+            .push(header.asyncness.print_with_space())
+            .push(header.unsafety.print_with_space())
+            .push_display(print_abi_with_space(header.abi))
+            .push("fn ")
+            .push(name)
+            .generics(&func.generics);
+        let header_len = self.buf.len();
+        self.push_display(func.decl.full_print(header_len, 0, header.asyncness, cx))
+            .where_clause(&func.generics)
+    }
+
+    /// Prints the header of an `impl` block, without its items.
+    crate fn impl_header(&mut self, i: &clean::Impl) -> &mut Self {
+        let cx = self.cx;
+        // The HTML output ends the `where` clause with a comma, for the items that follow it.
+        let header = format!("{:#}", i.print(false, cx));
+        self.push(i.unsafety.print_with_space()).push(header.trim_end().trim_end_matches(','))
+    }
+
+    crate fn finish(&mut self) -> String {
+        std::mem::take(&mut self.buf)
+    }
+}
@@ -0,0 +1,865 @@
+//! Rustdoc's Markdown backend
+//!
+//! This module contains the logic for rendering a crate as a tree of Markdown files rather than the
+//! normal static HTML output: every module gets an `index.md` listing its items, and every item
+//! gets its own page (named like its HTML counterpart, e.g. `struct.Foo.md`), linked together with
+//! relative links. The result can be hosted on wikis that can't serve rustdoc's JavaScript, or
+//! checked in so that API changes show up in code review.
+
+mod format;
+
+use std::fmt::Write as _;
+use std::fs;
+use std::ops::Range;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use pulldown_cmark::{BrokenLink, CowStr, Event, Parser, Tag};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def::CtorKind;
+use rustc_hir::def_id::DefId;
+use rustc_middle::ty::TyCtxt;
+use rustc_span::symbol::Symbol;
+
+use crate::clean;
+use crate::clean::utils::DOC_RUST_LANG_ORG_CHANNEL;
+use crate::config::RenderOptions;
+use crate::docfs::PathError;
+use crate::error::Error;
+use crate::formats::cache::Cache;
+use crate::formats::item_type::ItemType;
+use crate::formats::FormatRenderer;
+use crate::html::format::{href_relative_parts, PrintContext, PrintWithSpace};
+use crate::html::markdown::{map_line, opts, plain_text_summary, ErrorCodes, LangString, Line};
+use crate::html::render::cache::ExternalLocation;
+use crate::html::render::item_ty_to_strs;
+
+use self::format::Signature;
+
+#[derive(Clone)]
+crate struct MarkdownRenderer<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    /// The directory the pages of the current module are written to.
+    dst: PathBuf,
+    /// The path of the current module, starting with the crate name.
+    current: Vec<String>,
+    cache: Rc<Cache>,
+}
+
+impl<'tcx> MarkdownRenderer<'tcx> {
+    fn write(&self, file_name: &str, contents: String) -> Result<(), Error> {
+        fs::create_dir_all(&self.dst).map_err(|e| Error::new(e, &self.dst))?;
+        let path = self.dst.join(file_name);
+        fs::write(&path, contents).map_err(|e| Error::new(e, &path))
+    }
+
+    fn sig(&self) -> Signature<'_, 'tcx> {
+        Signature::new(self)
+    }
+
+    /// Returns the link to the page of `did`, relative to the current module.
+    ///
+    /// Items documented locally are linked to their Markdown page, items from crates documented
+    /// elsewhere to their HTML page.
+    fn href(&self, did: DefId) -> Option<String> {
+        let cache = &self.cache;
+        if !did.is_local() && !cache.access_levels.is_public(did) && !cache.document_private {
+            return None;
+        }
+
+        fn to_module_fqp(shortty: ItemType, fqp: &[String]) -> &[String] {
+            if shortty == ItemType::Module { &fqp[..] } else { &fqp[..fqp.len() - 1] }
+        }
+
+        let (fqp, shortty, remote) = match cache.paths.get(&did) {
+            Some(&(ref fqp, shortty)) => (fqp, shortty, None),
+            None => {
+                let &(ref fqp, shortty) = cache.external_paths.get(&did)?;
+                match cache.extern_locations.get(&did.krate)? {
+                    ExternalLocation::Remote(ref s) => {
+                        (fqp, shortty, Some(s.trim_end_matches('/')))
+                    }
+                    ExternalLocation::Local => (fqp, shortty, None),
+                    ExternalLocation::Unknown => return None,
+                }
+            }
+        };
+        let module_fqp = to_module_fqp(shortty, fqp);
+        let (mut url_parts, extension) = match remote {
+            Some(root) => {
+                let mut parts = vec![root];
+                parts.extend(module_fqp.iter().map(String::as_str));
+                (parts, "html")
+            }
+            None => (href_relative_parts(module_fqp, &self.current), "md"),
+        };
+        let filename = page_name(shortty, fqp.last().unwrap(), extension);
+        url_parts.push(&filename);
+        Some(url_parts.join("/"))
+    }
+
+    /// Returns the documentation of `item`, with its intra-doc links pointing to Markdown pages and
+    /// its headings nested below the `heading_level` of the section it is rendered in.
+    fn docs(&self, item: &clean::Item, heading_level: usize) -> Option<String> {
+        let docs = item.collapsed_doc_value()?;
+        let links = self.cache.intra_doc_links.get(&item.def_id).map_or(&[][..], |v| &v[..]);
+        let mut hrefs = FxHashMap::default();
+        for link in links {
+            let href = match link.did {
+                Some(did) => match self.href(did) {
+                    Some(mut href) => {
+                        if let Some(ref fragment) = link.fragment {
+                            href.push('#');
+                            href.push_str(fragment);
+                        }
+                        href
+                    }
+                    None => continue,
+                },
+                // Primitives don't have a `DefId`; their name is passed in the fragment.
+                None => match link.fragment {
+                    Some(ref fragment) => {
+                        let tail = fragment.find('#').unwrap_or_else(|| fragment.len());
+                        format!(
+                            "{}/std/primitive.{}.html{}",
+                            DOC_RUST_LANG_ORG_CHANNEL,
+                            &fragment[..tail],
+                            &fragment[tail..],
+                        )
+                    }
+                    None => continue,
+                },
+            };
+            hrefs.insert(link.link.as_str(), href);
+        }
+        Some(adjust_markdown(&rewrite_links(&docs, &hrefs), heading_level))
+    }
+
+    fn push_docs(&self, out: &mut String, item: &clean::Item, heading_level: usize) {
+        if let Some(docs) = self.docs(item, heading_level) {
+            out.push_str(&docs);
+            out.push_str("\n\n");
+        }
+    }
+
+    fn render_module(&self, item: &clean::Item, module: &clean::Module) -> String {
+        let mut out = String::new();
+        if item.is_crate() {
+            writeln!(out, "# Crate `{}`\n", self.current[0]).unwrap();
+        } else {
+            writeln!(out, "# Module `{}`\n", self.current.join("::")).unwrap();
+        }
+        self.push_docs(&mut out, item, 1);
+
+        let mut items = module
+            .items
+            .iter()
+            .filter(|i| (i.name.is_some() || i.is_import()) && !i.is_stripped())
+            .map(|i| {
+                // Imports and extern crates are listed together as re-exports.
+                let ty = if i.is_extern_crate() { ItemType::Import } else { i.type_() };
+                (ty, i)
+            })
+            .collect::<Vec<_>>();
+        items.sort_by_cached_key(|&(ty, i)| {
+            (section_order(ty), i.name.map(|n| n.to_string()).unwrap_or_default())
+        });
+
+        let mut current_section = None;
+        for (ty, item) in items {
+            if current_section != Some(ty) {
+                if current_section.is_some() {
+                    out.push('\n');
+                }
+                current_section = Some(ty);
+                writeln!(out, "## {}\n", item_ty_to_strs(ty).1).unwrap();
+            }
+            match *item.kind {
+                clean::ImportItem(ref import) => {
+                    let mut sig = self.sig();
+                    sig.visibility(item).push("use ");
+                    let path = import.source.path.whole_name();
+                    match import.kind {
+                        clean::ImportKind::Simple(n) if n == import.source.path.last() => {
+                            sig.push(&path);
+                        }
+                        clean::ImportKind::Simple(n) => {
+                            sig.push(&path).push(" as ").push(&n.as_str());
+                        }
+                        clean::ImportKind::Glob => {
+                            sig.push(&path).push("::*");
+                        }
+                    }
+                    writeln!(out, "- `{};`", sig.finish()).unwrap();
+                }
+                clean::ExternCrateItem { ref src } => {
+                    let name = item.name.unwrap();
+                    let decl = match *src {
+                        Some(src) => format!("extern crate {} as {};", src, name),
+                        None => format!("extern crate {};", name),
+                    };
+                    writeln!(out, "- `{}`", decl).unwrap();
+                }
+                _ => {
+                    let name = item.name.unwrap();
+                    let link = if ty == ItemType::Module {
+                        format!("{}/index.md", name)
+                    } else {
+                        page_name(ty, &name.as_str(), "md")
+                    };
+                    let summary = plain_text_summary(&item.doc_value().unwrap_or_default());
+                    if summary.is_empty() {
+                        writeln!(out, "- [`{}`]({})", name, link).unwrap();
+                    } else {
+                        writeln!(out, "- [`{}`]({}): {}", name, link, summary).unwrap();
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    fn render_item(&self, item: &clean::Item) -> String {
+        let name = item.name.unwrap();
+        let mut out = String::new();
+        let mut path = self.current.join("::");
+        if !path.is_empty() {
+            path.push_str("::");
+        }
+        writeln!(out, "# {} `{}{}`\n", item_title(item.type_()), path, name).unwrap();
+
+        if let Some(decl) = self.declaration(item, name) {
+            writeln!(out, "```rust\n{}\n```\n", decl).unwrap();
+        }
+        self.push_docs(&mut out, item, 1);
+
+        match *item.kind {
+            clean::StructItem(clean::Struct { ref fields, .. })
+            | clean::UnionItem(clean::Union { ref fields, .. }) => {
+                self.render_fields(&mut out, fields, 2);
+            }
+            clean::EnumItem(ref e) => {
+                let variants = e.variants.iter().filter(|v| !v.is_stripped()).collect::<Vec<_>>();
+                if !variants.is_empty() {
+                    out.push_str("## Variants\n\n");
+                }
+                for variant in variants {
+                    let variant_name = variant.name.unwrap();
+                    let mut sig = self.sig();
+                    sig.push(&variant_name.as_str());
+                    if let clean::VariantItem(clean::Variant::Tuple(ref tys)) = *variant.kind {
+                        sig.push("(");
+                        for (i, ty) in tys.iter().enumerate() {
+                            if i > 0 {
+                                sig.push(", ");
+                            }
+                            sig.ty(ty);
+                        }
+                        sig.push(")");
+                    }
+                    self.push_member_heading(&mut out, variant, &sig.finish(), 3);
+                    self.push_docs(&mut out, variant, 3);
+                    if let clean::VariantItem(clean::Variant::Struct(ref s)) = *variant.kind {
+                        self.render_fields(&mut out, &s.fields, 4);
+                    }
+                }
+            }
+            clean::TraitItem(ref t) => {
+                for (ty, title) in &[
+                    (ItemType::AssocType, "Associated Types"),
+                    (ItemType::AssocConst, "Associated Constants"),
+                    (ItemType::TyMethod, "Required Methods"),
+                    (ItemType::Method, "Provided Methods"),
+                ] {
+                    let members = t.items.iter().filter(|i| i.type_() == *ty).collect::<Vec<_>>();
+                    if members.is_empty() {
+                        continue;
+                    }
+                    writeln!(out, "## {}\n", title).unwrap();
+                    for member in members {
+                        if let Some(decl) = self.assoc_item(member) {
+                            self.push_member_heading(&mut out, member, &decl, 3);
+                            self.push_docs(&mut out, member, 3);
+                        }
+                    }
+                }
+                if let Some(implementors) =
+                    item.def_id.as_real().and_then(|did| self.cache.implementors.get(&did))
+                {
+                    out.push_str("## Implementors\n\n");
+                    for implementor in implementors {
+                        let header = self.sig().impl_header(implementor.inner_impl()).finish();
+                        writeln!(out, "- `{}`", single_line(&header)).unwrap();
+                    }
+                    out.push('\n');
+                }
+            }
+            _ => {}
+        }
+
+        if let Some(impls) = item.def_id.as_real().and_then(|did| self.cache.impls.get(&did)) {
+            self.render_impls(&mut out, impls);
+        }
+        out
+    }
+
+    fn render_fields(&self, out: &mut String, fields: &[clean::Item], heading_level: usize) {
+        let fields = fields
+            .iter()
+            .filter_map(|f| match *f.kind {
+                clean::StructFieldItem(ref ty) => Some((f, ty)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        if fields.is_empty() {
+            return;
+        }
+        writeln!(out, "{} Fields\n", "#".repeat(heading_level)).unwrap();
+        for (field, ty) in fields {
+            let decl = self.sig().push(&field.name.unwrap().as_str()).push(": ").ty(ty).finish();
+            self.push_member_heading(out, field, &decl, heading_level + 1);
+            self.push_docs(out, field, heading_level + 1);
+        }
+    }
+
+    fn render_impls(&self, out: &mut String, impls: &[crate::formats::Impl]) {
+        let (inherent, trait_impls): (Vec<_>, Vec<_>) =
+            impls.iter().partition(|i| i.inner_impl().trait_.is_none());
+
+        if !inherent.is_empty() {
+            out.push_str("## Implementations\n\n");
+        }
+        for i in inherent {
+            let header = self.sig().impl_header(i.inner_impl()).finish();
+            writeln!(out, "### `{}`\n", single_line(&header)).unwrap();
+            self.push_docs(out, &i.impl_item, 3);
+            for member in &i.inner_impl().items {
+                if let Some(decl) = self.assoc_item(member) {
+                    self.push_member_heading(out, member, &decl, 4);
+                    self.push_docs(out, member, 4);
+                }
+            }
+        }
+
+        let (synthetic, trait_impls): (Vec<_>, Vec<_>) =
+            trait_impls.into_iter().partition(|i| i.inner_impl().synthetic);
+        let (blanket, trait_impls): (Vec<_>, Vec<_>) =
+            trait_impls.into_iter().partition(|i| i.inner_impl().blanket_impl.is_some());
+        for (title, impls) in &[
+            ("Trait Implementations", trait_impls),
+            ("Auto Trait Implementations", synthetic),
+            ("Blanket Implementations", blanket),
+        ] {
+            if impls.is_empty() {
+                continue;
+            }
+            writeln!(out, "## {}\n", title).unwrap();
+            for i in impls {
+                let header = self.sig().impl_header(i.inner_impl()).finish();
+                writeln!(out, "- `{}`", single_line(&header)).unwrap();
+            }
+            out.push('\n');
+        }
+    }
+
+    /// Pushes the heading of a field, variant or associated item, preceded by the same anchor
+    /// as in the HTML output so that links with fragments keep working.
+    fn push_member_heading(
+        &self,
+        out: &mut String,
+        item: &clean::Item,
+        decl: &str,
+        heading_level: usize,
+    ) {
+        let anchor = format!("{}.{}", item.type_(), item.name.unwrap());
+        writeln!(out, "<a id=\"{}\"></a>\n", anchor).unwrap();
+        writeln!(out, "{} `{}`\n", "#".repeat(heading_level), single_line(decl)).unwrap();
+    }
+
+    /// Returns the declaration of an associated item of a trait or an impl.
+    fn assoc_item(&self, item: &clean::Item) -> Option<String> {
+        let name = item.name?.as_str();
+        let mut sig = self.sig();
+        match *item.kind {
+            clean::TyMethodItem(ref f) => {
+                sig.function(item, &name, f);
+            }
+            clean::MethodItem(ref f, _) => {
+                sig.function(item, &name, f);
+            }
+            clean::AssocConstItem(ref ty, ref default) => {
+                sig.push("const ").push(&name).push(": ").ty(ty);
+                if let Some(ref default) = *default {
+                    sig.push(" = ").push(default);
+                }
+            }
+            clean::AssocTypeItem(ref bounds, ref default) => {
+                sig.push("type ").push(&name);
+                if !bounds.is_empty() {
+                    sig.push(": ").bounds(bounds);
+                }
+                if let Some(ref default) = *default {
+                    sig.push(" = ").ty(default);
+                }
+            }
+            clean::TypedefItem(ref t, true) => {
+                sig.push("type ").push(&name).push(" = ").ty(&t.type_);
+            }
+            _ => return None,
+        }
+        Some(sig.finish())
+    }
+
+    /// Returns the declaration shown at the top of the page of `item`.
+    fn declaration(&self, item: &clean::Item, name: Symbol) -> Option<String> {
+        let name = name.as_str();
+        let name = &*name;
+        let mut sig = self.sig();
+        match *item.kind {
+            clean::FunctionItem(ref f) | clean::ForeignFunctionItem(ref f) => {
+                sig.function(item, name, f);
+            }
+            clean::StructItem(ref s) => {
+                sig.visibility(item).push("struct ").push(name).generics(&s.generics);
+                match s.struct_type {
+                    CtorKind::Fictive => {
+                        sig.where_clause(&s.generics);
+                        self.braced_fields(&mut sig, &s.fields, s.fields_stripped);
+                    }
+                    CtorKind::Fn => {
+                        sig.push("(");
+                        for (i, field) in s.fields.iter().enumerate() {
+                            if i > 0 {
+                                sig.push(", ");
+                            }
+                            match *field.kind {
+                                clean::StructFieldItem(ref ty) => {
+                                    sig.visibility(field).ty(ty);
+                                }
+                                _ => {
+                                    sig.push("_");
+                                }
+                            }
+                        }
+                        sig.push(")").where_clause(&s.generics).push(";");
+                    }
+                    CtorKind::Const => {
+                        sig.where_clause(&s.generics).push(";");
+                    }
+                }
+            }
+            clean::UnionItem(ref u) => {
+                sig.visibility(item)
+                    .push("union ")
+                    .push(name)
+                    .generics(&u.generics)
+                    .where_clause(&u.generics);
+                self.braced_fields(&mut sig, &u.fields, u.fields_stripped);
+            }
+            clean::EnumItem(ref e) => {
+                sig.visibility(item)
+                    .push("enum ")
+                    .push(name)
+                    .generics(&e.generics)
+                    .where_clause(&e.generics)
+                    .push(" {");
+                for variant in e.variants.iter().filter(|v| !v.is_stripped()) {
+                    sig.push("\n    ").push(&variant.name.unwrap().as_str());
+                    match *variant.kind {
+                        clean::VariantItem(clean::Variant::Tuple(ref tys)) => {
+                            sig.push("(");
+                            for (i, ty) in tys.iter().enumerate() {
+                                if i > 0 {
+                                    sig.push(", ");
+                                }
+                                sig.ty(ty);
+                            }
+                            sig.push(")");
+                        }
+                        clean::VariantItem(clean::Variant::Struct(_)) => {
+                            sig.push(" { /* fields */ }");
+                        }
+                        _ => {}
+                    }
+                    sig.push(",");
+                }
+                if e.variants_stripped {
+                    sig.push("\n    // some variants omitted");
+                }
+                sig.push("\n}");
+            }
+            clean::TraitItem(ref t) => {
+                sig.visibility(item).push(t.unsafety.print_with_space());
+                if t.is_auto {
+                    sig.push("auto ");
+                }
+                sig.push("trait ").push(name).generics(&t.generics);
+                if !t.bounds.is_empty() {
+                    sig.push(": ").bounds(&t.bounds);
+                }
+                sig.where_clause(&t.generics).push(" {");
+                for member in &t.items {
+                    if let Some(decl) = self.assoc_item(member) {
+                        sig.push("\n    ").push(&decl.replace('\n', "\n    "));
+                        if let clean::MethodItem(..) = *member.kind {
+                            sig.push(" { ... }");
+                        } else {
+                            sig.push(";");
+                        }
+                    }
+                }
+                sig.push("\n}");
+            }
+            clean::TraitAliasItem(ref ta) => {
+                sig.push("trait ")
+                    .push(name)
+                    .generics(&ta.generics)
+                    .push(" = ")
+                    .bounds(&ta.bounds)
+                    .where_clause(&ta.generics)
+                    .push(";");
+            }
+            clean::TypedefItem(ref t, _) => {
+                sig.visibility(item)
+                    .push("type ")
+                    .push(name)
+                    .generics(&t.generics)
+                    .where_clause(&t.generics)
+                    .push(" = ")
+                    .ty(&t.type_)
+                    .push(";");
+            }
+            clean::OpaqueTyItem(ref t) => {
+                sig.push("type ")
+                    .push(name)
+                    .generics(&t.generics)
+                    .where_clause(&t.generics)
+                    .push(" = impl ")
+                    .bounds(&t.bounds)
+                    .push(";");
+            }
+            clean::StaticItem(ref s) | clean::ForeignStaticItem(ref s) => {
+                sig.visibility(item)
+                    .push("static ")
+                    .push(s.mutability.print_with_space())
+                    .push(name)
+                    .push(": ")
+                    .ty(&s.type_)
+                    .push(";");
+            }
+            clean::ConstantItem(ref c) => {
+                let expr = c.expr(self.tcx);
+                sig.visibility(item)
+                    .push("const ")
+                    .push(name)
+                    .push(": ")
+                    .ty(&c.type_)
+                    .push(" = ")
+                    .push(&expr)
+                    .push(";");
+            }
+            clean::ForeignTypeItem => {
+                sig.visibility(item).push("type ").push(name).push(";");
+            }
+            clean::MacroItem(ref m) => {
+                sig.push(&m.source);
+            }
+            clean::ProcMacroItem(ref m) => match m.kind {
+                rustc_span::hygiene::MacroKind::Bang => {
+                    sig.push(name).push("!() { /* proc-macro */ }");
+                }
+                rustc_span::hygiene::MacroKind::Attr => {
+                    sig.push("#[").push(name).push("]");
+                }
+                rustc_span::hygiene::MacroKind::Derive => {
+                    sig.push("#[derive(").push(name).push(")]");
+                    if !m.helpers.is_empty() {
+                        sig.push("\n\n// Helper attributes");
+                        for helper in &m.helpers {
+                            sig.push("\n#[").push(&helper.as_str()).push("]");
+                        }
+                    }
+                }
+            },
+            _ => return None,
+        }
+        Some(sig.finish())
+    }
+
+    fn braced_fields(&self, sig: &mut Signature<'_, '_>, fields: &[clean::Item], stripped: bool) {
+        sig.push(" {");
+        for field in fields {
+            if let clean::StructFieldItem(ref ty) = *field.kind {
+                sig.push("\n    ")
+                    .visibility(field)
+                    .push(&field.name.unwrap().as_str())
+                    .push(": ")
+                    .ty(ty)
+                    .push(",");
+            }
+        }
+        if stripped || fields.iter().any(|f| f.is_stripped()) {
+            sig.push("\n    /* private fields */");
+        }
+        sig.push("\n}");
+    }
+}
+
+impl<'tcx> PrintContext<'tcx> for MarkdownRenderer<'tcx> {
+    fn tcx(&self) -> TyCtxt<'tcx> {
+        self.tcx
+    }
+
+    fn cache(&self) -> &Cache {
+        &self.cache
+    }
+
+    fn current(&self) -> &[String] {
+        &self.current
+    }
+}
+
+impl<'tcx> FormatRenderer<'tcx> for MarkdownRenderer<'tcx> {
+    fn descr() -> &'static str {
+        "markdown"
+    }
+
+    const RUN_ON_MODULE: bool = true;
+
+    fn init(
+        krate: clean::Crate,
+        options: RenderOptions,
+        cache: Cache,
+        tcx: TyCtxt<'tcx>,
+    ) -> Result<(Self, clean::Crate), Error> {
+        debug!("Initializing markdown renderer");
+        Ok((
+            MarkdownRenderer {
+                tcx,
+                dst: options.output,
+                current: Vec::new(),
+                cache: Rc::new(cache),
+            },
+            krate,
+        ))
+    }
+
+    fn make_child_renderer(&self) -> Self {
+        self.clone()
+    }
+
+    fn item(&mut self, item: clean::Item) -> Result<(), Error> {
+        if item.is_stripped() {
+            return Ok(());
+        }
+        let page = self.render_item(&item);
+        let file_name = page_name(item.type_(), &item.name.unwrap().as_str(), "md");
+        self.write(&file_name, page)
+    }
+
+    fn mod_item_in(&mut self, item: &clean::Item) -> Result<(), Error> {
+        let item_name = item.name.unwrap().to_string();
+        self.dst.push(&item_name);
+        self.current.push(item_name);
+
+        // Stripped modules survive the rustdoc passes if they contain impls for public types, but
+        // they don't get a page.
+        if item.is_stripped() {
+            return Ok(());
+        }
+        let module = match *item.kind {
+            clean::ModuleItem(ref m) => m,
+            _ => unreachable!(),
+        };
+        let page = self.render_module(item, module);
+        self.write("index.md", page)
+    }
+
+    fn mod_item_out(&mut self) -> Result<(), Error> {
+        self.dst.pop();
+        self.current.pop();
+        Ok(())
+    }
+
+    fn after_krate(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn cache(&self) -> &Cache {
+        &self.cache
+    }
+}
+
+/// Returns the name of the page documenting an item, e.g. `struct.Foo.md`.
+fn page_name(ty: ItemType, name: &str, extension: &str) -> String {
+    if ty == ItemType::Module {
+        format!("index.{}", extension)
+    } else {
+        format!("{}.{}.{}", ty, name, extension)
+    }
+}
+
+/// The order of item types in module pages, the same as in the HTML output.
+fn section_order(ty: ItemType) -> u8 {
+    match ty {
+        ItemType::ExternCrate | ItemType::Import => 0,
+        ItemType::Primitive => 1,
+        ItemType::Module => 2,
+        ItemType::Macro => 3,
+        ItemType::Struct => 4,
+        ItemType::Enum => 5,
+        ItemType::Constant => 6,
+        ItemType::Static => 7,
+        ItemType::Trait => 8,
+        ItemType::Function => 9,
+        ItemType::Typedef => 10,
+        ItemType::Union => 11,
+        _ => 12 + ty as u8,
+    }
+}
+
+fn item_title(ty: ItemType) -> &'static str {
+    match ty {
+        ItemType::Module => "Module",
+        ItemType::Function => "Function",
+        ItemType::ForeignType => "Foreign Type",
+        ItemType::Trait => "Trait",
+        ItemType::Struct => "Struct",
+        ItemType::Union => "Union",
+        ItemType::Enum => "Enum",
+        ItemType::Typedef => "Type Definition",
+        ItemType::Macro => "Macro",
+        ItemType::ProcAttribute => "Attribute Macro",
+        ItemType::ProcDerive => "Derive Macro",
+        ItemType::Primitive => "Primitive Type",
+        ItemType::Static => "Static",
+        ItemType::Constant => "Constant",
+        ItemType::Keyword => "Keyword",
+        ItemType::OpaqueTy => "Opaque Type",
+        ItemType::TraitAlias => "Trait Alias",
+        _ => "Item",
+    }
+}
+
+/// Joins the lines of a declaration wrapped by `html::format`, for use in a heading or a list.
+fn single_line(decl: &str) -> String {
+    let decl = decl.lines().map(str::trim).collect::<Vec<_>>().join(" ");
+    decl.replace("( ", "(").replace(", )", ")")
+}
+
+/// Points the intra-doc links of `md` to Markdown pages, `hrefs` mapping the destination of each
+/// link, as collected by `collect_intra_doc_links`, to its new `href`.
+///
+/// The links are found by parsing `md`, so that text and code which merely contain a destination
+/// are left alone. Every matching link is replaced with an inline link to `href` with the same
+/// text: this covers reference links, whose definitions become unused, and shortcut links to
+/// items (``[`Foo`]``), which have no definition at all.
+fn rewrite_links(md: &str, hrefs: &FxHashMap<&str, String>) -> String {
+    if hrefs.is_empty() {
+        return md.to_owned();
+    }
+    let mut resolve_broken_link = |link: BrokenLink<'_>| {
+        if hrefs.contains_key(link.reference) {
+            Some((link.reference.to_owned().into(), "".into()))
+        } else {
+            None
+        }
+    };
+    let parser = Parser::new_with_broken_link_callback(md, opts(), Some(&mut resolve_broken_link));
+
+    let mut out = String::with_capacity(md.len());
+    let mut last = 0;
+    // The link being rewritten: its range, its new destination, its title and the range of its
+    // text, which covers the events found until its end.
+    let mut link: Option<(Range<usize>, &str, CowStr<'_>, Option<Range<usize>>)> = None;
+    for (event, range) in parser.into_offset_iter() {
+        match event {
+            Event::Start(Tag::Link(_, dest, title)) => {
+                if let Some(href) = hrefs.get(&*dest) {
+                    link = Some((range, href.as_str(), title, None));
+                }
+            }
+            Event::End(Tag::Link(..)) => {
+                if let Some((range, href, title, text)) = link.take() {
+                    out.push_str(&md[last..range.start]);
+                    let text = text.map_or("", |text| &md[text]);
+                    if title.is_empty() {
+                        write!(out, "[{}]({})", text, href).unwrap();
+                    } else {
+                        let title = title.replace('"', "\\\"");
+                        write!(out, "[{}]({} \"{}\")", text, href, title).unwrap();
+                    }
+                    last = range.end;
+                }
+            }
+            _ => {
+                if let Some((_, _, _, ref mut text)) = link {
+                    let start = text.as_ref().map_or(range.start, |text| text.start);
+                    *text = Some(start..range.end);
+                }
+            }
+        }
+    }
+    out.push_str(&md[last..]);
+    out
+}
+
+/// Prepares documentation written for the HTML output to be embedded in a Markdown page.
+///
+/// Headings are nested below `heading_level`, Rust code blocks are tagged as `rust` and their
+/// hidden lines (starting with `# `) are removed, like in the HTML output.
+fn adjust_markdown(md: &str, heading_level: usize) -> String {
+    let mut out = String::with_capacity(md.len());
+    // The fence of the code block we're in, and whether it contains Rust code.
+    let mut code_block: Option<(&str, bool)> = None;
+
+    for line in md.lines() {
+        let trimmed = line.trim_start();
+        match code_block {
+            Some((fence, is_rust)) => {
+                if trimmed.starts_with(fence)
+                    && trimmed.trim_end().chars().all(|c| c == '`' || c == '~')
+                {
+                    code_block = None;
+                    out.push_str(line);
+                } else if is_rust {
+                    match map_line(line) {
+                        Line::Hidden(_) => continue,
+                        Line::Shown(l) => out.push_str(&l),
+                    }
+                } else {
+                    out.push_str(line);
+                }
+            }
+            None if trimmed.starts_with("```") || trimmed.starts_with("~~~") => {
+                let fence_char = trimmed.chars().next().unwrap();
+                let fence_len = trimmed.chars().take_while(|&c| c == fence_char).count();
+                let (fence, info) = trimmed.split_at(fence_len);
+                let is_rust = LangString::parse_without_check(info, ErrorCodes::No, false).rust;
+                code_block = Some((fence, is_rust));
+                if is_rust {
+                    write!(out, "{}{}rust", &line[..line.len() - trimmed.len()], fence).unwrap();
+                } else {
+                    out.push_str(line);
+                }
+            }
+            None if trimmed.starts_with('#') => {
+                let level = trimmed.chars().take_while(|&c| c == '#').count();
+                let rest = &trimmed[level..];
+                if level <= 6 && (rest.is_empty() || rest.starts_with(' ')) {
+                    let new_level = (level + heading_level).min(6);
+                    write!(out, "{}{}", "#".repeat(new_level), rest).unwrap();
+                } else {
+                    out.push_str(line);
+                }
+            }
+            None => out.push_str(line),
+        }
+        out.push('\n');
+    }
+    out.truncate(out.trim_end().len());
+    out
+}
+
+#[cfg(test)]
+mod tests;
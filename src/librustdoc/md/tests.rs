@@ -0,0 +1,64 @@
+use rustc_data_structures::fx::FxHashMap;
+
+use super::{adjust_markdown, rewrite_links, single_line};
+
+#[test]
+fn test_adjust_markdown_headings() {
+    fn t(input: &str, level: usize, expect: &str) {
+        assert_eq!(adjust_markdown(input, level), expect);
+    }
+
+    t("# Examples", 1, "## Examples");
+    t("## Panics\n\ntext", 3, "##### Panics\n\ntext");
+    t("#### Deep", 4, "###### Deep");
+    t("#no-heading", 1, "#no-heading");
+    t("```text\n# not a heading\n```", 1, "```text\n# not a heading\n```");
+}
+
+#[test]
+fn test_adjust_markdown_code_blocks() {
+    fn t(input: &str, expect: &str) {
+        assert_eq!(adjust_markdown(input, 1), expect);
+    }
+
+    t("```\n# use std::fmt;\nlet x = 1;\n```", "```rust\nlet x = 1;\n```");
+    t("```should_panic\n## not hidden\npanic!();\n```", "```rust\n# not hidden\npanic!();\n```");
+    t("```text\n# kept\n```", "```text\n# kept\n```");
+    t("~~~\n# hidden\n```\nshown\n~~~", "~~~rust\n```\nshown\n~~~");
+}
+
+#[test]
+fn test_rewrite_links() {
+    fn t(input: &str, expect: &str) {
+        let hrefs: FxHashMap<_, _> = vec![
+            ("Foo", "struct.Foo.md".to_owned()),
+            ("crate::Foo", "struct.Foo.md".to_owned()),
+            ("`Foo`", "struct.Foo.md".to_owned()),
+            ("Bar::baz", "struct.Bar.md#method.baz".to_owned()),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(rewrite_links(input, &hrefs), expect);
+    }
+
+    t("See [this](Foo).", "See [this](struct.Foo.md).");
+    t("See [this](Foo \"the Foo\").", "See [this](struct.Foo.md \"the Foo\").");
+    t("See [this].\n\n[this]: crate::Foo", "See [this](struct.Foo.md).\n\n[this]: crate::Foo");
+    t("See [that][x].\n\n[x]: Bar::baz", "See [that](struct.Bar.md#method.baz).\n\n[x]: Bar::baz");
+    t("See [`Foo`].", "See [`Foo`](struct.Foo.md).");
+    t("See [*the* `Foo`](Foo)!", "See [*the* `Foo`](struct.Foo.md)!");
+    t("[Foo] and [Bar]", "[Foo](struct.Foo.md) and [Bar]");
+    t("Not a link: `[x](Foo)`, ](Foo).", "Not a link: `[x](Foo)`, ](Foo).");
+    t("```\n[x](Foo)\n```\nafter [x](Foo)", "```\n[x](Foo)\n```\nafter [x](struct.Foo.md)");
+    t("[this](Foo)[that](https://example.com)", "[this](struct.Foo.md)[that](https://example.com)");
+    t("Nothing here.", "Nothing here.");
+}
+
+#[test]
+fn test_single_line() {
+    assert_eq!(single_line("fn f(x: u8)"), "fn f(x: u8)");
+    assert_eq!(
+        single_line("fn f(\n    x: u8,\n    y: u8,\n) -> u8\nwhere\n    T: Copy"),
+        "fn f(x: u8, y: u8) -> u8 where T: Copy"
+    );
+}
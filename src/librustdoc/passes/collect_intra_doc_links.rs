@@ -16,7 +16,7 @@
 use rustc_middle::ty::TyCtxt;
 use rustc_middle::{bug, ty};
 use rustc_resolve::ParentScope;
-use rustc_session::lint::Lint;
+use rustc_session::lint::{Level, Lint};
 use rustc_span::hygiene::{MacroKind, SyntaxContext};
 use rustc_span::symbol::{sym, Ident, Symbol};
 use rustc_span::DUMMY_SP;
@@ -33,8 +33,8 @@
 use crate::clean::{self, utils::find_nearest_parent_module, Crate, Item, ItemLink, PrimitiveType};
 use crate::core::DocContext;
 use crate::fold::DocFolder;
-use crate::html::markdown::{markdown_links, MarkdownLink};
-use crate::lint::{BROKEN_INTRA_DOC_LINKS, PRIVATE_INTRA_DOC_LINKS};
+use crate::html::markdown::{markdown_code_spans, markdown_links, MarkdownLink};
+use crate::lint::{BROKEN_INTRA_DOC_LINKS, PRIVATE_INTRA_DOC_LINKS, STALE_CODE_REFERENCES};
 use crate::passes::Pass;
 
 crate const COLLECT_INTRA_DOC_LINKS: Pass = Pass {
@@ -859,6 +859,12 @@ fn fold_item(&mut self, item: Item) -> Option<Item> {
             self.mod_ids.push(item.def_id.expect_real());
         }
 
+        // Resolving every code span is only worth it if the lint would be reported.
+        let check_code_spans =
+            DocContext::as_local_hir_id(self.cx.tcx, item.def_id).map_or(false, |hir_id| {
+                self.cx.tcx.lint_level_at_node(STALE_CODE_REFERENCES, hir_id).0 != Level::Allow
+            });
+
         // We want to resolve in the lexical scope of the documentation.
         // In the presence of re-exports, this is not the same as the module of the item.
         // Rather than merging all documentation into one, resolve it one attribute at a time
@@ -879,6 +885,11 @@ fn fold_item(&mut self, item: Item) -> Option<Item> {
                     self.cx.cache.intra_doc_links.entry(item.def_id).or_default().push(link);
                 }
             }
+            if check_code_spans {
+                for (code, range) in markdown_code_spans(&doc) {
+                    self.check_code_span(&item, &doc, &self_name, parent_node, krate, &code, range);
+                }
+            }
         }
 
         Some(if item.is_mod() {
@@ -1050,7 +1061,7 @@ fn resolve_link(
                     return None;
                 }
             };
-        let mut path_str = &*path_str;
+        let path_str = &*path_str;
 
         let inner_docs = item.inner_docs(self.cx.tcx);
 
@@ -1083,36 +1094,8 @@ fn resolve_link(
             return None;
         };
 
-        let resolved_self;
-        // replace `Self` with suitable item's parent name
-        let is_lone_self = path_str == "Self";
-        let is_lone_crate = path_str == "crate";
-        if path_str.starts_with("Self::") || is_lone_self {
-            if let Some(ref name) = self_name {
-                if is_lone_self {
-                    path_str = name;
-                } else {
-                    resolved_self = format!("{}::{}", name, &path_str[6..]);
-                    path_str = &resolved_self;
-                }
-            }
-        } else if path_str.starts_with("crate::") || is_lone_crate {
-            use rustc_span::def_id::CRATE_DEF_INDEX;
-
-            // HACK(jynelson): rustc_resolve thinks that `crate` is the crate currently being documented.
-            // But rustdoc wants it to mean the crate this item was originally present in.
-            // To work around this, remove it and resolve relative to the crate root instead.
-            // HACK(jynelson)(2): If we just strip `crate::` then suddenly primitives become ambiguous
-            // (consider `crate::char`). Instead, change it to `self::`. This works because 'self' is now the crate root.
-            // FIXME(#78696): This doesn't always work.
-            if is_lone_crate {
-                path_str = "self";
-            } else {
-                resolved_self = format!("self::{}", &path_str["crate::".len()..]);
-                path_str = &resolved_self;
-            }
-            module_id = DefId { krate, index: CRATE_DEF_INDEX };
-        }
+        let resolved_path = resolve_self_and_crate(path_str, self_name, &mut module_id, krate);
+        let path_str = &*resolved_path;
 
         let (mut res, mut fragment) = self.resolve_with_disambiguator_cached(
             ResolutionInfo {
@@ -1274,6 +1257,66 @@ fn resolve_link(
         }
     }
 
+    /// Checks that the path mentioned in an inline code span (like `` `Foo::new` ``) resolves, to
+    /// catch documentation that kept mentioning an item after it was renamed or removed.
+    ///
+    /// Unlike for intra-doc links, failing to resolve is only reported through the
+    /// `stale_code_references` lint, and code spans which don't look like they mention an item
+    /// are skipped.
+    fn check_code_span(
+        &mut self,
+        item: &Item,
+        dox: &str,
+        self_name: &Option<String>,
+        parent_node: Option<DefId>,
+        krate: CrateNum,
+        code: &str,
+        range: Range<usize>,
+    ) {
+        let path_str = match code_span_path(code) {
+            Some(path_str) => path_str,
+            None => return,
+        };
+        // See `resolve_link` for why the module depends on the kind of doc comment.
+        let base_node = if item.is_mod() && item.inner_docs(self.cx.tcx) {
+            self.mod_ids.last().copied()
+        } else {
+            parent_node
+        };
+        let mut module_id = match base_node {
+            Some(id) => id,
+            None => return,
+        };
+        let resolved_path = resolve_self_and_crate(path_str, self_name, &mut module_id, krate);
+        let path_str = &*resolved_path;
+        if path_str == "Self" || path_str.starts_with("Self::") {
+            // There's no `Self` type in scope of this item.
+            return;
+        }
+
+        let resolves = if code.trim_end().ends_with('!') {
+            self.resolve_macro(path_str, module_id).is_ok()
+        } else {
+            self.resolve(path_str, TypeNS, module_id, &None).is_ok()
+                || self.resolve(path_str, ValueNS, module_id, &None).is_ok()
+                || self.resolve_macro(path_str, module_id).is_ok()
+        };
+        // Associated items leave their kind here for `resolve_link`, which doesn't apply.
+        self.kind_side_channel.take();
+        if resolves {
+            return;
+        }
+
+        let diag_info = DiagnosticInfo { item, dox, ori_link: code, link_range: range };
+        let msg = format!("`{}` does not resolve to any item in scope", code);
+        report_diagnostic(self.cx.tcx, STALE_CODE_REFERENCES, &msg, &diag_info, |diag, sp| {
+            if let Some(sp) = sp {
+                diag.span_label(sp, "no item with this path");
+            }
+            diag.help("if the item was renamed or removed, update the documentation to match");
+        });
+    }
+
     fn resolve_with_disambiguator_cached(
         &mut self,
         key: ResolutionInfo,
@@ -1459,6 +1502,65 @@ fn resolve_with_disambiguator(
     }
 }
 
+/// Returns the path mentioned by an inline code span, if it looks like it refers to an item:
+/// `Foo`, `foo::bar`, `Foo::new()` or `foo!`, but not `x`, `foo()` or `a + b`.
+///
+/// Lowercase names without a path are skipped because they are usually local variables or
+/// methods of another type, and one-letter names because they are usually generic parameters.
+fn code_span_path(code: &str) -> Option<&str> {
+    let code = code.trim();
+    let is_macro = code.ends_with('!');
+    let path = code.strip_suffix("()").or_else(|| code.strip_suffix('!')).unwrap_or(code);
+    if !path.split("::").all(rustc_lexer::is_ident) {
+        return None;
+    }
+    if path.contains("::") || is_macro {
+        Some(path)
+    } else if path.len() > 1 && path.starts_with(|c: char| c.is_ascii_uppercase()) {
+        Some(path)
+    } else {
+        None
+    }
+}
+
+/// Replaces a leading `Self` or `crate` in `path_str` with a path `rustc_resolve` understands,
+/// updating `module_id` if the new path has to be resolved from another module.
+fn resolve_self_and_crate<'a>(
+    path_str: &'a str,
+    self_name: &'a Option<String>,
+    module_id: &mut DefId,
+    krate: CrateNum,
+) -> Cow<'a, str> {
+    // replace `Self` with suitable item's parent name
+    let is_lone_self = path_str == "Self";
+    let is_lone_crate = path_str == "crate";
+    if path_str.starts_with("Self::") || is_lone_self {
+        if let Some(ref name) = self_name {
+            if is_lone_self {
+                return Cow::Borrowed(name);
+            } else {
+                return Cow::Owned(format!("{}::{}", name, &path_str[6..]));
+            }
+        }
+    } else if path_str.starts_with("crate::") || is_lone_crate {
+        use rustc_span::def_id::CRATE_DEF_INDEX;
+
+        // HACK(jynelson): rustc_resolve thinks that `crate` is the crate currently being documented.
+        // But rustdoc wants it to mean the crate this item was originally present in.
+        // To work around this, remove it and resolve relative to the crate root instead.
+        // HACK(jynelson)(2): If we just strip `crate::` then suddenly primitives become ambiguous
+        // (consider `crate::char`). Instead, change it to `self::`. This works because 'self' is now the crate root.
+        // FIXME(#78696): This doesn't always work.
+        *module_id = DefId { krate, index: CRATE_DEF_INDEX };
+        if is_lone_crate {
+            return Cow::Borrowed("self");
+        } else {
+            return Cow::Owned(format!("self::{}", &path_str["crate::".len()..]));
+        }
+    }
+    Cow::Borrowed(path_str)
+}
+
 /// Get the section of a link between the backticks,
 /// or the whole link if there aren't any backticks.
 ///
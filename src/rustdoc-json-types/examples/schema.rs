@@ -0,0 +1,9 @@
+//! Prints the JSON schema of the rustdoc JSON format.
+//!
+//! Run `cargo run --example schema --features schemars > rustdoc-json.schema.json` from this
+//! crate's directory after changing any of the types.
+
+fn main() {
+    let schema = rustdoc_json_types::json_schema();
+    println!("{}", serde_json::to_string_pretty(&schema).unwrap());
+}
@@ -32,3 +32,14 @@ fn test_union_info_roundtrip() {
 
     assert_eq!(u, de_u);
 }
+
+#[test]
+#[cfg(feature = "schemars")]
+fn test_schema_up_to_date() {
+    let schema = serde_json::to_string_pretty(&json_schema()).unwrap() + "\n";
+    assert!(
+        schema == include_str!("rustdoc-json.schema.json"),
+        "rustdoc-json.schema.json is out of date, regenerate it with \
+         `cargo run --example schema --features schemars > rustdoc-json.schema.json`"
+    );
+}
@@ -0,0 +1,22 @@
+#![crate_name = "foo"]
+
+//! Links to [`Foo`], [its method](Foo::frobnicate) and [`Bar`][bar].
+//!
+//! The code `[Foo](Foo)` is left alone.
+//!
+//! [bar]: crate::inner::Bar
+
+/// A struct with a method whose signature is wrapped by the HTML output.
+pub struct Foo;
+
+impl Foo {
+    /// Frobnicates the arguments.
+    pub fn frobnicate(&self, first_argument: u32, second_argument: u32, third: u32) -> u32 {
+        first_argument + second_argument + third
+    }
+}
+
+pub mod inner {
+    /// Points back to [`crate::Foo`].
+    pub struct Bar;
+}
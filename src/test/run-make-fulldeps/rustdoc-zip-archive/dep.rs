@@ -0,0 +1,3 @@
+#![crate_name = "dep"]
+
+pub struct Dep;
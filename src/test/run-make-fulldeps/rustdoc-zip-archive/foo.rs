@@ -0,0 +1,4 @@
+#![crate_name = "foo"]
+
+/// Wraps a [`dep::Dep`].
+pub struct Foo(pub dep::Dep);
@@ -0,0 +1,19 @@
+#!/usr/bin/env python
+
+import sys
+import zipfile
+
+archive = zipfile.ZipFile(sys.argv[1])
+assert archive.testzip() is None
+
+names = set(archive.namelist())
+for name in ["dep/struct.Dep.html", "foo/struct.Foo.html", "search-index.js"]:
+    assert name in names, "missing {}".format(name)
+assert ".lock" not in names
+
+search_index = archive.read("search-index.js").decode("utf-8")
+for krate in ["dep", "foo"]:
+    assert '"{}"'.format(krate) in search_index, "{} isn't in the search index".format(krate)
+
+page = archive.read("foo/struct.Foo.html").decode("utf-8")
+assert 'href="../dep/struct.Dep.html"' in page, "the link to `dep::Dep` isn't relative"
@@ -1,5 +1,7 @@
-// @has header.json "$.index[*][?(@.name=='FnPointer')].inner.type.inner.header" "[]"
+// ignore-tidy-linelength
+
+// @has header.json "$.index[*][?(@.name=='FnPointer')].inner.type.inner.header" '{"const":false,"unsafe":false,"async":false}'
 pub type FnPointer = fn();
 
-// @has - "$.index[*][?(@.name=='UnsafePointer')].inner.type.inner.header" '["unsafe"]'
+// @has - "$.index[*][?(@.name=='UnsafePointer')].inner.type.inner.header" '{"const":false,"unsafe":true,"async":false}'
 pub type UnsafePointer = unsafe fn();
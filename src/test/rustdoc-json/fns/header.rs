@@ -1,22 +1,19 @@
+// ignore-tidy-linelength
 // edition:2018
 
-// @has header.json "$.index[*][?(@.name=='nothing_fn')].inner.header" "[]"
+// @has header.json "$.index[*][?(@.name=='nothing_fn')].inner.header" '{"const":false,"unsafe":false,"async":false}'
 pub fn nothing_fn() {}
 
-// @has - "$.index[*][?(@.name=='const_fn')].inner.header" '["const"]'
+// @has - "$.index[*][?(@.name=='const_fn')].inner.header" '{"const":true,"unsafe":false,"async":false}'
 pub const fn const_fn() {}
 
-// @has - "$.index[*][?(@.name=='async_fn')].inner.header" '["async"]'
+// @has - "$.index[*][?(@.name=='async_fn')].inner.header" '{"const":false,"unsafe":false,"async":true}'
 pub async fn async_fn() {}
 
-// @count - "$.index[*][?(@.name=='async_unsafe_fn')].inner.header[*]" 2
-// @has - "$.index[*][?(@.name=='async_unsafe_fn')].inner.header[*]" '"async"'
-// @has - "$.index[*][?(@.name=='async_unsafe_fn')].inner.header[*]" '"unsafe"'
+// @has - "$.index[*][?(@.name=='async_unsafe_fn')].inner.header" '{"const":false,"unsafe":true,"async":true}'
 pub async unsafe fn async_unsafe_fn() {}
 
-// @count - "$.index[*][?(@.name=='const_unsafe_fn')].inner.header[*]" 2
-// @has - "$.index[*][?(@.name=='const_unsafe_fn')].inner.header[*]" '"const"'
-// @has - "$.index[*][?(@.name=='const_unsafe_fn')].inner.header[*]" '"unsafe"'
+// @has - "$.index[*][?(@.name=='const_unsafe_fn')].inner.header" '{"const":true,"unsafe":true,"async":false}'
 pub const unsafe fn const_unsafe_fn() {}
 
 // It's impossible for a function to be both const and async, so no test for that
@@ -0,0 +1,24 @@
+// ignore-tidy-linelength
+// edition:2018
+// compile-flags: --json-format-version 6
+
+// @is format_version_6.json "$.format_version" 6
+
+// @has - "$.index[*][?(@.name=='nothing_fn')].inner.header" "[]"
+pub fn nothing_fn() {}
+
+// @has - "$.index[*][?(@.name=='const_unsafe_fn')].inner.header" '["const", "unsafe"]'
+pub const unsafe fn const_unsafe_fn() {}
+
+// @has - "$.index[*][?(@.name=='async_fn')].inner.header" '["async"]'
+pub async fn async_fn() {}
+
+pub struct Foo;
+
+impl Foo {
+    // @has - "$.index[*][?(@.name=='unsafe_meth')].inner.header" '["unsafe"]'
+    pub unsafe fn unsafe_meth() {}
+}
+
+// @has - "$.index[*][?(@.name=='UnsafePointer')].inner.type.inner.header" '["unsafe"]'
+pub type UnsafePointer = unsafe fn();
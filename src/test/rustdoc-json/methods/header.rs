@@ -1,25 +1,22 @@
+// ignore-tidy-linelength
 // edition:2018
 
 pub struct Foo;
 
 impl Foo {
-    // @has header.json "$.index[*][?(@.name=='nothing_meth')].inner.header" "[]"
+    // @has header.json "$.index[*][?(@.name=='nothing_meth')].inner.header" '{"const":false,"unsafe":false,"async":false}'
     pub fn nothing_meth() {}
 
-    // @has - "$.index[*][?(@.name=='const_meth')].inner.header" '["const"]'
+    // @has - "$.index[*][?(@.name=='const_meth')].inner.header" '{"const":true,"unsafe":false,"async":false}'
     pub const fn const_meth() {}
 
-    // @has - "$.index[*][?(@.name=='async_meth')].inner.header" '["async"]'
+    // @has - "$.index[*][?(@.name=='async_meth')].inner.header" '{"const":false,"unsafe":false,"async":true}'
     pub async fn async_meth() {}
 
-    // @count - "$.index[*][?(@.name=='async_unsafe_meth')].inner.header[*]" 2
-    // @has - "$.index[*][?(@.name=='async_unsafe_meth')].inner.header[*]" '"async"'
-    // @has - "$.index[*][?(@.name=='async_unsafe_meth')].inner.header[*]" '"unsafe"'
+    // @has - "$.index[*][?(@.name=='async_unsafe_meth')].inner.header" '{"const":false,"unsafe":true,"async":true}'
     pub async unsafe fn async_unsafe_meth() {}
 
-    // @count - "$.index[*][?(@.name=='const_unsafe_meth')].inner.header[*]" 2
-    // @has - "$.index[*][?(@.name=='const_unsafe_meth')].inner.header[*]" '"const"'
-    // @has - "$.index[*][?(@.name=='const_unsafe_meth')].inner.header[*]" '"unsafe"'
+    // @has - "$.index[*][?(@.name=='const_unsafe_meth')].inner.header" '{"const":true,"unsafe":true,"async":false}'
     pub const unsafe fn const_unsafe_meth() {}
 
     // It's impossible for a method to be both const and async, so no test for that
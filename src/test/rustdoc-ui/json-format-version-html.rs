@@ -0,0 +1,3 @@
+// compile-flags:-Z unstable-options --json-format-version 6
+
+pub struct Foo;
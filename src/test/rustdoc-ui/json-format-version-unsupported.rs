@@ -0,0 +1,3 @@
+// compile-flags:-Z unstable-options --output-format json --json-format-version 5
+
+pub struct Foo;
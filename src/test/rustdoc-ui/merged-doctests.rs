@@ -0,0 +1,51 @@
+// Checks that `--merge-doctests` runs compatible doctests from a single binary, and still
+// compiles the other ones on their own. A failing merged doctest must report its location in this
+// file, like it would when compiled on its own.
+
+// edition:2018
+// compile-flags:--test --test-args=--test-threads=1 -Zunstable-options --merge-doctests
+// normalize-stdout-test: "src/test/rustdoc-ui" -> "$$DIR"
+// normalize-stdout-test "finished in \d+\.\d+s" -> "finished in $$TIME"
+// rustc-env:RUST_BACKTRACE=0
+// failure-status: 101
+
+/// ```
+/// assert_eq!(1 + 1, 2);
+/// ```
+pub fn merged() {}
+
+/// ```
+/// fn main() {
+///     assert_eq!(std::env::args().count(), 1);
+/// }
+/// ```
+pub fn with_main() {}
+
+/// ```
+/// let n: u32 = "42".parse()?;
+/// assert_eq!(n, 42);
+/// Ok::<(), std::num::ParseIntError>(())
+/// ```
+pub fn returns_result() {}
+
+/// ```should_panic
+/// panic!("standalone");
+/// ```
+pub fn should_panic() {}
+
+/// ```compile_fail
+/// let x: u32 = "not a number";
+/// ```
+pub fn compile_fail() {}
+
+/// ```
+/// #![allow(dead_code)]
+/// fn unused() {}
+/// ```
+pub fn crate_attrs() {}
+
+/// ```
+/// println!("merged stdout");
+/// panic!("merged failure");
+/// ```
+pub fn failing() {}
@@ -0,0 +1,3 @@
+// compile-flags:-Z unstable-options --output-format zip --static-root-path /static/
+
+pub struct Foo;
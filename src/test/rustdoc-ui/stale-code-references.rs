@@ -0,0 +1,24 @@
+#![deny(rustdoc::stale_code_references)]
+
+/// Creates a [`Widget`] with `Widget::new()`, like `Widget::build()` used to.
+//~^ ERROR `Widget::build()` does not resolve to any item in scope
+///
+/// Variables like `x`, generic parameters like `T`, methods like `len()` and code like `a + b` or
+/// `Vec<u8>` are not checked.
+pub fn make() -> Widget {
+    Widget::new()
+}
+
+pub struct Widget;
+
+impl Widget {
+    /// Same as `Self::new`, which replaced `OldWidget`.
+    //~^ ERROR `OldWidget` does not resolve to any item in scope
+    pub fn new() -> Self {
+        Widget
+    }
+}
+
+/// Uses `vec!`, `crate::make` and `missing_macro!`.
+//~^ ERROR `missing_macro!` does not resolve to any item in scope
+pub fn macros() {}
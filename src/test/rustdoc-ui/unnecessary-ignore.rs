@@ -0,0 +1,26 @@
+// compile-flags:--test
+// normalize-stderr-test: "src/test/rustdoc-ui" -> "$$DIR"
+
+#![deny(rustdoc::unnecessary_ignore)]
+
+/// This example compiles, so it could be tested.
+///
+/// ```ignore
+/// let x = 1 + 1;
+/// assert_eq!(x, 2);
+/// ```
+pub fn compiles() {}
+
+/// This example doesn't compile.
+///
+/// ```ignore
+/// let x: u8 = "not a number";
+/// ```
+pub fn does_not_compile() {}
+
+/// Examples which only need to be compiled are `no_run`.
+///
+/// ```no_run
+/// loop {}
+/// ```
+pub fn no_run() {}
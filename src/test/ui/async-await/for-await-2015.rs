@@ -0,0 +1,13 @@
+// run-pass
+// edition:2015
+
+// `await` is an ordinary identifier in the 2015 edition, so `for await in` is a plain `for` loop
+// binding it, which doesn't need the `async_for_loop` feature.
+
+fn main() {
+    let mut sum = 0;
+    for await in 0..4 {
+        sum += await;
+    }
+    assert_eq!(sum, 6);
+}
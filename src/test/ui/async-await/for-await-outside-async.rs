@@ -0,0 +1,11 @@
+// edition:2018
+
+#![feature(async_for_loop, async_stream)]
+
+use std::stream;
+
+fn main() { //~ NOTE this is not `async`
+    for await _ in stream::from_iter(0..3) {}
+    //~^ ERROR `await` is only allowed inside `async` functions and blocks
+    //~| NOTE only allowed inside `async` functions and blocks
+}
@@ -0,0 +1,76 @@
+// run-pass
+// edition:2018
+
+#![feature(async_for_loop, async_stream)]
+
+use std::future::Future;
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+use std::stream::{self, Stream};
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
+struct NoopWaker;
+
+impl Wake for NoopWaker {
+    fn wake(self: Arc<Self>) {}
+}
+
+fn block_on<F: Future>(fut: F) -> F::Output {
+    let mut fut = Box::pin(fut);
+    let waker = Waker::from(Arc::new(NoopWaker));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(output) = fut.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+// A `!Unpin` stream that returns `Poll::Pending` before each element, so the
+// loop has to pin it and actually await it.
+struct Countdown {
+    n: u32,
+    ready: bool,
+    _pinned: PhantomPinned,
+}
+
+impl Stream for Countdown {
+    type Item = u32;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<u32>> {
+        let this = unsafe { self.get_unchecked_mut() };
+        if !this.ready {
+            this.ready = true;
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        this.ready = false;
+        if this.n == 0 {
+            return Poll::Ready(None);
+        }
+        this.n -= 1;
+        Poll::Ready(Some(this.n + 1))
+    }
+}
+
+async fn run() -> u32 {
+    let mut total = 0;
+    for await x in stream::from_iter(vec![1, 2, 3]) {
+        total += x;
+    }
+    'outer: for await x in (Countdown { n: 5, ready: false, _pinned: PhantomPinned }) {
+        if x == 1 {
+            break 'outer;
+        }
+        if x % 2 == 0 {
+            continue;
+        }
+        total += x * 10;
+    }
+    total
+}
+
+fn main() {
+    assert_eq!(block_on(run()), 6 + 50 + 30);
+}
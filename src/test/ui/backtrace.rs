@@ -68,6 +68,18 @@ fn runtest(me: &str) {
             "bad output: {}", s);
     assert!(s.contains(" 0:"), "the frame number should start at 0");
 
+    // Make sure the compact stack trace is printed without the frames of
+    // the standard library
+    let p = template(me).arg("fail").env("RUST_BACKTRACE", "compact").spawn().unwrap();
+    let out = p.wait_with_output().unwrap();
+    assert!(!out.status.success());
+    let s = str::from_utf8(&out.stderr).unwrap();
+    assert!(s.contains("stack backtrace") && s.contains(&expected("foo")),
+            "bad output compact: {}", s);
+    assert!(s.contains(" 0:"), "the frame number should start at 0");
+    assert!(!s.contains(" std::panicking::") && !s.contains(" core::ops::function::"),
+            "bad output compact: {}", s);
+
     // Make sure the stack trace is *not* printed
     // (Remove RUST_BACKTRACE from our own environment, in case developer
     // is running `make check` with it on.)
@@ -0,0 +1,11 @@
+// edition:2018
+// gate-test-async_for_loop
+#![feature(async_stream)]
+
+use std::stream;
+
+async fn f() {
+    for await _ in stream::from_iter(0..3) {} //~ ERROR `for await` loops are experimental
+}
+
+fn main() {}
@@ -0,0 +1,46 @@
+// run-pass
+// needs-unwind
+// ignore-emscripten no threads support
+
+#![feature(panic_update_hook, panic_info_message, panic_can_unwind)]
+
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+static A: AtomicUsize = AtomicUsize::new(0);
+static B: AtomicUsize = AtomicUsize::new(0);
+
+fn spawn(name: &str, f: fn()) {
+    let _ = thread::Builder::new().name(name.to_string()).spawn(f).unwrap().join();
+}
+
+fn main() {
+    panic::set_hook(Box::new(|_| {
+        A.fetch_add(1, Ordering::SeqCst);
+    }));
+    panic::update_hook(|prev, info| {
+        // The new hook runs first, and decides when to run the previous one.
+        assert_eq!(A.load(Ordering::SeqCst), B.load(Ordering::SeqCst));
+        B.fetch_add(1, Ordering::SeqCst);
+
+        assert!(info.can_unwind());
+        let message = info.message().map(|message| message.to_string());
+        let expected = match thread::current().name().unwrap() {
+            "explicit" => Some("explicit panic"),
+            "formatted" => Some("1 + 2"),
+            "any" => None,
+            name => panic!("unexpected thread {}", name),
+        };
+        assert_eq!(message.as_deref(), expected);
+
+        prev(info);
+    });
+
+    spawn("explicit", || panic!());
+    spawn("formatted", || panic!("{} + {}", 1, 2));
+    spawn("any", || panic::panic_any(42));
+
+    assert_eq!(A.load(Ordering::SeqCst), 3);
+    assert_eq!(B.load(Ordering::SeqCst), 3);
+}
@@ -639,8 +639,8 @@ fn to_control_flow(expr: &ast::Expr, expr_type: ExprType) -> Option<ControlFlow<
                 expr.span,
             ))
         }
-        ast::ExprKind::ForLoop(ref pat, ref cond, ref block, label) => {
-            Some(ControlFlow::new_for(pat, cond, block, label, expr.span))
+        ast::ExprKind::ForLoop(ref pat, ref cond, ref block, label, kind) => {
+            Some(ControlFlow::new_for(pat, cond, block, label, kind, expr.span))
         }
         ast::ExprKind::Loop(ref block, label) => {
             Some(ControlFlow::new_loop(block, label, expr.span))
@@ -727,8 +727,13 @@ fn new_for(
         cond: &'a ast::Expr,
         block: &'a ast::Block,
         label: Option<ast::Label>,
+        kind: ast::ForLoopKind,
         span: Span,
     ) -> ControlFlow<'a> {
+        let matcher = match kind {
+            ast::ForLoopKind::For => "",
+            ast::ForLoopKind::ForAwait => "await",
+        };
         ControlFlow {
             cond: Some(cond),
             block,
@@ -736,7 +741,7 @@ fn new_for(
             label,
             pat: Some(pat),
             keyword: "for",
-            matcher: "",
+            matcher,
             connector: " in",
             allow_single_line: false,
             nested_if: false,